@@ -0,0 +1,193 @@
+//! Throughput of [`VerificationEngine::verify_batch`] against the
+//! `[nebula-guard]` SLOs in `config/slo.toml`.
+//!
+//! With `crypto` the receipts carry real Dilithium5 signatures; without it
+//! a keyed-BLAKE3 stand-in keeps the rest of the pipeline measurable.
+//!
+//! Criterion only reports timings, so the suite ends with `slo_summary`,
+//! which prints one `slo verify_batch/<size>` line per batch marking each
+//! objective `ok` or `MISSED`. Those lines are the bench's intended output
+//! for SLO review.
+//!
+//! ```text
+//! cargo bench -p glyph-lib --bench verify
+//! ```
+
+use std::collections::BTreeMap;
+use std::hint::black_box;
+use std::time::Duration;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use glyph_lib::anchors::quorum::QuorumPolicy;
+use glyph_lib::builders::anchor::AnchorBuilder;
+use glyph_lib::config::slo::SloConfig;
+use glyph_lib::pq::envelope::{GlyphSigner, KeyId, SignatureVerifier};
+use glyph_lib::testing::{bore_payload, sealed};
+use glyph_lib::verify::engine::VerificationEngine;
+use glyph_lib::{
+    AnchorGlyph, ChainLink, Daemon, GlyphId, GlyphKind, Guardian, ReceiptGlyph, TargetContext,
+    Verdict,
+};
+
+const SLO_TOML: &str = include_str!("../../../../config/slo.toml");
+const SWARM_ROLES_YAML: &str = include_str!("../../../../config/agents/swarm_roles.yaml");
+
+const TENANT_ID: &str = "xai-memphis-01";
+const NOW: i64 = 1_764_382_460;
+const BATCH_SIZES: [usize; 3] = [1, 120, 1024];
+
+/// The two `[nebula-guard]` objectives this suite measures.
+struct Slo {
+    min_verify_per_second: f64,
+    max_latency: Duration,
+}
+
+impl Slo {
+    fn load() -> Self {
+        let guard = SloConfig::from_toml(SLO_TOML)
+            .expect("slo.toml must parse")
+            .nebula_guard;
+        Slo {
+            min_verify_per_second: guard.min_kyber_verify_per_second as f64,
+            max_latency: Duration::from_millis(guard.max_verification_latency_ms),
+        }
+    }
+}
+
+type Verifier = Box<dyn SignatureVerifier + Send + Sync>;
+
+#[cfg(feature = "crypto")]
+fn keypair(_label: &str) -> (Box<dyn GlyphSigner>, Verifier) {
+    let keypair = glyph_lib::pq::dilithium::DilithiumKeypair::generate();
+    let public = keypair.public_key().clone();
+    (Box::new(keypair), Box::new(public))
+}
+
+#[cfg(not(feature = "crypto"))]
+fn keypair(label: &str) -> (Box<dyn GlyphSigner>, Verifier) {
+    use glyph_lib::testing::MacKey;
+    (Box::new(MacKey::of(label)), Box::new(MacKey::of(label)))
+}
+
+/// Keys, policy and one sealed, quorum-signed batch per size.
+struct Fixture {
+    policy: QuorumPolicy,
+    guardians: BTreeMap<(Guardian, String), Box<dyn SignatureVerifier>>,
+    emitters: BTreeMap<(Daemon, KeyId), Verifier>,
+    batches: Vec<(AnchorGlyph, Vec<ReceiptGlyph>)>,
+}
+
+impl Fixture {
+    fn new() -> Self {
+        let policy =
+            QuorumPolicy::from_swarm_roles_yaml(SWARM_ROLES_YAML).expect("swarm_roles.yaml");
+        let (emitter, emitter_key) = keypair("rocket-engine");
+        let emitters =
+            BTreeMap::from([((Daemon::RocketEngine, emitter_key.key_id()), emitter_key)]);
+
+        let mut voters = Vec::new();
+        let mut guardians = BTreeMap::new();
+        for &guardian in Guardian::ALL {
+            let (signer, verifier) = keypair(guardian.as_str());
+            let key_id = format!("guardian-{}-key-01", guardian.handle());
+            guardians.insert(
+                (guardian, key_id.clone()),
+                verifier as Box<dyn SignatureVerifier>,
+            );
+            voters.push((guardian, key_id, signer));
+        }
+
+        let intent = GlyphId::derive(GlyphKind::Intent, b"verify bench intent");
+        let batches = BATCH_SIZES
+            .iter()
+            .map(|&size| {
+                let receipts = sealed(TENANT_ID, intent, NOW, size, emitter.as_ref());
+                let pending = AnchorBuilder::new(
+                    TENANT_ID,
+                    TargetContext::TunnelBore,
+                    ChainLink::Genesis,
+                    Daemon::LedgerExplorer,
+                )
+                .timestamp(NOW + size as i64)
+                .receipts(&receipts)
+                .payload(bore_payload())
+                .prepare()
+                .expect("anchor");
+                let votes = voters
+                    .iter()
+                    .map(|(g, key_id, signer)| {
+                        pending.vote(*g, key_id, Verdict::Approve, signer.as_ref())
+                    })
+                    .collect::<glyph_lib::Result<_>>()
+                    .expect("votes");
+                let (anchor, _) = pending
+                    .finalize(&policy, &guardians, votes)
+                    .expect("quorum");
+                (anchor, receipts)
+            })
+            .collect();
+
+        Fixture {
+            policy,
+            guardians,
+            emitters,
+            batches,
+        }
+    }
+
+    fn engine(&self) -> VerificationEngine<'_> {
+        VerificationEngine::new(&self.policy, &self.guardians, &self.emitters)
+    }
+}
+
+fn verify_batch(c: &mut Criterion) {
+    let fixture = Fixture::new();
+    let engine = fixture.engine();
+    let mut group = c.benchmark_group("verify_batch");
+    for (anchor, receipts) in &fixture.batches {
+        group.throughput(Throughput::Elements(receipts.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(receipts.len()),
+            receipts,
+            |b, receipts| {
+                b.iter(|| {
+                    let report = engine.verify_batch(black_box(anchor), black_box(receipts));
+                    assert!(report.is_valid());
+                    report
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Prints the median of several runs per batch next to the SLO; not a
+/// criterion measurement, see the module docs.
+fn slo_summary(_: &mut Criterion) {
+    const RUNS: usize = 15;
+    let slo = Slo::load();
+    let fixture = Fixture::new();
+    let engine = fixture.engine();
+    for (anchor, receipts) in &fixture.batches {
+        let mut elapsed: Vec<Duration> = (0..RUNS)
+            .map(|_| engine.verify_batch(anchor, receipts).elapsed)
+            .collect();
+        elapsed.sort_unstable();
+        let median = elapsed[RUNS / 2];
+        let per_second = receipts.len() as f64 / median.as_secs_f64();
+        let verdict = |ok: bool| if ok { "ok" } else { "MISSED" };
+        println!(
+            "slo verify_batch/{:<5} {:>10.0} receipts/s (min {:.0}: {}), {:>8.2} ms (max {} ms: {})",
+            receipts.len(),
+            per_second,
+            slo.min_verify_per_second,
+            verdict(per_second >= slo.min_verify_per_second),
+            median.as_secs_f64() * 1e3,
+            slo.max_latency.as_millis(),
+            verdict(median <= slo.max_latency),
+        );
+    }
+}
+
+criterion_group!(benches, verify_batch, slo_summary);
+criterion_main!(benches);
@@ -0,0 +1,42 @@
+//! Hostile JSON into every glyph decoder.
+//!
+//! Whatever decodes must re-encode to a fixed point, and hashing, ID
+//! derivation and schema validation must return rather than panic.
+
+#![no_main]
+
+use std::fmt::Debug;
+
+use glyph_lib::anchors::receipt_payload::check_receipt;
+use glyph_lib::hashing::canonical::{content_hash, to_canonical_bytes};
+use glyph_lib::hashing::ids::verify_id;
+use glyph_lib::schemas::validator::check_glyph;
+use glyph_lib::{AnchorGlyph, DaemonStatusGlyph, Glyph, IntentGlyph, ReceiptGlyph};
+use libfuzzer_sys::fuzz_target;
+
+fn decode<G: Glyph + PartialEq + Debug>(data: &[u8]) -> Option<G> {
+    let glyph: G = serde_json::from_slice(data).ok()?;
+    let json = serde_json::to_vec(&glyph).expect("a decoded glyph re-encodes");
+    let again: G = serde_json::from_slice(&json).expect("a re-encoded glyph decodes");
+    assert_eq!(again, glyph);
+    assert_eq!(serde_json::to_vec(&again).unwrap(), json);
+
+    let value = serde_json::to_value(&glyph).unwrap();
+    if let Ok(canonical) = to_canonical_bytes(&value) {
+        let reparsed: serde_json::Value = serde_json::from_slice(&canonical).unwrap();
+        assert_eq!(to_canonical_bytes(&reparsed).unwrap(), canonical);
+    }
+    let _ = content_hash(&glyph);
+    let _ = verify_id(&glyph);
+    let _ = check_glyph(&glyph);
+    Some(glyph)
+}
+
+fuzz_target!(|data: &[u8]| {
+    if let Some(receipt) = decode::<ReceiptGlyph>(data) {
+        let _ = check_receipt(&receipt);
+    }
+    decode::<AnchorGlyph>(data);
+    decode::<IntentGlyph>(data);
+    decode::<DaemonStatusGlyph>(data);
+});
@@ -0,0 +1,72 @@
+//! Arbitrary inclusion proofs into the Merkle verifier.
+//!
+//! A forged proof must never panic and may only verify against the root it
+//! computes; a genuine proof must verify and stop verifying once any bit of
+//! its path changes.
+
+#![no_main]
+
+use glyph_lib::anchors::merkle::{leaf_hash, MerkleHash, MerkleProof, MerkleTree};
+use glyph_lib::EmbeddedMerkleProof;
+use libfuzzer_sys::arbitrary::{self, Arbitrary};
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    /// A proof assembled from raw parts.
+    forged: Forged,
+    /// A genuine proof over `leaf_count` leaves, then one flipped bit.
+    leaf_count: u16,
+    leaf_index: u16,
+    sibling: u8,
+    bit: u8,
+    /// Stored form with free-text siblings.
+    embedded_index: u64,
+    embedded_siblings: Vec<String>,
+}
+
+#[derive(Debug, Arbitrary)]
+struct Forged {
+    leaf_index: u64,
+    leaf_count: u64,
+    siblings: Vec<MerkleHash>,
+    leaf: MerkleHash,
+    root: MerkleHash,
+}
+
+fuzz_target!(|input: Input| {
+    let forged = MerkleProof {
+        leaf_index: input.forged.leaf_index,
+        leaf_count: input.forged.leaf_count,
+        siblings: input.forged.siblings,
+    };
+    let verified = forged.verify(&input.forged.root, &input.forged.leaf);
+    let computed = forged.compute_root(&input.forged.leaf);
+    assert_eq!(verified, computed.is_ok_and(|r| r == input.forged.root));
+
+    let count = usize::from(input.leaf_count % 1024).max(1);
+    let leaves: Vec<MerkleHash> = (0..count)
+        .map(|i| leaf_hash(&(i as u64).to_be_bytes()))
+        .collect();
+    let tree = MerkleTree::from_leaf_hashes(leaves.clone());
+    let root = tree.root();
+    let index = usize::from(input.leaf_index) % count;
+    let proof = tree.proof(index).expect("index is in range");
+    assert!(proof.verify(&root, &leaves[index]));
+    if !proof.siblings.is_empty() {
+        let mut tampered = proof.clone();
+        let s = usize::from(input.sibling) % tampered.siblings.len();
+        tampered.siblings[s][usize::from(input.bit / 8)] ^= 1 << (input.bit % 8);
+        assert!(!tampered.verify(&root, &leaves[index]));
+    }
+
+    let embedded = EmbeddedMerkleProof {
+        leaf_index: input.embedded_index,
+        siblings: input.embedded_siblings,
+        additional: Default::default(),
+    };
+    if let Ok(parsed) = MerkleProof::from_embedded(&embedded, count as u64) {
+        assert_eq!(parsed.to_embedded(), embedded);
+        let _ = parsed.verify(&root, &leaves[index]);
+    }
+});
@@ -0,0 +1,32 @@
+//! Arbitrary strings and bytes into the signature envelope parser.
+//!
+//! The hex form is canonical, so anything that parses must encode back to
+//! exactly the input.
+
+#![no_main]
+
+use glyph_lib::pq::envelope::{SignatureEnvelope, SignatureField};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(envelope) = SignatureEnvelope::from_bytes(data) {
+        assert_eq!(envelope.to_bytes(), data);
+    }
+
+    let Ok(field) = std::str::from_utf8(data) else {
+        return;
+    };
+    match SignatureField::parse(field) {
+        Ok(SignatureField::Envelope(envelope)) => {
+            assert_eq!(envelope.to_hex(), field);
+            assert_eq!(
+                SignatureField::parse(field).unwrap().into_envelope().unwrap(),
+                envelope
+            );
+        }
+        Ok(legacy @ SignatureField::Legacy(_)) => {
+            assert!(legacy.into_envelope().is_err());
+        }
+        Err(_) => {}
+    }
+});
@@ -0,0 +1,628 @@
+//! Core glyph type definitions.
+//!
+//! Every struct here mirrors one schema in `glyphs/schemas/` field for field.
+//! Closed `enum` properties become Rust enums, optional properties become
+//! `Option`, and objects the schemas leave open (`additionalProperties: true`)
+//! keep their unknown keys in a flattened map so decoding and re-encoding a
+//! glyph never drops data that was hashed or signed.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+
+use crate::error::GlyphError;
+use crate::hashing::ids::{ChainLink, GlyphId};
+
+/// JSON object used for open-ended payloads and metadata.
+pub type JsonMap = Map<String, Value>;
+
+/// Declares a closed schema enum together with its wire spelling.
+///
+/// Every generated type gets `ALL`, `as_str`, `Display` and `FromStr`, so
+/// daemons and storage layers can move between the typed and string forms
+/// without repeating the schema's spelling.
+macro_rules! glyph_enum {
+    (
+        $(#[$meta:meta])*
+        $name:ident {
+            $( $(#[$vmeta:meta])* $variant:ident => $wire:literal ),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        pub enum $name {
+            $( $(#[$vmeta])* #[serde(rename = $wire)] $variant, )+
+        }
+
+        impl $name {
+            /// Every variant, in schema order.
+            pub const ALL: &'static [$name] = &[ $( $name::$variant ),+ ];
+
+            /// Wire spelling used in the JSON schemas.
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $( $name::$variant => $wire, )+
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = GlyphError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $( $wire => Ok($name::$variant), )+
+                    other => Err(GlyphError::UnknownVariant {
+                        kind: stringify!($name),
+                        value: other.to_string(),
+                    }),
+                }
+            }
+        }
+    };
+}
+
+glyph_enum! {
+    /// Schema version carried in every glyph's `version` field.
+    #[derive(Default)]
+    SchemaVersion {
+        #[default]
+        V1_0 => "1.0",
+    }
+}
+
+impl SchemaVersion {
+    /// Version new glyphs are emitted in and every schema pins.
+    pub const CURRENT: SchemaVersion = SchemaVersion::V1_0;
+}
+
+glyph_enum! {
+    /// Guardians from `config/agents/guardians_org.yaml`.
+    Guardian {
+        StarLord => "Star-Lord",
+        Gamora => "Gamora",
+        Rocket => "Rocket",
+        Groot => "Groot",
+        Drax => "Drax",
+        Nebula => "Nebula",
+        Mantis => "Mantis",
+        Yondu => "Yondu",
+        Kraglin => "Kraglin",
+    }
+}
+
+impl Guardian {
+    /// Lower-case handle used in `guardian_id`, e.g. `star-lord`.
+    pub fn handle(&self) -> String {
+        self.as_str().to_ascii_lowercase()
+    }
+
+    /// Inverse of [`Guardian::handle`].
+    pub fn from_handle(handle: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|g| g.handle() == handle)
+    }
+}
+
+/// The Guardian that signed an IntentGlyph.
+pub type AuthorizedBy = Guardian;
+
+glyph_enum! {
+    /// Every daemon (and glyph-lib itself) that can emit a glyph.
+    ///
+    /// Individual schemas accept a subset: ReceiptGlyph excludes `portal-zero`
+    /// and `glyph-lib`, AnchorGlyph excludes `glyph-lib`. Schema validation
+    /// enforces the narrower sets.
+    Daemon {
+        GrootSwarm => "groot-swarm",
+        GlyphLib => "glyph-lib",
+        RocketEngine => "rocket-engine",
+        SpvApi => "spv-api",
+        DigitalTwinGroot => "digital-twin-groot",
+        PortalZero => "portal-zero",
+        LedgerExplorer => "ledger-explorer",
+        StarLordOrchestrator => "star-lord-orchestrator",
+        MantisCommunity => "mantis-community",
+        NebulaGuard => "nebula-guard",
+        DraxMetrics => "drax-metrics",
+    }
+}
+
+glyph_enum! {
+    /// What the swarm is being commanded to do.
+    IntentType {
+        InitiateBore => "initiate_bore",
+        VerifyOrbitalFeed => "verify_orbital_feed",
+        ShareZkAnomaly => "share_zk_anomaly",
+        SimulateEntanglement => "simulate_entanglement",
+        TriggerPhaseTransition => "trigger_phase_transition",
+        EmergencyHalt => "emergency_halt",
+    }
+}
+
+glyph_enum! {
+    /// Maximum acceptable deviation from SLOs for an intent.
+    RiskAppetite {
+        Conservative => "conservative",
+        Balanced => "balanced",
+        Aggressive => "aggressive",
+    }
+}
+
+glyph_enum! {
+    /// AnchorGlyph context; also the `target_context` of an IntentGlyph.
+    TargetContext {
+        TunnelBore => "tunnel_bore",
+        OrbitalVerify => "orbital_verify",
+        ZkAnomalyShare => "zk_anomaly_share",
+        EntanglementPrediction => "entanglement_prediction",
+    }
+}
+
+glyph_enum! {
+    /// Discriminator for ReceiptGlyph payloads.
+    ReceiptType {
+        BoreProgress => "bore_progress",
+        OrbitalTelemetry => "orbital_telemetry",
+        ZkAnomalyProof => "zk_anomaly_proof",
+        EntanglementPrediction => "entanglement_prediction",
+        AnomalyDetected => "anomaly_detected",
+        PhaseTransition => "phase_transition",
+        SwarmVote => "swarm_vote",
+        CompactionComplete => "compaction_complete",
+        VoicePageSent => "voice_page_sent",
+    }
+}
+
+glyph_enum! {
+    /// Outcome classification for the glyph a receipt refers to.
+    ReceiptResult {
+        Ok => "ok",
+        Anomaly => "anomaly",
+        Fraud => "fraud",
+        Rejected => "rejected",
+    }
+}
+
+glyph_enum! {
+    /// Severity of an `anomaly_detected` receipt.
+    AnomalySeverity {
+        Warning => "warning",
+        Critical => "critical",
+    }
+}
+
+glyph_enum! {
+    /// Overall daemon health reported in a DaemonStatusGlyph.
+    DaemonHealth {
+        Healthy => "healthy",
+        Degraded => "degraded",
+        Critical => "critical",
+        Halted => "halted",
+    }
+}
+
+glyph_enum! {
+    /// Scheme declared by an AnchorGlyph signature bundle.
+    SignatureScheme {
+        Kyber1024 => "kyber-1024",
+        Kyber1024Compatible => "kyber-1024-compatible",
+    }
+}
+
+glyph_enum! {
+    /// Proof system named in `extensions.zk.proof_system`.
+    ProofSystem {
+        Groth16 => "groth16",
+        Groth16Compatible => "groth16-compatible",
+    }
+}
+
+/// The four glyph families defined in `glyphs/schemas/`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum GlyphKind {
+    Intent,
+    Receipt,
+    Anchor,
+    DaemonStatus,
+}
+
+impl GlyphKind {
+    /// Prefix of this kind's identifiers, e.g. `receipt` in `receipt-…`.
+    pub fn id_prefix(&self) -> &'static str {
+        match self {
+            GlyphKind::Intent => "intent",
+            GlyphKind::Receipt => "receipt",
+            GlyphKind::Anchor => "anchor",
+            GlyphKind::DaemonStatus => "status",
+        }
+    }
+
+    /// Inverse of [`GlyphKind::id_prefix`].
+    pub fn from_id_prefix(prefix: &str) -> Option<Self> {
+        match prefix {
+            "intent" => Some(GlyphKind::Intent),
+            "receipt" => Some(GlyphKind::Receipt),
+            "anchor" => Some(GlyphKind::Anchor),
+            "status" => Some(GlyphKind::DaemonStatus),
+            _ => None,
+        }
+    }
+}
+
+/// Behaviour shared by every top-level glyph.
+pub trait Glyph: Serialize + serde::de::DeserializeOwned {
+    /// Family this glyph belongs to.
+    const KIND: GlyphKind;
+
+    /// `glyph_id` (or `receipt_id` for receipts).
+    fn id(&self) -> &GlyphId;
+
+    /// Unix timestamp in seconds.
+    fn timestamp(&self) -> i64;
+}
+
+/// `merkle_proof` object embedded in receipts and anchors.
+///
+/// Siblings are hex-encoded BLAKE3 hashes ordered from leaf to root.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmbeddedMerkleProof {
+    pub leaf_index: u64,
+    pub siblings: Vec<String>,
+    /// Extra keys are tolerated on anchors; receipts must leave this empty.
+    #[serde(flatten)]
+    pub additional: JsonMap,
+}
+
+// ---------------------------------------------------------------------------
+// IntentGlyph
+// ---------------------------------------------------------------------------
+
+/// `constraints` object of an IntentGlyph.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IntentConstraints {
+    pub max_latency_ms: u32,
+    pub min_entanglement_quality: f64,
+    pub require_zk_proof: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_drift_percent: Option<f64>,
+}
+
+/// Immutable declaration of authorized intent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IntentGlyph {
+    pub version: SchemaVersion,
+    pub glyph_id: GlyphId,
+    pub timestamp: i64,
+    pub tenant_id: String,
+    pub intent_type: IntentType,
+    pub authorized_by: AuthorizedBy,
+    pub risk_appetite: RiskAppetite,
+    pub target_context: TargetContext,
+    pub constraints: IntentConstraints,
+    pub expires_at: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+impl Glyph for IntentGlyph {
+    const KIND: GlyphKind = GlyphKind::Intent;
+
+    fn id(&self) -> &GlyphId {
+        &self.glyph_id
+    }
+
+    fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+}
+
+// ---------------------------------------------------------------------------
+// ReceiptGlyph
+// ---------------------------------------------------------------------------
+
+/// Minimal, verifiable proof of a single atomic event.
+///
+/// The fields common to every `receipt_type` are typed; the type-specific
+/// fields (`meters_advanced`, `zk_proof`, `correlation_score`, ...) live in
+/// `details` and are written back verbatim.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReceiptGlyph {
+    pub version: SchemaVersion,
+    pub receipt_id: GlyphId,
+    pub timestamp: i64,
+    pub tenant_id: String,
+    pub receipt_type: ReceiptType,
+    /// AnchorGlyph or IntentGlyph this receipt refers to.
+    pub ref_glyph_id: GlyphId,
+    pub result: ReceiptResult,
+    pub blake3_hash: String,
+    pub merkle_root: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merkle_proof: Option<EmbeddedMerkleProof>,
+    pub kyber_signature: String,
+    pub emitted_by: Daemon,
+    #[serde(flatten)]
+    pub details: JsonMap,
+}
+
+impl Glyph for ReceiptGlyph {
+    const KIND: GlyphKind = GlyphKind::Receipt;
+
+    fn id(&self) -> &GlyphId {
+        &self.receipt_id
+    }
+
+    fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+}
+
+// ---------------------------------------------------------------------------
+// AnchorGlyph
+// ---------------------------------------------------------------------------
+
+/// One Guardian's entry in an anchor signature bundle.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GuardianSignature {
+    /// Lower-case guardian handle, e.g. `star-lord`.
+    pub guardian_id: String,
+    pub public_key_id: String,
+    pub signature: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<JsonMap>,
+}
+
+glyph_enum! {
+    /// Vote a Guardian casts on an anchor, kept in `metadata.verdict`.
+    #[derive(Default)]
+    Verdict {
+        #[default]
+        Approve => "approve",
+        Reject => "reject",
+    }
+}
+
+impl GuardianSignature {
+    /// This entry's vote; entries without `metadata.verdict` approve.
+    pub fn verdict(&self) -> crate::error::Result<Verdict> {
+        match self.metadata.as_ref().and_then(|m| m.get("verdict")) {
+            None => Ok(Verdict::Approve),
+            Some(Value::String(s)) => s.parse(),
+            Some(_) => Err(GlyphError::UnknownVariant {
+                kind: "Verdict",
+                value: "non-string verdict".to_string(),
+            }),
+        }
+    }
+}
+
+/// Quorum signature bundle stored in an AnchorGlyph's `kyber_signature`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuorumSignatureBundle {
+    pub scheme: SignatureScheme,
+    pub quorum_threshold: u32,
+    pub quorum_observed: u32,
+    pub signatures: Vec<GuardianSignature>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<JsonMap>,
+    #[serde(flatten)]
+    pub additional: JsonMap,
+}
+
+/// Compact reference from an anchor to one of its receipts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AnchorReceiptRef {
+    pub receipt_id: GlyphId,
+    pub result: ReceiptResult,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merkle_root: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<JsonMap>,
+}
+
+/// `extensions.zk` metadata.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ZkExtension {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof_system: Option<ProofSystem>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof_ref: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_inputs_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_id: Option<String>,
+    #[serde(flatten)]
+    pub additional: JsonMap,
+}
+
+/// `extensions.entanglement` metadata.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntanglementExtension {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scenario_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bell_correlation: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub negation_ms: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sim_backend: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_hash: Option<String>,
+    #[serde(flatten)]
+    pub additional: JsonMap,
+}
+
+/// `extensions.mmr`: state of the receipts log accumulator at this anchor.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MmrExtension {
+    /// Number of receipts appended to the log so far.
+    pub leaf_count: u64,
+    /// Hex MMR root over those receipts.
+    pub root: String,
+    #[serde(flatten)]
+    pub additional: JsonMap,
+}
+
+/// `extensions.config`: the shared config files this anchor pins.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigManifestExtension {
+    /// Hex BLAKE3 of each file's canonical text, keyed by path relative to
+    /// `config/`.
+    pub files: BTreeMap<String, String>,
+    /// Hex Merkle root over `files` in path order.
+    pub root: String,
+    #[serde(flatten)]
+    pub additional: JsonMap,
+}
+
+/// Forward-compatible `extensions` container.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnchorExtensions {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zk: Option<ZkExtension>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entanglement: Option<EntanglementExtension>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mmr: Option<MmrExtension>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config: Option<ConfigManifestExtension>,
+    #[serde(flatten)]
+    pub additional: JsonMap,
+}
+
+/// Immutable, cryptographically anchored state transition.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnchorGlyph {
+    pub version: SchemaVersion,
+    pub glyph_id: GlyphId,
+    pub timestamp: i64,
+    pub tenant_id: String,
+    pub context: TargetContext,
+    pub merkle_root: String,
+    pub blake3_hash: String,
+    pub kyber_signature: QuorumSignatureBundle,
+    /// Previous anchor in the chain, or `genesis`.
+    pub previous_glyph_id: ChainLink,
+    pub emitted_by: Daemon,
+    pub receipts: Vec<AnchorReceiptRef>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merkle_proof: Option<EmbeddedMerkleProof>,
+    /// Context-specific payload; its required keys depend on `context`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<JsonMap>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extensions: Option<AnchorExtensions>,
+    #[serde(flatten)]
+    pub additional: JsonMap,
+}
+
+/// `previous_glyph_id` value of the first anchor in a stream.
+pub const GENESIS: &str = "genesis";
+
+impl AnchorGlyph {
+    /// True when this anchor starts a new chain.
+    pub fn is_genesis(&self) -> bool {
+        self.previous_glyph_id == ChainLink::Genesis
+    }
+}
+
+impl Glyph for AnchorGlyph {
+    const KIND: GlyphKind = GlyphKind::Anchor;
+
+    fn id(&self) -> &GlyphId {
+        &self.glyph_id
+    }
+
+    fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+}
+
+// ---------------------------------------------------------------------------
+// DaemonStatusGlyph
+// ---------------------------------------------------------------------------
+
+/// `slo_compliance` object of a DaemonStatusGlyph.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SloCompliance {
+    pub latency_p95_ms: f64,
+    pub entanglement_quality: f64,
+    /// Required by the schema but `null` when the daemon does no ZK work.
+    pub zk_proof_time_ms: Option<f64>,
+    pub anomaly_rate_per_hour: f64,
+}
+
+/// Periodic, signed heartbeat from a Guardian daemon.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DaemonStatusGlyph {
+    pub version: SchemaVersion,
+    pub glyph_id: GlyphId,
+    pub timestamp: i64,
+    pub daemon_name: Daemon,
+    pub guardian: Guardian,
+    pub status: DaemonHealth,
+    pub blake3_hash: String,
+    pub kyber_signature: String,
+    pub uptime_seconds: u64,
+    pub glyphs_emitted: u64,
+    /// Absent, explicit `null`, or an anchor ID. The outer `Option` tracks
+    /// presence so that `null` survives a round trip.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "present_nullable",
+        serialize_with = "serialize_nullable"
+    )]
+    pub last_anchor_glyph_id: Option<Option<GlyphId>>,
+    pub slo_compliance: SloCompliance,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_phase: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub self_diagnosed_lie: Option<bool>,
+}
+
+impl Glyph for DaemonStatusGlyph {
+    const KIND: GlyphKind = GlyphKind::DaemonStatus;
+
+    fn id(&self) -> &GlyphId {
+        &self.glyph_id
+    }
+
+    fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+}
+
+fn present_nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+fn serialize_nullable<S, T>(value: &Option<Option<T>>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize,
+{
+    match value {
+        Some(inner) => inner.serialize(serializer),
+        None => serializer.serialize_none(),
+    }
+}
@@ -0,0 +1,215 @@
+//! EntanglementGlyph: digital-twin-groot's latency-negation prediction.
+//!
+//! The same shape appears in two places on an AnchorGlyph: as the `payload`
+//! of an `entanglement_prediction` anchor, where every member is required,
+//! and as `extensions.entanglement` on any other anchor. Receipts carry only
+//! the prediction half (`correlation_score`, `predicted_negation_ms`,
+//! `bell_state`); the [`SimulationSource`] half stays on the anchor, so
+//! turning a receipt back into a glyph needs both.
+//!
+//! [`EntanglementGlyph::validate`] applies the `[digital-twin-groot]`
+//! objectives from `config/slo.toml` on top of the structural rules.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::anchors::anchor_types::{
+    AnchorGlyph, EntanglementExtension, JsonMap, ReceiptGlyph, TargetContext,
+};
+use crate::anchors::merkle::hash_from_hex;
+use crate::anchors::receipt_payload::{
+    EntanglementPrediction, ReceiptPayload, BELL_STATE_NORM_TOLERANCE,
+};
+use crate::config::slo::{require_at_least, SloConfig};
+use crate::error::{GlyphError, Result};
+
+/// The simulation a prediction came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulationSource {
+    pub scenario_id: String,
+    pub sim_backend: String,
+    /// Hex BLAKE3 hash of the model parameters.
+    pub model_hash: String,
+}
+
+/// A typed entanglement prediction with its simulation provenance.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntanglementGlyph {
+    pub scenario_id: String,
+    pub bell_correlation: f64,
+    pub negation_ms: f64,
+    pub sim_backend: String,
+    pub model_hash: String,
+    /// Real amplitudes over |00⟩, |01⟩, |10⟩, |11⟩.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bell_state: Option<[f64; 4]>,
+    #[serde(flatten)]
+    pub additional: JsonMap,
+}
+
+fn invalid(reason: impl Into<String>) -> GlyphError {
+    GlyphError::InvalidAnchorPayload {
+        context: TargetContext::EntanglementPrediction,
+        reason: reason.into(),
+    }
+}
+
+impl EntanglementGlyph {
+    /// Joins a receipt's prediction with the simulation that produced it.
+    pub fn from_prediction(prediction: &EntanglementPrediction, source: SimulationSource) -> Self {
+        EntanglementGlyph {
+            scenario_id: source.scenario_id,
+            bell_correlation: prediction.correlation_score,
+            negation_ms: prediction.predicted_negation_ms,
+            sim_backend: source.sim_backend,
+            model_hash: source.model_hash,
+            bell_state: prediction.bell_state,
+            additional: JsonMap::new(),
+        }
+    }
+
+    /// Reads an `entanglement_prediction` receipt; its payload rules apply.
+    pub fn from_receipt(receipt: &ReceiptGlyph, source: SimulationSource) -> Result<Self> {
+        match ReceiptPayload::of(receipt)? {
+            ReceiptPayload::EntanglementPrediction(prediction) => {
+                Ok(Self::from_prediction(&prediction, source))
+            }
+            other => Err(GlyphError::InvalidReceiptPayload {
+                receipt_type: other.receipt_type(),
+                reason: "not an entanglement_prediction receipt".into(),
+            }),
+        }
+    }
+
+    /// Reads an anchor's `payload` if it is an `entanglement_prediction`
+    /// anchor, otherwise its `extensions.entanglement`.
+    pub fn from_anchor(anchor: &AnchorGlyph) -> Result<Self> {
+        if anchor.context == TargetContext::EntanglementPrediction {
+            let payload = anchor
+                .payload
+                .clone()
+                .ok_or_else(|| invalid("entanglement_prediction anchor has no payload"))?;
+            return Ok(serde_json::from_value(Value::Object(payload))?);
+        }
+        let extension = anchor
+            .extensions
+            .as_ref()
+            .and_then(|e| e.entanglement.as_ref())
+            .ok_or_else(|| invalid("anchor carries no extensions.entanglement"))?;
+        Self::from_extension(extension)
+    }
+
+    /// Reads `extensions.entanglement`, whose members are all optional there.
+    pub fn from_extension(extension: &EntanglementExtension) -> Result<Self> {
+        fn required<T: Clone>(value: &Option<T>, name: &str) -> Result<T> {
+            value
+                .clone()
+                .ok_or_else(|| invalid(format!("extensions.entanglement has no {name}")))
+        }
+        let mut additional = extension.additional.clone();
+        let bell_state = match additional.remove("bell_state") {
+            Some(value) => Some(serde_json::from_value(value)?),
+            None => None,
+        };
+        Ok(EntanglementGlyph {
+            scenario_id: required(&extension.scenario_id, "scenario_id")?,
+            bell_correlation: required(&extension.bell_correlation, "bell_correlation")?,
+            negation_ms: required(&extension.negation_ms, "negation_ms")?,
+            sim_backend: required(&extension.sim_backend, "sim_backend")?,
+            model_hash: required(&extension.model_hash, "model_hash")?,
+            bell_state,
+            additional,
+        })
+    }
+
+    /// This glyph as `extensions.entanglement`.
+    pub fn to_extension(&self) -> Result<EntanglementExtension> {
+        let mut additional = self.additional.clone();
+        if let Some(bell_state) = self.bell_state {
+            additional.insert("bell_state".into(), serde_json::to_value(bell_state)?);
+        }
+        Ok(EntanglementExtension {
+            scenario_id: Some(self.scenario_id.clone()),
+            bell_correlation: Some(self.bell_correlation),
+            negation_ms: Some(self.negation_ms),
+            sim_backend: Some(self.sim_backend.clone()),
+            model_hash: Some(self.model_hash.clone()),
+            additional,
+        })
+    }
+
+    /// This glyph as the `payload` of an `entanglement_prediction` anchor.
+    pub fn to_payload(&self) -> Result<JsonMap> {
+        match serde_json::to_value(self)? {
+            Value::Object(map) => Ok(map),
+            _ => Err(invalid("glyph did not serialize to a JSON object")),
+        }
+    }
+
+    /// The receipt half of this glyph.
+    pub fn prediction(&self) -> EntanglementPrediction {
+        EntanglementPrediction {
+            correlation_score: self.bell_correlation,
+            predicted_negation_ms: self.negation_ms,
+            bell_state: self.bell_state,
+        }
+    }
+
+    /// The anchor half of this glyph.
+    pub fn source(&self) -> SimulationSource {
+        SimulationSource {
+            scenario_id: self.scenario_id.clone(),
+            sim_backend: self.sim_backend.clone(),
+            model_hash: self.model_hash.clone(),
+        }
+    }
+
+    /// Structural rules, then the `[digital-twin-groot]` objectives.
+    pub fn validate(&self, slo: &SloConfig) -> Result<()> {
+        hash_from_hex(&self.model_hash)?;
+        if !(0.0..=1.0).contains(&self.bell_correlation) {
+            return Err(invalid(format!(
+                "bell_correlation {} is outside [0, 1]",
+                self.bell_correlation
+            )));
+        }
+        if let Some(amplitudes) = self.bell_state {
+            let norm: f64 = amplitudes.iter().map(|a| a * a).sum();
+            if (norm - 1.0).abs() > BELL_STATE_NORM_TOLERANCE {
+                return Err(invalid(format!(
+                    "bell_state has squared norm {norm}, not 1"
+                )));
+            }
+        }
+        check_prediction_slo(self.bell_correlation, self.negation_ms, slo)
+    }
+}
+
+impl From<&EntanglementGlyph> for ReceiptPayload {
+    fn from(glyph: &EntanglementGlyph) -> Self {
+        ReceiptPayload::EntanglementPrediction(glyph.prediction())
+    }
+}
+
+/// Checks a receipt's prediction against the `[digital-twin-groot]` objectives.
+pub fn check_prediction(prediction: &EntanglementPrediction, slo: &SloConfig) -> Result<()> {
+    check_prediction_slo(
+        prediction.correlation_score,
+        prediction.predicted_negation_ms,
+        slo,
+    )
+}
+
+fn check_prediction_slo(correlation: f64, negation_ms: f64, slo: &SloConfig) -> Result<()> {
+    let twin = &slo.digital_twin_groot;
+    require_at_least(
+        "digital-twin-groot.min_entanglement_correlation",
+        correlation,
+        twin.min_entanglement_correlation,
+    )?;
+    require_at_least(
+        "digital-twin-groot.min_predicted_latency_negation_ms",
+        negation_ms,
+        twin.min_predicted_latency_negation_ms,
+    )
+}
@@ -0,0 +1,372 @@
+//! Merkle trees over receipt batches.
+//!
+//! # Tree rules
+//!
+//! - **Leaves** are `BLAKE3(0x00 || item)`, where `item` is normally the
+//!   32-byte `blake3_hash` of a ReceiptGlyph.
+//! - **Inner nodes** are `BLAKE3(0x01 || left || right)`.
+//! - **Odd nodes**: when a level has an odd width, its last node is promoted
+//!   to the next level unchanged. Nodes are never duplicated, so `[a, b, c]`
+//!   and `[a, b, c, c]` have different roots.
+//! - **Empty batch**: the root is `BLAKE3("")`, which can never collide with
+//!   a prefixed leaf or node hash.
+//!
+//! The prefixes stop an inner node from being replayed as a leaf (second
+//! preimage), and promotion removes the duplicate-leaf ambiguity of the
+//! "copy the last node" scheme.
+//!
+//! Because promoted levels contribute no sibling, a verifier needs the
+//! batch's `leaf_count` in addition to `leaf_index` and `siblings`. For
+//! receipts in an AnchorGlyph that is `anchor.receipts.len()`.
+
+use crate::anchors::anchor_types::{EmbeddedMerkleProof, JsonMap, ReceiptGlyph};
+use crate::error::{GlyphError, Result};
+
+/// A 32-byte BLAKE3 digest used as a Merkle node.
+pub type MerkleHash = [u8; 32];
+
+/// Domain prefix for leaf hashes.
+pub const LEAF_PREFIX: u8 = 0x00;
+/// Domain prefix for inner node hashes.
+pub const NODE_PREFIX: u8 = 0x01;
+
+/// Hashes one item into a leaf.
+pub fn leaf_hash(item: &[u8]) -> MerkleHash {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[LEAF_PREFIX]);
+    hasher.update(item);
+    *hasher.finalize().as_bytes()
+}
+
+/// Hashes two children into their parent.
+pub fn node_hash(left: &MerkleHash, right: &MerkleHash) -> MerkleHash {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    *hasher.finalize().as_bytes()
+}
+
+/// Root of an empty batch.
+pub fn empty_root() -> MerkleHash {
+    *blake3::hash(&[]).as_bytes()
+}
+
+/// Parses a 64-character lower-case hex digest.
+pub fn hash_from_hex(s: &str) -> Result<MerkleHash> {
+    let invalid = || GlyphError::InvalidHash {
+        value: s.to_string(),
+    };
+    if s.len() != 64 || !s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')) {
+        return Err(invalid());
+    }
+    let mut out = [0u8; 32];
+    hex::decode_to_slice(s, &mut out).map_err(|_| invalid())?;
+    Ok(out)
+}
+
+/// Lower-case hex encoding of a digest.
+pub fn hash_to_hex(hash: &MerkleHash) -> String {
+    hex::encode(hash)
+}
+
+/// Leaf of a receipt: the leaf hash of its decoded `blake3_hash`.
+pub fn receipt_leaf(receipt: &ReceiptGlyph) -> Result<MerkleHash> {
+    Ok(leaf_hash(&hash_from_hex(&receipt.blake3_hash)?))
+}
+
+fn invalid_proof(reason: &'static str) -> GlyphError {
+    GlyphError::InvalidMerkleProof { reason }
+}
+
+/// A fully materialised Merkle tree, kept level by level.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleTree {
+    /// `levels[0]` are the leaves, the last level holds the root.
+    levels: Vec<Vec<MerkleHash>>,
+}
+
+impl MerkleTree {
+    /// Builds a tree over already leaf-hashed values.
+    pub fn from_leaf_hashes(leaves: Vec<MerkleHash>) -> Self {
+        let mut levels = vec![leaves];
+        while levels.last().is_some_and(|l| l.len() > 1) {
+            let level = levels.last().expect("non-empty");
+            let next = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => node_hash(left, right),
+                    [lone] => *lone,
+                    _ => unreachable!("chunks(2) yields one or two nodes"),
+                })
+                .collect();
+            levels.push(next);
+        }
+        MerkleTree { levels }
+    }
+
+    /// Builds a tree by leaf-hashing every item.
+    pub fn from_items<I, T>(items: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<[u8]>,
+    {
+        Self::from_leaf_hashes(items.into_iter().map(|i| leaf_hash(i.as_ref())).collect())
+    }
+
+    /// Builds a tree over the receipts of one batch, in order.
+    pub fn from_receipts<'a, I>(receipts: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = &'a ReceiptGlyph>,
+    {
+        let leaves = receipts
+            .into_iter()
+            .map(receipt_leaf)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::from_leaf_hashes(leaves))
+    }
+
+    /// Number of leaves.
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.levels[0].is_empty()
+    }
+
+    /// The leaf hashes, in order.
+    pub fn leaves(&self) -> &[MerkleHash] {
+        &self.levels[0]
+    }
+
+    /// Every level from the leaves up to the root, for stores that keep
+    /// inner nodes instead of recomputing them.
+    pub fn levels(&self) -> &[Vec<MerkleHash>] {
+        &self.levels
+    }
+
+    /// Root of the tree.
+    pub fn root(&self) -> MerkleHash {
+        match self.levels.last().and_then(|l| l.first()) {
+            Some(root) => *root,
+            None => empty_root(),
+        }
+    }
+
+    /// Inclusion proof for the leaf at `index`.
+    pub fn proof(&self, index: usize) -> Result<MerkleProof> {
+        if index >= self.len() {
+            return Err(invalid_proof("leaf index out of range"));
+        }
+        let mut siblings = Vec::new();
+        let mut i = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(i ^ 1) {
+                siblings.push(*sibling);
+            }
+            i /= 2;
+        }
+        Ok(MerkleProof {
+            leaf_index: index as u64,
+            leaf_count: self.len() as u64,
+            siblings,
+        })
+    }
+
+    /// Compact proof for several leaves at once.
+    ///
+    /// Only nodes that cannot be recomputed from the proven leaves are
+    /// included, so proving every receipt of an anchor needs no hashes.
+    pub fn multiproof(&self, indices: &[usize]) -> Result<MultiProof> {
+        let mut known = sorted_unique(indices.iter().map(|&i| i as u64))?;
+        if known.last().is_some_and(|&i| i as usize >= self.len()) {
+            return Err(invalid_proof("leaf index out of range"));
+        }
+        let leaf_indices = known.clone();
+        let mut hashes = Vec::new();
+        for level in &self.levels[..self.levels.len() - 1] {
+            let width = level.len() as u64;
+            let mut next = Vec::with_capacity(known.len());
+            let mut k = 0;
+            while k < known.len() {
+                let i = known[k];
+                let sibling = i ^ 1;
+                if sibling >= width {
+                    k += 1;
+                } else if known.get(k + 1) == Some(&sibling) {
+                    k += 2;
+                } else {
+                    hashes.push(level[sibling as usize]);
+                    k += 1;
+                }
+                next.push(i / 2);
+            }
+            known = next;
+        }
+        Ok(MultiProof {
+            leaf_count: self.len() as u64,
+            leaf_indices,
+            hashes,
+        })
+    }
+}
+
+fn sorted_unique(indices: impl Iterator<Item = u64>) -> Result<Vec<u64>> {
+    let mut v: Vec<u64> = indices.collect();
+    if v.is_empty() {
+        return Err(invalid_proof("no leaves to prove"));
+    }
+    v.sort_unstable();
+    v.dedup();
+    Ok(v)
+}
+
+/// Inclusion proof for a single leaf.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProof {
+    pub leaf_index: u64,
+    pub leaf_count: u64,
+    /// Sibling hashes from leaf to root, skipping promoted levels.
+    pub siblings: Vec<MerkleHash>,
+}
+
+impl MerkleProof {
+    /// Recomputes the root implied by `leaf` (already leaf-hashed).
+    pub fn compute_root(&self, leaf: &MerkleHash) -> Result<MerkleHash> {
+        if self.leaf_index >= self.leaf_count {
+            return Err(invalid_proof("leaf index out of range"));
+        }
+        let mut acc = *leaf;
+        let mut i = self.leaf_index;
+        let mut width = self.leaf_count;
+        let mut siblings = self.siblings.iter();
+        while width > 1 {
+            if i ^ 1 < width {
+                let sibling = siblings
+                    .next()
+                    .ok_or_else(|| invalid_proof("too few siblings"))?;
+                acc = if i & 1 == 0 {
+                    node_hash(&acc, sibling)
+                } else {
+                    node_hash(sibling, &acc)
+                };
+            }
+            i /= 2;
+            width = width.div_ceil(2);
+        }
+        if siblings.next().is_some() {
+            return Err(invalid_proof("too many siblings"));
+        }
+        Ok(acc)
+    }
+
+    /// True if `leaf` (already leaf-hashed) is included under `root`.
+    pub fn verify(&self, root: &MerkleHash, leaf: &MerkleHash) -> bool {
+        self.compute_root(leaf).is_ok_and(|r| &r == root)
+    }
+
+    /// True if `receipt` is included under `root`.
+    pub fn verify_receipt(&self, root: &MerkleHash, receipt: &ReceiptGlyph) -> bool {
+        receipt_leaf(receipt).is_ok_and(|leaf| self.verify(root, &leaf))
+    }
+
+    /// The `{leaf_index, siblings}` object stored on glyphs.
+    pub fn to_embedded(&self) -> EmbeddedMerkleProof {
+        EmbeddedMerkleProof {
+            leaf_index: self.leaf_index,
+            siblings: self.siblings.iter().map(hash_to_hex).collect(),
+            additional: JsonMap::new(),
+        }
+    }
+
+    /// Reads a stored proof; `leaf_count` comes from the batch it belongs to.
+    pub fn from_embedded(embedded: &EmbeddedMerkleProof, leaf_count: u64) -> Result<Self> {
+        Ok(MerkleProof {
+            leaf_index: embedded.leaf_index,
+            leaf_count,
+            siblings: embedded
+                .siblings
+                .iter()
+                .map(|s| hash_from_hex(s))
+                .collect::<Result<_>>()?,
+        })
+    }
+}
+
+/// Compact inclusion proof for several leaves of one tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiProof {
+    pub leaf_count: u64,
+    /// Proven leaf positions, sorted and unique.
+    pub leaf_indices: Vec<u64>,
+    /// Missing sibling hashes in the order the verifier consumes them:
+    /// level by level from the leaves up, left to right within a level.
+    pub hashes: Vec<MerkleHash>,
+}
+
+impl MultiProof {
+    /// Recomputes the root from `(leaf_index, leaf_hash)` pairs.
+    ///
+    /// The indices must be exactly `leaf_indices`.
+    pub fn compute_root(&self, leaves: &[(u64, MerkleHash)]) -> Result<MerkleHash> {
+        let mut known: Vec<(u64, MerkleHash)> = leaves.to_vec();
+        known.sort_unstable_by_key(|(i, _)| *i);
+        known.dedup_by_key(|(i, _)| *i);
+        if known.len() != leaves.len()
+            || known
+                .iter()
+                .map(|(i, _)| *i)
+                .ne(self.leaf_indices.iter().copied())
+        {
+            return Err(invalid_proof("leaves do not match proven indices"));
+        }
+        if known.is_empty() {
+            return Err(invalid_proof("no leaves to prove"));
+        }
+        if known.last().is_some_and(|(i, _)| *i >= self.leaf_count) {
+            return Err(invalid_proof("leaf index out of range"));
+        }
+
+        let mut hashes = self.hashes.iter();
+        let mut width = self.leaf_count;
+        while width > 1 {
+            let mut next = Vec::with_capacity(known.len());
+            let mut k = 0;
+            while k < known.len() {
+                let (i, hash) = known[k];
+                let sibling = i ^ 1;
+                let parent = if sibling >= width {
+                    k += 1;
+                    hash
+                } else if known.get(k + 1).map(|(j, _)| *j) == Some(sibling) {
+                    let right = known[k + 1].1;
+                    k += 2;
+                    node_hash(&hash, &right)
+                } else {
+                    let other = hashes
+                        .next()
+                        .ok_or_else(|| invalid_proof("too few proof hashes"))?;
+                    k += 1;
+                    if i & 1 == 0 {
+                        node_hash(&hash, other)
+                    } else {
+                        node_hash(other, &hash)
+                    }
+                };
+                next.push((i / 2, parent));
+            }
+            known = next;
+            width = width.div_ceil(2);
+        }
+        if hashes.next().is_some() {
+            return Err(invalid_proof("too many proof hashes"));
+        }
+        Ok(known[0].1)
+    }
+
+    /// True if every `(leaf_index, leaf_hash)` pair is included under `root`.
+    pub fn verify(&self, root: &MerkleHash, leaves: &[(u64, MerkleHash)]) -> bool {
+        self.compute_root(leaves).is_ok_and(|r| &r == root)
+    }
+}
@@ -0,0 +1,352 @@
+//! Merkle Mountain Range over the append-only receipts log.
+//!
+//! # Structure
+//!
+//! A log of `n` leaves is split into perfect binary trees ("mountains"), one
+//! per set bit of `n`, largest first. Leaves and inner nodes use the same
+//! domain-separated hashes as [`merkle`](super::merkle). Appending only ever
+//! adds nodes, so every historical mountain is still present later.
+//!
+//! The root of a log of `n` leaves bags its peaks left to right:
+//!
+//! ```text
+//! BLAKE3(0x02 || n as u64 big-endian || peak_0 || peak_1 || ...)
+//! ```
+//!
+//! # Proofs
+//!
+//! Both proof kinds carry a list of hashes consumed by the same walk: for
+//! each mountain of the target size, left to right, descend from its root
+//! and take one hash for every subtree that contains no node the verifier
+//! already knows.
+//!
+//! - **Inclusion**: the verifier knows one leaf.
+//! - **Consistency** (size `m` is a prefix of size `n`): the verifier knows
+//!   the `m` peaks, checks they bag to the old root, and rebuilds the new
+//!   root from them.
+//!
+//! AnchorGlyphs publish their log state in `extensions.mmr`, which is
+//! covered by `blake3_hash`, so a `previous_glyph_id` link can be checked
+//! with [`verify_anchor_link`].
+
+use crate::anchors::anchor_types::{AnchorGlyph, ReceiptGlyph};
+use crate::anchors::merkle::{hash_from_hex, leaf_hash, node_hash, receipt_leaf, MerkleHash};
+use crate::error::{GlyphError, Result};
+use crate::hashing::ids::ChainLink;
+
+/// Domain prefix for bagging peaks into a root.
+pub const PEAK_BAG_PREFIX: u8 = 0x02;
+
+/// Root of a log of `leaf_count` leaves with the given peaks.
+pub fn bag_peaks(leaf_count: u64, peaks: &[MerkleHash]) -> MerkleHash {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[PEAK_BAG_PREFIX]);
+    hasher.update(&leaf_count.to_be_bytes());
+    for peak in peaks {
+        hasher.update(peak);
+    }
+    *hasher.finalize().as_bytes()
+}
+
+/// `(height, index)` of every mountain of a log of `leaf_count` leaves.
+///
+/// A node at `(h, j)` is the root of leaves `j << h .. (j + 1) << h`.
+pub fn mountains(leaf_count: u64) -> Vec<(u32, u64)> {
+    let mut out = Vec::new();
+    let mut start = 0u64;
+    for h in (0..u64::BITS).rev() {
+        if leaf_count & (1 << h) != 0 {
+            out.push((h, start >> h));
+            start += 1 << h;
+        }
+    }
+    out
+}
+
+fn invalid_proof(reason: &'static str) -> GlyphError {
+    GlyphError::InvalidMerkleProof { reason }
+}
+
+/// True if node `(h, j)` lies inside the subtree rooted at `(root_h, root_j)`.
+fn contains(root_h: u32, root_j: u64, h: u32, j: u64) -> bool {
+    h <= root_h && j >> (root_h - h) == root_j
+}
+
+/// Append-only MMR accumulator.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Mmr {
+    /// `levels[h][j]` is the node at `(h, j)`; `levels[0]` are the leaves.
+    levels: Vec<Vec<MerkleHash>>,
+}
+
+impl Mmr {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rebuilds an accumulator from already leaf-hashed values.
+    pub fn from_leaf_hashes<I: IntoIterator<Item = MerkleHash>>(leaves: I) -> Self {
+        let mut mmr = Self::new();
+        for leaf in leaves {
+            mmr.append_leaf_hash(leaf);
+        }
+        mmr
+    }
+
+    /// Appends an already leaf-hashed value and returns its leaf index.
+    pub fn append_leaf_hash(&mut self, leaf: MerkleHash) -> u64 {
+        let index = self.leaf_count();
+        let mut h = 0;
+        let mut node = leaf;
+        loop {
+            if self.levels.len() == h {
+                self.levels.push(Vec::new());
+            }
+            let level = &mut self.levels[h];
+            level.push(node);
+            if level.len() % 2 == 1 {
+                break;
+            }
+            node = node_hash(&level[level.len() - 2], &level[level.len() - 1]);
+            h += 1;
+        }
+        index
+    }
+
+    /// Leaf-hashes `item`, appends it and returns its leaf index.
+    pub fn append(&mut self, item: &[u8]) -> u64 {
+        self.append_leaf_hash(leaf_hash(item))
+    }
+
+    /// Appends a receipt by its `blake3_hash` and returns its leaf index.
+    pub fn append_receipt(&mut self, receipt: &ReceiptGlyph) -> Result<u64> {
+        Ok(self.append_leaf_hash(receipt_leaf(receipt)?))
+    }
+
+    /// Number of leaves appended so far.
+    pub fn leaf_count(&self) -> u64 {
+        self.levels.first().map_or(0, |l| l.len() as u64)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaf_count() == 0
+    }
+
+    fn node(&self, h: u32, j: u64) -> MerkleHash {
+        self.levels[h as usize][j as usize]
+    }
+
+    fn check_size(&self, size: u64) -> Result<()> {
+        if size > self.leaf_count() {
+            return Err(invalid_proof("size exceeds the accumulator"));
+        }
+        Ok(())
+    }
+
+    /// Peaks of the log as it was at `size` leaves.
+    pub fn peaks_at(&self, size: u64) -> Result<Vec<MerkleHash>> {
+        self.check_size(size)?;
+        Ok(mountains(size)
+            .into_iter()
+            .map(|(h, j)| self.node(h, j))
+            .collect())
+    }
+
+    /// Root of the log as it was at `size` leaves.
+    pub fn root_at(&self, size: u64) -> Result<MerkleHash> {
+        Ok(bag_peaks(size, &self.peaks_at(size)?))
+    }
+
+    /// Current root.
+    pub fn root(&self) -> MerkleHash {
+        let n = self.leaf_count();
+        bag_peaks(n, &self.peaks_at(n).expect("current size is in range"))
+    }
+
+    /// Hashes a verifier holding `known` needs to rebuild the root at `size`.
+    fn walk_hashes(&self, size: u64, known: &[(u32, u64)]) -> Vec<MerkleHash> {
+        let mut out = Vec::new();
+        for (h, j) in mountains(size) {
+            self.collect(h, j, known, &mut out);
+        }
+        out
+    }
+
+    fn collect(&self, h: u32, j: u64, known: &[(u32, u64)], out: &mut Vec<MerkleHash>) {
+        if known.contains(&(h, j)) {
+            return;
+        }
+        if !known.iter().any(|&(kh, kj)| contains(h, j, kh, kj)) {
+            out.push(self.node(h, j));
+            return;
+        }
+        self.collect(h - 1, 2 * j, known, out);
+        self.collect(h - 1, 2 * j + 1, known, out);
+    }
+
+    /// Proof that leaf `leaf_index` is included in the log at `size` leaves.
+    pub fn inclusion_proof(&self, leaf_index: u64, size: u64) -> Result<MmrInclusionProof> {
+        self.check_size(size)?;
+        if leaf_index >= size {
+            return Err(invalid_proof("leaf index out of range"));
+        }
+        Ok(MmrInclusionProof {
+            leaf_index,
+            leaf_count: size,
+            hashes: self.walk_hashes(size, &[(0, leaf_index)]),
+        })
+    }
+
+    /// Proof that the log at `old_size` is a prefix of the log at `new_size`.
+    pub fn consistency_proof(&self, old_size: u64, new_size: u64) -> Result<MmrConsistencyProof> {
+        self.check_size(new_size)?;
+        if old_size > new_size {
+            return Err(invalid_proof("old size exceeds new size"));
+        }
+        Ok(MmrConsistencyProof {
+            old_size,
+            new_size,
+            old_peaks: self.peaks_at(old_size)?,
+            hashes: self.walk_hashes(new_size, &mountains(old_size)),
+        })
+    }
+}
+
+/// Rebuilds the root at `size` from known nodes and the proof's hashes.
+fn rebuild_root(
+    size: u64,
+    known: &[((u32, u64), MerkleHash)],
+    hashes: &[MerkleHash],
+) -> Result<MerkleHash> {
+    fn walk<'a>(
+        h: u32,
+        j: u64,
+        known: &[((u32, u64), MerkleHash)],
+        hashes: &mut impl Iterator<Item = &'a MerkleHash>,
+    ) -> Result<MerkleHash> {
+        if let Some((_, hash)) = known.iter().find(|(pos, _)| *pos == (h, j)) {
+            return Ok(*hash);
+        }
+        if !known.iter().any(|&((kh, kj), _)| contains(h, j, kh, kj)) {
+            return hashes
+                .next()
+                .copied()
+                .ok_or_else(|| invalid_proof("too few proof hashes"));
+        }
+        let left = walk(h - 1, 2 * j, known, hashes)?;
+        let right = walk(h - 1, 2 * j + 1, known, hashes)?;
+        Ok(node_hash(&left, &right))
+    }
+
+    let mut iter = hashes.iter();
+    let peaks = mountains(size)
+        .into_iter()
+        .map(|(h, j)| walk(h, j, known, &mut iter))
+        .collect::<Result<Vec<_>>>()?;
+    if iter.next().is_some() {
+        return Err(invalid_proof("too many proof hashes"));
+    }
+    Ok(bag_peaks(size, &peaks))
+}
+
+/// Inclusion proof for one leaf against a historical root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MmrInclusionProof {
+    pub leaf_index: u64,
+    /// Size of the log the proof is against.
+    pub leaf_count: u64,
+    pub hashes: Vec<MerkleHash>,
+}
+
+impl MmrInclusionProof {
+    /// Recomputes the root implied by `leaf` (already leaf-hashed).
+    pub fn compute_root(&self, leaf: &MerkleHash) -> Result<MerkleHash> {
+        if self.leaf_index >= self.leaf_count {
+            return Err(invalid_proof("leaf index out of range"));
+        }
+        rebuild_root(
+            self.leaf_count,
+            &[((0, self.leaf_index), *leaf)],
+            &self.hashes,
+        )
+    }
+
+    /// True if `leaf` (already leaf-hashed) is included under `root`.
+    pub fn verify(&self, root: &MerkleHash, leaf: &MerkleHash) -> bool {
+        self.compute_root(leaf).is_ok_and(|r| &r == root)
+    }
+}
+
+/// Proof that one log state is a prefix of a later one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MmrConsistencyProof {
+    pub old_size: u64,
+    pub new_size: u64,
+    /// Peaks of the old log, largest mountain first.
+    pub old_peaks: Vec<MerkleHash>,
+    pub hashes: Vec<MerkleHash>,
+}
+
+impl MmrConsistencyProof {
+    /// True if `old_root` at `old_size` is a prefix of `new_root` at `new_size`.
+    pub fn verify(&self, old_root: &MerkleHash, new_root: &MerkleHash) -> bool {
+        self.check(old_root, new_root).is_ok()
+    }
+
+    fn check(&self, old_root: &MerkleHash, new_root: &MerkleHash) -> Result<()> {
+        if self.old_size > self.new_size {
+            return Err(invalid_proof("old size exceeds new size"));
+        }
+        let positions = mountains(self.old_size);
+        if positions.len() != self.old_peaks.len() {
+            return Err(invalid_proof("wrong number of old peaks"));
+        }
+        if &bag_peaks(self.old_size, &self.old_peaks) != old_root {
+            return Err(invalid_proof("old peaks do not match the old root"));
+        }
+        let known: Vec<_> = positions
+            .into_iter()
+            .zip(self.old_peaks.iter().copied())
+            .collect();
+        if &rebuild_root(self.new_size, &known, &self.hashes)? != new_root {
+            return Err(invalid_proof("new root does not extend the old root"));
+        }
+        Ok(())
+    }
+}
+
+/// Reads the `(leaf_count, root)` an anchor publishes in `extensions.mmr`.
+pub fn anchor_mmr_state(anchor: &AnchorGlyph) -> Result<(u64, MerkleHash)> {
+    let mmr = anchor
+        .extensions
+        .as_ref()
+        .and_then(|e| e.mmr.as_ref())
+        .ok_or(GlyphError::BrokenChain {
+            reason: "anchor does not publish extensions.mmr",
+        })?;
+    Ok((mmr.leaf_count, hash_from_hex(&mmr.root)?))
+}
+
+/// Verifies that `next` extends `prev`.
+///
+/// `next.previous_glyph_id` must name `prev`, and `proof` must show that
+/// the log state published by `prev` is a prefix of the one in `next`.
+pub fn verify_anchor_link(
+    prev: &AnchorGlyph,
+    next: &AnchorGlyph,
+    proof: &MmrConsistencyProof,
+) -> Result<()> {
+    if next.previous_glyph_id != ChainLink::Anchor(prev.glyph_id) {
+        return Err(GlyphError::BrokenChain {
+            reason: "previous_glyph_id does not name the previous anchor",
+        });
+    }
+    let (old_size, old_root) = anchor_mmr_state(prev)?;
+    let (new_size, new_root) = anchor_mmr_state(next)?;
+    if proof.old_size != old_size || proof.new_size != new_size {
+        return Err(GlyphError::BrokenChain {
+            reason: "consistency proof sizes do not match the anchors",
+        });
+    }
+    proof.check(&old_root, &new_root)
+}
@@ -0,0 +1,208 @@
+//! OrbitalAnchorGlyph: an AnchorGlyph whose context is `orbital_verify`.
+//!
+//! The schema only requires `orbit_slot`, `sat_id`, `link_id` and a
+//! `measurement` of `drift_mm`, `snr_db` and `rtt_ms`; everything else in
+//! the payload rides along in `additional` so a round trip is lossless.
+//!
+//! An `orbital_telemetry` receipt is one measurement of one satellite: its
+//! `satellite_id` is the payload's `sat_id` and its `latency_ms` the
+//! `rtt_ms`. The receipt has no slot, link or beam offsets, so those travel
+//! in [`OrbitalLink`] when building a payload from a receipt, and the
+//! receipt's `signal_strength_dbm` must be present in the measurement to go
+//! the other way.
+//!
+//! [`OrbitalAnchorGlyph::validate`] applies `[nebula-guard]
+//! max_drift_percent` from `config/slo.toml`, plus the
+//! `[digital-twin-groot]` objectives when the anchor carries an
+//! entanglement extension.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::anchors::anchor_types::{AnchorGlyph, JsonMap, ReceiptGlyph, TargetContext};
+use crate::anchors::entanglement::EntanglementGlyph;
+use crate::anchors::receipt_payload::{OrbitalTelemetry, ReceiptPayload};
+use crate::config::slo::{require_at_most, SloConfig};
+use crate::error::{GlyphError, Result};
+
+/// `payload.measurement` of an `orbital_verify` anchor.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrbitalMeasurement {
+    /// Offset from the expected orbit or beam position, in millimetres.
+    pub drift_mm: f64,
+    pub snr_db: f64,
+    pub rtt_ms: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signal_strength_dbm: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drift_percent: Option<f64>,
+    #[serde(flatten)]
+    pub additional: JsonMap,
+}
+
+/// `payload` of an `orbital_verify` anchor.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrbitalPayload {
+    pub orbit_slot: String,
+    pub sat_id: String,
+    pub link_id: String,
+    pub measurement: OrbitalMeasurement,
+    #[serde(flatten)]
+    pub additional: JsonMap,
+}
+
+/// The parts of an orbital payload an `orbital_telemetry` receipt lacks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrbitalLink {
+    pub orbit_slot: String,
+    pub link_id: String,
+    pub drift_mm: f64,
+    pub snr_db: f64,
+}
+
+fn invalid(reason: impl Into<String>) -> GlyphError {
+    GlyphError::InvalidAnchorPayload {
+        context: TargetContext::OrbitalVerify,
+        reason: reason.into(),
+    }
+}
+
+impl OrbitalPayload {
+    /// Builds a payload around one receipt's measurement.
+    pub fn from_telemetry(telemetry: &OrbitalTelemetry, link: OrbitalLink) -> Self {
+        OrbitalPayload {
+            orbit_slot: link.orbit_slot,
+            sat_id: telemetry.satellite_id.clone(),
+            link_id: link.link_id,
+            measurement: OrbitalMeasurement {
+                drift_mm: link.drift_mm,
+                snr_db: link.snr_db,
+                rtt_ms: telemetry.latency_ms,
+                signal_strength_dbm: Some(telemetry.signal_strength_dbm),
+                drift_percent: telemetry.drift_percent,
+                additional: JsonMap::new(),
+            },
+            additional: JsonMap::new(),
+        }
+    }
+
+    /// Reads an `orbital_telemetry` receipt; its payload rules apply.
+    pub fn from_receipt(receipt: &ReceiptGlyph, link: OrbitalLink) -> Result<Self> {
+        match ReceiptPayload::of(receipt)? {
+            ReceiptPayload::OrbitalTelemetry(telemetry) => {
+                Ok(Self::from_telemetry(&telemetry, link))
+            }
+            other => Err(GlyphError::InvalidReceiptPayload {
+                receipt_type: other.receipt_type(),
+                reason: "not an orbital_telemetry receipt".into(),
+            }),
+        }
+    }
+
+    /// The `orbital_telemetry` receipt payload for this measurement.
+    pub fn telemetry(&self) -> Result<OrbitalTelemetry> {
+        let signal_strength_dbm = self
+            .measurement
+            .signal_strength_dbm
+            .ok_or_else(|| invalid("measurement has no signal_strength_dbm"))?;
+        Ok(OrbitalTelemetry {
+            satellite_id: self.sat_id.clone(),
+            signal_strength_dbm,
+            latency_ms: self.measurement.rtt_ms,
+            drift_percent: self.measurement.drift_percent,
+        })
+    }
+}
+
+/// An `orbital_verify` AnchorGlyph with its payload decoded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrbitalAnchorGlyph {
+    anchor: AnchorGlyph,
+    payload: OrbitalPayload,
+}
+
+impl OrbitalAnchorGlyph {
+    /// Pairs an anchor with a payload, replacing whatever payload it had.
+    ///
+    /// The anchor's `context` is set to `orbital_verify`; hashes and
+    /// signatures are left as they are, so build before hashing.
+    pub fn new(mut anchor: AnchorGlyph, payload: OrbitalPayload) -> Result<Self> {
+        anchor.context = TargetContext::OrbitalVerify;
+        anchor.payload = Some(match serde_json::to_value(&payload)? {
+            Value::Object(map) => map,
+            _ => return Err(invalid("payload did not serialize to a JSON object")),
+        });
+        Ok(OrbitalAnchorGlyph { anchor, payload })
+    }
+
+    pub fn anchor(&self) -> &AnchorGlyph {
+        &self.anchor
+    }
+
+    pub fn payload(&self) -> &OrbitalPayload {
+        &self.payload
+    }
+
+    /// The entanglement prediction in `extensions.entanglement`, if any.
+    pub fn entanglement(&self) -> Result<Option<EntanglementGlyph>> {
+        match self
+            .anchor
+            .extensions
+            .as_ref()
+            .and_then(|e| e.entanglement.as_ref())
+        {
+            Some(extension) => EntanglementGlyph::from_extension(extension).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Structural rules, then the `[nebula-guard]` drift objective and,
+    /// if present, the entanglement extension's objectives.
+    pub fn validate(&self, slo: &SloConfig) -> Result<()> {
+        let measurement = &self.payload.measurement;
+        if !measurement.rtt_ms.is_finite() || measurement.rtt_ms < 0.0 {
+            return Err(invalid(format!(
+                "rtt_ms {} is not a finite non-negative duration",
+                measurement.rtt_ms
+            )));
+        }
+        if let Some(drift_percent) = measurement.drift_percent {
+            if !drift_percent.is_finite() || drift_percent < 0.0 {
+                return Err(invalid(format!(
+                    "drift_percent {drift_percent} is not finite and non-negative"
+                )));
+            }
+            require_at_most(
+                "nebula-guard.max_drift_percent",
+                drift_percent,
+                slo.nebula_guard.max_drift_percent,
+            )?;
+        }
+        if let Some(entanglement) = self.entanglement()? {
+            entanglement.validate(slo)?;
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<AnchorGlyph> for OrbitalAnchorGlyph {
+    type Error = GlyphError;
+
+    fn try_from(anchor: AnchorGlyph) -> Result<Self> {
+        if anchor.context != TargetContext::OrbitalVerify {
+            return Err(invalid(format!("anchor context is {}", anchor.context)));
+        }
+        let payload = anchor
+            .payload
+            .clone()
+            .ok_or_else(|| invalid("anchor has no payload"))?;
+        let payload = serde_json::from_value(Value::Object(payload))?;
+        Ok(OrbitalAnchorGlyph { anchor, payload })
+    }
+}
+
+impl From<OrbitalAnchorGlyph> for AnchorGlyph {
+    fn from(glyph: OrbitalAnchorGlyph) -> Self {
+        glyph.anchor
+    }
+}
@@ -0,0 +1,359 @@
+//! Weighted Guardian quorum over AnchorGlyph finalization.
+//!
+//! Each Guardian signs either [`anchor_signing_message`] (approve) or
+//! [`anchor_rejection_message`] (reject) for the anchor, and the entries are
+//! collected in its `kyber_signature` bundle. An anchor is final when:
+//!
+//! 1. every entry names a known Guardian at most once and verifies under the
+//!    key its `public_key_id` points to;
+//! 2. nothing in the bundle is unsigned: entry metadata holds only the
+//!    Guardian's own `role` and its `verdict`, and the counters and bundle
+//!    metadata are exactly what [`assemble_bundle`] derives from the entries;
+//! 3. no Guardian with `veto_power` rejected it;
+//! 4. Drax approved it, if it anchors an anomaly and the policy sets
+//!    `require_drax_approval_on_anomaly`;
+//! 5. approving weight / total weight reaches `min_acceptance_score`;
+//! 6. at least one approver has `can_finalize`.
+//!
+//! Weights, flags and the threshold come from `config/agents/swarm_roles.yaml`.
+//! Guardians without a role there carry no weight, and a policy whose
+//! weights overflow `u32` is rejected, so every sum of distinct roles fits.
+//! In bundles assembled here, `quorum_threshold` and `quorum_observed` count
+//! weight units, not signers.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::anchors::anchor_types::{
+    AnchorGlyph, Guardian, GuardianSignature, JsonMap, QuorumSignatureBundle, ReceiptResult,
+    SignatureScheme, TargetContext, Verdict,
+};
+use crate::error::{GlyphError, Result};
+use crate::pq::envelope::{
+    anchor_rejection_message, anchor_signing_message, GlyphSigner, SignatureField,
+    SignatureVerifier,
+};
+
+/// One role from `swarm_roles.yaml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GuardianRole {
+    pub weight: u32,
+    pub can_finalize: bool,
+    pub veto_power: bool,
+}
+
+/// Weights and thresholds a quorum is checked against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuorumPolicy {
+    roles: BTreeMap<Guardian, GuardianRole>,
+    total_weight: u32,
+    min_acceptance_score: f64,
+    drax_approval_on_anomaly: bool,
+}
+
+#[derive(Deserialize)]
+struct SwarmRolesFile {
+    roles: BTreeMap<String, RoleEntry>,
+    selection: SelectionEntry,
+}
+
+#[derive(Deserialize)]
+struct RoleEntry {
+    weight: u32,
+    #[serde(default)]
+    can_finalize: bool,
+    #[serde(default)]
+    veto_power: bool,
+}
+
+#[derive(Deserialize)]
+struct SelectionEntry {
+    min_acceptance_score: f64,
+    #[serde(default)]
+    require_drax_approval_on_anomaly: bool,
+}
+
+impl QuorumPolicy {
+    pub fn new(roles: BTreeMap<Guardian, GuardianRole>, min_acceptance_score: f64) -> Result<Self> {
+        if !(min_acceptance_score > 0.0 && min_acceptance_score <= 1.0) {
+            return Err(GlyphError::InvalidPolicy {
+                reason: "min_acceptance_score must be in (0, 1]".to_string(),
+            });
+        }
+        let total_weight = roles
+            .values()
+            .try_fold(0u32, |sum, r| sum.checked_add(r.weight))
+            .ok_or_else(|| GlyphError::InvalidPolicy {
+                reason: "role weights overflow u32".to_string(),
+            })?;
+        if total_weight == 0 {
+            return Err(GlyphError::InvalidPolicy {
+                reason: "roles carry no weight".to_string(),
+            });
+        }
+        Ok(QuorumPolicy {
+            roles,
+            total_weight,
+            min_acceptance_score,
+            drax_approval_on_anomaly: false,
+        })
+    }
+
+    /// Requires Drax among the approvers of anomaly anchors.
+    pub fn with_drax_approval_on_anomaly(mut self, required: bool) -> Self {
+        self.drax_approval_on_anomaly = required;
+        self
+    }
+
+    /// Parses the contents of `config/agents/swarm_roles.yaml`.
+    pub fn from_swarm_roles_yaml(yaml: &str) -> Result<Self> {
+        let file: SwarmRolesFile =
+            serde_yaml::from_str(yaml).map_err(|e| GlyphError::InvalidPolicy {
+                reason: e.to_string(),
+            })?;
+        let roles = file
+            .roles
+            .into_iter()
+            .map(|(name, entry)| {
+                let guardian = name.parse::<Guardian>()?;
+                Ok((
+                    guardian,
+                    GuardianRole {
+                        weight: entry.weight,
+                        can_finalize: entry.can_finalize,
+                        veto_power: entry.veto_power,
+                    },
+                ))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self::new(roles, file.selection.min_acceptance_score)?
+            .with_drax_approval_on_anomaly(file.selection.require_drax_approval_on_anomaly))
+    }
+
+    pub fn role(&self, guardian: Guardian) -> Option<&GuardianRole> {
+        self.roles.get(&guardian)
+    }
+
+    pub fn min_acceptance_score(&self) -> f64 {
+        self.min_acceptance_score
+    }
+
+    pub fn drax_approval_on_anomaly(&self) -> bool {
+        self.drax_approval_on_anomaly
+    }
+
+    /// Sum of every role's weight.
+    pub fn total_weight(&self) -> u32 {
+        self.total_weight
+    }
+
+    /// Summed weight of `guardians`, each counted once.
+    fn weight_of(&self, guardians: impl IntoIterator<Item = Guardian>) -> u32 {
+        guardians
+            .into_iter()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter_map(|g| self.role(g))
+            .map(|r| r.weight)
+            .sum()
+    }
+
+    /// Smallest approving weight that meets `min_acceptance_score`.
+    pub fn required_weight(&self) -> u32 {
+        // Shave off float noise so e.g. 0.9 * 10 still needs 9, not 10.
+        (self.min_acceptance_score * f64::from(self.total_weight()) - 1e-9).ceil() as u32
+    }
+}
+
+/// Looks up the verifying key behind a bundle entry.
+pub trait GuardianKeyring {
+    fn guardian_key(
+        &self,
+        guardian: Guardian,
+        public_key_id: &str,
+    ) -> Option<&dyn SignatureVerifier>;
+}
+
+impl GuardianKeyring for BTreeMap<(Guardian, String), Box<dyn SignatureVerifier>> {
+    fn guardian_key(
+        &self,
+        guardian: Guardian,
+        public_key_id: &str,
+    ) -> Option<&dyn SignatureVerifier> {
+        self.get(&(guardian, public_key_id.to_string()))
+            .map(|k| k.as_ref())
+    }
+}
+
+fn vote_message(anchor: &AnchorGlyph, verdict: Verdict) -> Result<Vec<u8>> {
+    match verdict {
+        Verdict::Approve => anchor_signing_message(anchor),
+        Verdict::Reject => anchor_rejection_message(anchor),
+    }
+}
+
+/// Signs one Guardian's vote on `anchor`.
+pub fn sign_vote(
+    anchor: &AnchorGlyph,
+    guardian: Guardian,
+    public_key_id: &str,
+    verdict: Verdict,
+    signer: &dyn GlyphSigner,
+) -> Result<GuardianSignature> {
+    let envelope = signer.sign(&vote_message(anchor, verdict)?);
+    let mut metadata = JsonMap::new();
+    metadata.insert("role".into(), Value::from(guardian.as_str()));
+    metadata.insert("verdict".into(), Value::from(verdict.as_str()));
+    Ok(GuardianSignature {
+        guardian_id: guardian.handle(),
+        public_key_id: public_key_id.to_string(),
+        signature: envelope.to_hex(),
+        metadata: Some(metadata),
+    })
+}
+
+/// Collects votes into a bundle with weight-based quorum counters.
+pub fn assemble_bundle(
+    policy: &QuorumPolicy,
+    signatures: Vec<GuardianSignature>,
+) -> QuorumSignatureBundle {
+    let observed = policy.weight_of(
+        signatures
+            .iter()
+            .filter(|s| matches!(s.verdict(), Ok(Verdict::Approve)))
+            .filter_map(|s| Guardian::from_handle(&s.guardian_id)),
+    );
+    let mut metadata = JsonMap::new();
+    metadata.insert("quorum_policy".into(), Value::from("weighted"));
+    metadata.insert(
+        "min_acceptance_score".into(),
+        Value::from(policy.min_acceptance_score),
+    );
+    QuorumSignatureBundle {
+        scheme: SignatureScheme::Kyber1024Compatible,
+        quorum_threshold: policy.required_weight(),
+        quorum_observed: observed,
+        signatures,
+        metadata: Some(metadata),
+        additional: JsonMap::new(),
+    }
+}
+
+/// Result of a successful quorum check.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuorumOutcome {
+    pub approvers: Vec<Guardian>,
+    /// Guardians without veto power who rejected.
+    pub dissenters: Vec<Guardian>,
+    pub approved_weight: u32,
+    pub total_weight: u32,
+    /// `approved_weight / total_weight`.
+    pub score: f64,
+}
+
+/// Whether `anchor` records an anomaly: a ZK anomaly share, or a receipt
+/// whose result is `anomaly` or `fraud`.
+fn anchors_anomaly(anchor: &AnchorGlyph) -> bool {
+    anchor.context == TargetContext::ZkAnomalyShare
+        || anchor
+            .receipts
+            .iter()
+            .any(|r| matches!(r.result, ReceiptResult::Anomaly | ReceiptResult::Fraud))
+}
+
+/// Checks an anchor's quorum bundle against `policy`.
+pub fn verify_quorum(
+    anchor: &AnchorGlyph,
+    policy: &QuorumPolicy,
+    keyring: &dyn GuardianKeyring,
+) -> Result<QuorumOutcome> {
+    let mut seen = BTreeSet::new();
+    let mut approvers = Vec::new();
+    let mut dissenters = Vec::new();
+    let mut vetoed_by = None;
+
+    for entry in &anchor.kyber_signature.signatures {
+        let guardian = Guardian::from_handle(&entry.guardian_id).ok_or_else(|| {
+            GlyphError::UnknownVariant {
+                kind: "Guardian",
+                value: entry.guardian_id.clone(),
+            }
+        })?;
+        if !seen.insert(guardian) {
+            return Err(GlyphError::BadSignature {
+                reason: "guardian signed the bundle more than once",
+            });
+        }
+        let verdict = entry.verdict()?;
+        if let Some(metadata) = &entry.metadata {
+            let bound = metadata.iter().all(|(k, v)| match k.as_str() {
+                "role" => v.as_str() == Some(guardian.as_str()),
+                "verdict" => true,
+                _ => false,
+            });
+            if !bound {
+                return Err(GlyphError::BadSignature {
+                    reason: "entry metadata is not covered by its signature",
+                });
+            }
+        }
+        let key = keyring.guardian_key(guardian, &entry.public_key_id).ok_or(
+            GlyphError::BadSignature {
+                reason: "no key for guardian public_key_id",
+            },
+        )?;
+        let envelope = SignatureField::parse(&entry.signature)?.into_envelope()?;
+        key.verify(&vote_message(anchor, verdict)?, &envelope)?;
+
+        let role = policy.role(guardian);
+        match verdict {
+            Verdict::Approve => approvers.push(guardian),
+            Verdict::Reject if role.is_some_and(|r| r.veto_power) => {
+                vetoed_by.get_or_insert(guardian);
+            }
+            Verdict::Reject => dissenters.push(guardian),
+        }
+    }
+
+    if anchor.kyber_signature != assemble_bundle(policy, anchor.kyber_signature.signatures.clone())
+    {
+        return Err(GlyphError::BadSignature {
+            reason: "bundle counters or metadata do not match its entries",
+        });
+    }
+    if let Some(guardian) = vetoed_by {
+        return Err(GlyphError::QuorumVetoed { guardian });
+    }
+    if policy.drax_approval_on_anomaly
+        && anchors_anomaly(anchor)
+        && !approvers.contains(&Guardian::Drax)
+    {
+        return Err(GlyphError::QuorumMissingApproval {
+            guardian: Guardian::Drax,
+        });
+    }
+    let approved_weight = policy.weight_of(approvers.iter().copied());
+    let total_weight = policy.total_weight();
+    let score = f64::from(approved_weight) / f64::from(total_weight);
+    if approved_weight < policy.required_weight() {
+        return Err(GlyphError::QuorumNotMet {
+            score,
+            required: policy.min_acceptance_score,
+        });
+    }
+    if !approvers
+        .iter()
+        .any(|g| policy.role(*g).is_some_and(|r| r.can_finalize))
+    {
+        return Err(GlyphError::QuorumNotFinalized);
+    }
+    Ok(QuorumOutcome {
+        approvers,
+        dissenters,
+        approved_weight,
+        total_weight,
+        score,
+    })
+}
@@ -0,0 +1,361 @@
+//! Typed, per-`receipt_type` views of a ReceiptGlyph's details.
+//!
+//! [`ReceiptGlyph`] keeps its type-specific members in a flattened map so it
+//! re-encodes byte for byte. [`ReceiptPayload`] decodes that map into one
+//! struct per `receipt_type` and applies the rules a JSON Schema cannot
+//! express:
+//!
+//! | `receipt_type`            | rules beyond the schema                                         |
+//! |---------------------------|-----------------------------------------------------------------|
+//! | `bore_progress`           | `meters_advanced` finite                                         |
+//! | `orbital_telemetry`       | `latency_ms` and `drift_percent` finite and non-negative         |
+//! | `zk_anomaly_proof`        | `zk_proof` present; `public_inputs` non-empty 32-byte hex        |
+//! | `entanglement_prediction` | `correlation_score` in [0, 1]; `bell_state` normalized          |
+//! | `anomaly_detected`        | `result` is `anomaly` or `fraud`; auto-halt only when critical   |
+//! | `compaction_complete`     | rows never grow; `reduction_percent` matches rows or live bytes  |
+//! | `phase_transition`, `swarm_vote`, `voice_page_sent` | no type-specific members            |
+//!
+//! `result = "fraud"` is reserved for the verification engine's receipt
+//! types: `orbital_telemetry`, `zk_anomaly_proof` and `anomaly_detected`.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::anchors::anchor_types::{
+    AnomalySeverity, JsonMap, ReceiptGlyph, ReceiptResult, ReceiptType,
+};
+use crate::error::{GlyphError, Result};
+
+/// Largest allowed distance of `bell_state`'s squared norm from 1.
+pub const BELL_STATE_NORM_TOLERANCE: f64 = 1e-3;
+
+/// `bore_progress`: one TBM advance.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BoreProgress {
+    pub meters_advanced: f64,
+    pub cutter_head_rpm: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub segment_id: Option<String>,
+}
+
+/// `orbital_telemetry`: one satellite link measurement.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OrbitalTelemetry {
+    pub satellite_id: String,
+    pub signal_strength_dbm: f64,
+    pub latency_ms: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drift_percent: Option<f64>,
+}
+
+/// Groth16 proof points as carried in `zk_proof`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ZkProofPoints {
+    pub pi_a: [String; 2],
+    pub pi_b: [[String; 2]; 2],
+    pub pi_c: [String; 2],
+    #[serde(flatten)]
+    pub additional: JsonMap,
+}
+
+/// `zk_anomaly_proof`: a private anomaly confirmed by a Groth16 proof.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ZkAnomalyProof {
+    pub zk_proof: ZkProofPoints,
+    pub public_inputs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anomaly_hint: Option<String>,
+}
+
+/// `entanglement_prediction`: a digital-twin latency-negation forecast.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EntanglementPrediction {
+    pub correlation_score: f64,
+    pub predicted_negation_ms: f64,
+    /// Real amplitudes over |00⟩, |01⟩, |10⟩, |11⟩.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bell_state: Option<[f64; 4]>,
+}
+
+/// `anomaly_detected`: a drift past its threshold.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AnomalyDetected {
+    pub severity: AnomalySeverity,
+    pub drift_value: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_halt_triggered: Option<bool>,
+}
+
+/// `compaction_complete`: the weekly red-loop ledger compaction.
+///
+/// `null` live-byte counts decode as `None` and are omitted on re-encode.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CompactionComplete {
+    pub input_row_count: u64,
+    pub output_row_count: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reduction_percent: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rocksdb_live_bytes_before: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rocksdb_live_bytes_after: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pce_transitivity: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub death_triggered: Option<bool>,
+}
+
+impl CompactionComplete {
+    /// The larger of the row-count and live-byte reductions, in percent.
+    pub fn expected_reduction_percent(&self) -> f64 {
+        fn percent(before: u64, after: u64) -> f64 {
+            if before == 0 {
+                0.0
+            } else {
+                before.saturating_sub(after) as f64 * 100.0 / before as f64
+            }
+        }
+        let rows = percent(self.input_row_count, self.output_row_count);
+        let bytes = match (
+            self.rocksdb_live_bytes_before,
+            self.rocksdb_live_bytes_after,
+        ) {
+            (Some(before), Some(after)) => percent(before, after),
+            _ => 0.0,
+        };
+        rows.max(bytes)
+    }
+}
+
+/// A receipt's type-specific members, one variant per `receipt_type`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReceiptPayload {
+    BoreProgress(BoreProgress),
+    OrbitalTelemetry(OrbitalTelemetry),
+    ZkAnomalyProof(ZkAnomalyProof),
+    EntanglementPrediction(EntanglementPrediction),
+    AnomalyDetected(AnomalyDetected),
+    PhaseTransition,
+    SwarmVote,
+    CompactionComplete(CompactionComplete),
+    VoicePageSent,
+}
+
+impl ReceiptPayload {
+    pub fn receipt_type(&self) -> ReceiptType {
+        match self {
+            ReceiptPayload::BoreProgress(_) => ReceiptType::BoreProgress,
+            ReceiptPayload::OrbitalTelemetry(_) => ReceiptType::OrbitalTelemetry,
+            ReceiptPayload::ZkAnomalyProof(_) => ReceiptType::ZkAnomalyProof,
+            ReceiptPayload::EntanglementPrediction(_) => ReceiptType::EntanglementPrediction,
+            ReceiptPayload::AnomalyDetected(_) => ReceiptType::AnomalyDetected,
+            ReceiptPayload::PhaseTransition => ReceiptType::PhaseTransition,
+            ReceiptPayload::SwarmVote => ReceiptType::SwarmVote,
+            ReceiptPayload::CompactionComplete(_) => ReceiptType::CompactionComplete,
+            ReceiptPayload::VoicePageSent => ReceiptType::VoicePageSent,
+        }
+    }
+
+    /// Decodes `details` as `receipt_type`'s payload, without semantic checks.
+    pub fn from_details(receipt_type: ReceiptType, details: &JsonMap) -> Result<Self> {
+        fn decode<T: DeserializeOwned>(receipt_type: ReceiptType, details: &JsonMap) -> Result<T> {
+            serde_json::from_value(Value::Object(details.clone())).map_err(|e| {
+                GlyphError::InvalidReceiptPayload {
+                    receipt_type,
+                    reason: e.to_string(),
+                }
+            })
+        }
+        let empty = |payload: ReceiptPayload| match details.keys().next() {
+            Some(key) => Err(GlyphError::InvalidReceiptPayload {
+                receipt_type,
+                reason: format!("unknown field `{key}`"),
+            }),
+            None => Ok(payload),
+        };
+        Ok(match receipt_type {
+            ReceiptType::BoreProgress => {
+                ReceiptPayload::BoreProgress(decode(receipt_type, details)?)
+            }
+            ReceiptType::OrbitalTelemetry => {
+                ReceiptPayload::OrbitalTelemetry(decode(receipt_type, details)?)
+            }
+            ReceiptType::ZkAnomalyProof => {
+                ReceiptPayload::ZkAnomalyProof(decode(receipt_type, details)?)
+            }
+            ReceiptType::EntanglementPrediction => {
+                ReceiptPayload::EntanglementPrediction(decode(receipt_type, details)?)
+            }
+            ReceiptType::AnomalyDetected => {
+                ReceiptPayload::AnomalyDetected(decode(receipt_type, details)?)
+            }
+            ReceiptType::CompactionComplete => {
+                ReceiptPayload::CompactionComplete(decode(receipt_type, details)?)
+            }
+            ReceiptType::PhaseTransition => empty(ReceiptPayload::PhaseTransition)?,
+            ReceiptType::SwarmVote => empty(ReceiptPayload::SwarmVote)?,
+            ReceiptType::VoicePageSent => empty(ReceiptPayload::VoicePageSent)?,
+        })
+    }
+
+    /// Decodes and validates a receipt's payload against its `result`.
+    pub fn of(receipt: &ReceiptGlyph) -> Result<Self> {
+        let payload = Self::from_details(receipt.receipt_type, &receipt.details)?;
+        payload.validate(receipt.result)?;
+        Ok(payload)
+    }
+
+    /// The flattened members this payload contributes to a ReceiptGlyph.
+    pub fn to_details(&self) -> Result<JsonMap> {
+        let value = match self {
+            ReceiptPayload::BoreProgress(p) => serde_json::to_value(p)?,
+            ReceiptPayload::OrbitalTelemetry(p) => serde_json::to_value(p)?,
+            ReceiptPayload::ZkAnomalyProof(p) => serde_json::to_value(p)?,
+            ReceiptPayload::EntanglementPrediction(p) => serde_json::to_value(p)?,
+            ReceiptPayload::AnomalyDetected(p) => serde_json::to_value(p)?,
+            ReceiptPayload::CompactionComplete(p) => serde_json::to_value(p)?,
+            ReceiptPayload::PhaseTransition
+            | ReceiptPayload::SwarmVote
+            | ReceiptPayload::VoicePageSent => return Ok(JsonMap::new()),
+        };
+        match value {
+            Value::Object(map) => Ok(map),
+            _ => Err(GlyphError::Canonical {
+                reason: "receipt payload must be a JSON object",
+            }),
+        }
+    }
+
+    /// Checks the cross-field rules in the module table.
+    pub fn validate(&self, result: ReceiptResult) -> Result<()> {
+        let receipt_type = self.receipt_type();
+        if !allowed_results(receipt_type).contains(&result) {
+            return Err(GlyphError::ResultNotAllowed {
+                receipt_type,
+                result,
+            });
+        }
+        let invalid = |reason: String| {
+            Err(GlyphError::InvalidReceiptPayload {
+                receipt_type,
+                reason,
+            })
+        };
+        let non_negative = |field: &str, value: f64| {
+            if value.is_finite() && value >= 0.0 {
+                Ok(())
+            } else {
+                invalid(format!("{field} {value} must be finite and non-negative"))
+            }
+        };
+        match self {
+            ReceiptPayload::BoreProgress(p) => non_negative("meters_advanced", p.meters_advanced),
+            ReceiptPayload::OrbitalTelemetry(p) => {
+                non_negative("latency_ms", p.latency_ms)?;
+                if !p.signal_strength_dbm.is_finite() {
+                    return invalid("signal_strength_dbm must be finite".into());
+                }
+                match p.drift_percent {
+                    Some(drift) => non_negative("drift_percent", drift),
+                    None => Ok(()),
+                }
+            }
+            ReceiptPayload::ZkAnomalyProof(p) => {
+                if p.public_inputs.is_empty() {
+                    return invalid("public_inputs is empty".into());
+                }
+                match p.public_inputs.iter().find(|i| !is_field_element_hex(i)) {
+                    Some(input) => invalid(format!(
+                        "public input {input:?} is not a 32-byte hex field element"
+                    )),
+                    None => Ok(()),
+                }
+            }
+            ReceiptPayload::EntanglementPrediction(p) => {
+                if !(0.0..=1.0).contains(&p.correlation_score) {
+                    return invalid(format!(
+                        "correlation_score {} is outside [0, 1]",
+                        p.correlation_score
+                    ));
+                }
+                non_negative("predicted_negation_ms", p.predicted_negation_ms)?;
+                match p.bell_state {
+                    Some(amplitudes) => {
+                        let norm: f64 = amplitudes.iter().map(|a| a * a).sum();
+                        if (norm - 1.0).abs() <= BELL_STATE_NORM_TOLERANCE {
+                            Ok(())
+                        } else {
+                            invalid(format!("bell_state has squared norm {norm}, not 1"))
+                        }
+                    }
+                    None => Ok(()),
+                }
+            }
+            ReceiptPayload::AnomalyDetected(p) => {
+                if !p.drift_value.is_finite() {
+                    return invalid("drift_value must be finite".into());
+                }
+                if p.auto_halt_triggered == Some(true) && p.severity != AnomalySeverity::Critical {
+                    return invalid(format!(
+                        "auto_halt_triggered requires critical severity, not {}",
+                        p.severity
+                    ));
+                }
+                Ok(())
+            }
+            ReceiptPayload::CompactionComplete(p) => {
+                if p.output_row_count > p.input_row_count {
+                    return invalid(format!(
+                        "output_row_count {} exceeds input_row_count {}",
+                        p.output_row_count, p.input_row_count
+                    ));
+                }
+                match p.reduction_percent {
+                    Some(reduction) => {
+                        let expected = p.expected_reduction_percent();
+                        // Emitters round to two decimals.
+                        if (reduction - expected).abs() <= 0.005 + 1e-9 {
+                            Ok(())
+                        } else {
+                            invalid(format!(
+                                "reduction_percent {reduction} does not match the counts ({expected:.2})"
+                            ))
+                        }
+                    }
+                    None => Ok(()),
+                }
+            }
+            ReceiptPayload::PhaseTransition
+            | ReceiptPayload::SwarmVote
+            | ReceiptPayload::VoicePageSent => Ok(()),
+        }
+    }
+}
+
+/// Results a receipt of `receipt_type` may carry.
+pub fn allowed_results(receipt_type: ReceiptType) -> &'static [ReceiptResult] {
+    use ReceiptResult::*;
+    match receipt_type {
+        ReceiptType::OrbitalTelemetry | ReceiptType::ZkAnomalyProof => {
+            &[Ok, Anomaly, Fraud, Rejected]
+        }
+        ReceiptType::AnomalyDetected => &[Anomaly, Fraud],
+        _ => &[Ok, Anomaly, Rejected],
+    }
+}
+
+/// Decodes and validates `receipt`'s typed payload, discarding it.
+pub fn check_receipt(receipt: &ReceiptGlyph) -> Result<()> {
+    ReceiptPayload::of(receipt).map(|_| ())
+}
+
+fn is_field_element_hex(s: &str) -> bool {
+    s.len() == 64 && s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
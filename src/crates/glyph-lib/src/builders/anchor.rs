@@ -0,0 +1,165 @@
+//! AnchorGlyph builder.
+//!
+//! An anchor is signed by a Guardian quorum rather than a single key, so it
+//! is built in two stages. [`AnchorBuilder::prepare`] fills `merkle_root`,
+//! `glyph_id` and `blake3_hash` and returns a [`PendingAnchor`] for the
+//! Guardians to vote on; [`PendingAnchor::finalize`] assembles their votes
+//! into `kyber_signature`, checks the quorum and the schema, and only then
+//! hands out the anchor.
+
+use crate::anchors::anchor_types::{
+    AnchorExtensions, AnchorGlyph, AnchorReceiptRef, Daemon, GlyphKind, Guardian,
+    GuardianSignature, JsonMap, QuorumSignatureBundle, ReceiptGlyph, SchemaVersion,
+    SignatureScheme, TargetContext, Verdict,
+};
+use crate::anchors::merkle::{empty_root, hash_to_hex, MerkleTree};
+use crate::anchors::quorum::{
+    assemble_bundle, sign_vote, verify_quorum, GuardianKeyring, QuorumOutcome, QuorumPolicy,
+};
+use crate::error::Result;
+use crate::hashing::canonical::content_hash;
+use crate::hashing::ids::{derive_id, ChainLink};
+use crate::pq::envelope::GlyphSigner;
+use crate::schemas::validator::check_glyph;
+
+use super::receipt::{placeholder_id, unix_now};
+
+/// Typed inputs for one AnchorGlyph.
+#[derive(Debug, Clone)]
+pub struct AnchorBuilder {
+    tenant_id: String,
+    context: TargetContext,
+    previous_glyph_id: ChainLink,
+    emitted_by: Daemon,
+    receipts: Vec<ReceiptGlyph>,
+    payload: Option<JsonMap>,
+    extensions: Option<AnchorExtensions>,
+    timestamp: Option<i64>,
+}
+
+impl AnchorBuilder {
+    pub fn new(
+        tenant_id: impl Into<String>,
+        context: TargetContext,
+        previous_glyph_id: ChainLink,
+        emitted_by: Daemon,
+    ) -> Self {
+        AnchorBuilder {
+            tenant_id: tenant_id.into(),
+            context,
+            previous_glyph_id,
+            emitted_by,
+            receipts: Vec::new(),
+            payload: None,
+            extensions: None,
+            timestamp: None,
+        }
+    }
+
+    /// Overrides the default timestamp of "now".
+    pub fn timestamp(mut self, timestamp: i64) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Anchors `receipts`; `merkle_root` becomes the root over them, in order.
+    pub fn receipts<'a>(mut self, receipts: impl IntoIterator<Item = &'a ReceiptGlyph>) -> Self {
+        self.receipts.extend(receipts.into_iter().cloned());
+        self
+    }
+
+    /// Context-specific payload, such as `segment_id` for `tunnel_bore`.
+    pub fn payload(mut self, payload: JsonMap) -> Self {
+        self.payload = Some(payload);
+        self
+    }
+
+    pub fn extensions(mut self, extensions: AnchorExtensions) -> Self {
+        self.extensions = Some(extensions);
+        self
+    }
+
+    /// Fills the root, ID and hash, leaving the quorum bundle empty.
+    pub fn prepare(self) -> Result<PendingAnchor> {
+        let merkle_root = if self.receipts.is_empty() {
+            empty_root()
+        } else {
+            MerkleTree::from_receipts(&self.receipts)?.root()
+        };
+        let receipts = self
+            .receipts
+            .iter()
+            .map(|r| AnchorReceiptRef {
+                receipt_id: r.receipt_id,
+                result: r.result,
+                merkle_root: Some(r.merkle_root.clone()),
+                metadata: None,
+            })
+            .collect();
+        let mut anchor = AnchorGlyph {
+            version: SchemaVersion::CURRENT,
+            glyph_id: placeholder_id(GlyphKind::Anchor),
+            timestamp: self.timestamp.unwrap_or_else(unix_now),
+            tenant_id: self.tenant_id,
+            context: self.context,
+            merkle_root: hash_to_hex(&merkle_root),
+            blake3_hash: String::new(),
+            kyber_signature: QuorumSignatureBundle {
+                scheme: SignatureScheme::Kyber1024Compatible,
+                quorum_threshold: 0,
+                quorum_observed: 0,
+                signatures: Vec::new(),
+                metadata: None,
+                additional: JsonMap::new(),
+            },
+            previous_glyph_id: self.previous_glyph_id,
+            emitted_by: self.emitted_by,
+            receipts,
+            merkle_proof: None,
+            payload: self.payload,
+            extensions: self.extensions,
+            additional: JsonMap::new(),
+        };
+        anchor.glyph_id = derive_id(&anchor)?;
+        anchor.blake3_hash = content_hash(&anchor)?.to_hex().to_string();
+        Ok(PendingAnchor { anchor })
+    }
+}
+
+/// A hashed anchor waiting for Guardian votes.
+#[derive(Debug, Clone)]
+pub struct PendingAnchor {
+    anchor: AnchorGlyph,
+}
+
+impl PendingAnchor {
+    /// The anchor as Guardians see it; its quorum bundle is still empty.
+    pub fn anchor(&self) -> &AnchorGlyph {
+        &self.anchor
+    }
+
+    /// Signs one Guardian's vote on this anchor.
+    pub fn vote(
+        &self,
+        guardian: Guardian,
+        public_key_id: &str,
+        verdict: Verdict,
+        signer: &dyn GlyphSigner,
+    ) -> Result<GuardianSignature> {
+        sign_vote(&self.anchor, guardian, public_key_id, verdict, signer)
+    }
+
+    /// Bundles the votes and returns the anchor once the quorum holds.
+    pub fn finalize(
+        self,
+        policy: &QuorumPolicy,
+        keyring: &dyn GuardianKeyring,
+        signatures: Vec<GuardianSignature>,
+    ) -> Result<(AnchorGlyph, QuorumOutcome)> {
+        let mut anchor = self.anchor;
+        anchor.kyber_signature = assemble_bundle(policy, signatures);
+        let outcome = verify_quorum(&anchor, policy, keyring)?;
+        check_glyph(&anchor)?;
+        Ok((anchor, outcome))
+    }
+}
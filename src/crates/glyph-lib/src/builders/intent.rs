@@ -0,0 +1,84 @@
+//! IntentGlyph builder.
+//!
+//! The intent is signed by the Guardian that authorizes it: `glyph_id` is
+//! derived first, then `signature` covers the canonical preimage, ID included.
+
+use crate::anchors::anchor_types::{
+    GlyphKind, Guardian, IntentConstraints, IntentGlyph, IntentType, RiskAppetite, SchemaVersion,
+    TargetContext,
+};
+use crate::error::{GlyphError, Result};
+use crate::hashing::ids::derive_id;
+use crate::pq::envelope::{sign_intent, GlyphSigner};
+use crate::schemas::validator::check_glyph;
+
+use super::receipt::{placeholder_id, unix_now};
+
+/// Typed inputs for one IntentGlyph.
+#[derive(Debug, Clone)]
+pub struct IntentBuilder {
+    tenant_id: String,
+    intent_type: IntentType,
+    risk_appetite: RiskAppetite,
+    target_context: TargetContext,
+    constraints: IntentConstraints,
+    expires_at: i64,
+    timestamp: Option<i64>,
+}
+
+impl IntentBuilder {
+    pub fn new(
+        tenant_id: impl Into<String>,
+        intent_type: IntentType,
+        risk_appetite: RiskAppetite,
+        target_context: TargetContext,
+        constraints: IntentConstraints,
+        expires_at: i64,
+    ) -> Self {
+        IntentBuilder {
+            tenant_id: tenant_id.into(),
+            intent_type,
+            risk_appetite,
+            target_context,
+            constraints,
+            expires_at,
+            timestamp: None,
+        }
+    }
+
+    /// Overrides the default timestamp of "now".
+    pub fn timestamp(mut self, timestamp: i64) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Derives the ID and signs as `authorized_by`.
+    pub fn build(self, authorized_by: Guardian, signer: &dyn GlyphSigner) -> Result<IntentGlyph> {
+        let timestamp = self.timestamp.unwrap_or_else(unix_now);
+        if self.expires_at <= timestamp {
+            return Err(GlyphError::Builder {
+                reason: format!(
+                    "intent expires at {} but is declared at {timestamp}",
+                    self.expires_at
+                ),
+            });
+        }
+        let mut intent = IntentGlyph {
+            version: SchemaVersion::CURRENT,
+            glyph_id: placeholder_id(GlyphKind::Intent),
+            timestamp,
+            tenant_id: self.tenant_id,
+            intent_type: self.intent_type,
+            authorized_by,
+            risk_appetite: self.risk_appetite,
+            target_context: self.target_context,
+            constraints: self.constraints,
+            expires_at: self.expires_at,
+            signature: None,
+        };
+        intent.glyph_id = derive_id(&intent)?;
+        sign_intent(&mut intent, signer)?;
+        check_glyph(&intent)?;
+        Ok(intent)
+    }
+}
@@ -0,0 +1,225 @@
+//! ReceiptGlyph builder.
+//!
+//! Receipt fields depend on each other, so they are filled in one fixed order:
+//!
+//! 1. the payload: caller inputs plus `version` and `timestamp`;
+//! 2. `receipt_id`, derived from the payload;
+//! 3. `blake3_hash`, over the payload and the ID;
+//! 4. `merkle_root` and `merkle_proof`, over the batch's hashes;
+//! 5. `kyber_signature`, over `blake3_hash` and `merkle_root`.
+//!
+//! Every sealed receipt is checked against its typed [`ReceiptPayload`] rules
+//! and the embedded schema before it is returned.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::Value;
+
+use crate::anchors::anchor_types::{
+    Daemon, GlyphKind, JsonMap, ReceiptGlyph, ReceiptResult, ReceiptType, SchemaVersion,
+};
+use crate::anchors::merkle::{hash_to_hex, MerkleTree};
+use crate::anchors::receipt_payload::ReceiptPayload;
+use crate::error::{GlyphError, Result};
+use crate::hashing::canonical::content_hash;
+use crate::hashing::ids::{derive_id, GlyphId, ID_DIGEST_LEN};
+use crate::pq::envelope::{sign_receipt, GlyphSigner};
+use crate::schemas::validator::check_glyph;
+
+/// Top-level receipt members a detail may not shadow.
+const RESERVED_FIELDS: &[&str] = &[
+    "version",
+    "receipt_id",
+    "timestamp",
+    "tenant_id",
+    "receipt_type",
+    "ref_glyph_id",
+    "result",
+    "blake3_hash",
+    "merkle_root",
+    "merkle_proof",
+    "kyber_signature",
+    "emitted_by",
+];
+
+/// Current Unix time in seconds, the default glyph timestamp.
+pub(crate) fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64)
+}
+
+/// ID placeholder, overwritten by the derived ID before anything reads it.
+pub(crate) fn placeholder_id(kind: GlyphKind) -> GlyphId {
+    GlyphId::from_digest(kind, [0; ID_DIGEST_LEN])
+}
+
+/// Typed inputs for one ReceiptGlyph.
+#[derive(Debug, Clone)]
+pub struct ReceiptBuilder {
+    tenant_id: String,
+    receipt_type: ReceiptType,
+    ref_glyph_id: GlyphId,
+    result: ReceiptResult,
+    emitted_by: Daemon,
+    timestamp: Option<i64>,
+    details: JsonMap,
+}
+
+impl ReceiptBuilder {
+    pub fn new(
+        tenant_id: impl Into<String>,
+        receipt_type: ReceiptType,
+        ref_glyph_id: GlyphId,
+        result: ReceiptResult,
+        emitted_by: Daemon,
+    ) -> Self {
+        ReceiptBuilder {
+            tenant_id: tenant_id.into(),
+            receipt_type,
+            ref_glyph_id,
+            result,
+            emitted_by,
+            timestamp: None,
+            details: JsonMap::new(),
+        }
+    }
+
+    /// Starts from a typed payload; its variant sets `receipt_type`.
+    pub fn from_payload(
+        tenant_id: impl Into<String>,
+        ref_glyph_id: GlyphId,
+        result: ReceiptResult,
+        emitted_by: Daemon,
+        payload: &ReceiptPayload,
+    ) -> Result<Self> {
+        Ok(Self::new(
+            tenant_id,
+            payload.receipt_type(),
+            ref_glyph_id,
+            result,
+            emitted_by,
+        )
+        .details(payload.to_details()?))
+    }
+
+    /// Overrides the default timestamp of "now".
+    pub fn timestamp(mut self, timestamp: i64) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Sets one type-specific member, such as `segment_id`.
+    pub fn detail(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.details.insert(key.into(), value.into());
+        self
+    }
+
+    /// Sets several type-specific members at once.
+    pub fn details(mut self, details: JsonMap) -> Self {
+        self.details.extend(details);
+        self
+    }
+
+    /// Builds and signs a receipt on its own, as a batch of one.
+    pub fn build(self, signer: &dyn GlyphSigner) -> Result<ReceiptGlyph> {
+        let mut batch = ReceiptBatch::new();
+        batch.push(self);
+        Ok(batch.seal(signer)?.remove(0))
+    }
+
+    /// Steps 1–3: the checked payload, its ID and its content hash.
+    fn hashed(self) -> Result<ReceiptGlyph> {
+        if let Some(key) = RESERVED_FIELDS
+            .iter()
+            .find(|k| self.details.contains_key(**k))
+        {
+            return Err(GlyphError::Builder {
+                reason: format!("detail {key:?} shadows a receipt field"),
+            });
+        }
+        ReceiptPayload::from_details(self.receipt_type, &self.details)?.validate(self.result)?;
+        let mut receipt = ReceiptGlyph {
+            version: SchemaVersion::CURRENT,
+            receipt_id: placeholder_id(GlyphKind::Receipt),
+            timestamp: self.timestamp.unwrap_or_else(unix_now),
+            tenant_id: self.tenant_id,
+            receipt_type: self.receipt_type,
+            ref_glyph_id: self.ref_glyph_id,
+            result: self.result,
+            blake3_hash: String::new(),
+            merkle_root: String::new(),
+            merkle_proof: None,
+            kyber_signature: String::new(),
+            emitted_by: self.emitted_by,
+            details: self.details,
+        };
+        receipt.receipt_id = derive_id(&receipt)?;
+        receipt.blake3_hash = content_hash(&receipt)?.to_hex().to_string();
+        Ok(receipt)
+    }
+}
+
+/// Receipts sealed under one Merkle root.
+///
+/// Each receipt carries the batch root and its own inclusion proof, so an
+/// AnchorGlyph built over the same receipts commits to the same root.
+#[derive(Debug, Clone, Default)]
+pub struct ReceiptBatch {
+    receipts: Vec<ReceiptBuilder>,
+}
+
+impl ReceiptBatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, receipt: ReceiptBuilder) {
+        self.receipts.push(receipt);
+    }
+
+    pub fn len(&self) -> usize {
+        self.receipts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.receipts.is_empty()
+    }
+
+    /// Hashes, Merkle-stamps and signs every receipt, in push order.
+    pub fn seal(self, signer: &dyn GlyphSigner) -> Result<Vec<ReceiptGlyph>> {
+        if self.receipts.is_empty() {
+            return Err(GlyphError::Builder {
+                reason: "receipt batch is empty".into(),
+            });
+        }
+        let mut receipts = self
+            .receipts
+            .into_iter()
+            .map(ReceiptBuilder::hashed)
+            .collect::<Result<Vec<_>>>()?;
+        let tree = MerkleTree::from_receipts(&receipts)?;
+        let root = hash_to_hex(&tree.root());
+        for (index, receipt) in receipts.iter_mut().enumerate() {
+            receipt.merkle_root = root.clone();
+            receipt.merkle_proof = Some(tree.proof(index)?.to_embedded());
+            sign_receipt(receipt, signer)?;
+            check_glyph(receipt)?;
+        }
+        Ok(receipts)
+    }
+}
+
+impl Extend<ReceiptBuilder> for ReceiptBatch {
+    fn extend<I: IntoIterator<Item = ReceiptBuilder>>(&mut self, iter: I) {
+        self.receipts.extend(iter);
+    }
+}
+
+impl FromIterator<ReceiptBuilder> for ReceiptBatch {
+    fn from_iter<I: IntoIterator<Item = ReceiptBuilder>>(iter: I) -> Self {
+        ReceiptBatch {
+            receipts: iter.into_iter().collect(),
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! DaemonStatusGlyph builder.
+//!
+//! Order: `glyph_id`, then `blake3_hash` over the ID and payload, then
+//! `kyber_signature` over `blake3_hash`.
+
+use crate::anchors::anchor_types::{
+    Daemon, DaemonHealth, DaemonStatusGlyph, GlyphKind, Guardian, SchemaVersion, SloCompliance,
+};
+use crate::error::Result;
+use crate::hashing::canonical::content_hash;
+use crate::hashing::ids::{derive_id, GlyphId};
+use crate::pq::envelope::{sign_status, GlyphSigner};
+use crate::schemas::validator::check_glyph;
+
+use super::receipt::{placeholder_id, unix_now};
+
+/// Typed inputs for one DaemonStatusGlyph heartbeat.
+#[derive(Debug, Clone)]
+pub struct DaemonStatusBuilder {
+    daemon_name: Daemon,
+    guardian: Guardian,
+    status: DaemonHealth,
+    slo_compliance: SloCompliance,
+    uptime_seconds: u64,
+    glyphs_emitted: u64,
+    last_anchor_glyph_id: Option<Option<GlyphId>>,
+    active_phase: Option<u8>,
+    self_diagnosed_lie: Option<bool>,
+    timestamp: Option<i64>,
+}
+
+impl DaemonStatusBuilder {
+    pub fn new(
+        daemon_name: Daemon,
+        guardian: Guardian,
+        status: DaemonHealth,
+        slo_compliance: SloCompliance,
+    ) -> Self {
+        DaemonStatusBuilder {
+            daemon_name,
+            guardian,
+            status,
+            slo_compliance,
+            uptime_seconds: 0,
+            glyphs_emitted: 0,
+            last_anchor_glyph_id: None,
+            active_phase: None,
+            self_diagnosed_lie: None,
+            timestamp: None,
+        }
+    }
+
+    /// Overrides the default timestamp of "now".
+    pub fn timestamp(mut self, timestamp: i64) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    pub fn uptime_seconds(mut self, uptime_seconds: u64) -> Self {
+        self.uptime_seconds = uptime_seconds;
+        self
+    }
+
+    pub fn glyphs_emitted(mut self, glyphs_emitted: u64) -> Self {
+        self.glyphs_emitted = glyphs_emitted;
+        self
+    }
+
+    /// Records the last anchor; `None` emits an explicit `null`.
+    pub fn last_anchor(mut self, anchor: Option<GlyphId>) -> Self {
+        self.last_anchor_glyph_id = Some(anchor);
+        self
+    }
+
+    pub fn active_phase(mut self, phase: u8) -> Self {
+        self.active_phase = Some(phase);
+        self
+    }
+
+    pub fn self_diagnosed_lie(mut self, lie: bool) -> Self {
+        self.self_diagnosed_lie = Some(lie);
+        self
+    }
+
+    /// Derives the ID, hashes and signs.
+    pub fn build(self, signer: &dyn GlyphSigner) -> Result<DaemonStatusGlyph> {
+        let mut status = DaemonStatusGlyph {
+            version: SchemaVersion::CURRENT,
+            glyph_id: placeholder_id(GlyphKind::DaemonStatus),
+            timestamp: self.timestamp.unwrap_or_else(unix_now),
+            daemon_name: self.daemon_name,
+            guardian: self.guardian,
+            status: self.status,
+            blake3_hash: String::new(),
+            kyber_signature: String::new(),
+            uptime_seconds: self.uptime_seconds,
+            glyphs_emitted: self.glyphs_emitted,
+            last_anchor_glyph_id: self.last_anchor_glyph_id,
+            slo_compliance: self.slo_compliance,
+            active_phase: self.active_phase,
+            self_diagnosed_lie: self.self_diagnosed_lie,
+        };
+        status.glyph_id = derive_id(&status)?;
+        status.blake3_hash = content_hash(&status)?.to_hex().to_string();
+        sign_status(&mut status, signer)?;
+        check_glyph(&status)?;
+        Ok(status)
+    }
+}
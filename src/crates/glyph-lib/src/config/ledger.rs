@@ -0,0 +1,106 @@
+//! Typed `config/ledger.sqlite.toml` (hot ledger) and
+//! `config/ledger.rocksdb.toml` (cold archive).
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use crate::config::loader::toml_file;
+use crate::error::Result;
+
+/// One `[tenant."<id>"]` of `ledger.sqlite.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SqliteTenant {
+    pub wal_autocheckpoint: u32,
+    /// Hard cap on the tenant's database size.
+    pub max_size_bytes: u64,
+}
+
+/// `[vacuum]` of `ledger.sqlite.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SqliteVacuum {
+    pub auto_vacuum: String,
+    pub incremental_vacuum_pages: u32,
+}
+
+/// The whole of `config/ledger.sqlite.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SqliteLedgerConfig {
+    pub path: String,
+    pub journal_mode: String,
+    pub synchronous: String,
+    /// SQLite `cache_size` pragma; negative values are KiB.
+    pub cache_size: i64,
+    pub temp_store: String,
+    pub foreign_keys: bool,
+    pub busy_timeout_ms: u64,
+    pub tenant: BTreeMap<String, SqliteTenant>,
+    pub vacuum: SqliteVacuum,
+}
+
+impl SqliteLedgerConfig {
+    /// Parses the text of `config/ledger.sqlite.toml`.
+    pub fn from_toml(toml: &str) -> Result<Self> {
+        toml_file("ledger.sqlite.toml", toml)
+    }
+}
+
+/// `[options]` of `ledger.rocksdb.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RocksDbOptions {
+    pub max_open_files: i32,
+    pub write_buffer_size: u64,
+    pub target_file_size_base: u64,
+    pub max_background_jobs: i32,
+    pub compression: String,
+    pub bottommost_compression: String,
+}
+
+/// One `[column_families.<name>]`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ColumnFamily {
+    pub bloom_filter_bits_per_key: u32,
+    pub block_size: u64,
+}
+
+/// One `[tenant."<id>"]` of `ledger.rocksdb.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RocksDbTenant {
+    /// Key prefix isolating the tenant inside every column family.
+    pub prefix: String,
+}
+
+/// `[compaction]` of `ledger.rocksdb.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RocksDbCompaction {
+    /// Archive size that triggers the weekly red-loop compaction.
+    pub weekly_target_bytes: u64,
+}
+
+/// The whole of `config/ledger.rocksdb.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RocksDbLedgerConfig {
+    pub path: String,
+    pub create_if_missing: bool,
+    pub enable_statistics: bool,
+    pub stats_dump_period_sec: u64,
+    pub options: RocksDbOptions,
+    pub column_families: BTreeMap<String, ColumnFamily>,
+    pub tenant: BTreeMap<String, RocksDbTenant>,
+    pub compaction: RocksDbCompaction,
+}
+
+impl RocksDbLedgerConfig {
+    /// Parses the text of `config/ledger.rocksdb.toml`.
+    pub fn from_toml(toml: &str) -> Result<Self> {
+        toml_file("ledger.rocksdb.toml", toml)
+    }
+}
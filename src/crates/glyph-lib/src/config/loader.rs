@@ -0,0 +1,415 @@
+//! Loads the whole `config/` directory into one typed [`SwarmConfig`].
+//!
+//! Reading and parsing are separate: [`ConfigSources`] is the text of every
+//! `.toml`/`.yaml` file keyed by its path relative to `config/`, and
+//! [`SwarmConfig::from_sources`] turns that into types without touching the
+//! filesystem. Every missing file, parse failure and broken cross-file
+//! invariant is collected, so one [`GlyphError::ConfigViolations`] reports
+//! all of them instead of the first.
+//!
+//! Cross-file invariants:
+//!
+//! 1. every tenant named by `grpc.toml` `allowed_tenants`, `ipfs.yaml`
+//!    `paths` or a `ledger.*.toml` `[tenant]` block has a `tenants/*.yaml`;
+//! 2. `ipfs.yaml` `pin_targets` and `arweave.yaml` `anchor_targets` list
+//!    the same files;
+//! 3. `phase_map.yaml` numbers its phases from 1 without gaps, every daemon
+//!    in `routing_rules.yaml` is active in some phase, and every tenant's
+//!    `allowed_phases` are in it;
+//! 4. every Guardian with a swarm role is in `guardians_org.yaml`;
+//! 5. no two tenant files share a `tenant_id`.
+//!
+//! A file whose `# Root hash:` header has been filled in must also hash to
+//! that value; see [`pinning`](super::pinning).
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+
+use crate::anchors::anchor_types::Guardian;
+use crate::anchors::quorum::QuorumPolicy;
+use crate::config::ledger::{RocksDbLedgerConfig, SqliteLedgerConfig};
+use crate::config::orchestrator::{PhaseMap, RoutingRules};
+use crate::config::pinning::check_root_hash;
+use crate::config::services::{ArweaveConfig, GrpcConfig, IpfsConfig, NatsConfig};
+use crate::config::slo::SloConfig;
+use crate::config::tenant::TenantConfig;
+use crate::error::{GlyphError, Result};
+use crate::pq::registry::GuardianProfile;
+
+/// Files every daemon expects, relative to `config/`.
+pub const REQUIRED_FILES: [&str; 11] = [
+    "slo.toml",
+    "nats.toml",
+    "grpc.toml",
+    "ipfs.yaml",
+    "arweave.yaml",
+    "ledger.sqlite.toml",
+    "ledger.rocksdb.toml",
+    "agents/guardians_org.yaml",
+    "agents/swarm_roles.yaml",
+    "orchestrator/phase_map.yaml",
+    "orchestrator/routing_rules.yaml",
+];
+
+/// Directory holding one YAML file per tenant.
+pub const TENANTS_DIR: &str = "tenants/";
+
+/// One problem found while loading `config/`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigViolation {
+    /// Path relative to `config/`.
+    pub file: String,
+    pub reason: String,
+}
+
+impl fmt::Display for ConfigViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.file, self.reason)
+    }
+}
+
+/// The text of the config files, keyed by `/`-separated path relative to
+/// `config/`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigSources {
+    files: BTreeMap<String, String>,
+}
+
+impl ConfigSources {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads every `.toml`, `.yaml` and `.yml` file under `root`.
+    pub fn read_dir(root: &Path) -> Result<Self> {
+        let mut sources = Self::new();
+        sources.read_into(root, "")?;
+        Ok(sources)
+    }
+
+    fn read_into(&mut self, dir: &Path, prefix: &str) -> Result<()> {
+        let io_error = |path: &Path, e: std::io::Error| GlyphError::InvalidConfig {
+            file: path.display().to_string(),
+            reason: e.to_string(),
+        };
+        let mut entries = fs::read_dir(dir)
+            .map_err(|e| io_error(dir, e))?
+            .collect::<std::io::Result<Vec<_>>>()
+            .map_err(|e| io_error(dir, e))?;
+        entries.sort_by_key(|e| e.file_name());
+        for entry in entries {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let relative = format!("{prefix}{name}");
+            if path.is_dir() {
+                self.read_into(&path, &format!("{relative}/"))?;
+            } else if [".toml", ".yaml", ".yml"].iter().any(|e| name.ends_with(e)) {
+                let text = fs::read_to_string(&path).map_err(|e| io_error(&path, e))?;
+                self.insert(relative, text);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn insert(&mut self, file: impl Into<String>, text: impl Into<String>) {
+        self.files.insert(file.into(), text.into());
+    }
+
+    pub fn remove(&mut self, file: &str) -> Option<String> {
+        self.files.remove(file)
+    }
+
+    pub fn get(&self, file: &str) -> Option<&str> {
+        self.files.get(file).map(String::as_str)
+    }
+
+    /// Files in path order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.files.iter().map(|(f, t)| (f.as_str(), t.as_str()))
+    }
+}
+
+/// Every shared config file, typed and cross-checked.
+#[derive(Debug, Clone)]
+pub struct SwarmConfig {
+    pub slo: SloConfig,
+    pub nats: NatsConfig,
+    pub grpc: GrpcConfig,
+    pub ipfs: IpfsConfig,
+    pub arweave: ArweaveConfig,
+    pub ledger_sqlite: SqliteLedgerConfig,
+    pub ledger_rocksdb: RocksDbLedgerConfig,
+    pub guardians: BTreeMap<Guardian, GuardianProfile>,
+    pub quorum: QuorumPolicy,
+    pub phase_map: PhaseMap,
+    pub routing_rules: RoutingRules,
+    /// Keyed by `tenant_id`.
+    pub tenants: BTreeMap<String, TenantConfig>,
+}
+
+impl SwarmConfig {
+    /// Reads and checks the `config/` directory at `root`.
+    pub fn load(root: &Path) -> Result<Self> {
+        Self::from_sources(&ConfigSources::read_dir(root)?)
+    }
+
+    /// Parses and cross-checks already-read config text.
+    pub fn from_sources(sources: &ConfigSources) -> Result<Self> {
+        let mut v = Violations::default();
+        let slo = v.parse(sources, "slo.toml", SloConfig::from_toml);
+        let nats = v.parse(sources, "nats.toml", NatsConfig::from_toml);
+        let grpc = v.parse(sources, "grpc.toml", GrpcConfig::from_toml);
+        let ipfs = v.parse(sources, "ipfs.yaml", IpfsConfig::from_yaml);
+        let arweave = v.parse(sources, "arweave.yaml", ArweaveConfig::from_yaml);
+        let ledger_sqlite = v.parse(sources, "ledger.sqlite.toml", SqliteLedgerConfig::from_toml);
+        let ledger_rocksdb = v.parse(
+            sources,
+            "ledger.rocksdb.toml",
+            RocksDbLedgerConfig::from_toml,
+        );
+        let guardians = v.parse(sources, "agents/guardians_org.yaml", |text| {
+            yaml_file::<BTreeMap<Guardian, GuardianProfile>>("agents/guardians_org.yaml", text)
+        });
+        let quorum = v.parse(
+            sources,
+            "agents/swarm_roles.yaml",
+            QuorumPolicy::from_swarm_roles_yaml,
+        );
+        let phase_map = v.parse(sources, "orchestrator/phase_map.yaml", PhaseMap::from_yaml);
+        let routing_rules = v.parse(
+            sources,
+            "orchestrator/routing_rules.yaml",
+            RoutingRules::from_yaml,
+        );
+        let tenants = v.tenants(sources);
+        for (file, text) in sources.iter() {
+            if let Err(e) = check_root_hash(file, text) {
+                v.push(file, reason(e));
+            }
+        }
+
+        let named = [
+            (
+                "grpc.toml",
+                "allowed tenant",
+                grpc.iter()
+                    .flat_map(|c| c.auth.allowed_tenants.iter())
+                    .collect::<Vec<_>>(),
+            ),
+            (
+                "ipfs.yaml",
+                "path for tenant",
+                ipfs.iter().flat_map(|c| c.paths.keys()).collect(),
+            ),
+            (
+                "ledger.sqlite.toml",
+                "tenant",
+                ledger_sqlite.iter().flat_map(|c| c.tenant.keys()).collect(),
+            ),
+            (
+                "ledger.rocksdb.toml",
+                "tenant",
+                ledger_rocksdb
+                    .iter()
+                    .flat_map(|c| c.tenant.keys())
+                    .collect(),
+            ),
+        ];
+        for (file, what, tenant_ids) in named {
+            for tenant_id in tenant_ids {
+                if !tenants.contains_key(tenant_id) {
+                    v.push(
+                        file,
+                        format!("{what} {tenant_id} has no {TENANTS_DIR}*.yaml"),
+                    );
+                }
+            }
+        }
+        if let (Some(ipfs), Some(arweave)) = (&ipfs, &arweave) {
+            let pinned: BTreeSet<_> = ipfs.pin_targets.iter().collect();
+            let anchored: BTreeSet<_> = arweave.anchor_targets.iter().collect();
+            for target in pinned.difference(&anchored) {
+                v.push(
+                    "ipfs.yaml",
+                    format!("pin target {target} is not in arweave.yaml anchor_targets"),
+                );
+            }
+            for target in anchored.difference(&pinned) {
+                v.push(
+                    "arweave.yaml",
+                    format!("anchor target {target} is not in ipfs.yaml pin_targets"),
+                );
+            }
+        }
+        if let Some(phase_map) = &phase_map {
+            for (expected, &number) in (1..).zip(phase_map.phases.keys()) {
+                if number != expected {
+                    v.push(
+                        "orchestrator/phase_map.yaml",
+                        format!("phase {number} follows a gap; phase {expected} is missing"),
+                    );
+                    break;
+                }
+            }
+            for (tenant_id, tenant) in &tenants {
+                for phase in &tenant.allowed_phases {
+                    if !phase_map.phases.contains_key(phase) {
+                        v.push(
+                            "orchestrator/phase_map.yaml",
+                            format!(
+                                "tenant {tenant_id} is allowed phase {phase}, which is not mapped"
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+        if let (Some(phase_map), Some(routing_rules)) = (&phase_map, &routing_rules) {
+            let active = phase_map.daemons();
+            for (subject, routes) in &routing_rules.rules {
+                for route in routes.iter().filter(|r| !active.contains(&r.daemon)) {
+                    v.push(
+                        "orchestrator/routing_rules.yaml",
+                        format!(
+                            "{subject} routes to {}, which is active in no phase of phase_map.yaml",
+                            route.daemon
+                        ),
+                    );
+                }
+            }
+        }
+        if let (Some(guardians), Some(quorum)) = (&guardians, &quorum) {
+            for &g in Guardian::ALL {
+                if quorum.role(g).is_some() && !guardians.contains_key(&g) {
+                    v.push(
+                        "agents/swarm_roles.yaml",
+                        format!("{g} has a swarm role but is not in guardians_org.yaml"),
+                    );
+                }
+            }
+        }
+
+        match (
+            slo,
+            nats,
+            grpc,
+            ipfs,
+            arweave,
+            ledger_sqlite,
+            ledger_rocksdb,
+            guardians,
+            quorum,
+            phase_map,
+            routing_rules,
+        ) {
+            (
+                Some(slo),
+                Some(nats),
+                Some(grpc),
+                Some(ipfs),
+                Some(arweave),
+                Some(ledger_sqlite),
+                Some(ledger_rocksdb),
+                Some(guardians),
+                Some(quorum),
+                Some(phase_map),
+                Some(routing_rules),
+            ) if v.0.is_empty() => Ok(SwarmConfig {
+                slo,
+                nats,
+                grpc,
+                ipfs,
+                arweave,
+                ledger_sqlite,
+                ledger_rocksdb,
+                guardians,
+                quorum,
+                phase_map,
+                routing_rules,
+                tenants,
+            }),
+            _ => Err(GlyphError::ConfigViolations { violations: v.0 }),
+        }
+    }
+
+    pub fn tenant(&self, tenant_id: &str) -> Option<&TenantConfig> {
+        self.tenants.get(tenant_id)
+    }
+}
+
+#[derive(Default)]
+struct Violations(Vec<ConfigViolation>);
+
+impl Violations {
+    fn push(&mut self, file: &str, reason: impl Into<String>) {
+        self.0.push(ConfigViolation {
+            file: file.to_string(),
+            reason: reason.into(),
+        });
+    }
+
+    fn parse<T>(
+        &mut self,
+        sources: &ConfigSources,
+        file: &str,
+        parse: impl FnOnce(&str) -> Result<T>,
+    ) -> Option<T> {
+        let Some(text) = sources.get(file) else {
+            self.push(file, "missing");
+            return None;
+        };
+        parse(text).map_err(|e| self.push(file, reason(e))).ok()
+    }
+
+    fn tenants(&mut self, sources: &ConfigSources) -> BTreeMap<String, TenantConfig> {
+        let mut tenants = BTreeMap::new();
+        let mut files = BTreeMap::<String, &str>::new();
+        for (file, text) in sources.iter().filter(|(f, _)| f.starts_with(TENANTS_DIR)) {
+            let Some(tenant) = TenantConfig::from_yaml(file, text)
+                .map_err(|e| self.push(file, reason(e)))
+                .ok()
+            else {
+                continue;
+            };
+            if let Some(first) = files.insert(tenant.tenant_id.clone(), file) {
+                self.push(
+                    file,
+                    format!("tenant_id {} is also used by {first}", tenant.tenant_id),
+                );
+            }
+            tenants.insert(tenant.tenant_id.clone(), tenant);
+        }
+        if files.is_empty() {
+            self.push(TENANTS_DIR, "no tenant files");
+        }
+        tenants
+    }
+}
+
+/// The reason alone when the error already names the file.
+fn reason(error: GlyphError) -> String {
+    match error {
+        GlyphError::InvalidConfig { reason, .. } => reason,
+        other => other.to_string(),
+    }
+}
+
+/// Parses a TOML config file; `file` names it in errors.
+pub(crate) fn toml_file<T: DeserializeOwned>(file: &str, toml: &str) -> Result<T> {
+    toml::from_str(toml).map_err(|e| GlyphError::InvalidConfig {
+        file: file.into(),
+        reason: e.message().to_string(),
+    })
+}
+
+/// Parses a YAML config file; `file` names it in errors.
+pub(crate) fn yaml_file<T: DeserializeOwned>(file: &str, yaml: &str) -> Result<T> {
+    serde_yaml::from_str(yaml).map_err(|e| GlyphError::InvalidConfig {
+        file: file.into(),
+        reason: e.to_string(),
+    })
+}
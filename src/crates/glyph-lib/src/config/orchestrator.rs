@@ -0,0 +1,68 @@
+//! Typed `config/orchestrator/phase_map.yaml` and
+//! `config/orchestrator/routing_rules.yaml`, which star-lord-orchestrator
+//! uses to sequence phases and deliver messages.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Deserialize;
+
+use crate::anchors::anchor_types::{Daemon, Guardian};
+use crate::config::loader::yaml_file;
+use crate::error::Result;
+
+/// One entry of `phases`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Phase {
+    pub name: String,
+    pub description: String,
+    pub required_glyphs: Vec<String>,
+    pub active_daemons: Vec<Daemon>,
+    pub success_condition: String,
+    pub on_failure: String,
+}
+
+/// The whole of `phase_map.yaml`, keyed by phase number.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PhaseMap {
+    pub phases: BTreeMap<u8, Phase>,
+}
+
+impl PhaseMap {
+    /// Parses the text of `config/orchestrator/phase_map.yaml`.
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        yaml_file("orchestrator/phase_map.yaml", yaml)
+    }
+
+    /// Every daemon active in at least one phase.
+    pub fn daemons(&self) -> BTreeSet<Daemon> {
+        self.phases
+            .values()
+            .flat_map(|p| p.active_daemons.iter().copied())
+            .collect()
+    }
+}
+
+/// One delivery target of a subject.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Route {
+    pub guardian: Guardian,
+    pub daemon: Daemon,
+    pub queue_group: String,
+}
+
+/// The whole of `routing_rules.yaml`: NATS subject pattern to targets.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RoutingRules {
+    pub rules: BTreeMap<String, Vec<Route>>,
+}
+
+impl RoutingRules {
+    /// Parses the text of `config/orchestrator/routing_rules.yaml`.
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        yaml_file("orchestrator/routing_rules.yaml", yaml)
+    }
+}
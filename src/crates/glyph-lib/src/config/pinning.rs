@@ -0,0 +1,181 @@
+//! BLAKE3 pinning of the shared config files.
+//!
+//! A config file's hash is BLAKE3 over every byte of it except its
+//! `# Root hash: ...` line, so the file can carry its own hash there:
+//! [`fill_root_hash`] writes it in place of `[BLAKE3 to be filled on
+//! commit]` and the loader rejects a filled header that no longer matches.
+//! Only the first such line in the header, the run of `#` lines the file
+//! opens with, is left out; a `# Root hash:` line below the header is
+//! ordinary content.
+//!
+//! An AnchorGlyph pins a whole `config/` directory in `extensions.config`:
+//! every file's hash plus a Merkle root over the leaves
+//!
+//! ```text
+//! leaf_hash(path || 0x00 || file_hash)
+//! ```
+//!
+//! in path order. [`verify_pinned`] is the startup check: a daemon loads
+//! its config and refuses to start when any file it depends on hashes
+//! differently from the latest anchor.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use crate::anchors::anchor_types::{AnchorGlyph, ConfigManifestExtension, JsonMap};
+use crate::anchors::merkle::{hash_from_hex, hash_to_hex, MerkleHash, MerkleTree};
+use crate::config::loader::ConfigSources;
+use crate::error::{GlyphError, Result};
+
+/// Start of the header line that carries a file's own hash.
+pub const ROOT_HASH_HEADER: &str = "# Root hash:";
+
+/// Header value of a file whose hash has not been filled in.
+pub const UNFILLED_ROOT_HASH: &str = "[BLAKE3 to be filled on commit]";
+
+/// Files a daemon must not start with if they drift from the anchor.
+pub const STARTUP_PINNED: [&str; 3] = ["slo.toml", "nats.toml", "orchestrator/routing_rules.yaml"];
+
+/// Byte range of the header's `# Root hash:` line, line ending included.
+fn root_hash_line(text: &str) -> Option<Range<usize>> {
+    let mut start = 0;
+    for line in text.split_inclusive('\n') {
+        if !line.starts_with('#') {
+            return None;
+        }
+        if line.starts_with(ROOT_HASH_HEADER) {
+            return Some(start..start + line.len());
+        }
+        start += line.len();
+    }
+    None
+}
+
+/// The text a config file's hash is taken over.
+pub fn canonical_config_text(text: &str) -> Cow<'_, str> {
+    match root_hash_line(text) {
+        Some(line) => Cow::Owned([&text[..line.start], &text[line.end..]].concat()),
+        None => Cow::Borrowed(text),
+    }
+}
+
+/// BLAKE3 of [`canonical_config_text`].
+pub fn config_file_hash(text: &str) -> MerkleHash {
+    *blake3::hash(canonical_config_text(text).as_bytes()).as_bytes()
+}
+
+/// The hash a file's header claims, if it has a filled-in header.
+pub fn header_root_hash(text: &str) -> Result<Option<MerkleHash>> {
+    let Some(line) = root_hash_line(text) else {
+        return Ok(None);
+    };
+    let value = text[line][ROOT_HASH_HEADER.len()..].trim();
+    if value == UNFILLED_ROOT_HASH {
+        return Ok(None);
+    }
+    hash_from_hex(value).map(Some)
+}
+
+/// `text` with its `# Root hash:` line set to the file's hash; unchanged
+/// if its header has no such line.
+pub fn fill_root_hash(text: &str) -> String {
+    let Some(range) = root_hash_line(text) else {
+        return text.to_string();
+    };
+    let hash = hash_to_hex(&config_file_hash(text));
+    let line = &text[range.clone()];
+    let ending = &line[line.trim_end_matches(['\r', '\n']).len()..];
+    format!(
+        "{}{ROOT_HASH_HEADER} {hash}{ending}{}",
+        &text[..range.start],
+        &text[range.end..]
+    )
+}
+
+/// Errors if `text` has a filled header that is not its own hash.
+pub fn check_root_hash(file: &str, text: &str) -> Result<()> {
+    let header = header_root_hash(text).map_err(|_| GlyphError::InvalidConfig {
+        file: file.to_string(),
+        reason: "root hash header is not a 64-character hex BLAKE3 digest".into(),
+    })?;
+    match header {
+        Some(pinned) if pinned != config_file_hash(text) => Err(GlyphError::ConfigDrift {
+            file: file.to_string(),
+            pinned: hash_to_hex(&pinned),
+            loaded: hash_to_hex(&config_file_hash(text)),
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn manifest_leaf(file: &str, hash: &MerkleHash) -> Vec<u8> {
+    let mut leaf = Vec::with_capacity(file.len() + 1 + hash.len());
+    leaf.extend_from_slice(file.as_bytes());
+    leaf.push(0);
+    leaf.extend_from_slice(hash);
+    leaf
+}
+
+/// Merkle root over a manifest's file hashes, in path order.
+pub fn manifest_root(files: &BTreeMap<String, String>) -> Result<MerkleHash> {
+    let leaves = files
+        .iter()
+        .map(|(file, hash)| Ok(manifest_leaf(file, &hash_from_hex(hash)?)))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(MerkleTree::from_items(leaves).root())
+}
+
+/// The `extensions.config` pinning every file in `sources`.
+pub fn config_manifest(sources: &ConfigSources) -> ConfigManifestExtension {
+    let files: BTreeMap<String, String> = sources
+        .iter()
+        .map(|(file, text)| (file.to_string(), hash_to_hex(&config_file_hash(text))))
+        .collect();
+    let leaves = sources
+        .iter()
+        .map(|(file, text)| manifest_leaf(file, &config_file_hash(text)));
+    ConfigManifestExtension {
+        files,
+        root: hash_to_hex(&MerkleTree::from_items(leaves).root()),
+        additional: JsonMap::new(),
+    }
+}
+
+/// Checks that each of `files` in `sources` hashes to what `anchor` pins.
+///
+/// The anchor's manifest root must match its file list, so a manifest
+/// edited after the fact fails even before its `blake3_hash` is checked.
+pub fn verify_pinned(anchor: &AnchorGlyph, sources: &ConfigSources, files: &[&str]) -> Result<()> {
+    let not_pinned = |file: &str| GlyphError::ConfigNotPinned {
+        file: file.to_string(),
+    };
+    let manifest = anchor
+        .extensions
+        .as_ref()
+        .and_then(|e| e.config.as_ref())
+        .ok_or_else(|| not_pinned("extensions.config"))?;
+    let root = hash_to_hex(&manifest_root(&manifest.files)?);
+    if root != manifest.root {
+        return Err(GlyphError::HashMismatch {
+            expected: root,
+            found: manifest.root.clone(),
+        });
+    }
+    for &file in files {
+        let pinned = manifest.files.get(file).ok_or_else(|| not_pinned(file))?;
+        let text = sources.get(file).ok_or_else(|| GlyphError::InvalidConfig {
+            file: file.to_string(),
+            reason: "missing".into(),
+        })?;
+        let loaded = hash_to_hex(&config_file_hash(text));
+        if &loaded != pinned {
+            return Err(GlyphError::ConfigDrift {
+                file: file.to_string(),
+                pinned: pinned.clone(),
+                loaded,
+            });
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,246 @@
+//! Typed `config/grpc.toml`, `config/nats.toml`, `config/ipfs.yaml` and
+//! `config/arweave.yaml`: the transports and permanent stores every daemon
+//! talks to.
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use crate::config::loader::{toml_file, yaml_file};
+use crate::error::Result;
+
+/// `[server]` of `grpc.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GrpcServer {
+    pub bind: String,
+    pub port: u16,
+    pub max_connection_age_ms: u64,
+}
+
+/// `[tls]` of `grpc.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GrpcTls {
+    pub enabled: bool,
+    pub cert_file: String,
+    pub key_file: String,
+    pub ca_file: String,
+}
+
+/// `[auth]` of `grpc.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GrpcAuth {
+    pub require_kyber_client_auth: bool,
+    /// Every entry needs a `config/tenants/*.yaml` with that `tenant_id`.
+    pub allowed_tenants: Vec<String>,
+}
+
+/// One `[service."<name>"]`; `bind` and `port` default to `[server]`'s.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GrpcService {
+    pub package: String,
+    #[serde(default)]
+    pub bind: Option<String>,
+    #[serde(default)]
+    pub port: Option<u16>,
+    pub methods: Vec<String>,
+}
+
+/// `[rate_limit]` of `grpc.toml`, applied per tenant.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GrpcRateLimit {
+    pub requests_per_minute: u32,
+    pub burst: u32,
+}
+
+/// `[timeouts]` of `grpc.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GrpcTimeouts {
+    pub request_timeout_ms: u64,
+    pub proof_verification_deadline_ms: u64,
+}
+
+/// `[logging]` of `grpc.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GrpcLogging {
+    pub log_payloads: bool,
+    pub log_rejected_requests: bool,
+}
+
+/// The whole of `config/grpc.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GrpcConfig {
+    pub server: GrpcServer,
+    pub tls: GrpcTls,
+    pub auth: GrpcAuth,
+    pub service: BTreeMap<String, GrpcService>,
+    pub rate_limit: GrpcRateLimit,
+    pub timeouts: GrpcTimeouts,
+    pub logging: GrpcLogging,
+}
+
+impl GrpcConfig {
+    /// Parses the text of `config/grpc.toml`.
+    pub fn from_toml(toml: &str) -> Result<Self> {
+        toml_file("grpc.toml", toml)
+    }
+}
+
+/// `[server]` of `nats.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NatsServer {
+    pub listen: String,
+    pub jetstream: bool,
+    pub max_memory: String,
+    pub max_storage: String,
+}
+
+/// One `users` entry of a NATS account.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NatsUser {
+    pub user: String,
+    pub password: String,
+}
+
+/// One `[accounts.<tenant>]`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NatsAccount {
+    pub jetstream: bool,
+    #[serde(default)]
+    pub users: Vec<NatsUser>,
+}
+
+/// `[jetstream]` of `nats.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NatsJetStream {
+    pub store_dir: String,
+}
+
+/// A stream's `max_age`: `0` for unlimited or a duration such as `"168h"`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum NatsMaxAge {
+    Seconds(u64),
+    Duration(String),
+}
+
+/// One `[streams."<name>"]`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NatsStream {
+    pub subjects: Vec<String>,
+    pub retention: String,
+    /// `-1` for unlimited.
+    pub max_msgs: i64,
+    /// `-1` for unlimited.
+    pub max_bytes: i64,
+    pub max_age: NatsMaxAge,
+    pub replicas: u8,
+    pub discard: String,
+    pub storage: String,
+}
+
+/// One `[consumer."<name>"]`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NatsConsumer {
+    /// Must name one of `[streams]`.
+    pub stream: String,
+    pub durable_name: String,
+    #[serde(default)]
+    pub deliver_group: Option<String>,
+    #[serde(default)]
+    pub filter_subject: Option<String>,
+    #[serde(default)]
+    pub deliver_all: bool,
+    pub ack_policy: String,
+    #[serde(default)]
+    pub max_ack_pending: Option<u32>,
+}
+
+/// The whole of `config/nats.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NatsConfig {
+    pub server: NatsServer,
+    #[serde(default)]
+    pub accounts: BTreeMap<String, NatsAccount>,
+    pub jetstream: NatsJetStream,
+    pub streams: BTreeMap<String, NatsStream>,
+    #[serde(default)]
+    pub consumer: BTreeMap<String, NatsConsumer>,
+}
+
+impl NatsConfig {
+    /// Parses the text of `config/nats.toml`.
+    pub fn from_toml(toml: &str) -> Result<Self> {
+        toml_file("nats.toml", toml)
+    }
+}
+
+/// `pinning_service` of `ipfs.yaml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PinningService {
+    pub name: String,
+    pub endpoint: String,
+    pub jwt: String,
+}
+
+/// The whole of `config/ipfs.yaml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IpfsConfig {
+    pub gateway: String,
+    pub api: String,
+    pub timeout_seconds: u64,
+    pub pinning_service: PinningService,
+    /// Must list the same files as `arweave.yaml` `anchor_targets`.
+    pub pin_targets: Vec<String>,
+    /// Per-tenant root path.
+    pub paths: BTreeMap<String, String>,
+    pub pin_recursive: bool,
+    pub pin_timeout_minutes: u64,
+    pub retry_attempts: u32,
+}
+
+impl IpfsConfig {
+    /// Parses the text of `config/ipfs.yaml`.
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        yaml_file("ipfs.yaml", yaml)
+    }
+}
+
+/// The whole of `config/arweave.yaml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ArweaveConfig {
+    pub wallet: String,
+    pub gateway: String,
+    pub timeout_seconds: u64,
+    pub tags: BTreeMap<String, String>,
+    pub bundle_size_limit_bytes: u64,
+    pub min_confirmations: u32,
+    pub retry_attempts: u32,
+    pub retry_backoff_seconds: u64,
+    /// Must list the same files as `ipfs.yaml` `pin_targets`.
+    pub anchor_targets: Vec<String>,
+}
+
+impl ArweaveConfig {
+    /// Parses the text of `config/arweave.yaml`.
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        yaml_file("arweave.yaml", yaml)
+    }
+}
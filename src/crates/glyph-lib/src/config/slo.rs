@@ -0,0 +1,134 @@
+//! Typed `config/slo.toml`.
+//!
+//! Every section and key in the file is required and unknown keys are
+//! rejected, so a renamed objective fails at load time instead of silently
+//! falling back to a default. Validators across glyph-lib take a loaded
+//! [`SloConfig`] rather than re-reading the file.
+
+use serde::Deserialize;
+
+use crate::config::loader::toml_file;
+use crate::error::{GlyphError, Result};
+
+/// `[global]`: end-to-end objectives.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GlobalSlo {
+    pub max_e2e_latency_ms: u64,
+    pub max_anomaly_rate_per_hour: f64,
+}
+
+/// `[ledger]`: append and compaction objectives.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LedgerSlo {
+    pub min_append_throughput_per_sec: u64,
+    pub max_compaction_lag_seconds: u64,
+}
+
+/// `[nebula-guard]`: orbital verification and ZK objectives.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NebulaGuardSlo {
+    pub max_verification_latency_ms: u64,
+    pub max_zk_proof_generation_ms: u64,
+    pub min_kyber_verify_per_second: u64,
+    /// Orbital signal drift, in percent, before it counts as an anomaly.
+    pub max_drift_percent: f64,
+}
+
+/// `[digital-twin-groot]`: entanglement simulation objectives.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DigitalTwinSlo {
+    pub min_entanglement_correlation: f64,
+    pub min_predicted_latency_negation_ms: f64,
+    pub max_simulation_cycle_ms: u64,
+}
+
+/// `[rocket-engine]`: boring simulation objectives.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RocketEngineSlo {
+    pub min_meters_per_hour: f64,
+    pub max_prediction_error_percent: f64,
+}
+
+/// `[mantis-community]`: voice paging objectives.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MantisCommunitySlo {
+    pub max_page_delivery_seconds: u64,
+}
+
+/// `[drax-metrics]`: self-diagnostic objectives.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DraxMetricsSlo {
+    pub daemon_status_interval_seconds: u64,
+    pub max_missing_statuses: u32,
+}
+
+/// `[halts]`: thresholds that emit an `emergency_halt` IntentGlyph.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HaltTriggers {
+    pub e2e_latency_breached_ms: u64,
+    pub anomaly_rate_breached: f64,
+    pub entanglement_below_threshold: f64,
+    pub zk_proof_time_breached_ms: u64,
+    pub missing_statuses_breached: u32,
+    pub require_human_acknowledgment: bool,
+}
+
+/// The whole of `config/slo.toml`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SloConfig {
+    pub global: GlobalSlo,
+    pub ledger: LedgerSlo,
+    #[serde(rename = "nebula-guard")]
+    pub nebula_guard: NebulaGuardSlo,
+    #[serde(rename = "digital-twin-groot")]
+    pub digital_twin_groot: DigitalTwinSlo,
+    #[serde(rename = "rocket-engine")]
+    pub rocket_engine: RocketEngineSlo,
+    #[serde(rename = "mantis-community")]
+    pub mantis_community: MantisCommunitySlo,
+    #[serde(rename = "drax-metrics")]
+    pub drax_metrics: DraxMetricsSlo,
+    pub halts: HaltTriggers,
+}
+
+impl SloConfig {
+    /// Parses the text of `config/slo.toml`.
+    pub fn from_toml(toml: &str) -> Result<Self> {
+        toml_file("slo.toml", toml)
+    }
+}
+
+/// Errors unless `value` is at least `min`, for objective `objective`.
+pub(crate) fn require_at_least(objective: &'static str, value: f64, min: f64) -> Result<()> {
+    if value >= min {
+        Ok(())
+    } else {
+        Err(GlyphError::SloBreached {
+            objective,
+            value,
+            limit: min,
+        })
+    }
+}
+
+/// Errors unless `value` is at most `max`, for objective `objective`.
+pub(crate) fn require_at_most(objective: &'static str, value: f64, max: f64) -> Result<()> {
+    if value <= max {
+        Ok(())
+    } else {
+        Err(GlyphError::SloBreached {
+            objective,
+            value,
+            limit: max,
+        })
+    }
+}
@@ -0,0 +1,43 @@
+//! Typed `config/tenants/*.yaml`.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Deserialize;
+
+use crate::anchors::anchor_types::{Daemon, Guardian};
+use crate::config::loader::yaml_file;
+use crate::error::Result;
+use crate::pq::registry::TenantAccess;
+
+/// One tenant file.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TenantConfig {
+    pub tenant_id: String,
+    pub description: String,
+    pub ledger_prefix: String,
+    pub nats_subject_prefix: String,
+    pub ipfs_path: String,
+    pub arweave_tags: BTreeMap<String, String>,
+    pub storage_quota_bytes: u64,
+    pub allowed_guardians: BTreeSet<Guardian>,
+    pub allowed_daemons: BTreeSet<Daemon>,
+    /// Phases of `phase_map.yaml` this tenant may run in.
+    pub allowed_phases: BTreeSet<u8>,
+}
+
+impl TenantConfig {
+    /// Parses a tenant file; `file` names it in errors.
+    pub fn from_yaml(file: &str, yaml: &str) -> Result<Self> {
+        yaml_file(file, yaml)
+    }
+
+    /// The signer lists the key registry scopes keys by.
+    pub fn access(&self) -> TenantAccess {
+        TenantAccess {
+            tenant_id: self.tenant_id.clone(),
+            allowed_guardians: self.allowed_guardians.clone(),
+            allowed_daemons: self.allowed_daemons.clone(),
+        }
+    }
+}
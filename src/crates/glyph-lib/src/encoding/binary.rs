@@ -0,0 +1,172 @@
+//! Compact binary glyph encoding.
+//!
+//! A binary glyph is the glyph's JSON object written as deterministic CBOR
+//! (RFC 8949 §4.2.1). Like the canonical JSON encoder, the byte format is a
+//! wire contract:
+//!
+//! 1. **Objects** become definite-length maps with text keys, sorted by the
+//!    bytes of the encoded key (shorter keys first, then bytewise).
+//! 2. **Arrays** become definite-length arrays, order preserved.
+//! 3. **Integers** (JSON `i64`/`u64`) become CBOR integers in their shortest
+//!    head.
+//! 4. **Floats** become the shortest of half, single or double precision
+//!    that holds the value exactly. NaN and infinities are rejected, as in
+//!    canonical JSON.
+//! 5. **Strings**, `true`, `false` and `null` map to their CBOR counterparts.
+//!    Byte strings, tags, `undefined` and indefinite lengths never appear.
+//!
+//! Integers and floats stay distinct, so a binary glyph decodes to the same
+//! `serde_json::Value` it was encoded from, and therefore to the same
+//! canonical JSON preimage and `blake3_hash`. Signatures made over a JSON
+//! glyph keep verifying after it is stored in binary.
+//!
+//! Decoding accepts only the deterministic form: any other encoding of the
+//! same value, trailing bytes, or a value JSON cannot hold is rejected, so
+//! each glyph has exactly one binary form.
+
+use std::cmp::Ordering;
+
+use ciborium::value::{Integer, Value as Cbor};
+use serde_json::{Number, Value};
+
+use crate::anchors::anchor_types::{Glyph, GlyphKind, JsonMap};
+use crate::error::{GlyphError, Result};
+use crate::hashing::canonical;
+
+/// Version tag of the byte format documented above.
+pub const BINARY_FORMAT: &str = "deterministic-cbor/v1";
+
+/// Deterministic CBOR of an arbitrary JSON value.
+pub fn to_binary_value(value: &Value) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    ciborium::ser::into_writer(&to_cbor(value)?, &mut out)
+        .map_err(|e| binary_error(e.to_string()))?;
+    Ok(out)
+}
+
+/// Decodes deterministic CBOR back to JSON.
+pub fn from_binary_value(bytes: &[u8]) -> Result<Value> {
+    let mut rest = bytes;
+    let cbor: Cbor =
+        ciborium::de::from_reader(&mut rest).map_err(|e| binary_error(e.to_string()))?;
+    if !rest.is_empty() {
+        return Err(binary_error(format!("{} trailing bytes", rest.len())));
+    }
+    let value = from_cbor(cbor)?;
+    if to_binary_value(&value)? != bytes {
+        return Err(binary_error("input is not in deterministic form"));
+    }
+    Ok(value)
+}
+
+/// Binary form of a typed glyph.
+pub fn to_binary<G: Glyph>(glyph: &G) -> Result<Vec<u8>> {
+    to_binary_value(&serde_json::to_value(glyph)?)
+}
+
+/// Decodes a typed glyph from its binary form.
+pub fn from_binary<G: Glyph>(bytes: &[u8]) -> Result<G> {
+    Ok(serde_json::from_value(from_binary_value(bytes)?)?)
+}
+
+/// Canonical JSON of a binary glyph, for storage that must hand out JSON.
+pub fn binary_to_canonical_json(bytes: &[u8]) -> Result<Vec<u8>> {
+    canonical::to_canonical_bytes(&from_binary_value(bytes)?)
+}
+
+/// Hash preimage of a binary glyph; identical to its JSON form's.
+pub fn preimage_binary(kind: GlyphKind, bytes: &[u8]) -> Result<Vec<u8>> {
+    canonical::preimage_value(kind, &from_binary_value(bytes)?)
+}
+
+/// `blake3_hash` of a binary glyph.
+pub fn content_hash_binary(kind: GlyphKind, bytes: &[u8]) -> Result<blake3::Hash> {
+    Ok(blake3::hash(&preimage_binary(kind, bytes)?))
+}
+
+fn binary_error(reason: impl Into<String>) -> GlyphError {
+    GlyphError::Binary {
+        reason: reason.into(),
+    }
+}
+
+fn to_cbor(value: &Value) -> Result<Cbor> {
+    Ok(match value {
+        Value::Null => Cbor::Null,
+        Value::Bool(b) => Cbor::Bool(*b),
+        Value::Number(n) => {
+            if let Some(u) = n.as_u64() {
+                Cbor::Integer(u.into())
+            } else if let Some(i) = n.as_i64() {
+                Cbor::Integer(i.into())
+            } else {
+                match n.as_f64() {
+                    Some(f) if f.is_finite() => Cbor::Float(f),
+                    _ => return Err(binary_error("NaN and infinity have no binary form")),
+                }
+            }
+        }
+        Value::String(s) => Cbor::Text(s.clone()),
+        Value::Array(items) => Cbor::Array(items.iter().map(to_cbor).collect::<Result<_>>()?),
+        Value::Object(map) => {
+            let mut members: Vec<_> = map.iter().collect();
+            members.sort_unstable_by(|a, b| key_order(a.0, b.0));
+            Cbor::Map(
+                members
+                    .into_iter()
+                    .map(|(k, v)| Ok((Cbor::Text(k.clone()), to_cbor(v)?)))
+                    .collect::<Result<_>>()?,
+            )
+        }
+    })
+}
+
+/// RFC 8949 §4.2.1 order for text keys: encoded length, then bytes.
+fn key_order(a: &str, b: &str) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.as_bytes().cmp(b.as_bytes()))
+}
+
+fn from_cbor(cbor: Cbor) -> Result<Value> {
+    Ok(match cbor {
+        Cbor::Null => Value::Null,
+        Cbor::Bool(b) => Value::Bool(b),
+        Cbor::Integer(i) => Value::Number(integer_to_number(i)?),
+        Cbor::Float(f) => Value::Number(
+            Number::from_f64(f)
+                .ok_or_else(|| binary_error("NaN and infinity have no JSON form"))?,
+        ),
+        Cbor::Text(s) => Value::String(s),
+        Cbor::Array(items) => {
+            Value::Array(items.into_iter().map(from_cbor).collect::<Result<_>>()?)
+        }
+        Cbor::Map(members) => {
+            let mut map = JsonMap::new();
+            for (key, value) in members {
+                let Cbor::Text(key) = key else {
+                    return Err(binary_error("map keys must be text"));
+                };
+                if map.contains_key(&key) {
+                    return Err(binary_error(format!("duplicate key {key:?}")));
+                }
+                map.insert(key, from_cbor(value)?);
+            }
+            Value::Object(map)
+        }
+        Cbor::Bytes(_) => return Err(binary_error("byte strings have no JSON form")),
+        Cbor::Tag(tag, _) => return Err(binary_error(format!("tag {tag} has no JSON form"))),
+        _ => return Err(binary_error("value has no JSON form")),
+    })
+}
+
+fn integer_to_number(i: Integer) -> Result<Number> {
+    let wide = i128::from(i);
+    if let Ok(u) = u64::try_from(wide) {
+        Ok(u.into())
+    } else if let Ok(i) = i64::try_from(wide) {
+        Ok(i.into())
+    } else {
+        Err(binary_error(format!("integer {wide} is out of JSON range")))
+    }
+}
@@ -0,0 +1,205 @@
+//! Error type shared by every glyph-lib module.
+
+use thiserror::Error;
+
+use crate::anchors::anchor_types::{
+    GlyphKind, Guardian, ReceiptResult, ReceiptType, TargetContext,
+};
+use crate::hashing::ids::GlyphId;
+
+/// Everything that can go wrong while decoding, hashing or verifying a glyph.
+#[derive(Debug, Error)]
+pub enum GlyphError {
+    /// The input was not valid JSON or did not match the Rust model.
+    #[error("glyph decode failed: {0}")]
+    Decode(#[from] serde_json::Error),
+
+    /// A string did not name any variant of a closed schema enum.
+    #[error("unknown {kind} value: {value:?}")]
+    UnknownVariant { kind: &'static str, value: String },
+
+    /// A value has no canonical JSON encoding.
+    #[error("canonical encoding failed: {reason}")]
+    Canonical { reason: &'static str },
+
+    /// A string is not a well-formed glyph identifier.
+    #[error("invalid glyph id {value:?}: {reason}")]
+    InvalidGlyphId { value: String, reason: &'static str },
+
+    /// A glyph carries an ID that was not derived from its payload.
+    #[error("glyph id mismatch: expected {expected}, found {found}")]
+    GlyphIdMismatch { expected: GlyphId, found: GlyphId },
+
+    /// A string is not a 64-character lower-case hex BLAKE3 digest.
+    #[error("invalid blake3 digest {value:?}")]
+    InvalidHash { value: String },
+
+    /// A glyph's `blake3_hash` is not the hash of its content.
+    #[error("content hash mismatch: expected {expected}, found {found}")]
+    HashMismatch { expected: String, found: String },
+
+    /// A Merkle proof is structurally unusable for the claimed tree.
+    #[error("invalid merkle proof: {reason}")]
+    InvalidMerkleProof { reason: &'static str },
+
+    /// A receipt is not committed to by the anchor it is checked against.
+    #[error("receipt {receipt_id} is not anchored: {reason}")]
+    NotAnchored {
+        receipt_id: GlyphId,
+        reason: &'static str,
+    },
+
+    /// Two anchors are not linked the way `previous_glyph_id` claims.
+    #[error("broken anchor chain: {reason}")]
+    BrokenChain { reason: &'static str },
+
+    /// A signature field does not hold a well-formed envelope.
+    #[error("invalid signature envelope: {reason}")]
+    InvalidEnvelope { reason: &'static str },
+
+    /// A signature was well-formed but did not verify.
+    #[error("bad signature: {reason}")]
+    BadSignature { reason: &'static str },
+
+    /// Encoded key material could not be decoded.
+    #[error("invalid key: {reason}")]
+    InvalidKey { reason: &'static str },
+
+    /// The key registry's configuration or key material is unusable.
+    #[error("invalid key registry: {reason}")]
+    InvalidRegistry { reason: String },
+
+    /// A glyph names a tenant the key registry does not know.
+    #[error("unknown tenant {tenant_id:?}")]
+    UnknownTenant { tenant_id: String },
+
+    /// A Guardian or daemon signed something it is not allowed to sign.
+    #[error("{signer} may not sign for {scope}")]
+    SignerNotAllowed { signer: String, scope: String },
+
+    /// A signing key was used outside its validity window or after revocation.
+    #[error("key {key} of {signer} is not active at {at}")]
+    KeyNotActive {
+        signer: String,
+        key: String,
+        at: i64,
+    },
+
+    /// A quorum policy (e.g. `swarm_roles.yaml`) is unusable.
+    #[error("invalid quorum policy: {reason}")]
+    InvalidPolicy { reason: String },
+
+    /// A file under `config/` is missing keys, mistyped or inconsistent.
+    #[error("invalid config {file}: {reason}")]
+    InvalidConfig { file: String, reason: String },
+
+    /// The `config/` directory as a whole failed to load; every problem found is listed.
+    #[error("{} config violation(s): {}", violations.len(), violations.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+    ConfigViolations {
+        violations: Vec<crate::config::loader::ConfigViolation>,
+    },
+
+    /// A config file no longer hashes to the value pinned for it.
+    #[error("config {file} drifted: pinned {pinned}, loaded {loaded}")]
+    ConfigDrift {
+        file: String,
+        pinned: String,
+        loaded: String,
+    },
+
+    /// An anchor pins no hash for a config file a daemon depends on.
+    #[error("config {file} is not pinned")]
+    ConfigNotPinned { file: String },
+
+    /// A glyph reports a value outside its service-level objective.
+    #[error("{objective} breached: {value} against a limit of {limit}")]
+    SloBreached {
+        objective: &'static str,
+        value: f64,
+        limit: f64,
+    },
+
+    /// A Guardian holding `veto_power` signed a rejection.
+    #[error("anchor vetoed by {guardian}")]
+    QuorumVetoed { guardian: Guardian },
+
+    /// An anomaly anchor lacks the approval the policy requires.
+    #[error("anomaly anchor needs approval from {guardian}")]
+    QuorumMissingApproval { guardian: Guardian },
+
+    /// Approving weight fell short of `min_acceptance_score`.
+    #[error("quorum not met: score {score:.3} below {required}")]
+    QuorumNotMet { score: f64, required: f64 },
+
+    /// No approving Guardian is allowed to finalize anchors.
+    #[error("quorum has no approver with can_finalize")]
+    QuorumNotFinalized,
+
+    /// A ZK proof, its public inputs or its parameters are malformed.
+    #[error("invalid zk proof: {reason}")]
+    InvalidZkProof { reason: String },
+
+    /// A well-formed ZK proof did not verify.
+    #[error("zk proof rejected")]
+    ZkProofRejected,
+
+    /// The prover was asked to prove a drift that breaks its bound.
+    #[error("drift {drift_percent}% is not below max_drift_percent {max_drift_percent}%")]
+    DriftExceedsBound {
+        drift_percent: f64,
+        max_drift_percent: f64,
+    },
+
+    /// A glyph's `version` has no migration path to the current schema.
+    #[error("unsupported {kind:?} schema version {version:?}")]
+    UnsupportedVersion { kind: GlyphKind, version: String },
+
+    /// A migration step is malformed or failed on a glyph.
+    #[error("migration {from} -> {to} failed: {reason}")]
+    Migration {
+        from: String,
+        to: String,
+        reason: String,
+    },
+
+    /// A receipt's type-specific fields are missing, malformed or inconsistent.
+    #[error("invalid {receipt_type} payload: {reason}")]
+    InvalidReceiptPayload {
+        receipt_type: ReceiptType,
+        reason: String,
+    },
+
+    /// An anchor's context-specific `payload` or extension is missing or malformed.
+    #[error("invalid {context} anchor payload: {reason}")]
+    InvalidAnchorPayload {
+        context: TargetContext,
+        reason: String,
+    },
+
+    /// A receipt type that can never carry this `result`.
+    #[error("{receipt_type} receipts cannot have result {result}")]
+    ResultNotAllowed {
+        receipt_type: ReceiptType,
+        result: ReceiptResult,
+    },
+
+    /// A binary glyph is malformed or not in deterministic form.
+    #[cfg(feature = "binary")]
+    #[error("binary encoding failed: {reason}")]
+    Binary { reason: String },
+
+    /// A builder was given inputs no valid glyph can be made from.
+    #[error("cannot build glyph: {reason}")]
+    Builder { reason: String },
+
+    /// A document violates its embedded JSON Schema.
+    #[cfg(feature = "schemas")]
+    #[error("{} schema violated: {}", crate::schemas::validator::schema_name(*kind), violations.first().map(ToString::to_string).unwrap_or_default())]
+    SchemaInvalid {
+        kind: GlyphKind,
+        violations: Vec<crate::schemas::validator::SchemaViolation>,
+    },
+}
+
+/// Convenience alias used throughout glyph-lib.
+pub type Result<T> = std::result::Result<T, GlyphError>;
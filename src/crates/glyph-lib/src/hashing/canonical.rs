@@ -0,0 +1,248 @@
+//! Canonical JSON encoding for hashing.
+//!
+//! Every daemon hashes glyphs through this encoder, so the byte format below
+//! is a wire contract. Changing any rule changes every `blake3_hash` in the
+//! ledger; treat it like a schema bump.
+//!
+//! # Byte format (canonical JSON v1)
+//!
+//! 1. **Whitespace** — none. No spaces, newlines or trailing newline.
+//! 2. **Objects** — members sorted by key, comparing the keys' UTF-8 bytes.
+//!    Producers must not emit duplicate keys; a decoded `serde_json::Value`
+//!    keeps only the last occurrence.
+//! 3. **Arrays** — element order is preserved.
+//! 4. **Literals** — `true`, `false`, `null`. An explicit `null` is kept and
+//!    is distinct from an absent member.
+//! 5. **Integers** — values that decoded as JSON integers (`i64`/`u64`) are
+//!    written in plain decimal, exactly, with a leading `-` when negative.
+//! 6. **Floats** — finite `f64` values are written with the ECMAScript
+//!    `Number.prototype.toString` algorithm (as RFC 8785 does): the shortest
+//!    digit string that round-trips, decimal notation for exponents in
+//!    `[-6, 21)`, otherwise `d.ddde±x`. Consequently `10.0` is written `10`,
+//!    `0.1` is `0.1`, `1e21` is `1e+21`, `-0.0` is `0`. NaN and infinities
+//!    are rejected. Decimal input must be parsed with correct rounding;
+//!    glyph-lib enables serde_json's `float_roundtrip` for that reason, as the
+//!    default fast path can land one ULP away and change the digits.
+//! 7. **Strings** — UTF-8, wrapped in `"`. Only `"` and `\` and the C0 control
+//!    characters are escaped: `\b \t \n \f \r` use their short forms, every
+//!    other code point below U+0020 is written `\u00xx` with lower-case hex.
+//!    Everything else, including `/`, DEL and non-ASCII, is written verbatim.
+//!
+//! # Excluded fields
+//!
+//! The hash preimage of a glyph is the canonical encoding of the glyph with
+//! a fixed set of **top-level** members removed (see [`excluded_fields`]):
+//!
+//! | Glyph          | Removed before hashing                                  |
+//! |----------------|---------------------------------------------------------|
+//! | IntentGlyph    | `signature`                                             |
+//! | ReceiptGlyph   | `blake3_hash`, `kyber_signature`, `merkle_root`, `merkle_proof` |
+//! | AnchorGlyph    | `blake3_hash`, `kyber_signature`, `merkle_proof`        |
+//! | DaemonStatus   | `blake3_hash`, `kyber_signature`                        |
+//!
+//! A receipt's hash is the Merkle leaf of its batch, so the batch root and
+//! the receipt's own inclusion proof cannot be part of it. An anchor commits
+//! to its `merkle_root` but not to the proof placing it in a larger batch.
+//!
+//! Conformance vectors live in `glyphs/vectors/canonical_json.vectors.json`.
+
+use std::fmt::Write as _;
+
+use serde::Serialize;
+use serde_json::{Number, Value};
+
+use crate::anchors::anchor_types::{Glyph, GlyphKind};
+use crate::error::{GlyphError, Result};
+
+/// Version tag of the byte format documented above.
+pub const CANONICAL_FORMAT: &str = "canonical-json/v1";
+
+/// Top-level members removed from a glyph before hashing.
+pub fn excluded_fields(kind: GlyphKind) -> &'static [&'static str] {
+    match kind {
+        GlyphKind::Intent => &["signature"],
+        GlyphKind::Receipt => &["blake3_hash", "kyber_signature", "merkle_root", "merkle_proof"],
+        GlyphKind::Anchor => &["blake3_hash", "kyber_signature", "merkle_proof"],
+        GlyphKind::DaemonStatus => &["blake3_hash", "kyber_signature"],
+    }
+}
+
+/// Canonical bytes of an arbitrary JSON value, with nothing excluded.
+pub fn to_canonical_bytes(value: &Value) -> Result<Vec<u8>> {
+    let mut out = String::new();
+    write_value(&mut out, value)?;
+    Ok(out.into_bytes())
+}
+
+/// Canonical string of an arbitrary JSON value, with nothing excluded.
+pub fn to_canonical_string(value: &Value) -> Result<String> {
+    let mut out = String::new();
+    write_value(&mut out, value)?;
+    Ok(out)
+}
+
+/// Hash preimage of a glyph given as raw JSON.
+///
+/// The value must be an object; the members listed by [`excluded_fields`]
+/// for `kind` are skipped if present.
+pub fn preimage_value(kind: GlyphKind, value: &Value) -> Result<Vec<u8>> {
+    preimage_value_excluding(kind, value, &[])
+}
+
+/// Hash preimage of a raw JSON glyph with `extra` top-level members removed
+/// on top of [`excluded_fields`]. Used for ID derivation.
+pub fn preimage_value_excluding(kind: GlyphKind, value: &Value, extra: &[&str]) -> Result<Vec<u8>> {
+    let map = value.as_object().ok_or(GlyphError::Canonical {
+        reason: "glyph must be a JSON object",
+    })?;
+    let excluded = excluded_fields(kind);
+    let mut out = String::new();
+    write_object(
+        &mut out,
+        map.iter()
+            .filter(|(k, _)| !excluded.contains(&k.as_str()) && !extra.contains(&k.as_str())),
+    )?;
+    Ok(out.into_bytes())
+}
+
+/// Hash preimage of a typed glyph.
+pub fn preimage<G: Glyph>(glyph: &G) -> Result<Vec<u8>> {
+    preimage_value(G::KIND, &serde_json::to_value(glyph)?)
+}
+
+/// BLAKE3 over the preimage of a raw JSON glyph.
+pub fn content_hash_value(kind: GlyphKind, value: &Value) -> Result<blake3::Hash> {
+    Ok(blake3::hash(&preimage_value(kind, value)?))
+}
+
+/// BLAKE3 over the preimage of a typed glyph, i.e. its `blake3_hash`.
+pub fn content_hash<G: Glyph>(glyph: &G) -> Result<blake3::Hash> {
+    Ok(blake3::hash(&preimage(glyph)?))
+}
+
+/// Canonical bytes of any serializable value, with nothing excluded.
+pub fn canonical_bytes_of<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    to_canonical_bytes(&serde_json::to_value(value)?)
+}
+
+fn write_value(out: &mut String, value: &Value) -> Result<()> {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(true) => out.push_str("true"),
+        Value::Bool(false) => out.push_str("false"),
+        Value::Number(n) => write_number(out, n)?,
+        Value::String(s) => write_string(out, s),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(out, item)?;
+            }
+            out.push(']');
+        }
+        Value::Object(map) => write_object(out, map.iter())?,
+    }
+    Ok(())
+}
+
+fn write_object<'a, I>(out: &mut String, members: I) -> Result<()>
+where
+    I: Iterator<Item = (&'a String, &'a Value)>,
+{
+    let mut members: Vec<_> = members.collect();
+    members.sort_unstable_by(|a, b| a.0.as_bytes().cmp(b.0.as_bytes()));
+    out.push('{');
+    for (i, (key, value)) in members.into_iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        write_string(out, key);
+        out.push(':');
+        write_value(out, value)?;
+    }
+    out.push('}');
+    Ok(())
+}
+
+fn write_number(out: &mut String, n: &Number) -> Result<()> {
+    if let Some(u) = n.as_u64() {
+        let _ = write!(out, "{u}");
+    } else if let Some(i) = n.as_i64() {
+        let _ = write!(out, "{i}");
+    } else {
+        let f = n.as_f64().ok_or(GlyphError::Canonical {
+            reason: "number is not representable as f64",
+        })?;
+        write_f64(out, f)?;
+    }
+    Ok(())
+}
+
+/// ECMAScript `Number.prototype.toString` for finite doubles.
+fn write_f64(out: &mut String, f: f64) -> Result<()> {
+    if !f.is_finite() {
+        return Err(GlyphError::Canonical {
+            reason: "NaN and infinity have no canonical form",
+        });
+    }
+    if f == 0.0 {
+        out.push('0');
+        return Ok(());
+    }
+    if f < 0.0 {
+        out.push('-');
+    }
+
+    // Rust's `{:e}` yields the shortest round-tripping digits, e.g. "1.25e-3".
+    let sci = format!("{:e}", f.abs());
+    let (mantissa, exp) = sci.split_once('e').expect("`{:e}` always has an exponent");
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let exp: i32 = exp.parse().expect("`{:e}` exponent is an integer");
+    let k = digits.len() as i32;
+    // ECMAScript's n: value = 0.d1d2...dk × 10^n.
+    let n = exp + 1;
+
+    if k <= n && n <= 21 {
+        out.push_str(&digits);
+        out.extend(std::iter::repeat_n('0', (n - k) as usize));
+    } else if 0 < n && n <= 21 {
+        out.push_str(&digits[..n as usize]);
+        out.push('.');
+        out.push_str(&digits[n as usize..]);
+    } else if -6 < n && n <= 0 {
+        out.push_str("0.");
+        out.extend(std::iter::repeat_n('0', (-n) as usize));
+        out.push_str(&digits);
+    } else {
+        out.push_str(&digits[..1]);
+        if k > 1 {
+            out.push('.');
+            out.push_str(&digits[1..]);
+        }
+        let e = n - 1;
+        let _ = write!(out, "e{}{}", if e < 0 { '-' } else { '+' }, e.abs());
+    }
+    Ok(())
+}
+
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{08}' => out.push_str("\\b"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\u{0C}' => out.push_str("\\f"),
+            '\r' => out.push_str("\\r"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
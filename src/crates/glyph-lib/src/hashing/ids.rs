@@ -0,0 +1,197 @@
+//! BLAKE3-derived glyph identifiers.
+//!
+//! An ID is `<prefix>-<32 hex>`, where the hex is the first 16 bytes of
+//! BLAKE3 in `derive_key` mode over the glyph's ID preimage. The preimage is
+//! the canonical hash preimage (see [`canonical`](super::canonical)) with the
+//! ID field itself removed as well, so the ID commits to the payload and the
+//! `blake3_hash` then commits to the ID.
+//!
+//! Each glyph kind uses its own `derive_key` context, so an intent and a
+//! receipt with byte-identical payloads still get unrelated IDs.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+use crate::anchors::anchor_types::{Glyph, GlyphKind, GENESIS};
+use crate::error::{GlyphError, Result};
+use crate::hashing::canonical;
+
+/// Number of digest bytes kept in an ID (32 hex characters).
+pub const ID_DIGEST_LEN: usize = 16;
+
+/// BLAKE3 `derive_key` context for each glyph kind.
+pub fn id_context(kind: GlyphKind) -> &'static str {
+    match kind {
+        GlyphKind::Intent => "truth-tunnel glyph-lib v1 intent-id",
+        GlyphKind::Receipt => "truth-tunnel glyph-lib v1 receipt-id",
+        GlyphKind::Anchor => "truth-tunnel glyph-lib v1 anchor-id",
+        GlyphKind::DaemonStatus => "truth-tunnel glyph-lib v1 status-id",
+    }
+}
+
+/// Name of the member holding a glyph's own ID.
+pub fn id_field(kind: GlyphKind) -> &'static str {
+    match kind {
+        GlyphKind::Receipt => "receipt_id",
+        _ => "glyph_id",
+    }
+}
+
+/// A validated `intent-…`, `receipt-…`, `anchor-…` or `status-…` identifier.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GlyphId {
+    kind: GlyphKind,
+    digest: [u8; ID_DIGEST_LEN],
+}
+
+impl GlyphId {
+    /// Builds an ID from raw digest bytes.
+    pub fn from_digest(kind: GlyphKind, digest: [u8; ID_DIGEST_LEN]) -> Self {
+        GlyphId { kind, digest }
+    }
+
+    /// Derives the ID of `kind` for an already-encoded ID preimage.
+    pub fn derive(kind: GlyphKind, preimage: &[u8]) -> Self {
+        let key = blake3::derive_key(id_context(kind), preimage);
+        let mut digest = [0u8; ID_DIGEST_LEN];
+        digest.copy_from_slice(&key[..ID_DIGEST_LEN]);
+        GlyphId { kind, digest }
+    }
+
+    /// Parses an ID and requires it to be of `kind`.
+    pub fn parse_as(kind: GlyphKind, s: &str) -> Result<Self> {
+        let id: GlyphId = s.parse()?;
+        if id.kind != kind {
+            return Err(GlyphError::InvalidGlyphId {
+                value: s.to_string(),
+                reason: "wrong glyph kind for this field",
+            });
+        }
+        Ok(id)
+    }
+
+    pub fn kind(&self) -> GlyphKind {
+        self.kind
+    }
+
+    pub fn digest(&self) -> &[u8; ID_DIGEST_LEN] {
+        &self.digest
+    }
+}
+
+impl fmt::Display for GlyphId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.kind.id_prefix(), hex::encode(self.digest))
+    }
+}
+
+impl fmt::Debug for GlyphId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "GlyphId({self})")
+    }
+}
+
+impl FromStr for GlyphId {
+    type Err = GlyphError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = |reason| GlyphError::InvalidGlyphId {
+            value: s.to_string(),
+            reason,
+        };
+        let (prefix, hex_part) = s.split_once('-').ok_or_else(|| invalid("missing prefix"))?;
+        let kind = GlyphKind::from_id_prefix(prefix).ok_or_else(|| invalid("unknown prefix"))?;
+        if hex_part.len() != ID_DIGEST_LEN * 2
+            || !hex_part
+                .bytes()
+                .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+        {
+            return Err(invalid("expected 32 lower-case hex characters"));
+        }
+        let mut digest = [0u8; ID_DIGEST_LEN];
+        hex::decode_to_slice(hex_part, &mut digest).map_err(|_| invalid("invalid hex"))?;
+        Ok(GlyphId { kind, digest })
+    }
+}
+
+impl Serialize for GlyphId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for GlyphId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// `previous_glyph_id` of an AnchorGlyph: another anchor, or `genesis`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChainLink {
+    Genesis,
+    Anchor(GlyphId),
+}
+
+impl fmt::Display for ChainLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChainLink::Genesis => f.write_str(GENESIS),
+            ChainLink::Anchor(id) => id.fmt(f),
+        }
+    }
+}
+
+impl FromStr for ChainLink {
+    type Err = GlyphError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s == GENESIS {
+            Ok(ChainLink::Genesis)
+        } else {
+            GlyphId::parse_as(GlyphKind::Anchor, s).map(ChainLink::Anchor)
+        }
+    }
+}
+
+impl Serialize for ChainLink {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ChainLink {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// ID preimage of a raw JSON glyph: the hash preimage minus the ID field.
+pub fn id_preimage_value(kind: GlyphKind, value: &Value) -> Result<Vec<u8>> {
+    canonical::preimage_value_excluding(kind, value, &[id_field(kind)])
+}
+
+/// Derives the ID a raw JSON glyph should carry.
+pub fn derive_id_value(kind: GlyphKind, value: &Value) -> Result<GlyphId> {
+    Ok(GlyphId::derive(kind, &id_preimage_value(kind, value)?))
+}
+
+/// Derives the ID a typed glyph should carry.
+pub fn derive_id<G: Glyph>(glyph: &G) -> Result<GlyphId> {
+    derive_id_value(G::KIND, &serde_json::to_value(glyph)?)
+}
+
+/// Checks that a typed glyph carries the ID derived from its payload.
+pub fn verify_id<G: Glyph>(glyph: &G) -> Result<()> {
+    let expected = derive_id(glyph)?;
+    let found = *glyph.id();
+    if found != expected {
+        return Err(GlyphError::GlyphIdMismatch { expected, found });
+    }
+    Ok(())
+}
@@ -0,0 +1,94 @@
+//! glyph-lib — Swarm Glyph Engine.
+//!
+//! One canonical Rust model for every glyph in Truth-Tunnel, plus the
+//! hashing, Merkle and signature rules every daemon must obey. This crate
+//! performs no I/O; higher-level crates own NATS, storage and the network.
+
+pub mod error;
+
+pub mod anchors {
+    pub mod anchor_types;
+    pub mod entanglement;
+    pub mod merkle;
+    pub mod mmr;
+    pub mod orbital;
+    pub mod quorum;
+    pub mod receipt_payload;
+}
+
+/// Typed views of the shared files under `config/`.
+pub mod config {
+    pub mod ledger;
+    pub mod loader;
+    pub mod orchestrator;
+    pub mod pinning;
+    pub mod services;
+    pub mod slo;
+    pub mod tenant;
+}
+
+pub mod hashing {
+    pub mod canonical;
+    pub mod ids;
+}
+
+/// Post-quantum signatures and key encapsulation.
+///
+/// The envelope format is always available so any build can parse and
+/// route signatures; the Dilithium and Kyber backends need `crypto`.
+pub mod pq {
+    pub mod envelope;
+    pub mod registry;
+
+    #[cfg(feature = "crypto")]
+    pub mod dilithium;
+    #[cfg(feature = "crypto")]
+    pub mod kem;
+}
+
+/// Groth16 proofs for nebula-guard's anomaly circuits.
+#[cfg(feature = "zk-groth16")]
+pub mod zk {
+    pub mod groth16;
+}
+
+/// Deterministic binary encoding, hash-equivalent to canonical JSON.
+#[cfg(feature = "binary")]
+pub mod encoding {
+    pub mod binary;
+}
+
+/// Schema-version negotiation and migration of historical glyphs.
+pub mod versioning {
+    pub mod migrate;
+}
+
+/// Schema validation against the embedded `glyphs/schemas/`.
+#[cfg(feature = "schemas")]
+pub mod schemas {
+    pub mod validator;
+}
+
+/// Batched verification of anchored receipts across cores.
+#[cfg(feature = "parallel")]
+pub mod verify {
+    pub mod engine;
+}
+
+/// Builders that emit fully hashed, signed and schema-checked glyphs.
+#[cfg(feature = "schemas")]
+pub mod builders {
+    pub mod anchor;
+    pub mod intent;
+    pub mod receipt;
+    pub mod status;
+}
+
+/// Keys and glyph factories shared by the test suites; never enabled in a
+/// daemon build.
+#[cfg(feature = "test-support")]
+pub mod testing;
+
+pub use anchors::anchor_types::*;
+pub use error::{GlyphError, Result};
+pub use hashing::ids::{ChainLink, GlyphId};
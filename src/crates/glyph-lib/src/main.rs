@@ -0,0 +1,153 @@
+//! `glyph-lib` command-line tool.
+//!
+//! ```text
+//! glyph-lib validate --type=<schema> [FILE...]
+//! glyph-lib config-manifest [--fill] [DIR]
+//! ```
+//!
+//! `validate` checks one or more JSON documents (a single object, JSONL, or
+//! several concatenated objects) read from each FILE, or from stdin when
+//! none is given. `<schema>` is `intent_glyph`, `receipt_glyph`,
+//! `anchor_glyph` or `daemon_status_glyph`.
+//!
+//! `config-manifest` loads the config directory DIR (default `config`) and
+//! prints the `extensions.config` manifest pinning it. With `--fill`, every
+//! `# Root hash: [BLAKE3 to be filled on commit]` header is first replaced
+//! by the file's hash; nothing is filled unless every file loads, so a
+//! broken file is never pinned.
+//!
+//! Exit status: 0 when every document is valid or the config loads, 1 when
+//! any document violates the schema or the config is inconsistent, 2 on
+//! usage, I/O or JSON syntax errors.
+
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::process::ExitCode;
+
+use glyph_lib::config::loader::{ConfigSources, SwarmConfig};
+use glyph_lib::config::pinning::{config_manifest, fill_root_hash};
+use glyph_lib::schemas::validator::{kind_from_schema_name, validate};
+use glyph_lib::{GlyphError, GlyphKind};
+use serde_json::Value;
+
+const USAGE: &str = "usage: glyph-lib validate --type=<intent_glyph|receipt_glyph|anchor_glyph|daemon_status_glyph> [FILE...]
+       glyph-lib config-manifest [--fill] [DIR]";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(message) => {
+            eprintln!("glyph-lib: {message}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<bool, String> {
+    let (command, rest) = args.split_first().ok_or(USAGE)?;
+    match command.as_str() {
+        "validate" => run_validate(rest),
+        "config-manifest" => run_config_manifest(rest),
+        _ => Err(format!("unknown command {command:?}\n{USAGE}")),
+    }
+}
+
+fn run_validate(rest: &[String]) -> Result<bool, String> {
+    let mut kind = None;
+    let mut files = Vec::new();
+    let mut rest = rest.iter();
+    while let Some(arg) = rest.next() {
+        if let Some(name) = arg.strip_prefix("--type=") {
+            kind = Some(parse_kind(name)?);
+        } else if arg == "--type" {
+            kind = Some(parse_kind(rest.next().ok_or(USAGE)?)?);
+        } else if arg.starts_with("--") {
+            return Err(format!("unknown option {arg:?}\n{USAGE}"));
+        } else {
+            files.push(arg.clone());
+        }
+    }
+    let kind = kind.ok_or(USAGE)?;
+
+    let mut all_valid = true;
+    if files.is_empty() {
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .map_err(|e| format!("reading stdin: {e}"))?;
+        all_valid &= validate_source(kind, "<stdin>", &input)?;
+    } else {
+        for file in &files {
+            let input = fs::read_to_string(file).map_err(|e| format!("reading {file}: {e}"))?;
+            all_valid &= validate_source(kind, file, &input)?;
+        }
+    }
+    Ok(all_valid)
+}
+
+fn run_config_manifest(rest: &[String]) -> Result<bool, String> {
+    let mut fill = false;
+    let mut dir = None;
+    for arg in rest {
+        if arg == "--fill" {
+            fill = true;
+        } else if arg.starts_with("--") || dir.is_some() {
+            return Err(format!("unexpected argument {arg:?}\n{USAGE}"));
+        } else {
+            dir = Some(arg.as_str());
+        }
+    }
+    let root = Path::new(dir.unwrap_or("config"));
+    let sources = ConfigSources::read_dir(root).map_err(|e| e.to_string())?;
+
+    match SwarmConfig::from_sources(&sources) {
+        Ok(_) => {}
+        Err(GlyphError::ConfigViolations { violations }) => {
+            for violation in violations {
+                eprintln!("{}: {violation}", root.display());
+            }
+            return Ok(false);
+        }
+        Err(e) => return Err(e.to_string()),
+    }
+    if fill {
+        for (file, text) in sources.iter() {
+            let filled = fill_root_hash(text);
+            if filled != text {
+                let path = root.join(file);
+                fs::write(&path, filled).map_err(|e| format!("writing {}: {e}", path.display()))?;
+            }
+        }
+    }
+    let manifest = serde_json::to_string_pretty(&config_manifest(&sources))
+        .map_err(|e| format!("encoding manifest: {e}"))?;
+    println!("{manifest}");
+    Ok(true)
+}
+
+fn parse_kind(name: &str) -> Result<GlyphKind, String> {
+    kind_from_schema_name(name).ok_or_else(|| format!("unknown schema type {name:?}\n{USAGE}"))
+}
+
+fn validate_source(kind: GlyphKind, source: &str, input: &str) -> Result<bool, String> {
+    let mut valid = true;
+    let mut count = 0;
+    for (index, doc) in serde_json::Deserializer::from_str(input)
+        .into_iter::<Value>()
+        .enumerate()
+    {
+        let doc = doc.map_err(|e| format!("{source}: document {index}: {e}"))?;
+        count += 1;
+        for violation in validate(kind, &doc) {
+            valid = false;
+            eprintln!("{source}: document {index}: {violation}");
+        }
+    }
+    if count == 0 {
+        return Err(format!("{source}: no JSON documents"));
+    }
+    Ok(valid)
+}
@@ -0,0 +1,104 @@
+//! Dilithium5 signing keys backed by `pqcrypto-dilithium`.
+
+use pqcrypto_dilithium::dilithium5;
+use pqcrypto_traits::sign::{DetachedSignature as _, PublicKey as _, SecretKey as _};
+
+use crate::error::{GlyphError, Result};
+use crate::pq::envelope::{AlgorithmId, GlyphSigner, KeyId, SignatureVerifier};
+
+const ALGORITHM: AlgorithmId = AlgorithmId::Dilithium5;
+
+/// A Dilithium5 public key.
+#[derive(Clone)]
+pub struct DilithiumPublicKey {
+    key: dilithium5::PublicKey,
+    key_id: KeyId,
+}
+
+impl DilithiumPublicKey {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let key = dilithium5::PublicKey::from_bytes(bytes).map_err(|_| GlyphError::InvalidKey {
+            reason: "not a dilithium5 public key",
+        })?;
+        Ok(Self::wrap(key))
+    }
+
+    fn wrap(key: dilithium5::PublicKey) -> Self {
+        let key_id = KeyId::of_public_key(ALGORITHM, key.as_bytes());
+        DilithiumPublicKey { key, key_id }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.key.as_bytes()
+    }
+}
+
+impl SignatureVerifier for DilithiumPublicKey {
+    fn algorithm(&self) -> AlgorithmId {
+        ALGORITHM
+    }
+
+    fn key_id(&self) -> KeyId {
+        self.key_id
+    }
+
+    fn verify_raw(&self, message: &[u8], signature: &[u8]) -> bool {
+        match dilithium5::DetachedSignature::from_bytes(signature) {
+            Ok(sig) => dilithium5::verify_detached_signature(&sig, message, &self.key).is_ok(),
+            Err(_) => false,
+        }
+    }
+}
+
+/// A Dilithium5 key pair.
+pub struct DilithiumKeypair {
+    public: DilithiumPublicKey,
+    secret: dilithium5::SecretKey,
+}
+
+impl DilithiumKeypair {
+    /// Generates a fresh key pair from the system RNG.
+    pub fn generate() -> Self {
+        let (pk, sk) = dilithium5::keypair();
+        DilithiumKeypair {
+            public: DilithiumPublicKey::wrap(pk),
+            secret: sk,
+        }
+    }
+
+    /// Restores a key pair from its encoded halves.
+    pub fn from_bytes(public_key: &[u8], secret_key: &[u8]) -> Result<Self> {
+        let secret =
+            dilithium5::SecretKey::from_bytes(secret_key).map_err(|_| GlyphError::InvalidKey {
+                reason: "not a dilithium5 secret key",
+            })?;
+        Ok(DilithiumKeypair {
+            public: DilithiumPublicKey::from_bytes(public_key)?,
+            secret,
+        })
+    }
+
+    pub fn public_key(&self) -> &DilithiumPublicKey {
+        &self.public
+    }
+
+    pub fn secret_key_bytes(&self) -> &[u8] {
+        self.secret.as_bytes()
+    }
+}
+
+impl GlyphSigner for DilithiumKeypair {
+    fn algorithm(&self) -> AlgorithmId {
+        ALGORITHM
+    }
+
+    fn key_id(&self) -> KeyId {
+        self.public.key_id
+    }
+
+    fn sign_raw(&self, message: &[u8]) -> Vec<u8> {
+        dilithium5::detached_sign(message, &self.secret)
+            .as_bytes()
+            .to_vec()
+    }
+}
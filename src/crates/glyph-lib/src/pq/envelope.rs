@@ -0,0 +1,394 @@
+//! Versioned signature envelope stored in `kyber_signature` fields.
+//!
+//! The schemas keep their existing string fields; what changes is what the
+//! hex inside them means. An envelope is:
+//!
+//! ```text
+//! version: u8 (0x01) || algorithm: u16 BE || key_id: [u8; 16] || signature
+//! ```
+//!
+//! hex-encoded in lower case, so it still matches the receipt and intent
+//! schemas' `^[a-f0-9]+$` pattern and the anchor bundle's opaque
+//! `signature` string. A bare 32-byte value is the legacy BLAKE3 stub
+//! signature; it still decodes as [`SignatureField::Legacy`] so old
+//! glyphs can be read, but it never verifies.
+//!
+//! # Signing messages
+//!
+//! Signers never see a glyph, only a message built by one of the
+//! `*_signing_message` functions: a per-kind context string followed by
+//! the 32-byte `blake3_hash` and, where the glyph has one, the 32-byte
+//! `merkle_root`. Intents have no `blake3_hash`, so their message carries
+//! the canonical preimage instead.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::anchors::anchor_types::{
+    AnchorGlyph, Daemon, DaemonStatusGlyph, IntentGlyph, ReceiptGlyph,
+};
+use crate::anchors::merkle::{hash_from_hex, MerkleHash};
+use crate::error::{GlyphError, Result};
+use crate::hashing::canonical;
+
+/// Current envelope layout version.
+pub const ENVELOPE_VERSION: u8 = 0x01;
+
+/// Length of a signer's key identifier.
+pub const KEY_ID_LEN: usize = 16;
+
+/// Length of the legacy BLAKE3 stub signature.
+pub const LEGACY_STUB_LEN: usize = 32;
+
+const HEADER_LEN: usize = 1 + 2 + KEY_ID_LEN;
+
+/// Dilithium5 signature length, taken from the backend when it is built.
+#[cfg(feature = "crypto")]
+const DILITHIUM5_SIGNATURE_LEN: usize = pqcrypto_dilithium::dilithium5::signature_bytes();
+/// Dilithium5 signature length produced by `pqcrypto-dilithium` 0.5.
+#[cfg(not(feature = "crypto"))]
+const DILITHIUM5_SIGNATURE_LEN: usize = 4627;
+
+/// Signature algorithms an envelope can name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AlgorithmId {
+    /// CRYSTALS-Dilithium, NIST level 5 parameters.
+    Dilithium5,
+}
+
+impl AlgorithmId {
+    /// Every supported algorithm.
+    pub const ALL: &'static [AlgorithmId] = &[AlgorithmId::Dilithium5];
+
+    /// Wire value in the envelope header.
+    pub fn code(&self) -> u16 {
+        match self {
+            AlgorithmId::Dilithium5 => 0x0001,
+        }
+    }
+
+    pub fn from_code(code: u16) -> Option<Self> {
+        Self::ALL.iter().copied().find(|a| a.code() == code)
+    }
+
+    /// Inverse of [`AlgorithmId::as_str`].
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|a| a.as_str() == name)
+    }
+
+    /// Exact signature length, in bytes.
+    pub fn signature_len(&self) -> usize {
+        match self {
+            AlgorithmId::Dilithium5 => DILITHIUM5_SIGNATURE_LEN,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlgorithmId::Dilithium5 => "dilithium5",
+        }
+    }
+}
+
+impl fmt::Display for AlgorithmId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Identifier of a signing key: a BLAKE3 fingerprint of its public key.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct KeyId(pub [u8; KEY_ID_LEN]);
+
+impl KeyId {
+    /// Fingerprints an encoded public key.
+    pub fn of_public_key(algorithm: AlgorithmId, public_key: &[u8]) -> Self {
+        let mut hasher = blake3::Hasher::new_derive_key("truth-tunnel glyph-lib v1 pq-key-id");
+        hasher.update(&algorithm.code().to_be_bytes());
+        hasher.update(public_key);
+        let mut id = [0u8; KEY_ID_LEN];
+        id.copy_from_slice(&hasher.finalize().as_bytes()[..KEY_ID_LEN]);
+        KeyId(id)
+    }
+}
+
+impl fmt::Display for KeyId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&hex::encode(self.0))
+    }
+}
+
+impl fmt::Debug for KeyId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "KeyId({self})")
+    }
+}
+
+/// A decoded version-1 envelope.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureEnvelope {
+    pub algorithm: AlgorithmId,
+    pub key_id: KeyId,
+    pub signature: Vec<u8>,
+}
+
+fn invalid_envelope(reason: &'static str) -> GlyphError {
+    GlyphError::InvalidEnvelope { reason }
+}
+
+impl SignatureEnvelope {
+    /// Binary envelope bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(HEADER_LEN + self.signature.len());
+        out.push(ENVELOPE_VERSION);
+        out.extend_from_slice(&self.algorithm.code().to_be_bytes());
+        out.extend_from_slice(&self.key_id.0);
+        out.extend_from_slice(&self.signature);
+        out
+    }
+
+    /// Parses binary envelope bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < HEADER_LEN {
+            return Err(invalid_envelope("truncated header"));
+        }
+        if bytes[0] != ENVELOPE_VERSION {
+            return Err(invalid_envelope("unsupported envelope version"));
+        }
+        let algorithm = AlgorithmId::from_code(u16::from_be_bytes([bytes[1], bytes[2]]))
+            .ok_or_else(|| invalid_envelope("unknown algorithm"))?;
+        let mut key_id = [0u8; KEY_ID_LEN];
+        key_id.copy_from_slice(&bytes[3..HEADER_LEN]);
+        let signature = bytes[HEADER_LEN..].to_vec();
+        if signature.len() != algorithm.signature_len() {
+            return Err(invalid_envelope("wrong signature length for algorithm"));
+        }
+        Ok(SignatureEnvelope {
+            algorithm,
+            key_id: KeyId(key_id),
+            signature,
+        })
+    }
+
+    /// Lower-case hex form stored in glyph fields.
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+}
+
+/// What a `kyber_signature` (or intent `signature`) string contains.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignatureField {
+    /// Pre-envelope BLAKE3 stub; readable but never valid.
+    Legacy([u8; LEGACY_STUB_LEN]),
+    Envelope(SignatureEnvelope),
+}
+
+impl SignatureField {
+    /// Decodes a signature field.
+    pub fn parse(s: &str) -> Result<Self> {
+        if s.is_empty() || !s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')) {
+            return Err(invalid_envelope("expected lower-case hex"));
+        }
+        let bytes = hex::decode(s).map_err(|_| invalid_envelope("expected lower-case hex"))?;
+        if bytes.len() == LEGACY_STUB_LEN {
+            let mut stub = [0u8; LEGACY_STUB_LEN];
+            stub.copy_from_slice(&bytes);
+            return Ok(SignatureField::Legacy(stub));
+        }
+        SignatureEnvelope::from_bytes(&bytes).map(SignatureField::Envelope)
+    }
+
+    /// The envelope, or an error for legacy stubs.
+    pub fn into_envelope(self) -> Result<SignatureEnvelope> {
+        match self {
+            SignatureField::Envelope(env) => Ok(env),
+            SignatureField::Legacy(_) => Err(GlyphError::BadSignature {
+                reason: "legacy stub signatures are not verifiable",
+            }),
+        }
+    }
+}
+
+/// A private signing key.
+pub trait GlyphSigner {
+    fn algorithm(&self) -> AlgorithmId;
+    fn key_id(&self) -> KeyId;
+    /// Raw detached signature over `message`.
+    fn sign_raw(&self, message: &[u8]) -> Vec<u8>;
+
+    /// Signs `message` and wraps the result in an envelope.
+    fn sign(&self, message: &[u8]) -> SignatureEnvelope {
+        SignatureEnvelope {
+            algorithm: self.algorithm(),
+            key_id: self.key_id(),
+            signature: self.sign_raw(message),
+        }
+    }
+}
+
+/// A public verification key.
+pub trait SignatureVerifier {
+    fn algorithm(&self) -> AlgorithmId;
+    fn key_id(&self) -> KeyId;
+    /// True if `signature` is a valid detached signature over `message`.
+    fn verify_raw(&self, message: &[u8], signature: &[u8]) -> bool;
+
+    /// Checks the envelope names this key and carries a valid signature.
+    fn verify(&self, message: &[u8], envelope: &SignatureEnvelope) -> Result<()> {
+        if envelope.algorithm != self.algorithm() {
+            return Err(GlyphError::BadSignature {
+                reason: "algorithm does not match the verifying key",
+            });
+        }
+        if envelope.key_id != self.key_id() {
+            return Err(GlyphError::BadSignature {
+                reason: "envelope names a different key",
+            });
+        }
+        if !self.verify_raw(message, &envelope.signature) {
+            return Err(GlyphError::BadSignature {
+                reason: "signature does not verify",
+            });
+        }
+        Ok(())
+    }
+}
+
+fn message(context: &str, parts: &[&MerkleHash]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(context.len() + 1 + 32 * parts.len());
+    out.extend_from_slice(context.as_bytes());
+    out.push(0);
+    for part in parts {
+        out.extend_from_slice(*part);
+    }
+    out
+}
+
+/// Message signed into a receipt's `kyber_signature`.
+pub fn receipt_signing_message(receipt: &ReceiptGlyph) -> Result<Vec<u8>> {
+    Ok(message(
+        "truth-tunnel glyph-lib v1 receipt-signature",
+        &[
+            &hash_from_hex(&receipt.blake3_hash)?,
+            &hash_from_hex(&receipt.merkle_root)?,
+        ],
+    ))
+}
+
+/// Message every guardian signs into an anchor's quorum bundle.
+pub fn anchor_signing_message(anchor: &AnchorGlyph) -> Result<Vec<u8>> {
+    Ok(message(
+        "truth-tunnel glyph-lib v1 anchor-signature",
+        &[
+            &hash_from_hex(&anchor.blake3_hash)?,
+            &hash_from_hex(&anchor.merkle_root)?,
+        ],
+    ))
+}
+
+/// Message a Guardian signs to reject an anchor.
+///
+/// Distinct from [`anchor_signing_message`] so an approval can never be
+/// replayed as a veto or the other way round.
+pub fn anchor_rejection_message(anchor: &AnchorGlyph) -> Result<Vec<u8>> {
+    Ok(message(
+        "truth-tunnel glyph-lib v1 anchor-rejection",
+        &[
+            &hash_from_hex(&anchor.blake3_hash)?,
+            &hash_from_hex(&anchor.merkle_root)?,
+        ],
+    ))
+}
+
+/// Message signed into a DaemonStatusGlyph's `kyber_signature`.
+pub fn status_signing_message(status: &DaemonStatusGlyph) -> Result<Vec<u8>> {
+    Ok(message(
+        "truth-tunnel glyph-lib v1 status-signature",
+        &[&hash_from_hex(&status.blake3_hash)?],
+    ))
+}
+
+/// Message signed into an IntentGlyph's `signature`.
+pub fn intent_signing_message(intent: &IntentGlyph) -> Result<Vec<u8>> {
+    let mut out = b"truth-tunnel glyph-lib v1 intent-signature\0".to_vec();
+    out.extend_from_slice(&canonical::preimage(intent)?);
+    Ok(out)
+}
+
+/// Signs a receipt in place.
+pub fn sign_receipt(receipt: &mut ReceiptGlyph, signer: &dyn GlyphSigner) -> Result<()> {
+    receipt.kyber_signature = signer.sign(&receipt_signing_message(receipt)?).to_hex();
+    Ok(())
+}
+
+/// Verifies a receipt's `kyber_signature` against `verifier`.
+pub fn verify_receipt_signature(
+    receipt: &ReceiptGlyph,
+    verifier: &dyn SignatureVerifier,
+) -> Result<()> {
+    let envelope = SignatureField::parse(&receipt.kyber_signature)?.into_envelope()?;
+    verifier.verify(&receipt_signing_message(receipt)?, &envelope)
+}
+
+/// Looks up the verifying key behind a daemon-signed glyph.
+///
+/// The envelope names its key, so a daemon can hold several keys at once
+/// while rotating.
+pub trait EmitterKeyring {
+    fn emitter_key(&self, emitter: Daemon, key_id: &KeyId) -> Option<&dyn SignatureVerifier>;
+}
+
+impl EmitterKeyring for BTreeMap<(Daemon, KeyId), Box<dyn SignatureVerifier + Send + Sync>> {
+    fn emitter_key(&self, emitter: Daemon, key_id: &KeyId) -> Option<&dyn SignatureVerifier> {
+        self.get(&(emitter, *key_id))
+            .map(|k| k.as_ref() as &dyn SignatureVerifier)
+    }
+}
+
+/// Verifies a receipt's `kyber_signature` with its emitter's named key.
+pub fn verify_receipt_emitter(receipt: &ReceiptGlyph, keyring: &dyn EmitterKeyring) -> Result<()> {
+    let envelope = SignatureField::parse(&receipt.kyber_signature)?.into_envelope()?;
+    let key = keyring
+        .emitter_key(receipt.emitted_by, &envelope.key_id)
+        .ok_or(GlyphError::BadSignature {
+            reason: "no key for emitter key_id",
+        })?;
+    key.verify(&receipt_signing_message(receipt)?, &envelope)
+}
+
+/// Signs a DaemonStatusGlyph in place.
+pub fn sign_status(status: &mut DaemonStatusGlyph, signer: &dyn GlyphSigner) -> Result<()> {
+    status.kyber_signature = signer.sign(&status_signing_message(status)?).to_hex();
+    Ok(())
+}
+
+/// Verifies a DaemonStatusGlyph's `kyber_signature` against `verifier`.
+pub fn verify_status_signature(
+    status: &DaemonStatusGlyph,
+    verifier: &dyn SignatureVerifier,
+) -> Result<()> {
+    let envelope = SignatureField::parse(&status.kyber_signature)?.into_envelope()?;
+    verifier.verify(&status_signing_message(status)?, &envelope)
+}
+
+/// Signs an IntentGlyph in place.
+pub fn sign_intent(intent: &mut IntentGlyph, signer: &dyn GlyphSigner) -> Result<()> {
+    intent.signature = Some(signer.sign(&intent_signing_message(intent)?).to_hex());
+    Ok(())
+}
+
+/// Verifies an IntentGlyph's `signature` against `verifier`.
+pub fn verify_intent_signature(
+    intent: &IntentGlyph,
+    verifier: &dyn SignatureVerifier,
+) -> Result<()> {
+    let field = intent
+        .signature
+        .as_deref()
+        .ok_or(GlyphError::BadSignature {
+            reason: "intent is unsigned",
+        })?;
+    let envelope = SignatureField::parse(field)?.into_envelope()?;
+    verifier.verify(&intent_signing_message(intent)?, &envelope)
+}
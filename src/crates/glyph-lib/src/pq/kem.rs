@@ -0,0 +1,91 @@
+//! Kyber-1024 key encapsulation.
+//!
+//! Kyber is a KEM, not a signature scheme: it lets two daemons agree on a
+//! 32-byte shared secret for transport keys. Glyph signatures use
+//! [`dilithium`](super::dilithium) instead.
+
+use pqcrypto_kyber::kyber1024;
+use pqcrypto_traits::kem::{Ciphertext as _, PublicKey as _, SecretKey as _, SharedSecret as _};
+
+use crate::error::{GlyphError, Result};
+
+/// Length of the agreed shared secret.
+pub const SHARED_SECRET_LEN: usize = 32;
+
+/// A secret agreed through Kyber-1024.
+pub type SharedSecret = [u8; SHARED_SECRET_LEN];
+
+fn shared(ss: &kyber1024::SharedSecret) -> SharedSecret {
+    let mut out = [0u8; SHARED_SECRET_LEN];
+    out.copy_from_slice(ss.as_bytes());
+    out
+}
+
+/// A Kyber-1024 public (encapsulation) key.
+#[derive(Clone)]
+pub struct KemPublicKey(kyber1024::PublicKey);
+
+impl KemPublicKey {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        kyber1024::PublicKey::from_bytes(bytes)
+            .map(KemPublicKey)
+            .map_err(|_| GlyphError::InvalidKey {
+                reason: "not a kyber1024 public key",
+            })
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+
+    /// Generates a shared secret and the ciphertext that carries it.
+    pub fn encapsulate(&self) -> (SharedSecret, Vec<u8>) {
+        let (ss, ct) = kyber1024::encapsulate(&self.0);
+        (shared(&ss), ct.as_bytes().to_vec())
+    }
+}
+
+/// A Kyber-1024 key pair.
+pub struct KemKeypair {
+    public: KemPublicKey,
+    secret: kyber1024::SecretKey,
+}
+
+impl KemKeypair {
+    /// Generates a fresh key pair from the system RNG.
+    pub fn generate() -> Self {
+        let (pk, sk) = kyber1024::keypair();
+        KemKeypair {
+            public: KemPublicKey(pk),
+            secret: sk,
+        }
+    }
+
+    pub fn from_bytes(public_key: &[u8], secret_key: &[u8]) -> Result<Self> {
+        let secret =
+            kyber1024::SecretKey::from_bytes(secret_key).map_err(|_| GlyphError::InvalidKey {
+                reason: "not a kyber1024 secret key",
+            })?;
+        Ok(KemKeypair {
+            public: KemPublicKey::from_bytes(public_key)?,
+            secret,
+        })
+    }
+
+    pub fn public_key(&self) -> &KemPublicKey {
+        &self.public
+    }
+
+    pub fn secret_key_bytes(&self) -> &[u8] {
+        self.secret.as_bytes()
+    }
+
+    /// Recovers the shared secret from a ciphertext.
+    pub fn decapsulate(&self, ciphertext: &[u8]) -> Result<SharedSecret> {
+        let ct =
+            kyber1024::Ciphertext::from_bytes(ciphertext).map_err(|_| GlyphError::InvalidKey {
+                reason: "not a kyber1024 ciphertext",
+            })?;
+        Ok(shared(&kyber1024::decapsulate(&ct, &self.secret)))
+    }
+}
@@ -0,0 +1,575 @@
+//! Tenant-scoped registry of the keys allowed to sign glyphs.
+//!
+//! Glyphs name their signers: `authorized_by` and anchor bundle entries
+//! name Guardians, `emitted_by` and `daemon_name` name daemons. The
+//! registry maps those names to public keys using:
+//!
+//! - `config/agents/guardians_org.yaml`: every Guardian, the daemon it is
+//!   accountable for, and whether it signs intents;
+//! - `config/agents/swarm_roles.yaml`: the anchor [`QuorumPolicy`];
+//! - `config/tenants/*.yaml`: each tenant's `allowed_guardians` and
+//!   `allowed_daemons`;
+//! - a key file of public keys and their validity windows:
+//!
+//! ```yaml
+//! keys:
+//!   - owner: Star-Lord                   # Guardian or daemon name
+//!     key_id: guardian-star-lord-key-01  # anchor bundles' public_key_id
+//!     algorithm: dilithium5
+//!     public_key: "<hex>"
+//!     valid_from: 1764000000             # Unix seconds, inclusive
+//!     valid_until: 1795536000            # optional, exclusive
+//!     revoked_at: 1780000000             # optional
+//! ```
+//!
+//! A key verifies a glyph only if its owner is allowed for the glyph's
+//! `tenant_id` and the glyph's `timestamp` falls inside the key's window
+//! and before `revoked_at`. Overlapping windows let a signer rotate keys
+//! without a gap; revoking a compromised key at its `valid_from` rejects
+//! everything it ever signed. DaemonStatusGlyphs carry no tenant, so only
+//! the window and the Guardian-to-daemon mapping apply to them.
+//!
+//! A glyph's `timestamp` is chosen by its signer, so whoever holds a
+//! revoked key can backdate new glyphs into its window. Revocation is
+//! therefore also checked against `seen_at`, a time the verifier trusts
+//! the glyph to have existed by: its ingest time, or the timestamp of a
+//! quorum-signed anchor logged before it. A revoked key verifies nothing
+//! seen at or after `revoked_at`, whatever the glyph claims.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Deserialize;
+
+use crate::anchors::anchor_types::{
+    AnchorGlyph, Daemon, DaemonStatusGlyph, Guardian, IntentGlyph, ReceiptGlyph,
+};
+use crate::anchors::quorum::{verify_quorum, GuardianKeyring, QuorumOutcome, QuorumPolicy};
+use crate::error::{GlyphError, Result};
+use crate::pq::envelope::{
+    intent_signing_message, receipt_signing_message, status_signing_message, AlgorithmId,
+    EmitterKeyring, KeyId, SignatureEnvelope, SignatureField, SignatureVerifier,
+};
+
+/// A verifying key the registry can share across threads.
+pub type RegisteredKey = Box<dyn SignatureVerifier + Send + Sync>;
+
+/// Whoever a key belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Signer {
+    Guardian(Guardian),
+    Daemon(Daemon),
+}
+
+impl fmt::Display for Signer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Signer::Guardian(g) => g.fmt(f),
+            Signer::Daemon(d) => d.fmt(f),
+        }
+    }
+}
+
+impl FromStr for Signer {
+    type Err = GlyphError;
+
+    /// Guardian names (`Star-Lord`) and daemon names (`rocket-engine`)
+    /// never collide, so either spelling is accepted.
+    fn from_str(s: &str) -> Result<Self> {
+        s.parse()
+            .map(Signer::Guardian)
+            .or_else(|_| s.parse().map(Signer::Daemon))
+            .map_err(|_| GlyphError::UnknownVariant {
+                kind: "Signer",
+                value: s.to_string(),
+            })
+    }
+}
+
+/// One Guardian from `guardians_org.yaml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct GuardianProfile {
+    pub human: String,
+    pub daemon: Daemon,
+    pub scope: String,
+    #[serde(default)]
+    pub signs_intents: bool,
+}
+
+/// The signers one tenant YAML allows.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct TenantAccess {
+    pub tenant_id: String,
+    #[serde(default)]
+    pub allowed_guardians: BTreeSet<Guardian>,
+    #[serde(default)]
+    pub allowed_daemons: BTreeSet<Daemon>,
+}
+
+impl TenantAccess {
+    /// Reads the signer lists of a `config/tenants/*.yaml` file.
+    pub fn from_tenant_yaml(yaml: &str) -> Result<Self> {
+        let access: TenantAccess = serde_yaml::from_str(yaml).map_err(registry_error)?;
+        if access.tenant_id.is_empty() {
+            return Err(registry_error("tenant_id is empty"));
+        }
+        Ok(access)
+    }
+
+    pub fn allows(&self, signer: Signer) -> bool {
+        match signer {
+            Signer::Guardian(g) => self.allowed_guardians.contains(&g),
+            Signer::Daemon(d) => self.allowed_daemons.contains(&d),
+        }
+    }
+}
+
+/// When a key may sign: `[valid_from, valid_until)`, cut short by `revoked_at`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct KeyWindow {
+    pub valid_from: i64,
+    #[serde(default)]
+    pub valid_until: Option<i64>,
+    #[serde(default)]
+    pub revoked_at: Option<i64>,
+}
+
+impl KeyWindow {
+    /// A window open from `valid_from` onwards.
+    pub fn from(valid_from: i64) -> Self {
+        KeyWindow {
+            valid_from,
+            valid_until: None,
+            revoked_at: None,
+        }
+    }
+
+    pub fn until(self, valid_until: i64) -> Self {
+        KeyWindow {
+            valid_until: Some(valid_until),
+            ..self
+        }
+    }
+
+    pub fn revoked_at(self, revoked_at: i64) -> Self {
+        KeyWindow {
+            revoked_at: Some(revoked_at),
+            ..self
+        }
+    }
+
+    /// True if a glyph timestamped `at` may carry this key's signature.
+    pub fn active_at(&self, at: i64) -> bool {
+        at >= self.valid_from
+            && self.valid_until.is_none_or(|end| at < end)
+            && self.revoked_at.is_none_or(|revoked| at < revoked)
+    }
+
+    /// True if the key was not yet revoked at the trusted time `seen_at`.
+    pub fn unrevoked_at(&self, seen_at: i64) -> bool {
+        self.revoked_at.is_none_or(|revoked| seen_at < revoked)
+    }
+}
+
+struct KeyEntry {
+    signer: Signer,
+    label: String,
+    window: KeyWindow,
+    key: RegisteredKey,
+}
+
+/// How a glyph points at its key.
+#[derive(Clone, Copy)]
+enum KeyRef<'k> {
+    /// The envelope's fingerprint, for single-signer glyphs.
+    Id(&'k KeyId),
+    /// A bundle entry's `public_key_id`.
+    Label(&'k str),
+}
+
+#[derive(Deserialize)]
+struct KeyFile {
+    keys: Vec<KeyFileEntry>,
+}
+
+#[derive(Deserialize)]
+struct KeyFileEntry {
+    owner: String,
+    key_id: String,
+    algorithm: String,
+    public_key: String,
+    #[serde(flatten)]
+    window: KeyWindow,
+}
+
+/// Guardians, tenants, quorum policy and public keys in one place.
+pub struct KeyRegistry {
+    guardians: BTreeMap<Guardian, GuardianProfile>,
+    policy: QuorumPolicy,
+    tenants: BTreeMap<String, TenantAccess>,
+    keys: Vec<KeyEntry>,
+}
+
+impl KeyRegistry {
+    /// Loads `guardians_org.yaml` and `swarm_roles.yaml`; tenants and keys
+    /// are added afterwards.
+    pub fn from_yaml(guardians_org_yaml: &str, swarm_roles_yaml: &str) -> Result<Self> {
+        let raw: BTreeMap<String, GuardianProfile> =
+            serde_yaml::from_str(guardians_org_yaml).map_err(registry_error)?;
+        let guardians = raw
+            .into_iter()
+            .map(|(name, profile)| Ok((name.parse::<Guardian>()?, profile)))
+            .collect::<Result<BTreeMap<_, _>>>()?;
+        let policy = QuorumPolicy::from_swarm_roles_yaml(swarm_roles_yaml)?;
+        if let Some(g) = Guardian::ALL
+            .iter()
+            .find(|g| policy.role(**g).is_some() && !guardians.contains_key(g))
+        {
+            return Err(registry_error(format!(
+                "{g} has a swarm role but is not in guardians_org.yaml"
+            )));
+        }
+        Ok(KeyRegistry {
+            guardians,
+            policy,
+            tenants: BTreeMap::new(),
+            keys: Vec::new(),
+        })
+    }
+
+    /// Adds a tenant, replacing any earlier entry with the same `tenant_id`.
+    pub fn add_tenant(&mut self, access: TenantAccess) {
+        self.tenants.insert(access.tenant_id.clone(), access);
+    }
+
+    pub fn add_tenant_yaml(&mut self, yaml: &str) -> Result<()> {
+        self.add_tenant(TenantAccess::from_tenant_yaml(yaml)?);
+        Ok(())
+    }
+
+    /// Registers one key under `label`.
+    ///
+    /// Labels are unique per signer and fingerprints across the registry,
+    /// so every glyph resolves to at most one key.
+    pub fn register(
+        &mut self,
+        signer: Signer,
+        label: impl Into<String>,
+        window: KeyWindow,
+        key: RegisteredKey,
+    ) -> Result<()> {
+        let label = label.into();
+        if let Signer::Guardian(g) = signer {
+            if !self.guardians.contains_key(&g) {
+                return Err(registry_error(format!("{g} is not in guardians_org.yaml")));
+            }
+        }
+        if window
+            .valid_until
+            .is_some_and(|end| end <= window.valid_from)
+        {
+            return Err(registry_error(format!(
+                "key {label} of {signer} has an empty validity window"
+            )));
+        }
+        if self
+            .keys
+            .iter()
+            .any(|k| k.signer == signer && k.label == label)
+        {
+            return Err(registry_error(format!(
+                "{signer} already has a key labelled {label}"
+            )));
+        }
+        if let Some(other) = self.keys.iter().find(|k| k.key.key_id() == key.key_id()) {
+            return Err(registry_error(format!(
+                "key {label} of {signer} is already registered as {} of {}",
+                other.label, other.signer
+            )));
+        }
+        self.keys.push(KeyEntry {
+            signer,
+            label,
+            window,
+            key,
+        });
+        Ok(())
+    }
+
+    /// Registers every key in a key file; `decode` turns public key bytes
+    /// into a verifier for the named algorithm. Returns the number added.
+    pub fn load_keys_yaml(
+        &mut self,
+        yaml: &str,
+        decode: &dyn Fn(AlgorithmId, &[u8]) -> Result<RegisteredKey>,
+    ) -> Result<usize> {
+        let file: KeyFile = serde_yaml::from_str(yaml).map_err(registry_error)?;
+        let count = file.keys.len();
+        for entry in file.keys {
+            let signer = entry.owner.parse::<Signer>()?;
+            let algorithm = AlgorithmId::from_name(&entry.algorithm).ok_or_else(|| {
+                GlyphError::UnknownVariant {
+                    kind: "AlgorithmId",
+                    value: entry.algorithm.clone(),
+                }
+            })?;
+            let bytes = hex::decode(&entry.public_key).map_err(|_| GlyphError::InvalidKey {
+                reason: "public_key is not hex",
+            })?;
+            let key = decode(algorithm, &bytes)?;
+            if key.algorithm() != algorithm {
+                return Err(GlyphError::InvalidKey {
+                    reason: "decoded key has a different algorithm",
+                });
+            }
+            self.register(signer, entry.key_id, entry.window, key)?;
+        }
+        Ok(count)
+    }
+
+    pub fn policy(&self) -> &QuorumPolicy {
+        &self.policy
+    }
+
+    pub fn guardian(&self, guardian: Guardian) -> Option<&GuardianProfile> {
+        self.guardians.get(&guardian)
+    }
+
+    pub fn tenant(&self, tenant_id: &str) -> Option<&TenantAccess> {
+        self.tenants.get(tenant_id)
+    }
+
+    /// Fails unless `signer` may sign for `tenant_id`.
+    pub fn check_allowed(&self, tenant_id: &str, signer: Signer) -> Result<()> {
+        let access = self
+            .tenants
+            .get(tenant_id)
+            .ok_or_else(|| GlyphError::UnknownTenant {
+                tenant_id: tenant_id.to_string(),
+            })?;
+        if !access.allows(signer) {
+            return Err(not_allowed(signer, format!("tenant {tenant_id}")));
+        }
+        Ok(())
+    }
+
+    /// The key `signer` may use for a glyph of `tenant_id` timestamped `at`
+    /// and seen by `seen_at`.
+    fn resolve(
+        &self,
+        tenant_id: Option<&str>,
+        signer: Signer,
+        key: KeyRef<'_>,
+        at: i64,
+        seen_at: i64,
+    ) -> Result<&dyn SignatureVerifier> {
+        if let Some(tenant_id) = tenant_id {
+            self.check_allowed(tenant_id, signer)?;
+        }
+        let entry = self
+            .keys
+            .iter()
+            .filter(|k| k.signer == signer)
+            .find(|k| match key {
+                KeyRef::Id(id) => k.key.key_id() == *id,
+                KeyRef::Label(label) => k.label == label,
+            })
+            .ok_or(GlyphError::BadSignature {
+                reason: "no key registered for the signer",
+            })?;
+        let not_active = |at| GlyphError::KeyNotActive {
+            signer: signer.to_string(),
+            key: entry.label.clone(),
+            at,
+        };
+        if !entry.window.active_at(at) {
+            return Err(not_active(at));
+        }
+        if !entry.window.unrevoked_at(seen_at) {
+            return Err(not_active(seen_at));
+        }
+        Ok(entry.key.as_ref())
+    }
+
+    fn verify_single(
+        &self,
+        tenant_id: Option<&str>,
+        signer: Signer,
+        at: i64,
+        seen_at: i64,
+        field: &str,
+        message: &[u8],
+    ) -> Result<()> {
+        let envelope: SignatureEnvelope = SignatureField::parse(field)?.into_envelope()?;
+        self.resolve(tenant_id, signer, KeyRef::Id(&envelope.key_id), at, seen_at)?
+            .verify(message, &envelope)
+    }
+
+    /// Verifies a receipt under an `emitted_by` key allowed for its tenant
+    /// and not revoked by `seen_at`.
+    pub fn verify_receipt(&self, receipt: &ReceiptGlyph, seen_at: i64) -> Result<()> {
+        self.verify_single(
+            Some(&receipt.tenant_id),
+            Signer::Daemon(receipt.emitted_by),
+            receipt.timestamp,
+            seen_at,
+            &receipt.kyber_signature,
+            &receipt_signing_message(receipt)?,
+        )
+    }
+
+    /// Verifies an intent under an `authorized_by` key allowed for its
+    /// tenant and not revoked by `seen_at`; the Guardian must have
+    /// `signs_intents`.
+    pub fn verify_intent(&self, intent: &IntentGlyph, seen_at: i64) -> Result<()> {
+        let guardian = intent.authorized_by;
+        if !self
+            .guardians
+            .get(&guardian)
+            .is_some_and(|p| p.signs_intents)
+        {
+            return Err(not_allowed(Signer::Guardian(guardian), "intents".into()));
+        }
+        let field = intent
+            .signature
+            .as_deref()
+            .ok_or(GlyphError::BadSignature {
+                reason: "intent is unsigned",
+            })?;
+        self.verify_single(
+            Some(&intent.tenant_id),
+            Signer::Guardian(guardian),
+            intent.timestamp,
+            seen_at,
+            field,
+            &intent_signing_message(intent)?,
+        )
+    }
+
+    /// Verifies a status under a `daemon_name` key not revoked by `seen_at`;
+    /// `guardian` must be the Guardian accountable for that daemon.
+    pub fn verify_status(&self, status: &DaemonStatusGlyph, seen_at: i64) -> Result<()> {
+        let daemon = Signer::Daemon(status.daemon_name);
+        let accountable = self.guardians.get(&status.guardian).map(|p| p.daemon);
+        if accountable != Some(status.daemon_name) {
+            return Err(not_allowed(daemon, format!("guardian {}", status.guardian)));
+        }
+        self.verify_single(
+            None,
+            daemon,
+            status.timestamp,
+            seen_at,
+            &status.kyber_signature,
+            &status_signing_message(status)?,
+        )
+    }
+
+    /// Checks an anchor's Guardian bundle with keys scoped to its tenant and
+    /// not revoked by `seen_at`.
+    pub fn verify_anchor(&self, anchor: &AnchorGlyph, seen_at: i64) -> Result<QuorumOutcome> {
+        for entry in &anchor.kyber_signature.signatures {
+            if let Some(g) = Guardian::from_handle(&entry.guardian_id) {
+                self.check_allowed(&anchor.tenant_id, Signer::Guardian(g))?;
+                self.resolve(
+                    None,
+                    Signer::Guardian(g),
+                    KeyRef::Label(&entry.public_key_id),
+                    anchor.timestamp,
+                    seen_at,
+                )?;
+            }
+        }
+        verify_quorum(
+            anchor,
+            &self.policy,
+            &self
+                .scoped(&anchor.tenant_id, anchor.timestamp)?
+                .seen_at(seen_at),
+        )
+    }
+
+    /// Keys usable for `tenant_id` at `at`, as keyrings for
+    /// [`verify_quorum`] and the batch verification engine.
+    ///
+    /// `at` is also the trusted time revocation is checked against, so
+    /// pass one the caller trusts, such as the covering anchor's
+    /// timestamp, or narrow it with [`ScopedKeys::seen_at`].
+    pub fn scoped<'r>(&'r self, tenant_id: &'r str, at: i64) -> Result<ScopedKeys<'r>> {
+        if !self.tenants.contains_key(tenant_id) {
+            return Err(GlyphError::UnknownTenant {
+                tenant_id: tenant_id.to_string(),
+            });
+        }
+        Ok(ScopedKeys {
+            registry: self,
+            tenant_id,
+            at,
+            seen_at: at,
+        })
+    }
+}
+
+/// A [`KeyRegistry`] narrowed to one tenant and one point in time.
+///
+/// Keys of signers the tenant does not allow, or outside their window, are
+/// simply not found.
+#[derive(Clone, Copy)]
+pub struct ScopedKeys<'r> {
+    registry: &'r KeyRegistry,
+    tenant_id: &'r str,
+    at: i64,
+    seen_at: i64,
+}
+
+impl ScopedKeys<'_> {
+    /// Checks revocation at the trusted time `seen_at` instead of `at`.
+    pub fn seen_at(self, seen_at: i64) -> Self {
+        ScopedKeys { seen_at, ..self }
+    }
+}
+
+impl GuardianKeyring for ScopedKeys<'_> {
+    fn guardian_key(
+        &self,
+        guardian: Guardian,
+        public_key_id: &str,
+    ) -> Option<&dyn SignatureVerifier> {
+        self.registry
+            .resolve(
+                Some(self.tenant_id),
+                Signer::Guardian(guardian),
+                KeyRef::Label(public_key_id),
+                self.at,
+                self.seen_at,
+            )
+            .ok()
+    }
+}
+
+impl EmitterKeyring for ScopedKeys<'_> {
+    fn emitter_key(&self, emitter: Daemon, key_id: &KeyId) -> Option<&dyn SignatureVerifier> {
+        self.registry
+            .resolve(
+                Some(self.tenant_id),
+                Signer::Daemon(emitter),
+                KeyRef::Id(key_id),
+                self.at,
+                self.seen_at,
+            )
+            .ok()
+    }
+}
+
+fn registry_error(reason: impl ToString) -> GlyphError {
+    GlyphError::InvalidRegistry {
+        reason: reason.to_string(),
+    }
+}
+
+fn not_allowed(signer: Signer, scope: String) -> GlyphError {
+    GlyphError::SignerNotAllowed {
+        signer: signer.to_string(),
+        scope,
+    }
+}
@@ -0,0 +1,433 @@
+//! JSON Schema validation against the embedded `glyphs/schemas/`.
+//!
+//! The four schema files are compiled into the crate, so every daemon
+//! validates against exactly the schemas it was built with. The validator
+//! implements the draft 2020-12 keywords those schemas use:
+//!
+//! `type`, `const`, `enum`, `pattern`, `minLength`, `maxLength`, `minimum`,
+//! `maximum`, `exclusiveMinimum`, `exclusiveMaximum`, `items`, `minItems`,
+//! `maxItems`, `required`, `properties`, `additionalProperties`, `allOf`,
+//! `if`/`then`/`else` and `unevaluatedProperties`.
+//!
+//! Annotation-only keywords (`$id`, `title`, `description`, ...) are
+//! ignored. Validation never stops at the first problem: every violation is
+//! reported with the JSON pointer of the offending value, the keyword that
+//! failed and the value that keyword expected.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde_json::{Map, Value};
+
+use crate::anchors::anchor_types::{Glyph, GlyphKind};
+use crate::error::{GlyphError, Result};
+
+const INTENT_SCHEMA: &str = include_str!("../../../../../glyphs/schemas/intent_glyph.schema.json");
+const RECEIPT_SCHEMA: &str =
+    include_str!("../../../../../glyphs/schemas/receipt_glyph.schema.json");
+const ANCHOR_SCHEMA: &str = include_str!("../../../../../glyphs/schemas/anchor_glyph.schema.json");
+const DAEMON_STATUS_SCHEMA: &str =
+    include_str!("../../../../../glyphs/schemas/daemon_status_glyph.schema.json");
+
+/// Schema file stem for `kind`, as accepted by `glyph-lib validate --type=`.
+pub fn schema_name(kind: GlyphKind) -> &'static str {
+    match kind {
+        GlyphKind::Intent => "intent_glyph",
+        GlyphKind::Receipt => "receipt_glyph",
+        GlyphKind::Anchor => "anchor_glyph",
+        GlyphKind::DaemonStatus => "daemon_status_glyph",
+    }
+}
+
+/// Inverse of [`schema_name`].
+pub fn kind_from_schema_name(name: &str) -> Option<GlyphKind> {
+    [
+        GlyphKind::Intent,
+        GlyphKind::Receipt,
+        GlyphKind::Anchor,
+        GlyphKind::DaemonStatus,
+    ]
+    .into_iter()
+    .find(|k| schema_name(*k) == name)
+}
+
+/// Embedded schema text for `kind`.
+pub fn schema_source(kind: GlyphKind) -> &'static str {
+    match kind {
+        GlyphKind::Intent => INTENT_SCHEMA,
+        GlyphKind::Receipt => RECEIPT_SCHEMA,
+        GlyphKind::Anchor => ANCHOR_SCHEMA,
+        GlyphKind::DaemonStatus => DAEMON_STATUS_SCHEMA,
+    }
+}
+
+/// One failed keyword.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaViolation {
+    /// JSON pointer to the offending value; empty for the document root.
+    pub instance_path: String,
+    /// JSON pointer to the failing keyword inside the schema.
+    pub schema_path: String,
+    pub keyword: &'static str,
+    /// The keyword's value in the schema, e.g. the required property name.
+    pub expected: Value,
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let at = if self.instance_path.is_empty() {
+            "/"
+        } else {
+            &self.instance_path
+        };
+        write!(f, "{at}: {} expected {}", self.keyword, self.expected)
+    }
+}
+
+struct Compiled {
+    root: Value,
+    patterns: HashMap<String, Regex>,
+}
+
+fn collect_patterns(schema: &Value, out: &mut HashMap<String, Regex>) {
+    match schema {
+        Value::Object(map) => {
+            for (key, value) in map {
+                if let ("pattern", Value::String(p)) = (key.as_str(), value) {
+                    let re = Regex::new(p).unwrap_or_else(|e| {
+                        panic!("embedded schema has an invalid pattern {p:?}: {e}")
+                    });
+                    out.insert(p.clone(), re);
+                } else {
+                    collect_patterns(value, out);
+                }
+            }
+        }
+        Value::Array(items) => items.iter().for_each(|v| collect_patterns(v, out)),
+        _ => {}
+    }
+}
+
+fn compiled(kind: GlyphKind) -> &'static Compiled {
+    static CACHE: OnceLock<HashMap<GlyphKind, Compiled>> = OnceLock::new();
+    let cache = CACHE.get_or_init(|| {
+        [
+            GlyphKind::Intent,
+            GlyphKind::Receipt,
+            GlyphKind::Anchor,
+            GlyphKind::DaemonStatus,
+        ]
+        .into_iter()
+        .map(|k| {
+            let root: Value = serde_json::from_str(schema_source(k))
+                .unwrap_or_else(|e| panic!("embedded {} schema is not JSON: {e}", schema_name(k)));
+            let mut patterns = HashMap::new();
+            collect_patterns(&root, &mut patterns);
+            (k, Compiled { root, patterns })
+        })
+        .collect()
+    });
+    &cache[&kind]
+}
+
+/// Parsed embedded schema for `kind`.
+pub fn schema(kind: GlyphKind) -> &'static Value {
+    &compiled(kind).root
+}
+
+/// Every violation of `kind`'s schema in `instance`; empty when valid.
+pub fn validate(kind: GlyphKind, instance: &Value) -> Vec<SchemaViolation> {
+    let compiled = compiled(kind);
+    let mut walker = Walker {
+        patterns: &compiled.patterns,
+        out: Vec::new(),
+    };
+    walker.check(&compiled.root, "", instance, "");
+    walker.out
+}
+
+/// Validates raw JSON, failing with every violation found.
+pub fn check_value(kind: GlyphKind, instance: &Value) -> Result<()> {
+    let violations = validate(kind, instance);
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(GlyphError::SchemaInvalid { kind, violations })
+    }
+}
+
+/// Validates a typed glyph as it would be serialized.
+pub fn check_glyph<G: Glyph>(glyph: &G) -> Result<()> {
+    check_value(G::KIND, &serde_json::to_value(glyph)?)
+}
+
+fn pointer_push(base: &str, token: &str) -> String {
+    format!("{base}/{}", token.replace('~', "~0").replace('/', "~1"))
+}
+
+/// JSON Schema equality: numbers compare by value, so `1` equals `1.0`.
+fn json_eq(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => match (x.as_i64(), y.as_i64()) {
+            (Some(x), Some(y)) => x == y,
+            _ => x.as_f64() == y.as_f64(),
+        },
+        (Value::Array(x), Value::Array(y)) => {
+            x.len() == y.len() && x.iter().zip(y).all(|(a, b)| json_eq(a, b))
+        }
+        (Value::Object(x), Value::Object(y)) => {
+            x.len() == y.len()
+                && x.iter()
+                    .all(|(k, v)| y.get(k).is_some_and(|w| json_eq(v, w)))
+        }
+        _ => a == b,
+    }
+}
+
+fn has_type(instance: &Value, ty: &str) -> bool {
+    match ty {
+        "null" => instance.is_null(),
+        "boolean" => instance.is_boolean(),
+        "string" => instance.is_string(),
+        "array" => instance.is_array(),
+        "object" => instance.is_object(),
+        "number" => instance.is_number(),
+        "integer" => match instance {
+            Value::Number(n) => {
+                n.is_i64() || n.is_u64() || n.as_f64().is_some_and(|f| f.fract() == 0.0)
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+type NumericBound = fn(f64, f64) -> bool;
+
+struct Walker<'a> {
+    patterns: &'a HashMap<String, Regex>,
+    out: Vec<SchemaViolation>,
+}
+
+impl Walker<'_> {
+    fn fail(
+        &mut self,
+        instance_path: &str,
+        schema_path: &str,
+        keyword: &'static str,
+        expected: Value,
+    ) {
+        self.out.push(SchemaViolation {
+            instance_path: instance_path.to_string(),
+            schema_path: pointer_push(schema_path, keyword),
+            keyword,
+            expected,
+        });
+    }
+
+    /// Runs `schema` against `instance` in a scratch walker.
+    fn passes(
+        &self,
+        schema: &Value,
+        schema_path: &str,
+        instance: &Value,
+        path: &str,
+    ) -> (bool, BTreeSet<String>) {
+        let mut scratch = Walker {
+            patterns: self.patterns,
+            out: Vec::new(),
+        };
+        let evaluated = scratch.check(schema, schema_path, instance, path);
+        (scratch.out.is_empty(), evaluated)
+    }
+
+    /// Validates and returns the object members this schema evaluated.
+    fn check(&mut self, schema: &Value, sp: &str, instance: &Value, ip: &str) -> BTreeSet<String> {
+        let mut evaluated = BTreeSet::new();
+        let s = match schema {
+            Value::Bool(true) => return evaluated,
+            Value::Bool(false) => {
+                self.out.push(SchemaViolation {
+                    instance_path: ip.to_string(),
+                    schema_path: sp.to_string(),
+                    keyword: "false",
+                    expected: Value::Bool(false),
+                });
+                return evaluated;
+            }
+            Value::Object(s) => s,
+            _ => return evaluated,
+        };
+
+        if let Some(ty) = s.get("type") {
+            let ok = match ty {
+                Value::String(t) => has_type(instance, t),
+                Value::Array(ts) => ts
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .any(|t| has_type(instance, t)),
+                _ => true,
+            };
+            if !ok {
+                self.fail(ip, sp, "type", ty.clone());
+            }
+        }
+        if let Some(c) = s.get("const") {
+            if !json_eq(c, instance) {
+                self.fail(ip, sp, "const", c.clone());
+            }
+        }
+        if let Some(Value::Array(options)) = s.get("enum") {
+            if !options.iter().any(|o| json_eq(o, instance)) {
+                self.fail(ip, sp, "enum", Value::Array(options.clone()));
+            }
+        }
+
+        match instance {
+            Value::String(text) => self.check_string(s, sp, text, ip),
+            Value::Number(_) => self.check_number(s, sp, instance, ip),
+            Value::Array(items) => self.check_array(s, sp, items, ip),
+            Value::Object(members) => self.check_object(s, sp, members, ip, &mut evaluated),
+            _ => {}
+        }
+
+        if let Some(Value::Array(subs)) = s.get("allOf") {
+            for (i, sub) in subs.iter().enumerate() {
+                let sub_sp = pointer_push(&pointer_push(sp, "allOf"), &i.to_string());
+                evaluated.extend(self.check(sub, &sub_sp, instance, ip));
+            }
+        }
+        if let Some(cond) = s.get("if") {
+            let (matched, cond_eval) = self.passes(cond, &pointer_push(sp, "if"), instance, ip);
+            let branch = if matched { "then" } else { "else" };
+            if matched {
+                evaluated.extend(cond_eval);
+            }
+            if let Some(sub) = s.get(branch) {
+                evaluated.extend(self.check(sub, &pointer_push(sp, branch), instance, ip));
+            }
+        }
+
+        if let (Some(sub), Value::Object(members)) = (s.get("unevaluatedProperties"), instance) {
+            let sub_sp = pointer_push(sp, "unevaluatedProperties");
+            for (key, value) in members {
+                if evaluated.contains(key) {
+                    continue;
+                }
+                let path = pointer_push(ip, key);
+                if sub == &Value::Bool(false) {
+                    self.fail(&path, sp, "unevaluatedProperties", Value::Bool(false));
+                } else {
+                    self.check(sub, &sub_sp, value, &path);
+                }
+            }
+            evaluated.extend(members.keys().cloned());
+        }
+        evaluated
+    }
+
+    fn check_string(&mut self, s: &Map<String, Value>, sp: &str, text: &str, ip: &str) {
+        if let Some(Value::String(p)) = s.get("pattern") {
+            if !self.patterns[p].is_match(text) {
+                self.fail(ip, sp, "pattern", Value::from(p.as_str()));
+            }
+        }
+        let len = text.chars().count() as u64;
+        if let Some(min) = s.get("minLength").and_then(Value::as_u64) {
+            if len < min {
+                self.fail(ip, sp, "minLength", Value::from(min));
+            }
+        }
+        if let Some(max) = s.get("maxLength").and_then(Value::as_u64) {
+            if len > max {
+                self.fail(ip, sp, "maxLength", Value::from(max));
+            }
+        }
+    }
+
+    fn check_number(&mut self, s: &Map<String, Value>, sp: &str, instance: &Value, ip: &str) {
+        let Some(x) = instance.as_f64() else { return };
+        let bounds: [(&'static str, NumericBound); 4] = [
+            ("minimum", |x, b| x >= b),
+            ("maximum", |x, b| x <= b),
+            ("exclusiveMinimum", |x, b| x > b),
+            ("exclusiveMaximum", |x, b| x < b),
+        ];
+        for (keyword, holds) in bounds {
+            if let Some(bound) = s.get(keyword) {
+                if bound.as_f64().is_some_and(|b| !holds(x, b)) {
+                    self.fail(ip, sp, keyword, bound.clone());
+                }
+            }
+        }
+    }
+
+    fn check_array(&mut self, s: &Map<String, Value>, sp: &str, items: &[Value], ip: &str) {
+        let len = items.len() as u64;
+        if let Some(min) = s.get("minItems").and_then(Value::as_u64) {
+            if len < min {
+                self.fail(ip, sp, "minItems", Value::from(min));
+            }
+        }
+        if let Some(max) = s.get("maxItems").and_then(Value::as_u64) {
+            if len > max {
+                self.fail(ip, sp, "maxItems", Value::from(max));
+            }
+        }
+        if let Some(sub) = s.get("items") {
+            let sub_sp = pointer_push(sp, "items");
+            for (i, item) in items.iter().enumerate() {
+                self.check(sub, &sub_sp, item, &pointer_push(ip, &i.to_string()));
+            }
+        }
+    }
+
+    fn check_object(
+        &mut self,
+        s: &Map<String, Value>,
+        sp: &str,
+        members: &Map<String, Value>,
+        ip: &str,
+        evaluated: &mut BTreeSet<String>,
+    ) {
+        if let Some(Value::Array(required)) = s.get("required") {
+            for name in required.iter().filter_map(Value::as_str) {
+                if !members.contains_key(name) {
+                    self.fail(ip, sp, "required", Value::from(name));
+                }
+            }
+        }
+        let properties = s.get("properties").and_then(Value::as_object);
+        if let Some(properties) = properties {
+            let props_sp = pointer_push(sp, "properties");
+            for (name, sub) in properties {
+                if let Some(value) = members.get(name) {
+                    self.check(
+                        sub,
+                        &pointer_push(&props_sp, name),
+                        value,
+                        &pointer_push(ip, name),
+                    );
+                    evaluated.insert(name.clone());
+                }
+            }
+        }
+        if let Some(sub) = s.get("additionalProperties") {
+            let sub_sp = pointer_push(sp, "additionalProperties");
+            for (name, value) in members {
+                if properties.is_some_and(|p| p.contains_key(name)) {
+                    continue;
+                }
+                let path = pointer_push(ip, name);
+                if sub == &Value::Bool(false) {
+                    self.fail(&path, sp, "additionalProperties", Value::Bool(false));
+                } else {
+                    self.check(sub, &sub_sp, value, &path);
+                }
+                evaluated.insert(name.clone());
+            }
+        }
+    }
+}
@@ -0,0 +1,220 @@
+//! Keys, config and glyph factories shared by the glyph-lib and
+//! ledger-explorer test suites.
+//!
+//! Only built with the `test-support` feature. [`MacKey`] signs fast and
+//! deterministically, but its "public key" is the MAC key itself, so anyone
+//! who can verify with it can also forge with it. It never belongs in a
+//! registry outside a test.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+
+use crate::anchors::anchor_types::{AnchorGlyph, Guardian, JsonMap, Verdict};
+use crate::anchors::quorum::{assemble_bundle, sign_vote, QuorumPolicy};
+use crate::config::loader::ConfigSources;
+use crate::pq::envelope::{AlgorithmId, GlyphSigner, KeyId, SignatureVerifier};
+
+#[cfg(feature = "schemas")]
+use crate::anchors::anchor_types::{
+    Daemon, ReceiptGlyph, ReceiptResult, ReceiptType, TargetContext,
+};
+#[cfg(feature = "schemas")]
+use crate::builders::anchor::AnchorBuilder;
+#[cfg(feature = "schemas")]
+use crate::builders::receipt::{ReceiptBatch, ReceiptBuilder};
+#[cfg(feature = "schemas")]
+use crate::hashing::ids::{ChainLink, GlyphId};
+
+/// Keyed-BLAKE3 stand-in for a Dilithium5 key, with Dilithium5-sized
+/// signatures.
+pub struct MacKey(pub [u8; 32]);
+
+impl MacKey {
+    /// Key derived from a label: a Guardian, a daemon or a key ID.
+    pub fn of(label: &str) -> Self {
+        MacKey(*blake3::hash(label.as_bytes()).as_bytes())
+    }
+
+    fn tag(&self, message: &[u8]) -> Vec<u8> {
+        let mut out = vec![0u8; AlgorithmId::Dilithium5.signature_len()];
+        blake3::Hasher::new_keyed(&self.0)
+            .update(message)
+            .finalize_xof()
+            .fill(&mut out);
+        out
+    }
+}
+
+impl GlyphSigner for MacKey {
+    fn algorithm(&self) -> AlgorithmId {
+        AlgorithmId::Dilithium5
+    }
+
+    fn key_id(&self) -> KeyId {
+        KeyId::of_public_key(AlgorithmId::Dilithium5, &self.0)
+    }
+
+    fn sign_raw(&self, message: &[u8]) -> Vec<u8> {
+        self.tag(message)
+    }
+}
+
+impl SignatureVerifier for MacKey {
+    fn algorithm(&self) -> AlgorithmId {
+        AlgorithmId::Dilithium5
+    }
+
+    fn key_id(&self) -> KeyId {
+        KeyId::of_public_key(AlgorithmId::Dilithium5, &self.0)
+    }
+
+    fn verify_raw(&self, message: &[u8], signature: &[u8]) -> bool {
+        self.tag(message) == signature
+    }
+}
+
+/// Enough weight to pass the `swarm_roles.yaml` quorum on their own.
+pub const WEIGHTED: [Guardian; 7] = [
+    Guardian::StarLord,
+    Guardian::Gamora,
+    Guardian::Rocket,
+    Guardian::Groot,
+    Guardian::Drax,
+    Guardian::Kraglin,
+    Guardian::Yondu,
+];
+
+/// `public_key_id` a Guardian's test key is registered under.
+pub fn guardian_key_id(guardian: Guardian) -> String {
+    format!("guardian-{}-key-01", guardian.handle())
+}
+
+/// Every Guardian's [`MacKey`], keyed for [`verify_quorum`].
+///
+/// [`verify_quorum`]: crate::anchors::quorum::verify_quorum
+pub fn guardian_keyring() -> BTreeMap<(Guardian, String), Box<dyn SignatureVerifier>> {
+    Guardian::ALL
+        .iter()
+        .map(|&g| {
+            (
+                (g, guardian_key_id(g)),
+                Box::new(MacKey::of(g.as_str())) as Box<dyn SignatureVerifier>,
+            )
+        })
+        .collect()
+}
+
+/// A path relative to the repository root.
+pub fn repo_path(relative: impl AsRef<Path>) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../..")
+        .join(relative)
+}
+
+/// Reads a file relative to the repository root.
+pub fn read_repo_file(relative: impl AsRef<Path>) -> String {
+    let path = repo_path(relative);
+    fs::read_to_string(&path).unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()))
+}
+
+/// `config/` with `tests/fixtures/config` laid over it: the tenant files and
+/// full phase map the shipped directory still lacks.
+pub fn config_sources() -> ConfigSources {
+    let mut sources =
+        ConfigSources::read_dir(&repo_path("config")).expect("config/ must be readable");
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/config");
+    let fixtures = ConfigSources::read_dir(&fixtures).expect("fixtures must be readable");
+    for (file, text) in fixtures.iter() {
+        sources.insert(file, text);
+    }
+    sources
+}
+
+/// The quorum policy in `config/agents/swarm_roles.yaml`.
+pub fn swarm_policy() -> QuorumPolicy {
+    QuorumPolicy::from_swarm_roles_yaml(&read_repo_file("config/agents/swarm_roles.yaml"))
+        .expect("swarm_roles.yaml must parse")
+}
+
+/// A `bore_progress` anchor payload.
+pub fn bore_payload() -> JsonMap {
+    match json!({
+        "segment_id": "memphis-segment-001",
+        "tbm_id": "prufrock-01",
+        "position_m": 125.4,
+        "depth_m": 18.3,
+        "status": "advancing",
+    }) {
+        Value::Object(map) => map,
+        _ => unreachable!(),
+    }
+}
+
+/// `anchor` with a bundle of `voters`' approvals, each signed with their
+/// [`MacKey`] under [`guardian_key_id`].
+pub fn approved(mut anchor: AnchorGlyph, voters: &[Guardian]) -> AnchorGlyph {
+    let votes = voters
+        .iter()
+        .map(|&g| {
+            sign_vote(
+                &anchor,
+                g,
+                &guardian_key_id(g),
+                Verdict::Approve,
+                &MacKey::of(g.as_str()),
+            )
+            .expect("vote must sign")
+        })
+        .collect();
+    anchor.kyber_signature = assemble_bundle(&swarm_policy(), votes);
+    anchor
+}
+
+/// `count` rocket-engine `bore_progress` receipts under `ref_glyph_id`, one
+/// second apart from `first`, sealed as one batch by `signer`.
+#[cfg(feature = "schemas")]
+pub fn sealed(
+    tenant_id: &str,
+    ref_glyph_id: GlyphId,
+    first: i64,
+    count: usize,
+    signer: &dyn GlyphSigner,
+) -> Vec<ReceiptGlyph> {
+    (0..count)
+        .map(|m| {
+            ReceiptBuilder::new(
+                tenant_id,
+                ReceiptType::BoreProgress,
+                ref_glyph_id,
+                ReceiptResult::Ok,
+                Daemon::RocketEngine,
+            )
+            .timestamp(first + m as i64)
+            .detail("segment_id", "memphis-segment-001")
+            .detail("meters_advanced", m as f64 + 0.5)
+            .detail("cutter_head_rpm", 6)
+        })
+        .collect::<ReceiptBatch>()
+        .seal(signer)
+        .expect("receipts must seal")
+}
+
+/// An unsigned tunnel-bore anchor over `receipts`, stamped at `at`.
+#[cfg(feature = "schemas")]
+pub fn anchored(receipts: &[ReceiptGlyph], at: i64, previous: ChainLink) -> AnchorGlyph {
+    AnchorBuilder::new(
+        &receipts[0].tenant_id,
+        TargetContext::TunnelBore,
+        previous,
+        Daemon::LedgerExplorer,
+    )
+    .timestamp(at)
+    .receipts(receipts)
+    .prepare()
+    .expect("anchor must prepare")
+    .anchor()
+    .clone()
+}
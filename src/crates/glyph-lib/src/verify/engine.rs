@@ -0,0 +1,340 @@
+//! Batched, parallel verification of anchored receipts.
+//!
+//! [`VerificationEngine::verify_batch`] takes an AnchorGlyph and the
+//! receipts it commits to and runs every check on every glyph:
+//!
+//! | check        | receipt                                   | anchor                          |
+//! |--------------|-------------------------------------------|---------------------------------|
+//! | `id`         | `receipt_id` derives from the payload     | `glyph_id` derives from it      |
+//! | `hash`       | `blake3_hash` is the content hash         | same                            |
+//! | `membership` | listed by the anchor, same tenant, result and root | —                      |
+//! | `merkle`     | embedded proof leads to the anchor root   | root over the batch, if complete |
+//! | `signature`  | `kyber_signature` under the emitter's key | —                               |
+//! | `payload`    | typed [`ReceiptPayload`] rules            | —                               |
+//! | `schema`     | embedded JSON Schema                      | same                            |
+//! | `quorum`     | —                                         | Guardian bundle meets the policy |
+//!
+//! Receipts are independent of each other, so they are checked on rayon's
+//! global pool, one task per receipt; run the call inside
+//! [`rayon::ThreadPool::install`] to use a dedicated pool. The anchor is
+//! checked once on the calling thread. A failed check never stops the
+//! others, so one report lists everything wrong with a batch.
+//!
+//! [`ReceiptPayload`]: crate::anchors::receipt_payload::ReceiptPayload
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use rayon::prelude::*;
+
+use crate::anchors::anchor_types::{AnchorGlyph, Glyph, ReceiptGlyph};
+use crate::anchors::merkle::{hash_from_hex, receipt_leaf, MerkleHash, MerkleProof, MerkleTree};
+use crate::anchors::quorum::{verify_quorum, GuardianKeyring, QuorumOutcome, QuorumPolicy};
+use crate::anchors::receipt_payload::check_receipt;
+use crate::error::{GlyphError, Result};
+use crate::hashing::canonical::content_hash;
+use crate::hashing::ids::{verify_id, GlyphId};
+use crate::pq::envelope::{verify_receipt_emitter, EmitterKeyring};
+use crate::schemas::validator::check_glyph;
+
+/// One verification step, as listed in the module table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Check {
+    Id,
+    Hash,
+    Membership,
+    Merkle,
+    Signature,
+    Payload,
+    Schema,
+    Quorum,
+}
+
+impl Check {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Check::Id => "id",
+            Check::Hash => "hash",
+            Check::Membership => "membership",
+            Check::Merkle => "merkle",
+            Check::Signature => "signature",
+            Check::Payload => "payload",
+            Check::Schema => "schema",
+            Check::Quorum => "quorum",
+        }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A check that failed, with the error it failed on.
+#[derive(Debug)]
+pub struct CheckFailure {
+    pub check: Check,
+    pub error: GlyphError,
+}
+
+/// Outcome of every check on one glyph.
+#[derive(Debug)]
+pub struct GlyphVerdict {
+    pub glyph_id: GlyphId,
+    pub failures: Vec<CheckFailure>,
+}
+
+impl GlyphVerdict {
+    fn new(glyph_id: GlyphId) -> Self {
+        GlyphVerdict {
+            glyph_id,
+            failures: Vec::new(),
+        }
+    }
+
+    fn record(&mut self, check: Check, outcome: Result<()>) {
+        if let Err(error) = outcome {
+            self.failures.push(CheckFailure { check, error });
+        }
+    }
+
+    /// True when every check passed.
+    pub fn is_valid(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// True when `check` failed.
+    pub fn failed(&self, check: Check) -> bool {
+        self.failures.iter().any(|f| f.check == check)
+    }
+}
+
+/// Verdicts for one anchor and its receipts.
+#[derive(Debug)]
+pub struct BatchReport {
+    pub anchor: GlyphVerdict,
+    /// Set when the quorum check passed.
+    pub quorum: Option<QuorumOutcome>,
+    /// One verdict per input receipt, in input order.
+    pub receipts: Vec<GlyphVerdict>,
+    /// Receipts the anchor lists that were not in the batch.
+    pub missing: Vec<GlyphId>,
+    /// Wall-clock time of the whole batch.
+    pub elapsed: Duration,
+}
+
+impl BatchReport {
+    /// True when the anchor and every receipt passed and none is missing.
+    pub fn is_valid(&self) -> bool {
+        self.anchor.is_valid()
+            && self.missing.is_empty()
+            && self.receipts.iter().all(GlyphVerdict::is_valid)
+    }
+
+    /// Verdicts of receipts that failed at least one check.
+    pub fn rejected(&self) -> impl Iterator<Item = &GlyphVerdict> {
+        self.receipts.iter().filter(|v| !v.is_valid())
+    }
+
+    /// Receipts verified per second of wall-clock time.
+    pub fn receipts_per_second(&self) -> f64 {
+        self.receipts.len() as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+/// Verifies anchored receipt batches against one set of keys and one policy.
+#[derive(Clone, Copy)]
+pub struct VerificationEngine<'a> {
+    policy: &'a QuorumPolicy,
+    guardians: &'a dyn GuardianKeyring,
+    emitters: &'a (dyn EmitterKeyring + Sync),
+}
+
+impl<'a> VerificationEngine<'a> {
+    /// `guardians` check the anchor's quorum bundle; `emitters` check
+    /// receipt signatures and are shared across worker threads.
+    pub fn new(
+        policy: &'a QuorumPolicy,
+        guardians: &'a dyn GuardianKeyring,
+        emitters: &'a (dyn EmitterKeyring + Sync),
+    ) -> Self {
+        VerificationEngine {
+            policy,
+            guardians,
+            emitters,
+        }
+    }
+
+    /// Checks `anchor` and `receipts`, in any order, against each other.
+    pub fn verify_batch(&self, anchor: &AnchorGlyph, receipts: &[ReceiptGlyph]) -> BatchReport {
+        let started = Instant::now();
+        let positions: BTreeMap<GlyphId, usize> = anchor
+            .receipts
+            .iter()
+            .enumerate()
+            .map(|(i, r)| (r.receipt_id, i))
+            .collect();
+        let root = hash_from_hex(&anchor.merkle_root);
+
+        let emitters = self.emitters;
+
+        let (mut anchor_verdict, quorum) = self.check_anchor(anchor);
+        let mut receipt_verdicts: Vec<GlyphVerdict> = receipts
+            .par_iter()
+            .map(|receipt| check_receipt_in(anchor, &positions, &root, emitters, receipt))
+            .collect();
+
+        let mut seen = BTreeSet::new();
+        for (receipt, verdict) in receipts.iter().zip(&mut receipt_verdicts) {
+            if !seen.insert(receipt.receipt_id) {
+                verdict.record(
+                    Check::Membership,
+                    Err(not_anchored(receipt, "receipt appears twice in the batch")),
+                );
+            }
+        }
+        let missing: Vec<GlyphId> = anchor
+            .receipts
+            .iter()
+            .map(|r| r.receipt_id)
+            .filter(|id| !seen.contains(id))
+            .collect();
+
+        if missing.is_empty() {
+            let by_id: BTreeMap<GlyphId, &ReceiptGlyph> =
+                receipts.iter().map(|r| (r.receipt_id, r)).collect();
+            let ordered = anchor.receipts.iter().map(|r| by_id[&r.receipt_id]);
+            anchor_verdict.record(Check::Merkle, batch_root_matches(anchor, ordered));
+        }
+
+        BatchReport {
+            anchor: anchor_verdict,
+            quorum,
+            receipts: receipt_verdicts,
+            missing,
+            elapsed: started.elapsed(),
+        }
+    }
+
+    fn check_anchor(&self, anchor: &AnchorGlyph) -> (GlyphVerdict, Option<QuorumOutcome>) {
+        let mut verdict = GlyphVerdict::new(anchor.glyph_id);
+        verdict.record(Check::Id, verify_id(anchor));
+        verdict.record(Check::Hash, hash_matches(anchor, &anchor.blake3_hash));
+        verdict.record(Check::Schema, check_glyph(anchor));
+        let quorum = match verify_quorum(anchor, self.policy, self.guardians) {
+            Ok(outcome) => Some(outcome),
+            Err(error) => {
+                verdict.record(Check::Quorum, Err(error));
+                None
+            }
+        };
+        (verdict, quorum)
+    }
+}
+
+fn check_receipt_in(
+    anchor: &AnchorGlyph,
+    positions: &BTreeMap<GlyphId, usize>,
+    root: &Result<MerkleHash>,
+    emitters: &dyn EmitterKeyring,
+    receipt: &ReceiptGlyph,
+) -> GlyphVerdict {
+    let mut verdict = GlyphVerdict::new(receipt.receipt_id);
+    let position = positions.get(&receipt.receipt_id).copied();
+    verdict.record(Check::Id, verify_id(receipt));
+    verdict.record(Check::Hash, hash_matches(receipt, &receipt.blake3_hash));
+    verdict.record(Check::Membership, membership(anchor, position, receipt));
+    verdict.record(Check::Merkle, inclusion(anchor, position, root, receipt));
+    verdict.record(Check::Signature, verify_receipt_emitter(receipt, emitters));
+    verdict.record(Check::Payload, check_receipt(receipt));
+    verdict.record(Check::Schema, check_glyph(receipt));
+    verdict
+}
+
+fn hash_matches<G: Glyph>(glyph: &G, claimed: &str) -> Result<()> {
+    let expected = content_hash(glyph)?.to_hex().to_string();
+    if expected == claimed {
+        Ok(())
+    } else {
+        Err(GlyphError::HashMismatch {
+            expected,
+            found: claimed.to_string(),
+        })
+    }
+}
+
+fn not_anchored(receipt: &ReceiptGlyph, reason: &'static str) -> GlyphError {
+    GlyphError::NotAnchored {
+        receipt_id: receipt.receipt_id,
+        reason,
+    }
+}
+
+fn membership(anchor: &AnchorGlyph, position: Option<usize>, receipt: &ReceiptGlyph) -> Result<()> {
+    let listed = position
+        .map(|i| &anchor.receipts[i])
+        .ok_or_else(|| not_anchored(receipt, "anchor does not list the receipt"))?;
+    if receipt.tenant_id != anchor.tenant_id {
+        return Err(not_anchored(receipt, "tenant_id differs from the anchor"));
+    }
+    if listed.result != receipt.result {
+        return Err(not_anchored(
+            receipt,
+            "result differs from the anchor's reference",
+        ));
+    }
+    if receipt.merkle_root != anchor.merkle_root
+        || listed
+            .merkle_root
+            .as_ref()
+            .is_some_and(|r| *r != receipt.merkle_root)
+    {
+        return Err(not_anchored(receipt, "merkle_root differs from the anchor"));
+    }
+    Ok(())
+}
+
+fn inclusion(
+    anchor: &AnchorGlyph,
+    position: Option<usize>,
+    root: &Result<MerkleHash>,
+    receipt: &ReceiptGlyph,
+) -> Result<()> {
+    let root = root.as_ref().map_err(|_| GlyphError::InvalidHash {
+        value: anchor.merkle_root.clone(),
+    })?;
+    let embedded = receipt
+        .merkle_proof
+        .as_ref()
+        .ok_or(GlyphError::InvalidMerkleProof {
+            reason: "receipt carries no inclusion proof",
+        })?;
+    let proof = MerkleProof::from_embedded(embedded, anchor.receipts.len() as u64)?;
+    if position.is_some_and(|i| i as u64 != proof.leaf_index) {
+        return Err(GlyphError::InvalidMerkleProof {
+            reason: "leaf_index differs from the anchor's receipt order",
+        });
+    }
+    let leaf = receipt_leaf(receipt)?;
+    if proof.compute_root(&leaf)? != *root {
+        return Err(GlyphError::InvalidMerkleProof {
+            reason: "proof does not lead to the anchor root",
+        });
+    }
+    Ok(())
+}
+
+fn batch_root_matches<'r>(
+    anchor: &AnchorGlyph,
+    ordered: impl Iterator<Item = &'r ReceiptGlyph>,
+) -> Result<()> {
+    let root = MerkleTree::from_receipts(ordered)?.root();
+    if hash_from_hex(&anchor.merkle_root)? != root {
+        return Err(GlyphError::InvalidMerkleProof {
+            reason: "anchor merkle_root is not the root over its receipts",
+        });
+    }
+    Ok(())
+}
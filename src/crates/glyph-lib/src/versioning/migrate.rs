@@ -0,0 +1,284 @@
+//! Schema-version negotiation and glyph migrations.
+//!
+//! Every schema pins `version` to [`SchemaVersion::CURRENT`], but the ledger
+//! is append-only: a receipt written under an older schema must still decode,
+//! and its `blake3_hash` and ID must still verify, after the schema moves on.
+//!
+//! A [`MigrationRegistry`] holds one [`Migration`] per `(kind, from)` step.
+//! Each step has an `up` function (old → new) and a `down` function
+//! (new → old) and never touches `version` itself; the registry rewrites it.
+//! [`MigrationRegistry::decode`] walks `up` steps until the document reaches
+//! the current version and deserializes it into the in-memory model. The
+//! returned [`Versioned`] glyph remembers where it started, and re-emits that
+//! original version through the `down` steps whenever it is hashed, so the
+//! bytes checked are the bytes the emitter hashed.
+//!
+//! Decoding refuses a chain that does not reproduce its input exactly: a
+//! lossy migration would silently change historical hashes.
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::anchors::anchor_types::{Glyph, GlyphKind, JsonMap, SchemaVersion};
+use crate::error::{GlyphError, Result};
+use crate::hashing::{canonical, ids};
+
+/// Rewrites a glyph object between two adjacent schema versions.
+pub type MigrationFn = fn(JsonMap) -> Result<JsonMap>;
+
+/// One step in a kind's version chain.
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    pub kind: GlyphKind,
+    pub from: &'static str,
+    pub to: &'static str,
+    /// `from` → `to`.
+    pub up: MigrationFn,
+    /// `to` → `from`; must invert `up` exactly.
+    pub down: MigrationFn,
+}
+
+impl Migration {
+    fn error(&self, reason: impl Into<String>) -> GlyphError {
+        GlyphError::Migration {
+            from: self.from.to_string(),
+            to: self.to.to_string(),
+            reason: reason.into(),
+        }
+    }
+}
+
+/// Registered migration steps, keyed by glyph kind and source version.
+#[derive(Debug, Clone, Default)]
+pub struct MigrationRegistry {
+    steps: BTreeMap<GlyphKind, BTreeMap<&'static str, Migration>>,
+}
+
+impl MigrationRegistry {
+    /// A registry with no steps: only current-version glyphs decode.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The migrations shipped with this glyph-lib.
+    ///
+    /// Empty while every schema is still at 1.0. A schema bump adds the new
+    /// [`SchemaVersion`] variant, moves [`SchemaVersion::CURRENT`] and
+    /// registers the step from the previous version here.
+    pub fn builtin() -> Self {
+        Self::new()
+    }
+
+    /// Adds a step. Each `(kind, from)` may be registered once.
+    pub fn register(&mut self, migration: Migration) -> Result<()> {
+        if migration.from == migration.to {
+            return Err(migration.error("step does not change the version"));
+        }
+        if migration.from == SchemaVersion::CURRENT.as_str() {
+            return Err(migration.error("step starts at the current version"));
+        }
+        let steps = self.steps.entry(migration.kind).or_default();
+        if steps.contains_key(migration.from) {
+            return Err(migration.error("a step from this version is already registered"));
+        }
+        steps.insert(migration.from, migration);
+        Ok(())
+    }
+
+    /// Steps leading from `version` to the current version, in `up` order.
+    fn path(&self, kind: GlyphKind, version: &str) -> Result<Vec<Migration>> {
+        let steps = self.steps.get(&kind);
+        let mut path = Vec::new();
+        let mut at = version;
+        while at != SchemaVersion::CURRENT.as_str() {
+            let step =
+                steps
+                    .and_then(|s| s.get(at))
+                    .ok_or_else(|| GlyphError::UnsupportedVersion {
+                        kind,
+                        version: version.to_string(),
+                    })?;
+            if path.len() >= steps.map_or(0, BTreeMap::len) {
+                return Err(step.error("version chain loops"));
+            }
+            path.push(*step);
+            at = step.to;
+        }
+        Ok(path)
+    }
+
+    /// Every version of `kind` this registry can decode, oldest first.
+    pub fn supported_versions(&self, kind: GlyphKind) -> Vec<&'static str> {
+        let mut versions: Vec<&'static str> = self
+            .steps
+            .get(&kind)
+            .into_iter()
+            .flat_map(|s| s.keys().copied())
+            .filter(|v| self.path(kind, v).is_ok())
+            .chain([SchemaVersion::CURRENT.as_str()])
+            .collect();
+        versions.sort_by_key(|v| parse_version(v));
+        versions
+    }
+
+    /// Upcasts a raw glyph to the current version.
+    ///
+    /// Returns the migrated document and the version it was written in.
+    pub fn upcast(&self, kind: GlyphKind, value: Value) -> Result<(Value, String)> {
+        let mut map = into_object(value)?;
+        let original = version_of(kind, &map)?;
+        for step in self.path(kind, &original)? {
+            map = (step.up)(map)?;
+            map.insert("version".into(), Value::from(step.to));
+        }
+        Ok((Value::Object(map), original))
+    }
+
+    /// Re-emits a current-version raw glyph in `target`'s format.
+    pub fn downcast(&self, kind: GlyphKind, value: Value, target: &str) -> Result<Value> {
+        let map = into_object(value)?;
+        let found = version_of(kind, &map)?;
+        if found != SchemaVersion::CURRENT.as_str() {
+            return Err(GlyphError::UnsupportedVersion {
+                kind,
+                version: found,
+            });
+        }
+        apply_down(&self.path(kind, target)?, map)
+    }
+
+    /// Decodes a glyph written in any supported version.
+    pub fn decode<G: Glyph>(&self, value: Value) -> Result<Versioned<G>> {
+        let (current, original_version) = self.upcast(G::KIND, value.clone())?;
+        let glyph: G = serde_json::from_value(current)?;
+        let downgrade = self.path(G::KIND, &original_version)?;
+        let versioned = Versioned {
+            glyph,
+            original_version,
+            downgrade,
+        };
+        if versioned.is_migrated() {
+            let reemitted = versioned.to_original_value()?;
+            if canonical::to_canonical_bytes(&reemitted)? != canonical::to_canonical_bytes(&value)?
+            {
+                let first = versioned.downgrade[0];
+                return Err(first.error("re-emitted glyph differs from the stored one"));
+            }
+        }
+        Ok(versioned)
+    }
+
+    /// [`decode`](Self::decode) from JSON text.
+    pub fn decode_str<G: Glyph>(&self, json: &str) -> Result<Versioned<G>> {
+        self.decode(serde_json::from_str(json)?)
+    }
+}
+
+/// A glyph upcast to the current model, remembering its stored version.
+#[derive(Debug, Clone)]
+pub struct Versioned<G> {
+    glyph: G,
+    original_version: String,
+    /// Steps taken on decode, in `up` order.
+    downgrade: Vec<Migration>,
+}
+
+impl<G: Glyph> Versioned<G> {
+    /// Wraps a glyph that was built at the current version.
+    pub fn current(glyph: G) -> Self {
+        Versioned {
+            glyph,
+            original_version: SchemaVersion::CURRENT.as_str().to_string(),
+            downgrade: Vec::new(),
+        }
+    }
+
+    pub fn glyph(&self) -> &G {
+        &self.glyph
+    }
+
+    pub fn into_inner(self) -> G {
+        self.glyph
+    }
+
+    /// Version the glyph was stored in.
+    pub fn original_version(&self) -> &str {
+        &self.original_version
+    }
+
+    pub fn is_migrated(&self) -> bool {
+        !self.downgrade.is_empty()
+    }
+
+    /// The glyph as it was stored, in its original version.
+    pub fn to_original_value(&self) -> Result<Value> {
+        apply_down(
+            &self.downgrade,
+            into_object(serde_json::to_value(&self.glyph)?)?,
+        )
+    }
+
+    /// Hash preimage in the original version.
+    pub fn preimage(&self) -> Result<Vec<u8>> {
+        canonical::preimage_value(G::KIND, &self.to_original_value()?)
+    }
+
+    /// `blake3_hash` as the emitter computed it.
+    pub fn content_hash(&self) -> Result<blake3::Hash> {
+        Ok(blake3::hash(&self.preimage()?))
+    }
+
+    /// Checks the glyph's ID against its original-version payload.
+    pub fn verify_id(&self) -> Result<()> {
+        let expected = ids::derive_id_value(G::KIND, &self.to_original_value()?)?;
+        let found = *self.glyph.id();
+        if found != expected {
+            return Err(GlyphError::GlyphIdMismatch { expected, found });
+        }
+        Ok(())
+    }
+}
+
+/// Highest version offered by both sides, comparing `major.minor` numerically.
+///
+/// Entries that are not `major.minor` are ignored.
+pub fn negotiate<'a>(ours: &[&'a str], theirs: &[&str]) -> Option<&'a str> {
+    ours.iter()
+        .copied()
+        .filter(|v| parse_version(v).is_some())
+        .filter(|v| theirs.iter().any(|t| parse_version(t) == parse_version(v)))
+        .max_by_key(|v| parse_version(v))
+}
+
+fn parse_version(version: &str) -> Option<(u32, u32)> {
+    let (major, minor) = version.split_once('.')?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+fn into_object(value: Value) -> Result<JsonMap> {
+    match value {
+        Value::Object(map) => Ok(map),
+        _ => Err(GlyphError::Canonical {
+            reason: "glyph must be a JSON object",
+        }),
+    }
+}
+
+fn version_of(kind: GlyphKind, map: &JsonMap) -> Result<String> {
+    match map.get("version") {
+        Some(Value::String(v)) => Ok(v.clone()),
+        other => Err(GlyphError::UnsupportedVersion {
+            kind,
+            version: other.map(Value::to_string).unwrap_or_default(),
+        }),
+    }
+}
+
+fn apply_down(path: &[Migration], mut map: JsonMap) -> Result<Value> {
+    for step in path.iter().rev() {
+        map = (step.down)(map)?;
+        map.insert("version".into(), Value::from(step.from));
+    }
+    Ok(Value::Object(map))
+}
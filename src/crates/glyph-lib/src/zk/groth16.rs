@@ -0,0 +1,510 @@
+//! Groth16 drift-bound proofs over BLS12-381.
+//!
+//! nebula-guard proves that an orbital `drift_percent` stays below an
+//! intent's `max_drift_percent` without publishing the drift itself. The
+//! circuit ([`DriftBoundCircuit`]) has two public inputs, in this order:
+//!
+//! 1. `max_drift`: the bound, in [`DRIFT_SCALE`] fixed-point units;
+//! 2. `commitment`: MiMC(`drift`, `blinding`), binding the proof to a drift
+//!    reading the prover committed to without revealing it.
+//!
+//! and enforces `drift < max_drift` with 32-bit range checks on `drift` and
+//! `max_drift - drift - 1`.
+//!
+//! # Wire format
+//!
+//! A `zk_anomaly_proof` receipt carries the proof in the shape the receipt
+//! schema fixes. Every string is lower-case hex:
+//!
+//! - `zk_proof.pi_a`, `zk_proof.pi_c`: `[x, y]` of the G1 point;
+//! - `zk_proof.pi_b`: `[[x.c1, x.c0], [y.c1, y.c0]]` of the G2 point,
+//!
+//! each coordinate being its 48-byte slice of the zcash uncompressed point
+//! encoding, and `public_inputs` holds the two inputs above as 32-byte
+//! little-endian scalars. `extensions.zk.public_inputs_hash` is BLAKE3 over
+//! the concatenated `public_inputs` bytes (see [`public_inputs_hash`]).
+
+use std::sync::OnceLock;
+
+use bellman::gadgets::boolean::AllocatedBit;
+use bellman::groth16::{self, Parameters, PreparedVerifyingKey, Proof, VerifyingKey};
+use bellman::{Circuit, ConstraintSystem, LinearCombination, SynthesisError};
+use bls12_381::{Bls12, G1Affine, G2Affine, Scalar};
+use ff::Field;
+use rand_core::RngCore;
+use serde_json::Value;
+
+use crate::anchors::anchor_types::{JsonMap, ProofSystem, ReceiptGlyph, ReceiptType, ZkExtension};
+use crate::error::{GlyphError, Result};
+
+/// `extensions.zk.circuit_id` of the drift-bound circuit.
+pub const DRIFT_BOUND_CIRCUIT_ID: &str = "truth-tunnel/drift-bound/v1";
+
+/// Fixed-point units per percentage point: drift is proven to 1e-4 %.
+pub const DRIFT_SCALE: f64 = 10_000.0;
+
+/// Bits in the range checks; drift and bound stay below 2^32 units.
+const RANGE_BITS: usize = 32;
+
+/// MiMC-Feistel rounds, as recommended for the BLS12-381 scalar field.
+const MIMC_ROUNDS: usize = 322;
+
+const MIMC_CONSTANTS_CONTEXT: &str = "truth-tunnel glyph-lib v1 drift-bound mimc";
+
+/// Round constants, expanded from a fixed BLAKE3 context so every daemon
+/// derives the same circuit.
+fn mimc_constants() -> &'static [Scalar] {
+    static CONSTANTS: OnceLock<Vec<Scalar>> = OnceLock::new();
+    CONSTANTS.get_or_init(|| {
+        let mut xof = blake3::Hasher::new_derive_key(MIMC_CONSTANTS_CONTEXT).finalize_xof();
+        (0..MIMC_ROUNDS)
+            .map(|_| {
+                let mut wide = [0u8; 64];
+                xof.fill(&mut wide);
+                Scalar::from_bytes_wide(&wide)
+            })
+            .collect()
+    })
+}
+
+fn mimc(mut xl: Scalar, mut xr: Scalar) -> Scalar {
+    for c in mimc_constants() {
+        let t = xl + c;
+        let next = xr + t.square() * t;
+        xr = xl;
+        xl = next;
+    }
+    xl
+}
+
+/// Converts a drift percentage to circuit units.
+pub fn drift_units(percent: f64) -> Result<u64> {
+    let units = (percent * DRIFT_SCALE).round();
+    if !units.is_finite() || units < 0.0 || units >= (1u64 << RANGE_BITS) as f64 {
+        return Err(GlyphError::InvalidZkProof {
+            reason: format!("drift {percent}% is outside the provable range"),
+        });
+    }
+    Ok(units as u64)
+}
+
+/// Commitment to a drift reading, published before or alongside the proof.
+pub fn commit(drift_percent: f64, blinding: &Scalar) -> Result<Scalar> {
+    Ok(mimc(Scalar::from(drift_units(drift_percent)?), *blinding))
+}
+
+/// `drift < max_drift` with `commitment = MiMC(drift, blinding)`.
+///
+/// Witness values are `None` during parameter generation.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DriftBoundCircuit {
+    pub drift: Option<u64>,
+    pub blinding: Option<Scalar>,
+    pub max_drift: Option<u64>,
+}
+
+/// Enforces `lc == Σ bits·2^i` over [`RANGE_BITS`] fresh boolean witnesses.
+fn range_check<CS: ConstraintSystem<Scalar>>(
+    mut cs: CS,
+    value: Option<u64>,
+    lc: LinearCombination<Scalar>,
+) -> std::result::Result<(), SynthesisError> {
+    let mut sum = LinearCombination::zero();
+    let mut coeff = Scalar::ONE;
+    for i in 0..RANGE_BITS {
+        let bit = AllocatedBit::alloc(
+            cs.namespace(|| format!("bit {i}")),
+            value.map(|v| (v >> i) & 1 == 1),
+        )?;
+        sum = sum + (coeff, bit.get_variable());
+        coeff = coeff.double();
+    }
+    cs.enforce(|| "packing", |_| sum, |l| l + CS::one(), |_| lc);
+    Ok(())
+}
+
+impl Circuit<Scalar> for DriftBoundCircuit {
+    fn synthesize<CS: ConstraintSystem<Scalar>>(
+        self,
+        cs: &mut CS,
+    ) -> std::result::Result<(), SynthesisError> {
+        let max_drift = cs.alloc_input(
+            || "max_drift",
+            || {
+                self.max_drift
+                    .map(Scalar::from)
+                    .ok_or(SynthesisError::AssignmentMissing)
+            },
+        )?;
+        let drift = cs.alloc(
+            || "drift",
+            || {
+                self.drift
+                    .map(Scalar::from)
+                    .ok_or(SynthesisError::AssignmentMissing)
+            },
+        )?;
+
+        range_check(
+            cs.namespace(|| "drift range"),
+            self.drift,
+            LinearCombination::zero() + drift,
+        )?;
+        let slack = match (self.max_drift, self.drift) {
+            (Some(max), Some(d)) => Some(
+                max.checked_sub(d)
+                    .and_then(|s| s.checked_sub(1))
+                    .ok_or(SynthesisError::Unsatisfiable)?,
+            ),
+            _ => None,
+        };
+        range_check(
+            cs.namespace(|| "drift below bound"),
+            slack,
+            LinearCombination::zero() + max_drift - drift - CS::one(),
+        )?;
+
+        let mut xl_value = self.drift.map(Scalar::from);
+        let mut xr_value = self.blinding;
+        let mut xl = drift;
+        let mut xr = cs.alloc(
+            || "blinding",
+            || xr_value.ok_or(SynthesisError::AssignmentMissing),
+        )?;
+        for (i, c) in mimc_constants().iter().enumerate() {
+            let mut cs = cs.namespace(|| format!("mimc round {i}"));
+            let t_value = xl_value.map(|x| x + c);
+            let sq_value = t_value.map(|t| t.square());
+            let sq = cs.alloc(
+                || "(xl + c)^2",
+                || sq_value.ok_or(SynthesisError::AssignmentMissing),
+            )?;
+            cs.enforce(
+                || "square",
+                |l| l + xl + (*c, CS::one()),
+                |l| l + xl + (*c, CS::one()),
+                |l| l + sq,
+            );
+
+            let next_value = match (xr_value, sq_value, t_value) {
+                (Some(xr), Some(sq), Some(t)) => Some(xr + sq * t),
+                _ => None,
+            };
+            let assign = || next_value.ok_or(SynthesisError::AssignmentMissing);
+            let next = if i == MIMC_ROUNDS - 1 {
+                cs.alloc_input(|| "commitment", assign)?
+            } else {
+                cs.alloc(|| "xl'", assign)?
+            };
+            cs.enforce(
+                || "cube",
+                |l| l + sq,
+                |l| l + xl + (*c, CS::one()),
+                |l| l + next - xr,
+            );
+
+            xr = xl;
+            xr_value = xl_value;
+            xl = next;
+            xl_value = next_value;
+        }
+        Ok(())
+    }
+}
+
+/// Public inputs of one drift-bound proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DriftBoundPublic {
+    pub max_drift: u64,
+    pub commitment: Scalar,
+}
+
+impl DriftBoundPublic {
+    /// Inputs in circuit order.
+    pub fn inputs(&self) -> [Scalar; 2] {
+        [Scalar::from(self.max_drift), self.commitment]
+    }
+
+    /// `max_drift` back in percent.
+    pub fn max_drift_percent(&self) -> f64 {
+        self.max_drift as f64 / DRIFT_SCALE
+    }
+
+    /// Receipt `public_inputs` strings.
+    pub fn to_hex(&self) -> Vec<String> {
+        self.inputs()
+            .iter()
+            .map(|s| hex::encode(s.to_bytes()))
+            .collect()
+    }
+
+    /// Inverse of [`to_hex`](Self::to_hex).
+    pub fn from_hex(inputs: &[String]) -> Result<Self> {
+        let [max_drift, commitment] = inputs else {
+            return Err(zk_error("expected two public inputs"));
+        };
+        let max_drift = scalar_from_hex(max_drift)?;
+        let bytes = max_drift.to_bytes();
+        if bytes[8..].iter().any(|b| *b != 0) {
+            return Err(zk_error("max_drift does not fit in 64 bits"));
+        }
+        Ok(DriftBoundPublic {
+            max_drift: u64::from_le_bytes(bytes[..8].try_into().expect("8 bytes")),
+            commitment: scalar_from_hex(commitment)?,
+        })
+    }
+
+    /// `extensions.zk.public_inputs_hash` for these inputs.
+    pub fn public_inputs_hash(&self) -> String {
+        public_inputs_hash(&self.to_hex()).expect("encoded inputs are hex")
+    }
+
+    /// `extensions.zk` block for an anchor committing to this proof.
+    pub fn zk_extension(&self, proof_ref: impl Into<String>) -> ZkExtension {
+        ZkExtension {
+            proof_system: Some(ProofSystem::Groth16),
+            proof_ref: Some(proof_ref.into()),
+            public_inputs_hash: Some(self.public_inputs_hash()),
+            circuit_id: Some(DRIFT_BOUND_CIRCUIT_ID.to_string()),
+            additional: JsonMap::new(),
+        }
+    }
+}
+
+/// BLAKE3 over the decoded bytes of a receipt's `public_inputs`, in order.
+pub fn public_inputs_hash(inputs: &[String]) -> Result<String> {
+    let mut hasher = blake3::Hasher::new();
+    for input in inputs {
+        let bytes = hex::decode(input).map_err(|_| zk_error("public input is not hex"))?;
+        hasher.update(&bytes);
+    }
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+/// A proof together with the public inputs it was made for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DriftBoundProof {
+    pub proof: Proof<Bls12>,
+    pub public: DriftBoundPublic,
+}
+
+impl DriftBoundProof {
+    /// Receipt `zk_proof` object.
+    pub fn zk_proof_json(&self) -> Value {
+        let a = self.proof.a.to_uncompressed();
+        let b = self.proof.b.to_uncompressed();
+        let c = self.proof.c.to_uncompressed();
+        let h = |bytes: &[u8]| Value::from(hex::encode(bytes));
+        serde_json::json!({
+            "pi_a": [h(&a[..48]), h(&a[48..])],
+            "pi_b": [[h(&b[..48]), h(&b[48..96])], [h(&b[96..144]), h(&b[144..])]],
+            "pi_c": [h(&c[..48]), h(&c[48..])],
+        })
+    }
+
+    /// Writes `zk_proof` and `public_inputs` into a receipt's details.
+    pub fn write_details(&self, details: &mut JsonMap) {
+        details.insert("zk_proof".into(), self.zk_proof_json());
+        details.insert("public_inputs".into(), Value::from(self.public.to_hex()));
+    }
+
+    /// Reads the proof back from a `zk_anomaly_proof` receipt.
+    pub fn from_receipt(receipt: &ReceiptGlyph) -> Result<Self> {
+        if receipt.receipt_type != ReceiptType::ZkAnomalyProof {
+            return Err(zk_error("receipt is not a zk_anomaly_proof"));
+        }
+        let zk = receipt
+            .details
+            .get("zk_proof")
+            .ok_or_else(|| zk_error("missing zk_proof"))?;
+        let inputs: Vec<String> = receipt
+            .details
+            .get("public_inputs")
+            .cloned()
+            .map(serde_json::from_value)
+            .transpose()?
+            .ok_or_else(|| zk_error("missing public_inputs"))?;
+
+        let coords = |path: &str, len: usize| -> Result<Vec<u8>> {
+            let parts = zk
+                .pointer(path)
+                .and_then(Value::as_array)
+                .filter(|p| p.len() == 2)
+                .ok_or_else(|| zk_error(format!("zk_proof{path} must hold two coordinates")))?;
+            let mut out = Vec::with_capacity(len);
+            for part in parts {
+                let hex_str = part
+                    .as_str()
+                    .ok_or_else(|| zk_error(format!("zk_proof{path} must hold strings")))?;
+                out.extend(hex::decode(hex_str).map_err(|_| zk_error("coordinate is not hex"))?);
+            }
+            if out.len() != len {
+                return Err(zk_error(format!("zk_proof{path} has the wrong length")));
+            }
+            Ok(out)
+        };
+        let g1 = |path: &str| -> Result<G1Affine> {
+            let bytes: [u8; 96] = coords(path, 96)?.try_into().expect("length checked");
+            Option::from(G1Affine::from_uncompressed(&bytes))
+                .ok_or_else(|| zk_error(format!("zk_proof{path} is not a G1 point")))
+        };
+        let mut b = coords("/pi_b/0", 96)?;
+        b.extend(coords("/pi_b/1", 96)?);
+        let b: [u8; 192] = b.try_into().expect("length checked");
+        let b = Option::from(G2Affine::from_uncompressed(&b))
+            .ok_or_else(|| zk_error("zk_proof/pi_b is not a G2 point"))?;
+
+        Ok(DriftBoundProof {
+            proof: Proof {
+                a: g1("/pi_a")?,
+                b,
+                c: g1("/pi_c")?,
+            },
+            public: DriftBoundPublic::from_hex(&inputs)?,
+        })
+    }
+}
+
+/// Holder of the proving parameters.
+#[derive(Clone)]
+pub struct DriftBoundProver {
+    params: Parameters<Bls12>,
+}
+
+impl DriftBoundProver {
+    /// Runs a fresh (single-party) Groth16 setup for the circuit.
+    pub fn setup<R: RngCore>(rng: &mut R) -> Result<Self> {
+        let params =
+            groth16::generate_random_parameters::<Bls12, _, _>(DriftBoundCircuit::default(), rng)
+                .map_err(synthesis_error)?;
+        Ok(DriftBoundProver { params })
+    }
+
+    /// Loads parameters written by [`to_bytes`](Self::to_bytes), checking
+    /// every point.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let params =
+            Parameters::read(bytes, true).map_err(|e| zk_error(format!("bad parameters: {e}")))?;
+        Ok(DriftBoundProver { params })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.params
+            .write(&mut out)
+            .expect("writing to a Vec cannot fail");
+        out
+    }
+
+    /// Verifier sharing this setup's verifying key.
+    pub fn verifier(&self) -> DriftBoundVerifier {
+        DriftBoundVerifier::new(self.params.vk.clone())
+    }
+
+    /// Proves `drift_percent < max_drift_percent` for the reading committed
+    /// to by [`commit`]`(drift_percent, blinding)`.
+    pub fn prove<R: RngCore>(
+        &self,
+        drift_percent: f64,
+        max_drift_percent: f64,
+        blinding: Scalar,
+        rng: &mut R,
+    ) -> Result<DriftBoundProof> {
+        let drift = drift_units(drift_percent)?;
+        let max_drift = drift_units(max_drift_percent)?;
+        if drift >= max_drift {
+            return Err(GlyphError::DriftExceedsBound {
+                drift_percent,
+                max_drift_percent,
+            });
+        }
+        let circuit = DriftBoundCircuit {
+            drift: Some(drift),
+            blinding: Some(blinding),
+            max_drift: Some(max_drift),
+        };
+        let proof =
+            groth16::create_random_proof(circuit, &self.params, rng).map_err(synthesis_error)?;
+        Ok(DriftBoundProof {
+            proof,
+            public: DriftBoundPublic {
+                max_drift,
+                commitment: mimc(Scalar::from(drift), blinding),
+            },
+        })
+    }
+}
+
+/// Fresh blinding factor for [`commit`].
+pub fn random_blinding<R: RngCore>(rng: &mut R) -> Scalar {
+    Scalar::random(rng)
+}
+
+/// Checks drift-bound proofs against one verifying key.
+pub struct DriftBoundVerifier {
+    vk: VerifyingKey<Bls12>,
+    pvk: PreparedVerifyingKey<Bls12>,
+}
+
+impl DriftBoundVerifier {
+    pub fn new(vk: VerifyingKey<Bls12>) -> Self {
+        let pvk = groth16::prepare_verifying_key(&vk);
+        DriftBoundVerifier { vk, pvk }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let vk =
+            VerifyingKey::read(bytes).map_err(|e| zk_error(format!("bad verifying key: {e}")))?;
+        Ok(Self::new(vk))
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.vk
+            .write(&mut out)
+            .expect("writing to a Vec cannot fail");
+        out
+    }
+
+    pub fn verify(&self, proof: &DriftBoundProof) -> Result<()> {
+        groth16::verify_proof(&self.pvk, &proof.proof, &proof.public.inputs())
+            .map_err(|_| GlyphError::ZkProofRejected)
+    }
+
+    /// Verifies a `zk_anomaly_proof` receipt and returns its public inputs.
+    pub fn verify_receipt(&self, receipt: &ReceiptGlyph) -> Result<DriftBoundPublic> {
+        let proof = DriftBoundProof::from_receipt(receipt)?;
+        self.verify(&proof)?;
+        Ok(proof.public)
+    }
+}
+
+/// Checks an anchor's `extensions.zk` against a verified proof's inputs.
+pub fn check_zk_extension(zk: &ZkExtension, public: &DriftBoundPublic) -> Result<()> {
+    if zk.circuit_id.as_deref() != Some(DRIFT_BOUND_CIRCUIT_ID) {
+        return Err(zk_error("extensions.zk names another circuit"));
+    }
+    if zk.public_inputs_hash.as_deref() != Some(public.public_inputs_hash().as_str()) {
+        return Err(zk_error("public_inputs_hash does not match the proof"));
+    }
+    Ok(())
+}
+
+fn scalar_from_hex(s: &str) -> Result<Scalar> {
+    let bytes: [u8; 32] = hex::decode(s)
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| zk_error("public input must be 32 hex-encoded bytes"))?;
+    Option::from(Scalar::from_bytes(&bytes))
+        .ok_or_else(|| zk_error("public input is not a canonical scalar"))
+}
+
+fn zk_error(reason: impl Into<String>) -> GlyphError {
+    GlyphError::InvalidZkProof {
+        reason: reason.into(),
+    }
+}
+
+fn synthesis_error(e: SynthesisError) -> GlyphError {
+    zk_error(e.to_string())
+}
@@ -0,0 +1,154 @@
+#[cfg(test)]
+mod test_binary_encoding {
+    use glyph_lib::encoding::binary::{
+        binary_to_canonical_json, content_hash_binary, from_binary, from_binary_value,
+        preimage_binary, to_binary, to_binary_value,
+    };
+    use glyph_lib::hashing::canonical::{content_hash_value, preimage_value, to_canonical_bytes};
+    use glyph_lib::hashing::ids::verify_id;
+    use glyph_lib::{
+        AnchorGlyph, DaemonStatusGlyph, GlyphError, GlyphKind, IntentGlyph, ReceiptGlyph,
+    };
+    use serde_json::{json, Value};
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn examples() -> Vec<(GlyphKind, Value)> {
+        let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../..")
+            .join("glyphs/examples");
+        let read = |name: &str| fs::read_to_string(dir.join(name)).unwrap();
+        let mut out = vec![
+            (
+                GlyphKind::Intent,
+                serde_json::from_str(&read("intent_glyph.example.json")).unwrap(),
+            ),
+            (
+                GlyphKind::Anchor,
+                serde_json::from_str(&read("anchor_glyph.example.json")).unwrap(),
+            ),
+            (
+                GlyphKind::DaemonStatus,
+                serde_json::from_str(&read("daemon_status_glyph.example.json")).unwrap(),
+            ),
+        ];
+        for name in [
+            "receipt_glyph.bore.example.json",
+            "receipt_glyph.orbital.example.json",
+            "receipt_glyph.zk_anomaly.example.json",
+            "receipt_glyph.entanglement.example.json",
+        ] {
+            out.push((
+                GlyphKind::Receipt,
+                serde_json::from_str(&read(name)).unwrap(),
+            ));
+        }
+        for line in read("receipt_glyph.example.jsonl").lines() {
+            if !line.trim().is_empty() {
+                out.push((GlyphKind::Receipt, serde_json::from_str(line).unwrap()));
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn every_example_round_trips_with_the_same_hash() {
+        for (kind, raw) in examples() {
+            let binary = to_binary_value(&raw).unwrap();
+            let json = to_canonical_bytes(&raw).unwrap();
+            assert!(binary.len() < json.len(), "{kind:?} binary is not smaller");
+
+            assert_eq!(from_binary_value(&binary).unwrap(), raw);
+            assert_eq!(binary_to_canonical_json(&binary).unwrap(), json);
+            assert_eq!(
+                preimage_binary(kind, &binary).unwrap(),
+                preimage_value(kind, &raw).unwrap()
+            );
+            assert_eq!(
+                content_hash_binary(kind, &binary).unwrap(),
+                content_hash_value(kind, &raw).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn typed_glyphs_decode_identically() {
+        for (kind, raw) in examples() {
+            let binary = to_binary_value(&raw).unwrap();
+            match kind {
+                GlyphKind::Intent => {
+                    let glyph: IntentGlyph = serde_json::from_value(raw).unwrap();
+                    assert_eq!(to_binary(&glyph).unwrap(), binary);
+                    assert_eq!(from_binary::<IntentGlyph>(&binary).unwrap(), glyph);
+                }
+                GlyphKind::Anchor => {
+                    let glyph: AnchorGlyph = serde_json::from_value(raw).unwrap();
+                    assert_eq!(to_binary(&glyph).unwrap(), binary);
+                    assert_eq!(from_binary::<AnchorGlyph>(&binary).unwrap(), glyph);
+                }
+                GlyphKind::DaemonStatus => {
+                    let glyph: DaemonStatusGlyph = serde_json::from_value(raw).unwrap();
+                    assert_eq!(to_binary(&glyph).unwrap(), binary);
+                    assert_eq!(from_binary::<DaemonStatusGlyph>(&binary).unwrap(), glyph);
+                }
+                GlyphKind::Receipt => {
+                    let mut glyph: ReceiptGlyph = serde_json::from_value(raw).unwrap();
+                    glyph.receipt_id = glyph_lib::hashing::ids::derive_id(&glyph).unwrap();
+                    let decoded: ReceiptGlyph = from_binary(&to_binary(&glyph).unwrap()).unwrap();
+                    verify_id(&decoded).unwrap();
+                    assert_eq!(decoded, glyph);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn numbers_keep_their_json_kind() {
+        let raw = json!({
+            "u": u64::MAX,
+            "i": i64::MIN,
+            "small": 6,
+            "half": 1.5,
+            "single": 0.1f32 as f64,
+            "double": 0.1,
+            "huge": 1e300,
+            "negative_zero": -0.0,
+            "nested": [{"z": null, "a": true}, "", []],
+        });
+        let binary = to_binary_value(&raw).unwrap();
+        let back = from_binary_value(&binary).unwrap();
+        assert_eq!(back, raw);
+        assert!(back["small"].is_u64());
+        assert!(back["half"].is_f64());
+        assert_eq!(
+            to_canonical_bytes(&back).unwrap(),
+            to_canonical_bytes(&raw).unwrap()
+        );
+    }
+
+    #[test]
+    fn only_the_deterministic_form_decodes() {
+        let rejected =
+            |bytes: &[u8]| matches!(from_binary_value(bytes), Err(GlyphError::Binary { .. }));
+        // {"a": 1.5} as a double instead of a half.
+        assert!(rejected(&[
+            0xa1, 0x61, 0x61, 0xfb, 0x3f, 0xf8, 0, 0, 0, 0, 0, 0
+        ]));
+        assert_eq!(
+            to_binary_value(&json!({"a": 1.5})).unwrap(),
+            [0xa1, 0x61, 0x61, 0xf9, 0x3e, 0x00]
+        );
+        // {"bb": 1, "a": 2}: keys out of order.
+        assert!(rejected(&[0xa2, 0x62, 0x62, 0x62, 0x01, 0x61, 0x61, 0x02]));
+        assert!(from_binary_value(&[0xa2, 0x61, 0x61, 0x02, 0x62, 0x62, 0x62, 0x01]).is_ok());
+        // Duplicate key, trailing byte, byte string, integer head too long.
+        assert!(rejected(&[0xa2, 0x61, 0x61, 0x01, 0x61, 0x61, 0x02]));
+        assert!(rejected(&[0x01, 0x00]));
+        assert!(rejected(&[0x41, 0x00]));
+        assert!(rejected(&[0x18, 0x01]));
+        // -2^64 fits CBOR but not JSON.
+        assert!(rejected(&[
+            0x3b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff
+        ]));
+    }
+}
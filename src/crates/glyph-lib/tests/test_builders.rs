@@ -0,0 +1,316 @@
+#[cfg(test)]
+mod test_builders {
+    use glyph_lib::anchors::merkle::{hash_from_hex, receipt_leaf, MerkleProof};
+    use glyph_lib::anchors::quorum::verify_quorum;
+    use glyph_lib::builders::anchor::{AnchorBuilder, PendingAnchor};
+    use glyph_lib::builders::intent::IntentBuilder;
+    use glyph_lib::builders::receipt::{ReceiptBatch, ReceiptBuilder};
+    use glyph_lib::builders::status::DaemonStatusBuilder;
+    use glyph_lib::hashing::canonical::content_hash;
+    use glyph_lib::hashing::ids::verify_id;
+    use glyph_lib::pq::envelope::{
+        verify_intent_signature, verify_receipt_signature, verify_status_signature, GlyphSigner,
+    };
+    use glyph_lib::testing::{
+        bore_payload, guardian_key_id, guardian_keyring, swarm_policy, MacKey, WEIGHTED,
+    };
+    use glyph_lib::{
+        ChainLink, Daemon, DaemonHealth, GlyphError, Guardian, GuardianSignature,
+        IntentConstraints, IntentGlyph, IntentType, ReceiptResult, ReceiptType, RiskAppetite,
+        SloCompliance, TargetContext, Verdict,
+    };
+    use serde_json::{json, Value};
+
+    const TENANT_ID: &str = "xai-memphis-01";
+    const NOW: i64 = 1_764_382_460;
+
+    fn intent() -> IntentGlyph {
+        intent_signed_by(&MacKey::of(Guardian::StarLord.as_str()))
+    }
+
+    fn intent_signed_by(signer: &dyn GlyphSigner) -> IntentGlyph {
+        IntentBuilder::new(
+            TENANT_ID,
+            IntentType::InitiateBore,
+            RiskAppetite::Conservative,
+            TargetContext::TunnelBore,
+            IntentConstraints {
+                max_latency_ms: 800,
+                min_entanglement_quality: 0.8,
+                require_zk_proof: false,
+                max_drift_percent: Some(1.5),
+            },
+            NOW + 3600,
+        )
+        .timestamp(NOW)
+        .build(Guardian::StarLord, signer)
+        .unwrap()
+    }
+
+    fn bore(intent: &IntentGlyph, meters: f64) -> ReceiptBuilder {
+        ReceiptBuilder::new(
+            TENANT_ID,
+            ReceiptType::BoreProgress,
+            intent.glyph_id,
+            ReceiptResult::Ok,
+            Daemon::RocketEngine,
+        )
+        .timestamp(NOW + 10)
+        .detail("segment_id", "memphis-segment-001")
+        .detail("meters_advanced", meters)
+        .detail("cutter_head_rpm", 6)
+    }
+
+    fn votes(pending: &PendingAnchor, guardians: &[Guardian]) -> Vec<GuardianSignature> {
+        guardians
+            .iter()
+            .map(|&g| {
+                pending
+                    .vote(
+                        g,
+                        &guardian_key_id(g),
+                        Verdict::Approve,
+                        &MacKey::of(g.as_str()),
+                    )
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn intent_and_status_are_derived_signed_and_valid() {
+        let intent = intent();
+        verify_id(&intent).unwrap();
+        verify_intent_signature(&intent, &MacKey::of(Guardian::StarLord.as_str())).unwrap();
+        assert!(verify_intent_signature(&intent, &MacKey::of(Guardian::Rocket.as_str())).is_err());
+
+        let expired = IntentBuilder::new(
+            TENANT_ID,
+            IntentType::EmergencyHalt,
+            RiskAppetite::Aggressive,
+            TargetContext::TunnelBore,
+            intent.constraints.clone(),
+            NOW,
+        )
+        .timestamp(NOW)
+        .build(Guardian::Drax, &MacKey::of(Guardian::Drax.as_str()));
+        assert!(matches!(expired, Err(GlyphError::Builder { .. })));
+
+        let mut weak = intent.constraints.clone();
+        weak.min_entanglement_quality = 0.5;
+        let invalid = IntentBuilder::new(
+            TENANT_ID,
+            IntentType::InitiateBore,
+            RiskAppetite::Balanced,
+            TargetContext::TunnelBore,
+            weak,
+            NOW + 60,
+        )
+        .timestamp(NOW)
+        .build(Guardian::StarLord, &MacKey::of(Guardian::StarLord.as_str()));
+        assert!(matches!(invalid, Err(GlyphError::SchemaInvalid { .. })));
+
+        let key = MacKey::of(Guardian::Rocket.as_str());
+        let slo = SloCompliance {
+            latency_p95_ms: 612.5,
+            entanglement_quality: 0.82,
+            zk_proof_time_ms: None,
+            anomaly_rate_per_hour: 0.0,
+        };
+        let status = DaemonStatusBuilder::new(
+            Daemon::RocketEngine,
+            Guardian::Rocket,
+            DaemonHealth::Healthy,
+            slo.clone(),
+        )
+        .uptime_seconds(86_400)
+        .glyphs_emitted(1284)
+        .last_anchor(None)
+        .active_phase(2)
+        .build(&key)
+        .unwrap();
+        verify_id(&status).unwrap();
+        assert_eq!(
+            content_hash(&status).unwrap().to_hex().as_str(),
+            status.blake3_hash
+        );
+        verify_status_signature(&status, &key).unwrap();
+        assert_eq!(
+            serde_json::to_value(&status).unwrap()["last_anchor_glyph_id"],
+            Value::Null
+        );
+
+        let bad_phase = DaemonStatusBuilder::new(
+            Daemon::RocketEngine,
+            Guardian::Rocket,
+            DaemonHealth::Healthy,
+            slo,
+        )
+        .active_phase(9)
+        .build(&key);
+        assert!(matches!(bad_phase, Err(GlyphError::SchemaInvalid { .. })));
+    }
+
+    #[test]
+    fn receipts_are_hashed_before_stamping_and_signing() {
+        let intent = intent();
+        let key = MacKey::of(Guardian::Rocket.as_str());
+
+        let single = bore(&intent, 12.5).build(&key).unwrap();
+        verify_id(&single).unwrap();
+        assert_eq!(
+            content_hash(&single).unwrap().to_hex().as_str(),
+            single.blake3_hash
+        );
+        assert_eq!(
+            hash_from_hex(&single.merkle_root).unwrap(),
+            receipt_leaf(&single).unwrap()
+        );
+        verify_receipt_signature(&single, &key).unwrap();
+        assert_eq!(single.details["meters_advanced"], json!(12.5));
+
+        let batch: ReceiptBatch = (0..5).map(|i| bore(&intent, f64::from(i))).collect();
+        assert_eq!(batch.len(), 5);
+        let receipts = batch.seal(&key).unwrap();
+        let root = hash_from_hex(&receipts[0].merkle_root).unwrap();
+        for receipt in &receipts {
+            assert_eq!(hash_from_hex(&receipt.merkle_root).unwrap(), root);
+            let embedded = receipt.merkle_proof.as_ref().expect("inclusion proof");
+            let proof = MerkleProof::from_embedded(embedded, receipts.len() as u64).unwrap();
+            assert!(proof.verify_receipt(&root, receipt));
+            verify_receipt_signature(receipt, &key).unwrap();
+        }
+        assert_ne!(receipts[0].receipt_id, receipts[1].receipt_id);
+
+        assert!(matches!(
+            ReceiptBatch::new().seal(&key),
+            Err(GlyphError::Builder { .. })
+        ));
+        assert!(matches!(
+            bore(&intent, 1.0).detail("merkle_root", "00").build(&key),
+            Err(GlyphError::Builder { .. })
+        ));
+        assert!(matches!(
+            bore(&intent, 1.0)
+                .detail("satellite_id", "starlink-5508")
+                .build(&key),
+            Err(GlyphError::InvalidReceiptPayload { .. })
+        ));
+    }
+
+    #[test]
+    fn anchors_finalize_only_with_a_quorum() {
+        let intent = intent();
+        let receipts = (0..3)
+            .map(|i| bore(&intent, f64::from(i)))
+            .collect::<ReceiptBatch>()
+            .seal(&MacKey::of(Guardian::Rocket.as_str()))
+            .unwrap();
+
+        let pending = AnchorBuilder::new(
+            TENANT_ID,
+            TargetContext::TunnelBore,
+            ChainLink::Genesis,
+            Daemon::RocketEngine,
+        )
+        .timestamp(NOW + 20)
+        .receipts(&receipts)
+        .payload(bore_payload())
+        .prepare()
+        .unwrap();
+        let anchor = pending.anchor();
+        verify_id(anchor).unwrap();
+        assert_eq!(anchor.merkle_root, receipts[0].merkle_root);
+        assert_eq!(anchor.receipts.len(), 3);
+        assert_eq!(
+            content_hash(anchor).unwrap().to_hex().as_str(),
+            anchor.blake3_hash
+        );
+
+        let policy = swarm_policy();
+        let few = votes(&pending, &[Guardian::StarLord, Guardian::Rocket]);
+        assert!(matches!(
+            pending.clone().finalize(&policy, &guardian_keyring(), few),
+            Err(GlyphError::QuorumNotMet { .. })
+        ));
+
+        let all = votes(&pending, &WEIGHTED);
+        let (anchor, outcome) = pending.finalize(&policy, &guardian_keyring(), all).unwrap();
+        assert_eq!(outcome.approved_weight, policy.total_weight());
+        verify_quorum(&anchor, &policy, &guardian_keyring()).unwrap();
+        verify_id(&anchor).unwrap();
+
+        let mut partial = bore_payload();
+        partial.remove("tbm_id");
+        let missing_tbm = AnchorBuilder::new(
+            TENANT_ID,
+            TargetContext::TunnelBore,
+            ChainLink::Anchor(anchor.glyph_id),
+            Daemon::RocketEngine,
+        )
+        .payload(partial)
+        .prepare()
+        .unwrap();
+        let all = votes(&missing_tbm, &WEIGHTED);
+        assert!(matches!(
+            missing_tbm.finalize(&policy, &guardian_keyring(), all),
+            Err(GlyphError::SchemaInvalid { .. })
+        ));
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn builders_sign_with_dilithium5() {
+        use glyph_lib::pq::dilithium::DilithiumKeypair;
+        use glyph_lib::pq::envelope::{AlgorithmId, SignatureEnvelope, SignatureField};
+
+        fn envelope(field: &str) -> SignatureEnvelope {
+            match SignatureField::parse(field).unwrap() {
+                SignatureField::Envelope(env) => env,
+                SignatureField::Legacy(_) => panic!("builders never emit stub signatures"),
+            }
+        }
+
+        let starlord = DilithiumKeypair::generate();
+        let rocket = DilithiumKeypair::generate();
+
+        let intent = intent_signed_by(&starlord);
+        let env = envelope(intent.signature.as_deref().unwrap());
+        assert_eq!(env.algorithm, AlgorithmId::Dilithium5);
+        assert_eq!(env.key_id, GlyphSigner::key_id(&starlord));
+        verify_intent_signature(&intent, starlord.public_key()).unwrap();
+        assert!(verify_intent_signature(&intent, rocket.public_key()).is_err());
+
+        let receipts = (0..3)
+            .map(|i| bore(&intent, f64::from(i)))
+            .collect::<ReceiptBatch>()
+            .seal(&rocket)
+            .unwrap();
+        for receipt in &receipts {
+            let env = envelope(&receipt.kyber_signature);
+            assert_eq!(env.key_id, GlyphSigner::key_id(&rocket));
+            assert_eq!(env.signature.len(), AlgorithmId::Dilithium5.signature_len());
+            verify_receipt_signature(receipt, rocket.public_key()).unwrap();
+            assert!(verify_receipt_signature(receipt, starlord.public_key()).is_err());
+        }
+
+        let status = DaemonStatusBuilder::new(
+            Daemon::RocketEngine,
+            Guardian::Rocket,
+            DaemonHealth::Healthy,
+            SloCompliance {
+                latency_p95_ms: 612.5,
+                entanglement_quality: 0.82,
+                zk_proof_time_ms: None,
+                anomaly_rate_per_hour: 0.0,
+            },
+        )
+        .build(&rocket)
+        .unwrap();
+        assert_eq!(
+            envelope(&status.kyber_signature).key_id,
+            GlyphSigner::key_id(&rocket)
+        );
+        verify_status_signature(&status, rocket.public_key()).unwrap();
+    }
+}
@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod test_canonical_json {
+    use glyph_lib::hashing::canonical::{
+        content_hash, content_hash_value, excluded_fields, preimage, preimage_value,
+        to_canonical_bytes, CANONICAL_FORMAT,
+    };
+    use glyph_lib::{GlyphKind, ReceiptGlyph};
+    use serde_json::{json, Value};
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn repo_root() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../..")
+    }
+
+    fn load_json(relative: &str) -> Value {
+        let path = repo_root().join(relative);
+        let data = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()));
+        serde_json::from_str(&data)
+            .unwrap_or_else(|e| panic!("failed to parse {}: {e}", path.display()))
+    }
+
+    fn kind_from_str(kind: &str) -> GlyphKind {
+        match kind {
+            "intent" => GlyphKind::Intent,
+            "receipt" => GlyphKind::Receipt,
+            "anchor" => GlyphKind::Anchor,
+            "daemon_status" => GlyphKind::DaemonStatus,
+            other => panic!("unknown vector kind: {other}"),
+        }
+    }
+
+    #[test]
+    fn conformance_vectors_match() {
+        let doc = load_json("glyphs/vectors/canonical_json.vectors.json");
+        assert_eq!(doc["format"], json!(CANONICAL_FORMAT));
+
+        let vectors = doc["vectors"].as_array().expect("vectors array");
+        assert!(!vectors.is_empty(), "vector file must not be empty");
+
+        for v in vectors {
+            let name = v["name"].as_str().expect("name");
+            let input: Value = serde_json::from_str(v["input"].as_str().expect("input"))
+                .unwrap_or_else(|e| panic!("{name}: input is not JSON: {e}"));
+            let bytes = match v.get("kind").and_then(Value::as_str) {
+                Some(kind) => preimage_value(kind_from_str(kind), &input),
+                None => to_canonical_bytes(&input),
+            }
+            .unwrap_or_else(|e| panic!("{name}: encode failed: {e}"));
+
+            assert_eq!(
+                String::from_utf8(bytes.clone()).expect("utf-8"),
+                v["canonical"].as_str().expect("canonical"),
+                "{name}: canonical bytes differ"
+            );
+            assert_eq!(
+                blake3::hash(&bytes).to_hex().as_str(),
+                v["blake3"].as_str().expect("blake3"),
+                "{name}: blake3 differs"
+            );
+        }
+    }
+
+    #[test]
+    fn typed_and_raw_preimages_agree() {
+        let raw = load_json("glyphs/examples/receipt_glyph.orbital.example.json");
+        let typed: ReceiptGlyph = serde_json::from_value(raw.clone()).expect("decode");
+
+        assert_eq!(
+            preimage(&typed).unwrap(),
+            preimage_value(GlyphKind::Receipt, &raw).unwrap()
+        );
+        assert_eq!(
+            content_hash(&typed).unwrap(),
+            content_hash_value(GlyphKind::Receipt, &raw).unwrap()
+        );
+    }
+
+    #[test]
+    fn excluded_fields_do_not_affect_hash() {
+        let raw = load_json("glyphs/examples/receipt_glyph.bore.example.json");
+        let base = content_hash_value(GlyphKind::Receipt, &raw).unwrap();
+
+        for field in excluded_fields(GlyphKind::Receipt) {
+            let mut mutated = raw.clone();
+            mutated[*field] = json!("ffff");
+            assert_eq!(
+                content_hash_value(GlyphKind::Receipt, &mutated).unwrap(),
+                base,
+                "{field} must be excluded from the receipt preimage"
+            );
+        }
+
+        let mut mutated = raw.clone();
+        mutated["meters_advanced"] = json!(12.501);
+        assert_ne!(
+            content_hash_value(GlyphKind::Receipt, &mutated).unwrap(),
+            base,
+            "payload changes must change the hash"
+        );
+    }
+
+    #[test]
+    fn integral_floats_and_integers_encode_identically() {
+        let as_float: Value = serde_json::from_str(r#"{"meters_advanced":10.0}"#).unwrap();
+        let as_int: Value = serde_json::from_str(r#"{"meters_advanced":10}"#).unwrap();
+        assert_eq!(
+            to_canonical_bytes(&as_float).unwrap(),
+            to_canonical_bytes(&as_int).unwrap()
+        );
+    }
+}
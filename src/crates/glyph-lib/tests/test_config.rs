@@ -0,0 +1,259 @@
+#[cfg(test)]
+mod test_config {
+    use glyph_lib::config::loader::{ConfigSources, ConfigViolation, SwarmConfig, REQUIRED_FILES};
+    use glyph_lib::config::pinning::fill_root_hash;
+    use glyph_lib::config::services::NatsMaxAge;
+    use glyph_lib::testing::config_sources;
+    use glyph_lib::{Daemon, GlyphError, Guardian};
+    use std::path::PathBuf;
+
+    fn config_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../..")
+            .join("config")
+    }
+
+    fn shipped() -> ConfigSources {
+        ConfigSources::read_dir(&config_dir()).expect("config/ must be readable")
+    }
+
+    fn violations(sources: &ConfigSources) -> Vec<ConfigViolation> {
+        match SwarmConfig::from_sources(sources) {
+            Err(GlyphError::ConfigViolations { violations }) => violations,
+            other => panic!("expected violations, got {other:?}"),
+        }
+    }
+
+    /// Edits a file and re-pins its root hash header, as a commit would.
+    fn edit(sources: &mut ConfigSources, file: &str, from: &str, to: &str) {
+        let text = sources.get(file).unwrap().to_string();
+        assert!(text.contains(from), "{file} does not contain {from:?}");
+        sources.insert(file, fill_root_hash(&text.replace(from, to)));
+    }
+
+    #[test]
+    fn shipped_config_reports_missing_tenants_and_phases() {
+        let sources = shipped();
+        for file in REQUIRED_FILES {
+            assert!(sources.get(file).is_some(), "{file} missing");
+        }
+        assert!(sources.get("env.example").is_none());
+
+        let found = violations(&sources);
+        for (file, tenant_id) in [
+            ("grpc.toml", "xai-memphis-01"),
+            ("grpc.toml", "spacex-orbit-01"),
+            ("ipfs.yaml", "xai-memphis-01"),
+            ("ledger.sqlite.toml", "spacex-orbit-01"),
+            ("ledger.rocksdb.toml", "xai-memphis-01"),
+        ] {
+            assert!(
+                found
+                    .iter()
+                    .any(|v| v.file == file && v.reason.contains(tenant_id)),
+                "{file} names {tenant_id} without reporting it: {found:?}"
+            );
+        }
+        assert!(found
+            .iter()
+            .any(|v| v.file == "orchestrator/phase_map.yaml"));
+    }
+
+    #[test]
+    fn config_with_fixtures_loads() {
+        let config = SwarmConfig::from_sources(&config_sources()).expect("config/ must load");
+        assert_eq!(
+            config.slo.digital_twin_groot.min_entanglement_correlation,
+            0.707
+        );
+        assert_eq!(config.grpc.server.port, 50051);
+        assert_eq!(
+            config.grpc.service["internal.Orchestrator"].port,
+            Some(50052)
+        );
+        assert_eq!(
+            config.nats.streams["daemon.status"].max_age,
+            NatsMaxAge::Duration("168h".into())
+        );
+        assert_eq!(
+            config.nats.streams["glyph.anchor"].max_age,
+            NatsMaxAge::Seconds(0)
+        );
+        assert_eq!(config.nats.consumer["drax-monitor"].stream, "daemon.status");
+        assert_eq!(config.ipfs.pin_targets, config.arweave.anchor_targets);
+        assert_eq!(config.ledger_sqlite.tenant.len(), 2);
+        assert_eq!(
+            config.ledger_rocksdb.tenant["spacex-orbit-01"].prefix,
+            "spacex-orbit-01:"
+        );
+        assert_eq!(
+            config.guardians[&Guardian::Groot].daemon,
+            Daemon::DigitalTwinGroot
+        );
+        assert_eq!(config.quorum.required_weight(), 52);
+        assert_eq!(
+            config.phase_map.phases.keys().copied().collect::<Vec<_>>(),
+            (1..=7).collect::<Vec<u8>>()
+        );
+        assert_eq!(config.routing_rules.rules["anomaly.critical.>"].len(), 2);
+
+        assert_eq!(
+            config
+                .tenants
+                .keys()
+                .map(String::as_str)
+                .collect::<Vec<_>>(),
+            [
+                "acme-logistics-01",
+                "default-template",
+                "spacex-orbit-01",
+                "xai-memphis-01"
+            ]
+        );
+        for tenant_id in &config.grpc.auth.allowed_tenants {
+            let tenant = config.tenant(tenant_id).unwrap();
+            assert_eq!(tenant.ipfs_path, config.ipfs.paths[tenant_id]);
+            assert_eq!(
+                tenant.ledger_prefix,
+                config.ledger_rocksdb.tenant[tenant_id].prefix
+            );
+        }
+        let acme = config.tenant("acme-logistics-01").unwrap();
+        assert!(!acme.access().allowed_guardians.contains(&Guardian::Gamora));
+    }
+
+    #[test]
+    fn every_violation_is_reported_together() {
+        let mut sources = config_sources();
+        sources.remove("tenants/tenant_spacex_orbit_01.yaml");
+        edit(
+            &mut sources,
+            "arweave.yaml",
+            "  - \"ops/manifests/spacex_stage1_manifest.yaml\"",
+            "  - \"ops/manifests/spacex_stage2_manifest.yaml\"",
+        );
+        edit(
+            &mut sources,
+            "orchestrator/phase_map.yaml",
+            "      - \"mantis-community\"\n",
+            "",
+        );
+        edit(&mut sources, "slo.toml", "max_drift_percent", "max_drift");
+        sources.remove("nats.toml");
+
+        let mut found: Vec<_> = violations(&sources)
+            .into_iter()
+            .map(|v| (v.file, v.reason))
+            .collect();
+        found.sort();
+        let files: Vec<_> = found.iter().map(|(f, _)| f.as_str()).collect();
+        assert_eq!(
+            files,
+            [
+                "arweave.yaml",
+                "grpc.toml",
+                "ipfs.yaml",
+                "ipfs.yaml",
+                "ledger.rocksdb.toml",
+                "ledger.sqlite.toml",
+                "nats.toml",
+                "orchestrator/routing_rules.yaml",
+                "slo.toml",
+            ]
+        );
+        assert_eq!(
+            found[1].1,
+            "allowed tenant spacex-orbit-01 has no tenants/*.yaml"
+        );
+        assert_eq!(
+            found[2].1,
+            "path for tenant spacex-orbit-01 has no tenants/*.yaml"
+        );
+        assert_eq!(found[5].1, "tenant spacex-orbit-01 has no tenants/*.yaml");
+        assert_eq!(found[6].1, "missing");
+        assert!(found[7]
+            .1
+            .starts_with("anomaly.critical.> routes to mantis-community"));
+        assert!(found[8].1.contains("max_drift"));
+
+        let message = SwarmConfig::from_sources(&sources).unwrap_err().to_string();
+        assert!(message.starts_with("9 config violation(s): "));
+    }
+
+    #[test]
+    fn phase_map_must_cover_every_tenant() {
+        let mut sources = config_sources();
+        edit(
+            &mut sources,
+            "orchestrator/phase_map.yaml",
+            "  7:\n    name: \"harden\"",
+            "  8:\n    name: \"harden\"",
+        );
+        let found: Vec<_> = violations(&sources)
+            .into_iter()
+            .map(|v| (v.file, v.reason))
+            .collect();
+        let phase_map = "orchestrator/phase_map.yaml".to_string();
+        assert_eq!(
+            found,
+            [
+                (
+                    phase_map.clone(),
+                    "phase 8 follows a gap; phase 7 is missing".to_string()
+                ),
+                (
+                    phase_map.clone(),
+                    "tenant default-template is allowed phase 7, which is not mapped".to_string()
+                ),
+                (
+                    phase_map.clone(),
+                    "tenant spacex-orbit-01 is allowed phase 7, which is not mapped".to_string()
+                ),
+                (
+                    phase_map,
+                    "tenant xai-memphis-01 is allowed phase 7, which is not mapped".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn tenant_files_are_checked() {
+        let mut sources = config_sources();
+        let acme = sources
+            .get("tenants/tenant_example_acme.yaml")
+            .unwrap()
+            .to_string();
+        sources.insert("tenants/tenant_acme_copy.yaml", acme);
+        edit(
+            &mut sources,
+            "tenants/tenant_default.yaml",
+            "  - \"Kraglin\"",
+            "  - \"Ronan\"",
+        );
+        let found = violations(&sources);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].file, "tenants/tenant_default.yaml");
+        assert_eq!(
+            found[1],
+            ConfigViolation {
+                file: "tenants/tenant_example_acme.yaml".into(),
+                reason: "tenant_id acme-logistics-01 is also used by tenants/tenant_acme_copy.yaml"
+                    .into(),
+            }
+        );
+
+        let empty = ConfigSources::new();
+        let found = violations(&empty);
+        assert_eq!(found.len(), REQUIRED_FILES.len() + 1);
+        assert!(found.iter().any(|v| v.file == "tenants/"));
+    }
+
+    #[test]
+    fn unreadable_directory_fails_fast() {
+        assert!(matches!(
+            SwarmConfig::load(&config_dir().join("no-such-dir")),
+            Err(GlyphError::InvalidConfig { .. })
+        ));
+    }
+}
@@ -0,0 +1,211 @@
+#[cfg(test)]
+mod test_config_pinning {
+    use glyph_lib::anchors::merkle::hash_to_hex;
+    use glyph_lib::config::loader::{ConfigSources, SwarmConfig};
+    use glyph_lib::config::pinning::{
+        canonical_config_text, check_root_hash, config_file_hash, config_manifest, fill_root_hash,
+        header_root_hash, manifest_root, verify_pinned, STARTUP_PINNED, UNFILLED_ROOT_HASH,
+    };
+    use glyph_lib::testing::config_sources;
+    use glyph_lib::{AnchorGlyph, GlyphError};
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn repo_root() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../..")
+    }
+
+    fn sources() -> ConfigSources {
+        ConfigSources::read_dir(&repo_root().join("config")).expect("config/ readable")
+    }
+
+    fn pinned_anchor(sources: &ConfigSources) -> AnchorGlyph {
+        let path = repo_root().join("glyphs/examples/anchor_glyph.example.json");
+        let mut anchor: AnchorGlyph =
+            serde_json::from_str(&fs::read_to_string(path).unwrap()).expect("anchor json");
+        anchor
+            .extensions
+            .get_or_insert_with(Default::default)
+            .config = Some(config_manifest(sources));
+        anchor
+    }
+
+    const UNFILLED: &str =
+        "# config/demo.toml\n# Root hash: [BLAKE3 to be filled on commit]\n\n[server]\nport = 1\n";
+
+    #[test]
+    fn hash_covers_everything_but_the_root_hash_line() {
+        let base = config_file_hash(UNFILLED);
+        assert_eq!(
+            canonical_config_text(UNFILLED),
+            "# config/demo.toml\n\n[server]\nport = 1\n"
+        );
+        assert_eq!(
+            config_file_hash(&UNFILLED.replace(UNFILLED_ROOT_HASH, &hash_to_hex(&[7; 32]))),
+            base
+        );
+        for changed in [
+            UNFILLED.replace("# config/demo.toml", "# config/demo.toml (v2)"),
+            format!("# comment\n{UNFILLED}"),
+            UNFILLED.replace('\n', "\r\n"),
+            UNFILLED.replace("port = 1", "port = 1   "),
+            format!("{UNFILLED}\n"),
+            UNFILLED.trim_end().to_string(),
+            UNFILLED.replace("\n\n", "\n"),
+            UNFILLED.replace("port = 1", "port = 2"),
+            format!("{UNFILLED}# Root hash: {}\n", hash_to_hex(&base)),
+        ] {
+            assert_ne!(config_file_hash(&changed), base, "{changed:?}");
+        }
+    }
+
+    #[test]
+    fn filling_the_header_pins_the_file() {
+        assert_eq!(header_root_hash(UNFILLED).unwrap(), None);
+        assert!(UNFILLED.contains(UNFILLED_ROOT_HASH));
+
+        let filled = fill_root_hash(UNFILLED);
+        let hash = config_file_hash(UNFILLED);
+        assert_eq!(config_file_hash(&filled), hash);
+        assert_eq!(header_root_hash(&filled).unwrap(), Some(hash));
+        assert_eq!(fill_root_hash(&filled), filled);
+        assert!(filled.contains(&format!("# Root hash: {}\n", hash_to_hex(&hash))));
+        check_root_hash("demo.toml", &filled).unwrap();
+
+        let crlf = UNFILLED.replace('\n', "\r\n");
+        let filled_crlf = fill_root_hash(&crlf);
+        assert!(filled_crlf.contains(&format!(
+            "# Root hash: {}\r\n",
+            hash_to_hex(&config_file_hash(&crlf))
+        )));
+
+        let below = format!("\n# Root hash: {UNFILLED_ROOT_HASH}\n");
+        assert_eq!(fill_root_hash(&below), below);
+        assert_eq!(header_root_hash(&below).unwrap(), None);
+
+        let edited = filled.replace("port = 1", "port = 2");
+        assert!(matches!(
+            check_root_hash("demo.toml", &edited),
+            Err(GlyphError::ConfigDrift { ref file, .. }) if file == "demo.toml"
+        ));
+        assert!(matches!(
+            check_root_hash("demo.toml", &UNFILLED.replace(UNFILLED_ROOT_HASH, "abc")),
+            Err(GlyphError::InvalidConfig { .. })
+        ));
+    }
+
+    #[test]
+    fn shipped_headers_are_filled_and_current() {
+        let sources = sources();
+        let mut pinned = 0;
+        for (file, text) in sources.iter() {
+            if text.contains("# Root hash:") && !text.contains(UNFILLED_ROOT_HASH) {
+                assert_eq!(
+                    header_root_hash(text).unwrap(),
+                    Some(config_file_hash(text)),
+                    "{file} header is stale"
+                );
+                pinned += 1;
+            }
+        }
+        // phase_map.yaml is truncated upstream and stays unpinned until it
+        // is repaired; the loader refuses it, so `--fill` never pins it.
+        assert_eq!(pinned, 4);
+        assert!(sources
+            .get("orchestrator/phase_map.yaml")
+            .unwrap()
+            .contains(UNFILLED_ROOT_HASH));
+        for file in STARTUP_PINNED {
+            assert!(header_root_hash(sources.get(file).unwrap())
+                .unwrap()
+                .is_some());
+        }
+    }
+
+    #[test]
+    fn loader_rejects_stale_headers() {
+        let mut sources = config_sources();
+        let slo = sources
+            .get("slo.toml")
+            .unwrap()
+            .replace("max_drift_percent = 0.02", "max_drift_percent = 0.2");
+        sources.insert("slo.toml", slo);
+        let violations = match SwarmConfig::from_sources(&sources) {
+            Err(GlyphError::ConfigViolations { violations }) => violations,
+            other => panic!("expected violations, got {other:?}"),
+        };
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].file, "slo.toml");
+        assert!(violations[0].reason.contains("drifted"));
+    }
+
+    #[test]
+    fn manifest_commits_to_every_file() {
+        let sources = sources();
+        let manifest = config_manifest(&sources);
+        assert_eq!(manifest.files.len(), sources.iter().count());
+        assert_eq!(
+            manifest.root,
+            hash_to_hex(&manifest_root(&manifest.files).unwrap())
+        );
+        assert_eq!(
+            manifest.files["slo.toml"],
+            hash_to_hex(&config_file_hash(sources.get("slo.toml").unwrap()))
+        );
+
+        let encoded = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(
+            serde_json::from_value::<_>(encoded).ok(),
+            Some(manifest.clone())
+        );
+
+        let mut renamed = manifest.files.clone();
+        let hash = renamed.remove("nats.toml").unwrap();
+        renamed.insert("nats2.toml".into(), hash);
+        assert_ne!(
+            manifest_root(&renamed).unwrap(),
+            manifest_root(&manifest.files).unwrap()
+        );
+    }
+
+    #[test]
+    fn daemons_refuse_drifted_config() {
+        let sources = config_sources();
+        let anchor = pinned_anchor(&sources);
+        verify_pinned(&anchor, &sources, &STARTUP_PINNED).expect("config matches its anchor");
+
+        let mut drifted = sources.clone();
+        let rules = drifted
+            .get("orchestrator/routing_rules.yaml")
+            .unwrap()
+            .replace("drax-critical", "drax-fast");
+        drifted.insert("orchestrator/routing_rules.yaml", fill_root_hash(&rules));
+        SwarmConfig::from_sources(&drifted).expect("re-pinned edit still loads");
+        assert!(matches!(
+            verify_pinned(&anchor, &drifted, &STARTUP_PINNED),
+            Err(GlyphError::ConfigDrift { ref file, .. }) if file == "orchestrator/routing_rules.yaml"
+        ));
+
+        let mut forged = anchor.clone();
+        let manifest = forged.extensions.as_mut().unwrap().config.as_mut().unwrap();
+        manifest.files.insert(
+            "orchestrator/routing_rules.yaml".into(),
+            hash_to_hex(&config_file_hash(&rules)),
+        );
+        assert!(matches!(
+            verify_pinned(&forged, &drifted, &STARTUP_PINNED),
+            Err(GlyphError::HashMismatch { .. })
+        ));
+
+        assert!(matches!(
+            verify_pinned(&anchor, &sources, &["redis.toml"]),
+            Err(GlyphError::ConfigNotPinned { .. })
+        ));
+        let mut bare = anchor;
+        bare.extensions.as_mut().unwrap().config = None;
+        assert!(matches!(
+            verify_pinned(&bare, &sources, &STARTUP_PINNED),
+            Err(GlyphError::ConfigNotPinned { .. })
+        ));
+    }
+}
@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod test_entanglement {
+    use glyph_lib::anchors::entanglement::{check_prediction, EntanglementGlyph, SimulationSource};
+    use glyph_lib::anchors::receipt_payload::ReceiptPayload;
+    use glyph_lib::config::slo::SloConfig;
+    use glyph_lib::{AnchorGlyph, GlyphError, ReceiptGlyph, TargetContext};
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn repo_root() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../..")
+    }
+
+    fn read(relative: &str) -> String {
+        let path = repo_root().join(relative);
+        fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()))
+    }
+
+    fn slo() -> SloConfig {
+        SloConfig::from_toml(&read("config/slo.toml")).expect("slo.toml must load")
+    }
+
+    fn receipt() -> ReceiptGlyph {
+        serde_json::from_str(&read(
+            "glyphs/examples/receipt_glyph.entanglement.example.json",
+        ))
+        .expect("receipt json")
+    }
+
+    fn anchor() -> AnchorGlyph {
+        serde_json::from_str(&read("glyphs/examples/anchor_glyph.example.json"))
+            .expect("anchor json")
+    }
+
+    fn source() -> SimulationSource {
+        SimulationSource {
+            scenario_id: "memphis-colossus-starlink-5508".into(),
+            sim_backend: "offline-qutip-model".into(),
+            model_hash: "8e4efebd1fc35334609591f13a17ad115fd5859befbe6f9a187c31d81e35f980".into(),
+        }
+    }
+
+    #[test]
+    fn slo_toml_loads_strictly() {
+        let slo = slo();
+        assert_eq!(slo.digital_twin_groot.min_entanglement_correlation, 0.707);
+        assert_eq!(
+            slo.digital_twin_groot.min_predicted_latency_negation_ms,
+            1.8
+        );
+        assert_eq!(slo.nebula_guard.max_drift_percent, 0.02);
+
+        let renamed = read("config/slo.toml")
+            .replace("min_entanglement_correlation", "min_entanglement_corr");
+        assert!(matches!(
+            SloConfig::from_toml(&renamed),
+            Err(GlyphError::InvalidConfig { .. })
+        ));
+    }
+
+    #[test]
+    fn receipt_round_trips_through_the_glyph() {
+        let receipt = receipt();
+        let glyph = EntanglementGlyph::from_receipt(&receipt, source()).unwrap();
+        assert_eq!(glyph.bell_correlation, 0.82);
+        assert_eq!(glyph.negation_ms, 1.9);
+        assert_eq!(glyph.source(), source());
+        glyph.validate(&slo()).expect("example meets the SLOs");
+
+        let payload = ReceiptPayload::from(&glyph);
+        assert_eq!(payload.to_details().unwrap(), receipt.details);
+        assert_eq!(payload, ReceiptPayload::of(&receipt).unwrap());
+
+        let orbital: ReceiptGlyph =
+            serde_json::from_str(&read("glyphs/examples/receipt_glyph.orbital.example.json"))
+                .unwrap();
+        assert!(EntanglementGlyph::from_receipt(&orbital, source()).is_err());
+    }
+
+    #[test]
+    fn anchor_payload_and_extension_agree() {
+        let glyph = EntanglementGlyph::from_receipt(&receipt(), source()).unwrap();
+
+        let mut anchor = anchor();
+        anchor.context = TargetContext::EntanglementPrediction;
+        anchor.payload = Some(glyph.to_payload().unwrap());
+        assert_eq!(EntanglementGlyph::from_anchor(&anchor).unwrap(), glyph);
+        anchor.payload = None;
+        assert!(matches!(
+            EntanglementGlyph::from_anchor(&anchor),
+            Err(GlyphError::InvalidAnchorPayload { .. })
+        ));
+
+        let extension = glyph.to_extension().unwrap();
+        assert_eq!(
+            EntanglementGlyph::from_extension(&extension).unwrap(),
+            glyph
+        );
+    }
+
+    #[test]
+    fn slo_rejects_weak_predictions() {
+        let slo = slo();
+        let anchored = EntanglementGlyph::from_anchor(&anchor()).unwrap();
+        assert_eq!(anchored.negation_ms, 0.9);
+        assert!(matches!(
+            anchored.validate(&slo),
+            Err(GlyphError::SloBreached {
+                objective: "digital-twin-groot.min_predicted_latency_negation_ms",
+                ..
+            })
+        ));
+
+        let mut glyph = EntanglementGlyph::from_receipt(&receipt(), source()).unwrap();
+        glyph.bell_correlation = 0.70;
+        assert!(matches!(
+            glyph.validate(&slo),
+            Err(GlyphError::SloBreached {
+                objective: "digital-twin-groot.min_entanglement_correlation",
+                ..
+            })
+        ));
+        assert!(check_prediction(&glyph.prediction(), &slo).is_err());
+
+        glyph.bell_correlation = 0.9;
+        glyph.negation_ms = f64::NAN;
+        assert!(glyph.validate(&slo).is_err());
+    }
+
+    #[test]
+    fn structural_rules_precede_the_slo() {
+        let slo = slo();
+        let glyph = EntanglementGlyph::from_receipt(&receipt(), source()).unwrap();
+
+        let mut unnormalised = glyph.clone();
+        unnormalised.bell_state = Some([0.9, 0.0, 0.0, 0.9]);
+        assert!(matches!(
+            unnormalised.validate(&slo),
+            Err(GlyphError::InvalidAnchorPayload { .. })
+        ));
+
+        let mut bad_hash = glyph;
+        bad_hash.model_hash = "not-a-hash".into();
+        assert!(matches!(
+            bad_hash.validate(&slo),
+            Err(GlyphError::InvalidHash { .. })
+        ));
+    }
+}
@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod test_glyph_ids {
+    use glyph_lib::hashing::canonical::content_hash;
+    use glyph_lib::hashing::ids::{derive_id, derive_id_value, verify_id, GlyphId};
+    use glyph_lib::{ChainLink, GlyphError, GlyphKind, ReceiptGlyph};
+    use serde_json::{json, Value};
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn load_example(name: &str) -> Value {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../..")
+            .join("glyphs")
+            .join("examples")
+            .join(name);
+        let data = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()));
+        serde_json::from_str(&data)
+            .unwrap_or_else(|e| panic!("failed to parse {}: {e}", path.display()))
+    }
+
+    #[test]
+    fn parses_and_rejects_id_strings() {
+        let id: GlyphId = "receipt-eba65757773c2fbef4805aa5009fc1a5".parse().unwrap();
+        assert_eq!(id.kind(), GlyphKind::Receipt);
+        assert_eq!(id.to_string(), "receipt-eba65757773c2fbef4805aa5009fc1a5");
+
+        let status: GlyphId = "status-00000000000000000000000000000000".parse().unwrap();
+        assert_eq!(status.kind(), GlyphKind::DaemonStatus);
+
+        for bad in [
+            "receipt-spv-orbital-0000",
+            "receipt-EBA65757773C2FBEF4805AA5009FC1A5",
+            "receipt-eba65757773c2fbef4805aa5009fc1a",
+            "glyph-eba65757773c2fbef4805aa5009fc1a5",
+            "eba65757773c2fbef4805aa5009fc1a5",
+        ] {
+            assert!(bad.parse::<GlyphId>().is_err(), "{bad} must be rejected");
+        }
+
+        assert!(
+            GlyphId::parse_as(GlyphKind::Anchor, "intent-eba65757773c2fbef4805aa5009fc1a5")
+                .is_err()
+        );
+        assert_eq!("genesis".parse::<ChainLink>().unwrap(), ChainLink::Genesis);
+        assert!("intent-eba65757773c2fbef4805aa5009fc1a5"
+            .parse::<ChainLink>()
+            .is_err());
+    }
+
+    #[test]
+    fn derivation_is_deterministic_and_domain_separated() {
+        let raw = load_example("receipt_glyph.bore.example.json");
+        let a = derive_id_value(GlyphKind::Receipt, &raw).unwrap();
+        let b = derive_id_value(GlyphKind::Receipt, &raw).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.kind(), GlyphKind::Receipt);
+
+        let as_intent = derive_id_value(GlyphKind::Intent, &raw).unwrap();
+        assert_ne!(
+            a.digest(),
+            as_intent.digest(),
+            "kinds must not share an ID space"
+        );
+
+        let mut other_id = raw.clone();
+        other_id["receipt_id"] = json!("receipt-00000000000000000000000000000000");
+        assert_eq!(
+            derive_id_value(GlyphKind::Receipt, &other_id).unwrap(),
+            a,
+            "the ID must not depend on the ID field itself"
+        );
+
+        let mut moved = raw.clone();
+        moved["meters_advanced"] = json!(13.0);
+        assert_ne!(derive_id_value(GlyphKind::Receipt, &moved).unwrap(), a);
+    }
+
+    #[test]
+    fn verify_id_accepts_derived_and_rejects_forged() {
+        let mut receipt: ReceiptGlyph =
+            serde_json::from_value(load_example("receipt_glyph.orbital.example.json")).unwrap();
+        assert!(matches!(
+            verify_id(&receipt),
+            Err(GlyphError::GlyphIdMismatch { .. })
+        ));
+
+        let before = content_hash(&receipt).unwrap();
+        receipt.receipt_id = derive_id(&receipt).unwrap();
+        verify_id(&receipt).expect("derived ID must verify");
+        assert_ne!(
+            content_hash(&receipt).unwrap(),
+            before,
+            "blake3_hash must commit to the ID"
+        );
+    }
+
+    #[test]
+    fn typed_fields_reject_malformed_ids() {
+        let mut raw = load_example("receipt_glyph.bore.example.json");
+        raw["ref_glyph_id"] = json!("anchor-not-hex");
+        assert!(serde_json::from_value::<ReceiptGlyph>(raw).is_err());
+    }
+}
@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod test_glyph_types {
+    use glyph_lib::{
+        AnchorGlyph, DaemonStatusGlyph, Glyph, GlyphKind, IntentGlyph, ReceiptGlyph,
+        ReceiptResult, ReceiptType, TargetContext,
+    };
+    use serde_json::Value;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn examples_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../..")
+            .join("glyphs")
+            .join("examples")
+    }
+
+    fn load_example(name: &str) -> Value {
+        let path = examples_dir().join(name);
+        let data = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()));
+        serde_json::from_str(&data)
+            .unwrap_or_else(|e| panic!("failed to parse {}: {e}", path.display()))
+    }
+
+    fn assert_round_trip<G: Glyph>(raw: &Value) -> G {
+        let typed: G = serde_json::from_value(raw.clone())
+            .unwrap_or_else(|e| panic!("{:?} example must decode: {e}", G::KIND));
+        let back = serde_json::to_value(&typed).expect("re-encode");
+        assert_eq!(&back, raw, "{:?} must round-trip losslessly", G::KIND);
+        typed
+    }
+
+    #[test]
+    fn intent_example_round_trips() {
+        let raw = load_example("intent_glyph.example.json");
+        let intent: IntentGlyph = assert_round_trip(&raw);
+        assert_eq!(IntentGlyph::KIND, GlyphKind::Intent);
+        assert_eq!(intent.target_context, TargetContext::TunnelBore);
+        assert!(intent.constraints.max_drift_percent.is_some());
+    }
+
+    #[test]
+    fn anchor_example_round_trips() {
+        let raw = load_example("anchor_glyph.example.json");
+        let anchor: AnchorGlyph = assert_round_trip(&raw);
+        assert!(anchor.is_genesis());
+        assert_eq!(anchor.kyber_signature.signatures.len(), 2);
+        let ext = anchor.extensions.expect("example carries extensions");
+        assert_eq!(
+            ext.entanglement.and_then(|e| e.bell_correlation),
+            Some(0.82)
+        );
+        assert_eq!(
+            anchor.payload.expect("payload")["geology_hint"],
+            Value::from("silty_clay"),
+            "unknown payload keys must survive decoding"
+        );
+    }
+
+    #[test]
+    fn daemon_status_example_round_trips() {
+        let raw = load_example("daemon_status_glyph.example.json");
+        let status: DaemonStatusGlyph = assert_round_trip(&raw);
+        assert_eq!(status.slo_compliance.zk_proof_time_ms, None);
+
+        let mut null_anchor = raw.clone();
+        null_anchor["last_anchor_glyph_id"] = Value::Null;
+        let status: DaemonStatusGlyph = assert_round_trip(&null_anchor);
+        assert_eq!(status.last_anchor_glyph_id, Some(None));
+
+        let mut absent = raw.clone();
+        absent
+            .as_object_mut()
+            .expect("object")
+            .remove("last_anchor_glyph_id");
+        let status: DaemonStatusGlyph = assert_round_trip(&absent);
+        assert_eq!(status.last_anchor_glyph_id, None);
+    }
+
+    #[test]
+    fn receipt_examples_round_trip() {
+        let cases = [
+            ("receipt_glyph.bore.example.json", ReceiptType::BoreProgress),
+            ("receipt_glyph.orbital.example.json", ReceiptType::OrbitalTelemetry),
+            ("receipt_glyph.zk_anomaly.example.json", ReceiptType::ZkAnomalyProof),
+            (
+                "receipt_glyph.entanglement.example.json",
+                ReceiptType::EntanglementPrediction,
+            ),
+        ];
+        for (file, expected) in cases {
+            let raw = load_example(file);
+            let receipt: ReceiptGlyph = assert_round_trip(&raw);
+            assert_eq!(receipt.receipt_type, expected, "{file}");
+        }
+
+        let path = examples_dir().join("receipt_glyph.example.jsonl");
+        let data = fs::read_to_string(&path).expect("read receipts jsonl");
+        let mut count = 0;
+        for line in data.lines().filter(|l| !l.trim().is_empty()) {
+            let raw: Value = serde_json::from_str(line).expect("jsonl line");
+            let _: ReceiptGlyph = assert_round_trip(&raw);
+            count += 1;
+        }
+        assert!(count >= 4, "golden JSONL must cover the core receipt types");
+    }
+
+    #[test]
+    fn closed_enums_match_schema_spelling() {
+        assert_eq!(ReceiptType::ALL.len(), 9);
+        for ty in ReceiptType::ALL {
+            assert_eq!(ty.as_str().parse::<ReceiptType>().unwrap(), *ty);
+            assert_eq!(
+                serde_json::to_value(ty).unwrap(),
+                Value::from(ty.as_str())
+            );
+        }
+        assert_eq!(
+            serde_json::from_value::<ReceiptResult>(Value::from("fraud")).unwrap(),
+            ReceiptResult::Fraud
+        );
+        assert!("unknown_type".parse::<ReceiptType>().is_err());
+    }
+}
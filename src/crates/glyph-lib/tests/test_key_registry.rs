@@ -0,0 +1,374 @@
+#[cfg(test)]
+mod test_key_registry {
+    use glyph_lib::builders::anchor::AnchorBuilder;
+    use glyph_lib::builders::intent::IntentBuilder;
+    use glyph_lib::builders::receipt::ReceiptBuilder;
+    use glyph_lib::builders::status::DaemonStatusBuilder;
+    use glyph_lib::hashing::ids::GlyphId;
+    use glyph_lib::pq::envelope::{AlgorithmId, GlyphSigner};
+    use glyph_lib::pq::registry::{KeyRegistry, KeyWindow, RegisteredKey, Signer};
+    use glyph_lib::testing::{approved, bore_payload, guardian_key_id, read_repo_file, MacKey};
+    use glyph_lib::{
+        AnchorGlyph, ChainLink, Daemon, DaemonHealth, GlyphError, GlyphKind, Guardian,
+        IntentConstraints, IntentGlyph, IntentType, ReceiptGlyph, ReceiptResult, ReceiptType,
+        RiskAppetite, SloCompliance, TargetContext,
+    };
+
+    const DEFAULT_TENANT: &str = "default-template";
+    const ACME: &str = "acme-logistics-01";
+    const T0: i64 = 1_764_000_000;
+    const DAY: i64 = 86_400;
+    /// Trusted time the glyphs are seen at; only revocation looks at it.
+    const SEEN: i64 = T0 + 60 * DAY;
+
+    fn decode(_: AlgorithmId, bytes: &[u8]) -> glyph_lib::Result<RegisteredKey> {
+        let key: [u8; 32] = bytes.try_into().map_err(|_| GlyphError::InvalidKey {
+            reason: "mac keys are 32 bytes",
+        })?;
+        Ok(Box::new(MacKey(key)))
+    }
+
+    /// Every Guardian's key from T0, plus rocket-engine's rotation: `-01`
+    /// until day 30, `-02` from day 29, and a revoked `-03`.
+    fn key_file() -> String {
+        let mut yaml = String::from("keys:\n");
+        for guardian in Guardian::ALL {
+            yaml += &format!(
+                "  - owner: {g}\n    key_id: {label}\n    algorithm: dilithium5\n    public_key: \"{pk}\"\n    valid_from: {T0}\n",
+                g = guardian.as_str(),
+                label = guardian_key_id(*guardian),
+                pk = hex::encode(MacKey::of(guardian.as_str()).0),
+            );
+        }
+        for (label, window) in [
+            (
+                "rocket-engine-key-01",
+                format!("valid_until: {}", T0 + 30 * DAY),
+            ),
+            (
+                "rocket-engine-key-02",
+                format!("valid_from: {}", T0 + 29 * DAY),
+            ),
+            ("rocket-engine-key-03", format!("revoked_at: {}", T0)),
+        ] {
+            let from = if window.starts_with("valid_from") {
+                String::new()
+            } else {
+                format!("    valid_from: {T0}\n")
+            };
+            yaml += &format!(
+                "  - owner: rocket-engine\n    key_id: {label}\n    algorithm: dilithium5\n    public_key: \"{}\"\n{from}    {window}\n",
+                hex::encode(MacKey::of(label).0),
+            );
+        }
+        yaml
+    }
+
+    /// The Guardian, swarm and tenant configs, with no keys loaded.
+    fn keyless_registry() -> KeyRegistry {
+        let mut registry = KeyRegistry::from_yaml(
+            &read_repo_file("config/agents/guardians_org.yaml"),
+            &read_repo_file("config/agents/swarm_roles.yaml"),
+        )
+        .unwrap();
+        registry
+            .add_tenant_yaml(&read_repo_file("config/tenants/tenant_default.yaml"))
+            .unwrap();
+        registry
+            .add_tenant_yaml(&read_repo_file("config/tenants/tenant_example_acme.yaml"))
+            .unwrap();
+        registry
+    }
+
+    fn registry() -> KeyRegistry {
+        let mut registry = keyless_registry();
+        assert_eq!(registry.load_keys_yaml(&key_file(), &decode).unwrap(), 12);
+        registry
+    }
+
+    fn intent(tenant: &str, guardian: Guardian) -> IntentGlyph {
+        IntentBuilder::new(
+            tenant,
+            IntentType::InitiateBore,
+            RiskAppetite::Conservative,
+            TargetContext::TunnelBore,
+            IntentConstraints {
+                max_latency_ms: 800,
+                min_entanglement_quality: 0.8,
+                require_zk_proof: false,
+                max_drift_percent: None,
+            },
+            T0 + DAY,
+        )
+        .timestamp(T0 + 60)
+        .build(guardian, &MacKey::of(guardian.as_str()))
+        .unwrap()
+    }
+
+    fn receipt(tenant: &str, at: i64, key: &str) -> ReceiptGlyph {
+        receipt_signed_by(tenant, at, &MacKey::of(key))
+    }
+
+    fn receipt_signed_by(tenant: &str, at: i64, signer: &dyn GlyphSigner) -> ReceiptGlyph {
+        ReceiptBuilder::new(
+            tenant,
+            ReceiptType::BoreProgress,
+            GlyphId::derive(GlyphKind::Intent, b"registry intent"),
+            ReceiptResult::Ok,
+            Daemon::RocketEngine,
+        )
+        .timestamp(at)
+        .detail("segment_id", "memphis-segment-001")
+        .detail("meters_advanced", 1.5)
+        .detail("cutter_head_rpm", 6)
+        .build(signer)
+        .unwrap()
+    }
+
+    fn anchor(tenant: &str, voters: &[Guardian]) -> AnchorGlyph {
+        let receipt = receipt(tenant, T0 + 10, "rocket-engine-key-01");
+        let pending = AnchorBuilder::new(
+            tenant,
+            TargetContext::TunnelBore,
+            ChainLink::Genesis,
+            Daemon::LedgerExplorer,
+        )
+        .timestamp(T0 + 100)
+        .receipts([&receipt])
+        .payload(bore_payload())
+        .prepare()
+        .unwrap();
+        approved(pending.anchor().clone(), voters)
+    }
+
+    #[test]
+    fn configs_map_guardians_daemons_and_tenants() {
+        let registry = registry();
+        let gamora = registry.guardian(Guardian::Gamora).unwrap();
+        assert_eq!(gamora.daemon, Daemon::NebulaGuard);
+        assert!(gamora.signs_intents);
+        assert!(!registry.guardian(Guardian::Nebula).unwrap().signs_intents);
+        assert_eq!(registry.policy().total_weight(), 55);
+
+        let acme = registry.tenant(ACME).unwrap();
+        assert!(acme.allows(Signer::Guardian(Guardian::Rocket)));
+        assert!(!acme.allows(Signer::Guardian(Guardian::Gamora)));
+        assert!(acme.allows(Signer::Daemon(Daemon::RocketEngine)));
+        assert_eq!(
+            "Star-Lord".parse::<Signer>().unwrap(),
+            Signer::Guardian(Guardian::StarLord)
+        );
+        assert_eq!(
+            "spv-api".parse::<Signer>().unwrap(),
+            Signer::Daemon(Daemon::SpvApi)
+        );
+
+        let mut registry = registry;
+        let duplicate = registry.register(
+            Signer::Daemon(Daemon::SpvApi),
+            "spv-key",
+            KeyWindow::from(T0),
+            Box::new(MacKey::of("rocket-engine-key-02")),
+        );
+        assert!(matches!(duplicate, Err(GlyphError::InvalidRegistry { .. })));
+        let empty = registry.register(
+            Signer::Daemon(Daemon::SpvApi),
+            "spv-key",
+            KeyWindow::from(T0).until(T0),
+            Box::new(MacKey::of("spv-key")),
+        );
+        assert!(matches!(empty, Err(GlyphError::InvalidRegistry { .. })));
+    }
+
+    #[test]
+    fn signers_are_scoped_to_the_tenant() {
+        let registry = registry();
+        registry
+            .verify_intent(&intent(DEFAULT_TENANT, Guardian::Gamora), SEEN)
+            .unwrap();
+        registry
+            .verify_intent(&intent(ACME, Guardian::Rocket), SEEN)
+            .unwrap();
+        assert!(matches!(
+            registry.verify_intent(&intent(ACME, Guardian::Gamora), SEEN),
+            Err(GlyphError::SignerNotAllowed { signer, scope })
+                if signer == "Gamora" && scope == "tenant acme-logistics-01"
+        ));
+        assert!(matches!(
+            registry.verify_intent(&intent(DEFAULT_TENANT, Guardian::Nebula), SEEN),
+            Err(GlyphError::SignerNotAllowed { scope, .. }) if scope == "intents"
+        ));
+        assert!(matches!(
+            registry.verify_intent(&intent("unknown-tenant", Guardian::StarLord), SEEN),
+            Err(GlyphError::UnknownTenant { .. })
+        ));
+
+        // Signed by Rocket's key but claiming Groot's authority.
+        let mut forged = intent(DEFAULT_TENANT, Guardian::Rocket);
+        forged.authorized_by = Guardian::Groot;
+        assert!(registry.verify_intent(&forged, SEEN).is_err());
+
+        let weighted = [
+            Guardian::StarLord,
+            Guardian::Gamora,
+            Guardian::Rocket,
+            Guardian::Groot,
+            Guardian::Drax,
+            Guardian::Kraglin,
+            Guardian::Yondu,
+        ];
+        let anchor_ok = anchor(DEFAULT_TENANT, &weighted);
+        let outcome = registry.verify_anchor(&anchor_ok, SEEN).unwrap();
+        assert_eq!(outcome.approvers.len(), 7);
+        let acme_anchor = anchor(ACME, &[Guardian::StarLord, Guardian::Gamora]);
+        assert!(matches!(
+            registry.verify_anchor(&acme_anchor, SEEN),
+            Err(GlyphError::SignerNotAllowed { signer, .. }) if signer == "Gamora"
+        ));
+    }
+
+    #[test]
+    fn keys_rotate_and_revoke_by_glyph_timestamp() {
+        let registry = registry();
+        // Both keys are valid during the one-day overlap.
+        registry
+            .verify_receipt(&receipt(ACME, T0 + 29 * DAY + 1, "rocket-engine-key-01"), SEEN)
+            .unwrap();
+        registry
+            .verify_receipt(&receipt(ACME, T0 + 29 * DAY + 1, "rocket-engine-key-02"), SEEN)
+            .unwrap();
+        registry
+            .verify_receipt(&receipt(ACME, T0 + 40 * DAY, "rocket-engine-key-02"), SEEN)
+            .unwrap();
+
+        let not_active = |r: &ReceiptGlyph| {
+            matches!(
+                registry.verify_receipt(r, SEEN),
+                Err(GlyphError::KeyNotActive { .. })
+            )
+        };
+        assert!(not_active(&receipt(
+            ACME,
+            T0 + 30 * DAY,
+            "rocket-engine-key-01"
+        )));
+        assert!(not_active(&receipt(ACME, T0 + DAY, "rocket-engine-key-02")));
+        assert!(not_active(&receipt(ACME, T0 + DAY, "rocket-engine-key-03")));
+        assert!(not_active(&receipt(ACME, T0 - 1, "rocket-engine-key-01")));
+        assert!(matches!(
+            registry.verify_receipt(&receipt(ACME, T0 + DAY, "unregistered"), SEEN),
+            Err(GlyphError::BadSignature { .. })
+        ));
+
+        // Statuses have no tenant; the Guardian must own the daemon.
+        let status = |guardian: Guardian| {
+            DaemonStatusBuilder::new(
+                Daemon::RocketEngine,
+                guardian,
+                DaemonHealth::Healthy,
+                SloCompliance {
+                    latency_p95_ms: 120.0,
+                    entanglement_quality: 0.9,
+                    zk_proof_time_ms: None,
+                    anomaly_rate_per_hour: 0.0,
+                },
+            )
+            .timestamp(T0 + DAY)
+            .build(&MacKey::of("rocket-engine-key-01"))
+            .unwrap()
+        };
+        registry.verify_status(&status(Guardian::Rocket), SEEN).unwrap();
+        assert!(matches!(
+            registry.verify_status(&status(Guardian::Drax), SEEN),
+            Err(GlyphError::SignerNotAllowed { .. })
+        ));
+
+        // A scoped view hides keys outside their window.
+        use glyph_lib::pq::envelope::EmitterKeyring;
+        let old = GlyphSigner::key_id(&MacKey::of("rocket-engine-key-01"));
+        let early = registry.scoped(ACME, T0 + DAY).unwrap();
+        let late = registry.scoped(ACME, T0 + 31 * DAY).unwrap();
+        assert!(early.emitter_key(Daemon::RocketEngine, &old).is_some());
+        assert!(late.emitter_key(Daemon::RocketEngine, &old).is_none());
+    }
+
+    #[test]
+    fn revocation_is_checked_at_the_trusted_time() {
+        let mut registry = registry();
+        registry
+            .register(
+                Signer::Daemon(Daemon::RocketEngine),
+                "rocket-engine-key-04",
+                KeyWindow::from(T0).revoked_at(T0 + 10 * DAY),
+                Box::new(MacKey::of("rocket-engine-key-04")),
+            )
+            .unwrap();
+        // Signed inside the window and seen before the revocation.
+        let receipt = receipt(ACME, T0 + DAY, "rocket-engine-key-04");
+        registry.verify_receipt(&receipt, T0 + DAY + 5).unwrap();
+        // The same timestamp on a glyph first seen after it: backdated.
+        assert!(matches!(
+            registry.verify_receipt(&receipt, T0 + 10 * DAY),
+            Err(GlyphError::KeyNotActive { at, .. }) if at == T0 + 10 * DAY
+        ));
+
+        use glyph_lib::pq::envelope::EmitterKeyring;
+        let key_id = GlyphSigner::key_id(&MacKey::of("rocket-engine-key-04"));
+        let scoped = registry.scoped(ACME, T0 + DAY).unwrap();
+        assert!(scoped.emitter_key(Daemon::RocketEngine, &key_id).is_some());
+        assert!(scoped
+            .seen_at(T0 + 11 * DAY)
+            .emitter_key(Daemon::RocketEngine, &key_id)
+            .is_none());
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn dilithium5_keys_load_and_verify() {
+        use glyph_lib::pq::dilithium::{DilithiumKeypair, DilithiumPublicKey};
+        use glyph_lib::pq::envelope::SignatureField;
+
+        fn decode(algorithm: AlgorithmId, bytes: &[u8]) -> glyph_lib::Result<RegisteredKey> {
+            assert_eq!(algorithm, AlgorithmId::Dilithium5);
+            Ok(Box::new(DilithiumPublicKey::from_bytes(bytes)?))
+        }
+
+        fn key_file(public_key: &[u8]) -> String {
+            format!(
+                "keys:\n  - owner: rocket-engine\n    key_id: rocket-engine-key-01\n    algorithm: dilithium5\n    public_key: \"{}\"\n    valid_from: {T0}\n",
+                hex::encode(public_key),
+            )
+        }
+
+        let rocket = DilithiumKeypair::generate();
+        let mut registry = keyless_registry();
+        assert_eq!(
+            registry
+                .load_keys_yaml(&key_file(rocket.public_key().as_bytes()), &decode)
+                .unwrap(),
+            1
+        );
+
+        let signed = receipt_signed_by(ACME, T0 + DAY, &rocket);
+        match SignatureField::parse(&signed.kyber_signature).unwrap() {
+            SignatureField::Envelope(env) => {
+                assert_eq!(env.key_id, GlyphSigner::key_id(&rocket));
+                assert_eq!(env.signature.len(), AlgorithmId::Dilithium5.signature_len());
+            }
+            SignatureField::Legacy(_) => panic!("builders never emit stub signatures"),
+        }
+        registry.verify_receipt(&signed, SEEN).unwrap();
+
+        let forged = receipt_signed_by(ACME, T0 + DAY, &DilithiumKeypair::generate());
+        assert!(matches!(
+            registry.verify_receipt(&forged, SEEN),
+            Err(GlyphError::BadSignature { .. })
+        ));
+
+        // A MAC key is not a Dilithium5 public key.
+        assert!(matches!(
+            keyless_registry().load_keys_yaml(&key_file(&MacKey::of("rocket").0), &decode),
+            Err(GlyphError::InvalidKey { .. })
+        ));
+    }
+}
@@ -0,0 +1,138 @@
+#[cfg(test)]
+mod test_merkle {
+    use glyph_lib::anchors::merkle::{
+        empty_root, hash_to_hex, leaf_hash, node_hash, MerkleHash, MerkleProof, MerkleTree,
+    };
+    use glyph_lib::ReceiptGlyph;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn items(n: usize) -> Vec<MerkleHash> {
+        (0..n)
+            .map(|i| *blake3::hash(format!("receipt-{i}").as_bytes()).as_bytes())
+            .collect()
+    }
+
+    fn load_receipts() -> Vec<ReceiptGlyph> {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../..")
+            .join("glyphs")
+            .join("examples")
+            .join("receipt_glyph.example.jsonl");
+        let data = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()));
+        data.lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| serde_json::from_str(l).expect("receipt line"))
+            .collect()
+    }
+
+    #[test]
+    fn leaves_and_nodes_are_domain_separated() {
+        let [a, b]: [MerkleHash; 2] = items(2).try_into().unwrap();
+        let mut concat = Vec::new();
+        concat.extend_from_slice(&a);
+        concat.extend_from_slice(&b);
+        assert_ne!(leaf_hash(&concat), node_hash(&a, &b));
+        assert_ne!(
+            MerkleTree::from_items(Vec::<[u8; 32]>::new()).root(),
+            leaf_hash(&[])
+        );
+        assert_eq!(
+            MerkleTree::from_items(Vec::<[u8; 32]>::new()).root(),
+            empty_root()
+        );
+    }
+
+    #[test]
+    fn odd_nodes_are_promoted_not_duplicated() {
+        let v = items(3);
+        let three = MerkleTree::from_items(&v);
+        let dup = MerkleTree::from_items([&v[0], &v[1], &v[2], &v[2]]);
+        assert_ne!(three.root(), dup.root());
+
+        let l: Vec<MerkleHash> = v.iter().map(|i| leaf_hash(i)).collect();
+        assert_eq!(three.root(), node_hash(&node_hash(&l[0], &l[1]), &l[2]));
+    }
+
+    #[test]
+    fn single_proofs_verify_for_every_leaf() {
+        for n in 1..=17 {
+            let tree = MerkleTree::from_items(items(n));
+            let root = tree.root();
+            for (i, leaf) in tree.leaves().iter().enumerate() {
+                let proof = tree.proof(i).unwrap();
+                assert!(proof.verify(&root, leaf), "n={n} i={i}");
+
+                let embedded = proof.to_embedded();
+                let back = MerkleProof::from_embedded(&embedded, n as u64).unwrap();
+                assert_eq!(back, proof);
+
+                if n > 1 {
+                    let other = tree.leaves()[(i + 1) % n];
+                    assert!(!proof.verify(&root, &other), "n={n} i={i} wrong leaf");
+                    let mut wrong_index = proof.clone();
+                    wrong_index.leaf_index = ((i + 1) % n) as u64;
+                    assert!(!wrong_index.verify(&root, leaf), "n={n} i={i} index");
+                    if !proof.siblings.is_empty() {
+                        let mut tampered = proof.clone();
+                        tampered.siblings[0][0] ^= 1;
+                        assert!(!tampered.verify(&root, leaf), "n={n} i={i} sibling");
+                    }
+                }
+            }
+            assert!(tree.proof(n).is_err());
+        }
+    }
+
+    #[test]
+    fn multiproofs_are_compact_and_verify() {
+        let tree = MerkleTree::from_items(items(11));
+        let root = tree.root();
+        let pick = |idx: &[u64]| -> Vec<(u64, MerkleHash)> {
+            idx.iter()
+                .map(|&i| (i, tree.leaves()[i as usize]))
+                .collect()
+        };
+
+        let all: Vec<usize> = (0..11).collect();
+        let full = tree.multiproof(&all).unwrap();
+        assert!(full.hashes.is_empty(), "proving every leaf needs no hashes");
+        assert!(full.verify(&root, &pick(&(0..11).collect::<Vec<_>>())));
+
+        let proof = tree.multiproof(&[7, 2, 3, 10, 2]).unwrap();
+        assert_eq!(proof.leaf_indices, vec![2, 3, 7, 10]);
+        let separate: usize = [2, 3, 7, 10]
+            .iter()
+            .map(|&i| tree.proof(i).unwrap().siblings.len())
+            .sum();
+        assert!(proof.hashes.len() < separate);
+        assert!(proof.verify(&root, &pick(&[2, 3, 7, 10])));
+
+        let mut wrong = pick(&[2, 3, 7, 10]);
+        wrong[1].1 = tree.leaves()[4];
+        assert!(!proof.verify(&root, &wrong));
+        assert!(!proof.verify(&root, &pick(&[2, 3, 7])));
+        assert!(tree.multiproof(&[11]).is_err());
+        assert!(tree.multiproof(&[]).is_err());
+    }
+
+    #[test]
+    fn receipt_batches_embed_hex_proofs() {
+        let receipts = load_receipts();
+        let tree = MerkleTree::from_receipts(&receipts).unwrap();
+        let root = tree.root();
+        for (i, receipt) in receipts.iter().enumerate() {
+            let embedded = tree.proof(i).unwrap().to_embedded();
+            assert_eq!(embedded.leaf_index, i as u64);
+            assert!(embedded.siblings.iter().all(|s| s.len() == 64));
+            let proof = MerkleProof::from_embedded(&embedded, receipts.len() as u64).unwrap();
+            assert!(proof.verify_receipt(&root, receipt));
+        }
+        assert_eq!(hash_to_hex(&root).len(), 64);
+
+        let mut bad = receipts[0].clone();
+        bad.blake3_hash = "not-a-digest".into();
+        assert!(MerkleTree::from_receipts([&bad]).is_err());
+    }
+}
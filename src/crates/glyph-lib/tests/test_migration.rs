@@ -0,0 +1,185 @@
+#[cfg(test)]
+mod test_migration {
+    use glyph_lib::hashing::canonical::content_hash_value;
+    use glyph_lib::hashing::ids::{derive_id_value, verify_id};
+    use glyph_lib::versioning::migrate::{negotiate, Migration, MigrationRegistry};
+    use glyph_lib::{Daemon, GlyphError, GlyphKind, JsonMap, ReceiptGlyph, Result, SchemaVersion};
+    use serde_json::{json, Value};
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn rename(mut map: JsonMap, from: &str, to: &str) -> Result<JsonMap> {
+        if let Some(v) = map.remove(from) {
+            map.insert(to.into(), v);
+        }
+        Ok(map)
+    }
+
+    /// Hypothetical 0.9 receipts named the emitting daemon `emitter`.
+    fn receipt_0_9() -> Migration {
+        Migration {
+            kind: GlyphKind::Receipt,
+            from: "0.9",
+            to: "1.0",
+            up: |m| rename(m, "emitter", "emitted_by"),
+            down: |m| rename(m, "emitted_by", "emitter"),
+        }
+    }
+
+    fn registry() -> MigrationRegistry {
+        let mut registry = MigrationRegistry::builtin();
+        registry.register(receipt_0_9()).unwrap();
+        registry
+    }
+
+    fn receipts() -> Vec<Value> {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../..")
+            .join("glyphs/examples/receipt_glyph.example.jsonl");
+        let data = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()));
+        data.lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| serde_json::from_str(l).expect("receipt json"))
+            .collect()
+    }
+
+    /// Re-writes a 1.0 fixture as a 0.9 emitter would have stored it.
+    fn stored_at_0_9(registry: &MigrationRegistry, raw: Value) -> Value {
+        let mut old = registry.downcast(GlyphKind::Receipt, raw, "0.9").unwrap();
+        assert_eq!(old["version"], json!("0.9"));
+        assert!(old.get("emitted_by").is_none());
+        old["receipt_id"] = json!(derive_id_value(GlyphKind::Receipt, &old)
+            .unwrap()
+            .to_string());
+        old["blake3_hash"] = json!(content_hash_value(GlyphKind::Receipt, &old)
+            .unwrap()
+            .to_hex()
+            .to_string());
+        old
+    }
+
+    #[test]
+    fn historical_receipts_keep_verifying_after_a_bump() {
+        let registry = registry();
+        for raw in receipts() {
+            let emitter: Daemon = serde_json::from_value(raw["emitted_by"].clone()).unwrap();
+            let stored = stored_at_0_9(&registry, raw);
+
+            let versioned = registry.decode::<ReceiptGlyph>(stored.clone()).unwrap();
+            assert!(versioned.is_migrated());
+            assert_eq!(versioned.original_version(), "0.9");
+            assert_eq!(versioned.glyph().version, SchemaVersion::CURRENT);
+            assert_eq!(versioned.glyph().emitted_by, emitter);
+
+            assert_eq!(versioned.to_original_value().unwrap(), stored);
+            versioned.verify_id().expect("ID must verify at 0.9");
+            assert_eq!(
+                versioned.content_hash().unwrap().to_hex().as_str(),
+                versioned.glyph().blake3_hash
+            );
+            // Hashing the upcast model directly would not match.
+            assert!(verify_id(versioned.glyph()).is_err());
+        }
+    }
+
+    #[test]
+    fn current_and_unknown_versions() {
+        let registry = registry();
+        let raw = receipts().remove(0);
+        let versioned = registry.decode::<ReceiptGlyph>(raw.clone()).unwrap();
+        assert!(!versioned.is_migrated());
+        assert_eq!(versioned.original_version(), "1.0");
+        assert_eq!(versioned.to_original_value().unwrap(), raw);
+
+        let mut future = raw.clone();
+        future["version"] = json!("2.0");
+        assert!(matches!(
+            registry.decode::<ReceiptGlyph>(future),
+            Err(GlyphError::UnsupportedVersion { kind: GlyphKind::Receipt, version }) if version == "2.0"
+        ));
+
+        let mut missing = raw.clone();
+        missing.as_object_mut().unwrap().remove("version");
+        assert!(matches!(
+            registry.upcast(GlyphKind::Receipt, missing),
+            Err(GlyphError::UnsupportedVersion { .. })
+        ));
+
+        let mut other_kind = raw;
+        other_kind["version"] = json!("0.9");
+        assert!(matches!(
+            registry.upcast(GlyphKind::Anchor, other_kind),
+            Err(GlyphError::UnsupportedVersion {
+                kind: GlyphKind::Anchor,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn lossy_and_malformed_migrations_are_rejected() {
+        let mut registry = MigrationRegistry::new();
+        registry
+            .register(Migration {
+                kind: GlyphKind::Receipt,
+                from: "0.9",
+                to: "1.0",
+                up: |mut m| {
+                    m.remove("legacy_note");
+                    Ok(m)
+                },
+                down: Ok,
+            })
+            .unwrap();
+
+        let mut stored = receipts().remove(0);
+        stored["version"] = json!("0.9");
+        registry
+            .decode::<ReceiptGlyph>(stored.clone())
+            .expect("nothing dropped, round-trips");
+        stored["legacy_note"] = json!("dropped by up");
+        assert!(matches!(
+            registry.decode::<ReceiptGlyph>(stored),
+            Err(GlyphError::Migration { .. })
+        ));
+
+        assert!(registry.register(receipt_0_9()).is_err(), "duplicate step");
+        let mut from_current = receipt_0_9();
+        from_current.from = "1.0";
+        from_current.to = "1.1";
+        assert!(registry.register(from_current).is_err());
+
+        let mut looping = MigrationRegistry::new();
+        for (from, to) in [("0.7", "0.8"), ("0.8", "0.7")] {
+            let mut step = receipt_0_9();
+            step.from = from;
+            step.to = to;
+            looping.register(step).unwrap();
+        }
+        let mut raw = receipts().remove(0);
+        raw["version"] = json!("0.7");
+        assert!(matches!(
+            looping.upcast(GlyphKind::Receipt, raw),
+            Err(GlyphError::Migration { .. })
+        ));
+    }
+
+    #[test]
+    fn negotiation_picks_the_highest_common_version() {
+        let mut registry = registry();
+        for (from, to) in [("0.8", "0.9"), ("0.5", "0.7")] {
+            let mut step = receipt_0_9();
+            step.from = from;
+            step.to = to;
+            registry.register(step).unwrap();
+        }
+        let ours = registry.supported_versions(GlyphKind::Receipt);
+        assert_eq!(ours, vec!["0.8", "0.9", "1.0"], "0.5 has no path to 1.0");
+        assert_eq!(registry.supported_versions(GlyphKind::Anchor), vec!["1.0"]);
+
+        assert_eq!(negotiate(&ours, &["2.0", "1.0", "0.9"]), Some("1.0"));
+        assert_eq!(negotiate(&ours, &["0.9", "1.1"]), Some("0.9"));
+        assert_eq!(negotiate(&ours, &["0.10", "v1"]), None);
+    }
+}
@@ -0,0 +1,140 @@
+#[cfg(test)]
+mod test_mmr {
+    use glyph_lib::anchors::merkle::{hash_to_hex, leaf_hash, node_hash, MerkleHash};
+    use glyph_lib::anchors::mmr::{bag_peaks, mountains, verify_anchor_link, Mmr};
+    use glyph_lib::{AnchorGlyph, ChainLink, GlyphError, GlyphId, GlyphKind, MmrExtension};
+    use serde_json::Value;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn item(i: u64) -> Vec<u8> {
+        format!("receipt-{i}").into_bytes()
+    }
+
+    fn mmr_of(n: u64) -> Mmr {
+        let mut mmr = Mmr::new();
+        for i in 0..n {
+            assert_eq!(mmr.append(&item(i)), i);
+        }
+        mmr
+    }
+
+    fn load_anchor() -> AnchorGlyph {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../..")
+            .join("glyphs")
+            .join("examples")
+            .join("anchor_glyph.example.json");
+        let data = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()));
+        let raw: Value = serde_json::from_str(&data).expect("anchor json");
+        serde_json::from_value(raw).expect("anchor decode")
+    }
+
+    fn with_mmr(mut anchor: AnchorGlyph, mmr: &Mmr) -> AnchorGlyph {
+        let ext = anchor.extensions.get_or_insert_with(Default::default);
+        ext.mmr = Some(MmrExtension {
+            leaf_count: mmr.leaf_count(),
+            root: hash_to_hex(&mmr.root()),
+            additional: Default::default(),
+        });
+        anchor
+    }
+
+    #[test]
+    fn peaks_follow_the_binary_decomposition() {
+        assert_eq!(mountains(11), vec![(3, 0), (1, 4), (0, 10)]);
+        assert!(mountains(0).is_empty());
+
+        let mmr = mmr_of(3);
+        let l: Vec<MerkleHash> = (0..3).map(|i| leaf_hash(&item(i))).collect();
+        assert_eq!(
+            mmr.peaks_at(3).unwrap(),
+            vec![node_hash(&l[0], &l[1]), l[2]]
+        );
+        assert_eq!(mmr.root(), bag_peaks(3, &mmr.peaks_at(3).unwrap()));
+        assert_ne!(mmr_of(0).root(), mmr_of(1).root());
+    }
+
+    #[test]
+    fn historical_roots_are_stable_under_append() {
+        let small = mmr_of(7);
+        let big = mmr_of(40);
+        for size in 0..=7 {
+            assert_eq!(small.root_at(size).unwrap(), big.root_at(size).unwrap());
+        }
+        assert!(small.root_at(8).is_err());
+    }
+
+    #[test]
+    fn inclusion_proofs_hold_against_historical_roots() {
+        let mmr = mmr_of(23);
+        for size in 1..=23 {
+            let root = mmr.root_at(size).unwrap();
+            for i in 0..size {
+                let leaf = leaf_hash(&item(i));
+                let proof = mmr.inclusion_proof(i, size).unwrap();
+                assert!(proof.verify(&root, &leaf), "size={size} i={i}");
+                assert!(!proof.verify(&root, &leaf_hash(b"forged")));
+                if let Some(first) = proof.hashes.first() {
+                    let mut tampered = proof.clone();
+                    tampered.hashes[0] = leaf_hash(first);
+                    assert!(!tampered.verify(&root, &leaf));
+                }
+            }
+            assert!(mmr.inclusion_proof(size, size).is_err());
+        }
+    }
+
+    #[test]
+    fn consistency_proofs_show_prefixes() {
+        let mmr = mmr_of(29);
+        for old in 0..=29 {
+            for new in old..=29 {
+                let proof = mmr.consistency_proof(old, new).unwrap();
+                let old_root = mmr.root_at(old).unwrap();
+                let new_root = mmr.root_at(new).unwrap();
+                assert!(proof.verify(&old_root, &new_root), "{old} -> {new}");
+            }
+        }
+
+        let mut forked = mmr_of(9);
+        forked.append(b"rewritten history");
+        let proof = mmr.consistency_proof(9, 10).unwrap();
+        assert!(!proof.verify(&mmr.root_at(9).unwrap(), &forked.root()));
+        assert!(mmr.consistency_proof(10, 9).is_err());
+    }
+
+    #[test]
+    fn anchor_links_are_verified_through_the_log() {
+        let mut log = mmr_of(5);
+        let prev = with_mmr(load_anchor(), &log);
+        for i in 5..12 {
+            log.append(&item(i));
+        }
+        let mut next = with_mmr(load_anchor(), &log);
+        next.glyph_id = GlyphId::derive(GlyphKind::Anchor, b"next anchor");
+        next.previous_glyph_id = ChainLink::Anchor(prev.glyph_id);
+
+        let proof = log.consistency_proof(5, 12).unwrap();
+        verify_anchor_link(&prev, &next, &proof).expect("link must verify");
+
+        let round_trip: AnchorGlyph =
+            serde_json::from_value(serde_json::to_value(&next).unwrap()).unwrap();
+        assert_eq!(round_trip, next);
+
+        let mut unlinked = next.clone();
+        unlinked.previous_glyph_id = ChainLink::Genesis;
+        assert!(matches!(
+            verify_anchor_link(&prev, &unlinked, &proof),
+            Err(GlyphError::BrokenChain { .. })
+        ));
+
+        let mut other = Mmr::new();
+        for i in 0..12 {
+            other.append(format!("other-{i}").as_bytes());
+        }
+        let rewritten = with_mmr(next.clone(), &other);
+        assert!(verify_anchor_link(&prev, &rewritten, &proof).is_err());
+    }
+}
@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod test_orbital_anchor {
+    use glyph_lib::anchors::orbital::{OrbitalAnchorGlyph, OrbitalLink, OrbitalPayload};
+    use glyph_lib::anchors::receipt_payload::{OrbitalTelemetry, ReceiptPayload};
+    use glyph_lib::config::slo::SloConfig;
+    use glyph_lib::{AnchorGlyph, GlyphError, ReceiptGlyph, TargetContext};
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn read(relative: &str) -> String {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../..")
+            .join(relative);
+        fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()))
+    }
+
+    fn slo() -> SloConfig {
+        SloConfig::from_toml(&read("config/slo.toml")).expect("slo.toml must load")
+    }
+
+    fn receipt() -> ReceiptGlyph {
+        serde_json::from_str(&read("glyphs/examples/receipt_glyph.orbital.example.json"))
+            .expect("receipt json")
+    }
+
+    fn anchor() -> AnchorGlyph {
+        let mut anchor: AnchorGlyph =
+            serde_json::from_str(&read("glyphs/examples/anchor_glyph.example.json"))
+                .expect("anchor json");
+        // The example's entanglement extension misses the negation SLO.
+        anchor.extensions.as_mut().unwrap().entanglement = None;
+        anchor
+    }
+
+    fn link() -> OrbitalLink {
+        OrbitalLink {
+            orbit_slot: "leo-shell-1-plane-22".into(),
+            link_id: "memphis-gw-1<->starlink-5508".into(),
+            drift_mm: 3.2,
+            snr_db: 11.5,
+        }
+    }
+
+    fn orbital() -> OrbitalAnchorGlyph {
+        let payload = OrbitalPayload::from_receipt(&receipt(), link()).unwrap();
+        OrbitalAnchorGlyph::new(anchor(), payload).unwrap()
+    }
+
+    #[test]
+    fn receipt_round_trips_through_the_payload() {
+        let receipt = receipt();
+        let glyph = orbital();
+        assert_eq!(glyph.payload().sat_id, "starlink-5508");
+        assert_eq!(glyph.payload().measurement.rtt_ms, 23.7);
+
+        let telemetry = glyph.payload().telemetry().unwrap();
+        assert_eq!(
+            ReceiptPayload::OrbitalTelemetry(telemetry.clone()),
+            ReceiptPayload::of(&receipt).unwrap()
+        );
+        assert_eq!(
+            OrbitalPayload::from_telemetry(&telemetry, link()),
+            *glyph.payload()
+        );
+
+        let mut bare = glyph.payload().clone();
+        bare.measurement.signal_strength_dbm = None;
+        assert!(matches!(
+            bare.telemetry(),
+            Err(GlyphError::InvalidAnchorPayload {
+                context: TargetContext::OrbitalVerify,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn anchor_conversion_requires_orbital_verify() {
+        let anchor: AnchorGlyph = orbital().into();
+        assert_eq!(anchor.context, TargetContext::OrbitalVerify);
+        let reread = OrbitalAnchorGlyph::try_from(anchor.clone()).unwrap();
+        assert_eq!(reread, orbital());
+
+        let mut missing = anchor.clone();
+        missing.payload.as_mut().unwrap().remove("measurement");
+        assert!(OrbitalAnchorGlyph::try_from(missing).is_err());
+
+        let mut tunnel = anchor;
+        tunnel.context = TargetContext::TunnelBore;
+        assert!(matches!(
+            OrbitalAnchorGlyph::try_from(tunnel),
+            Err(GlyphError::InvalidAnchorPayload { .. })
+        ));
+    }
+
+    #[test]
+    fn unknown_payload_keys_survive() {
+        let mut anchor: AnchorGlyph = orbital().into();
+        let payload = anchor.payload.as_mut().unwrap();
+        payload.insert("ground_station".into(), "memphis-gw-1".into());
+        payload
+            .get_mut("measurement")
+            .and_then(|m| m.as_object_mut())
+            .unwrap()
+            .insert("elevation_deg".into(), 41.0.into());
+
+        let glyph = OrbitalAnchorGlyph::try_from(anchor.clone()).unwrap();
+        assert!(glyph.payload().additional.contains_key("ground_station"));
+        assert_eq!(AnchorGlyph::from(glyph).payload, anchor.payload);
+    }
+
+    #[test]
+    fn slo_rejects_excess_drift() {
+        let slo = slo();
+        orbital().validate(&slo).expect("example meets the SLOs");
+
+        let mut telemetry: OrbitalTelemetry = match ReceiptPayload::of(&receipt()).unwrap() {
+            ReceiptPayload::OrbitalTelemetry(t) => t,
+            other => panic!("unexpected {}", other.receipt_type()),
+        };
+        telemetry.drift_percent = Some(0.05);
+        let drifting =
+            OrbitalAnchorGlyph::new(anchor(), OrbitalPayload::from_telemetry(&telemetry, link()))
+                .unwrap();
+        assert!(matches!(
+            drifting.validate(&slo),
+            Err(GlyphError::SloBreached {
+                objective: "nebula-guard.max_drift_percent",
+                ..
+            })
+        ));
+
+        telemetry.drift_percent = None;
+        telemetry.latency_ms = -1.0;
+        let negative =
+            OrbitalAnchorGlyph::new(anchor(), OrbitalPayload::from_telemetry(&telemetry, link()))
+                .unwrap();
+        assert!(negative.validate(&slo).is_err());
+    }
+
+    #[test]
+    fn entanglement_extension_is_checked_too() {
+        let mut anchor: AnchorGlyph =
+            serde_json::from_str(&read("glyphs/examples/anchor_glyph.example.json")).unwrap();
+        anchor.context = TargetContext::OrbitalVerify;
+        let glyph = OrbitalAnchorGlyph::new(
+            anchor,
+            OrbitalPayload::from_receipt(&receipt(), link()).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(glyph.entanglement().unwrap().unwrap().negation_ms, 0.9);
+        assert!(matches!(
+            glyph.validate(&slo()),
+            Err(GlyphError::SloBreached {
+                objective: "digital-twin-groot.min_predicted_latency_negation_ms",
+                ..
+            })
+        ));
+    }
+}
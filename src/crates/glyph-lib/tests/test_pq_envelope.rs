@@ -0,0 +1,121 @@
+#[cfg(test)]
+mod test_pq_envelope {
+    use glyph_lib::pq::envelope::{
+        receipt_signing_message, sign_intent, sign_receipt, verify_intent_signature,
+        verify_receipt_signature, GlyphSigner, SignatureEnvelope, SignatureField, ENVELOPE_VERSION,
+    };
+    use glyph_lib::testing::{read_repo_file, MacKey};
+    use glyph_lib::{GlyphError, IntentGlyph, ReceiptGlyph};
+    use serde_json::Value;
+
+    fn load_example(name: &str) -> Value {
+        serde_json::from_str(&read_repo_file(format!("glyphs/examples/{name}")))
+            .unwrap_or_else(|e| panic!("failed to parse {name}: {e}"))
+    }
+
+    #[test]
+    fn envelope_round_trips_and_rejects_malformed_bytes() {
+        let env = MacKey([7; 32]).sign(b"message");
+        let bytes = env.to_bytes();
+        assert_eq!(bytes[0], ENVELOPE_VERSION);
+        assert_eq!(u16::from_be_bytes([bytes[1], bytes[2]]), 0x0001);
+        assert_eq!(SignatureEnvelope::from_bytes(&bytes).unwrap(), env);
+
+        let hex_form = env.to_hex();
+        assert!(hex_form
+            .bytes()
+            .all(|b| b.is_ascii_hexdigit() && !b.is_ascii_uppercase()));
+        assert_eq!(
+            SignatureField::parse(&hex_form).unwrap(),
+            SignatureField::Envelope(env)
+        );
+
+        let mut bad_version = bytes.clone();
+        bad_version[0] = 0x02;
+        let mut bad_algorithm = bytes.clone();
+        bad_algorithm[2] = 0xff;
+        for bad in [
+            bad_version,
+            bad_algorithm,
+            bytes[..bytes.len() - 1].to_vec(),
+            bytes[..10].to_vec(),
+        ] {
+            assert!(matches!(
+                SignatureEnvelope::from_bytes(&bad),
+                Err(GlyphError::InvalidEnvelope { .. })
+            ));
+        }
+        assert!(SignatureField::parse("ABCD").is_err());
+        assert!(SignatureField::parse("").is_err());
+    }
+
+    #[test]
+    fn legacy_stub_signatures_parse_but_never_verify() {
+        let raw = load_example("receipt_glyph.bore.example.json");
+        let receipt: ReceiptGlyph = serde_json::from_value(raw).unwrap();
+        assert!(matches!(
+            SignatureField::parse(&receipt.kyber_signature).unwrap(),
+            SignatureField::Legacy(_)
+        ));
+        assert!(matches!(
+            verify_receipt_signature(&receipt, &MacKey([1; 32])),
+            Err(GlyphError::BadSignature { .. })
+        ));
+    }
+
+    #[test]
+    fn receipts_sign_and_verify_through_envelopes() {
+        let raw = load_example("receipt_glyph.orbital.example.json");
+        let mut receipt: ReceiptGlyph = serde_json::from_value(raw).unwrap();
+        let key = MacKey([3; 32]);
+        sign_receipt(&mut receipt, &key).unwrap();
+        verify_receipt_signature(&receipt, &key).expect("signed receipt must verify");
+
+        assert!(verify_receipt_signature(&receipt, &MacKey([4; 32])).is_err());
+
+        let mut moved_root = receipt.clone();
+        moved_root.merkle_root = receipt.blake3_hash.clone();
+        assert_ne!(
+            receipt_signing_message(&moved_root).unwrap(),
+            receipt_signing_message(&receipt).unwrap()
+        );
+        assert!(verify_receipt_signature(&moved_root, &key).is_err());
+    }
+
+    #[test]
+    fn intent_signatures_cover_the_canonical_preimage() {
+        let raw = load_example("intent_glyph.example.json");
+        let mut intent: IntentGlyph = serde_json::from_value(raw).unwrap();
+        let key = MacKey([9; 32]);
+        sign_intent(&mut intent, &key).unwrap();
+        verify_intent_signature(&intent, &key).expect("signed intent must verify");
+
+        intent.constraints.max_latency_ms += 1;
+        assert!(verify_intent_signature(&intent, &key).is_err());
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn dilithium_and_kyber_backends_work_end_to_end() {
+        use glyph_lib::pq::dilithium::{DilithiumKeypair, DilithiumPublicKey};
+        use glyph_lib::pq::envelope::{AlgorithmId, SignatureVerifier};
+        use glyph_lib::pq::kem::KemKeypair;
+
+        let keypair = DilithiumKeypair::generate();
+        let env = keypair.sign(b"anchor");
+        assert_eq!(env.signature.len(), AlgorithmId::Dilithium5.signature_len());
+        assert_eq!(
+            SignatureField::parse(&env.to_hex()).unwrap(),
+            SignatureField::Envelope(env.clone())
+        );
+        let public = DilithiumPublicKey::from_bytes(keypair.public_key().as_bytes()).unwrap();
+        public
+            .verify(b"anchor", &env)
+            .expect("dilithium must verify");
+        assert!(public.verify(b"other", &env).is_err());
+
+        let kem = KemKeypair::generate();
+        let (secret, ciphertext) = kem.public_key().encapsulate();
+        assert_eq!(kem.decapsulate(&ciphertext).unwrap(), secret);
+    }
+}
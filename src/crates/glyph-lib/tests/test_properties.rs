@@ -0,0 +1,880 @@
+#[cfg(test)]
+mod test_properties {
+    use glyph_lib::anchors::merkle::{
+        hash_from_hex, leaf_hash, receipt_leaf, MerkleHash, MerkleProof, MerkleTree,
+    };
+    use glyph_lib::anchors::quorum::{verify_quorum, QuorumPolicy};
+    use glyph_lib::anchors::receipt_payload::{
+        allowed_results, check_receipt, AnomalyDetected, BoreProgress, CompactionComplete,
+        EntanglementPrediction, OrbitalTelemetry, ReceiptPayload, ZkAnomalyProof, ZkProofPoints,
+    };
+    use glyph_lib::builders::anchor::AnchorBuilder;
+    use glyph_lib::builders::intent::IntentBuilder;
+    use glyph_lib::builders::receipt::{ReceiptBatch, ReceiptBuilder};
+    use glyph_lib::builders::status::DaemonStatusBuilder;
+    use glyph_lib::hashing::canonical::{
+        content_hash, content_hash_value, to_canonical_bytes, to_canonical_string,
+    };
+    use glyph_lib::hashing::ids::{verify_id, GlyphId, ID_DIGEST_LEN};
+    use glyph_lib::pq::envelope::{
+        verify_intent_signature, verify_receipt_emitter, verify_status_signature, GlyphSigner,
+        KeyId, SignatureVerifier,
+    };
+    use glyph_lib::schemas::validator::check_glyph;
+    use glyph_lib::testing::{guardian_key_id, guardian_keyring, swarm_policy, MacKey, WEIGHTED};
+    use glyph_lib::{
+        AnchorGlyph, AnomalySeverity, ChainLink, Daemon, DaemonHealth, DaemonStatusGlyph, Glyph,
+        GlyphError, GlyphKind, Guardian, IntentConstraints, IntentGlyph, IntentType, JsonMap,
+        ReceiptGlyph, ReceiptResult, RiskAppetite, SloCompliance, TargetContext, Verdict,
+    };
+    use proptest::prelude::*;
+    use proptest::sample::Index;
+    use serde_json::{json, Value};
+    use std::collections::BTreeMap;
+    use std::fmt::Debug;
+    use std::sync::OnceLock;
+
+    /// Daemons the receipt schema accepts as `emitted_by`.
+    const RECEIPT_EMITTERS: [Daemon; 9] = [
+        Daemon::RocketEngine,
+        Daemon::NebulaGuard,
+        Daemon::DigitalTwinGroot,
+        Daemon::DraxMetrics,
+        Daemon::MantisCommunity,
+        Daemon::StarLordOrchestrator,
+        Daemon::GrootSwarm,
+        Daemon::LedgerExplorer,
+        Daemon::SpvApi,
+    ];
+
+    /// Daemons the anchor schema accepts as `emitted_by`.
+    const ANCHOR_EMITTERS: [Daemon; 4] = [
+        Daemon::LedgerExplorer,
+        Daemon::StarLordOrchestrator,
+        Daemon::GrootSwarm,
+        Daemon::PortalZero,
+    ];
+
+    fn emitters() -> BTreeMap<(Daemon, KeyId), Box<dyn SignatureVerifier + Send + Sync>> {
+        Daemon::ALL
+            .iter()
+            .map(|&d| {
+                let key = MacKey::of(d.as_str());
+                (
+                    (d, GlyphSigner::key_id(&key)),
+                    Box::new(key) as Box<dyn SignatureVerifier + Send + Sync>,
+                )
+            })
+            .collect()
+    }
+
+    fn policy() -> &'static QuorumPolicy {
+        static POLICY: OnceLock<QuorumPolicy> = OnceLock::new();
+        POLICY.get_or_init(swarm_policy)
+    }
+
+    fn object(value: Value) -> JsonMap {
+        match value {
+            Value::Object(map) => map,
+            other => panic!("expected a JSON object, got {other}"),
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Strategies
+    // -----------------------------------------------------------------------
+
+    // Numbers are kept to three decimals and well inside ryu's plain range,
+    // so every serialized digit is significant and no `e` exponent appears.
+    fn milli(range: std::ops::RangeInclusive<u32>) -> impl Strategy<Value = f64> {
+        range.prop_map(|n| f64::from(n) / 1000.0)
+    }
+
+    fn signed_milli(max: u32) -> impl Strategy<Value = f64> {
+        // No `-0.0`: it decodes equal to `0.0`, so its sign bit is not a tamper.
+        (milli(0..=max), any::<bool>()).prop_map(|(v, neg)| if neg && v > 0.0 { -v } else { v })
+    }
+
+    fn tenant_id() -> impl Strategy<Value = String> {
+        "[a-z][a-z0-9]{1,10}(-[a-z0-9]{1,8}){0,2}"
+    }
+
+    fn label() -> impl Strategy<Value = String> {
+        "[a-z][a-z0-9_-]{0,23}"
+    }
+
+    fn timestamp() -> impl Strategy<Value = i64> {
+        1_600_000_000i64..1_900_000_000
+    }
+
+    fn glyph_id(kind: GlyphKind) -> impl Strategy<Value = GlyphId> {
+        any::<[u8; ID_DIGEST_LEN]>().prop_map(move |digest| GlyphId::from_digest(kind, digest))
+    }
+
+    fn hex32() -> impl Strategy<Value = String> {
+        any::<[u8; 32]>().prop_map(hex::encode)
+    }
+
+    fn bore_progress() -> impl Strategy<Value = ReceiptPayload> {
+        (
+            milli(0..=50_000_000),
+            0u32..=20,
+            proptest::option::of(label()),
+        )
+            .prop_map(|(meters_advanced, cutter_head_rpm, segment_id)| {
+                ReceiptPayload::BoreProgress(BoreProgress {
+                    meters_advanced,
+                    cutter_head_rpm,
+                    segment_id,
+                })
+            })
+    }
+
+    fn orbital_telemetry() -> impl Strategy<Value = ReceiptPayload> {
+        (
+            label(),
+            signed_milli(150_000),
+            milli(0..=2_000_000),
+            proptest::option::of(milli(0..=100_000)),
+        )
+            .prop_map(
+                |(satellite_id, signal_strength_dbm, latency_ms, drift_percent)| {
+                    ReceiptPayload::OrbitalTelemetry(OrbitalTelemetry {
+                        satellite_id,
+                        signal_strength_dbm,
+                        latency_ms,
+                        drift_percent,
+                    })
+                },
+            )
+    }
+
+    fn zk_anomaly_proof() -> impl Strategy<Value = ReceiptPayload> {
+        let point = || [hex32(), hex32()];
+        (
+            point(),
+            [point(), point()],
+            point(),
+            proptest::collection::vec(hex32(), 1..4),
+            proptest::option::of(label()),
+        )
+            .prop_map(|(pi_a, pi_b, pi_c, public_inputs, anomaly_hint)| {
+                ReceiptPayload::ZkAnomalyProof(ZkAnomalyProof {
+                    zk_proof: ZkProofPoints {
+                        pi_a,
+                        pi_b,
+                        pi_c,
+                        additional: JsonMap::new(),
+                    },
+                    public_inputs,
+                    anomaly_hint,
+                })
+            })
+    }
+
+    fn entanglement_prediction() -> impl Strategy<Value = ReceiptPayload> {
+        // Unit vectors with short decimal amplitudes.
+        let bell_state = prop_oneof![
+            Just([0.6, 0.0, 0.0, 0.8]),
+            Just([0.0, 0.8, -0.6, 0.0]),
+            Just([0.28, 0.0, 0.0, -0.96]),
+            Just([0.5, 0.5, 0.5, 0.5]),
+        ];
+        (
+            milli(707..=1000),
+            milli(0..=500_000),
+            proptest::option::of(bell_state),
+        )
+            .prop_map(|(correlation_score, predicted_negation_ms, bell_state)| {
+                ReceiptPayload::EntanglementPrediction(EntanglementPrediction {
+                    correlation_score,
+                    predicted_negation_ms,
+                    bell_state,
+                })
+            })
+    }
+
+    fn anomaly_detected() -> impl Strategy<Value = ReceiptPayload> {
+        (
+            prop::sample::select(AnomalySeverity::ALL),
+            signed_milli(1_000_000),
+            proptest::option::of(any::<bool>()),
+        )
+            .prop_map(|(severity, drift_value, auto_halt)| {
+                ReceiptPayload::AnomalyDetected(AnomalyDetected {
+                    severity,
+                    drift_value,
+                    auto_halt_triggered: auto_halt
+                        .map(|halt| halt && severity == AnomalySeverity::Critical),
+                })
+            })
+    }
+
+    fn compaction_complete() -> impl Strategy<Value = ReceiptPayload> {
+        (
+            1u64..1_000_000,
+            any::<prop::sample::Index>(),
+            proptest::option::of((1u64..1 << 40, any::<prop::sample::Index>())),
+            any::<bool>(),
+            proptest::option::of(milli(0..=1000)),
+            proptest::option::of(any::<bool>()),
+        )
+            .prop_map(
+                |(input, output, live_bytes, with_reduction, pce_transitivity, death_triggered)| {
+                    let mut payload = CompactionComplete {
+                        input_row_count: input,
+                        output_row_count: output.index(input as usize + 1) as u64,
+                        reduction_percent: None,
+                        rocksdb_live_bytes_before: live_bytes.map(|(before, _)| before),
+                        rocksdb_live_bytes_after: live_bytes
+                            .map(|(before, after)| after.index(before as usize + 1) as u64),
+                        pce_transitivity,
+                        death_triggered,
+                    };
+                    if with_reduction {
+                        // Emitters round to two decimals.
+                        let expected = payload.expected_reduction_percent();
+                        payload.reduction_percent = Some((expected * 100.0).round() / 100.0);
+                    }
+                    ReceiptPayload::CompactionComplete(payload)
+                },
+            )
+    }
+
+    /// Every `receipt_type`, paired with a result it may carry.
+    fn receipt_payload() -> impl Strategy<Value = (ReceiptPayload, ReceiptResult)> {
+        prop_oneof![
+            bore_progress(),
+            orbital_telemetry(),
+            zk_anomaly_proof(),
+            entanglement_prediction(),
+            anomaly_detected(),
+            compaction_complete(),
+            Just(ReceiptPayload::PhaseTransition),
+            Just(ReceiptPayload::SwarmVote),
+            Just(ReceiptPayload::VoicePageSent),
+        ]
+        .prop_flat_map(|payload| {
+            let results = allowed_results(payload.receipt_type());
+            (Just(payload), prop::sample::select(results))
+        })
+    }
+
+    /// A sealed batch of 1 to 12 receipts from one emitter and tenant.
+    fn receipt_batch() -> impl Strategy<Value = Vec<ReceiptGlyph>> {
+        (
+            tenant_id(),
+            prop::sample::select(&RECEIPT_EMITTERS[..]),
+            prop_oneof![glyph_id(GlyphKind::Intent), glyph_id(GlyphKind::Anchor)],
+            timestamp(),
+            proptest::collection::vec((receipt_payload(), 0i64..3600), 1..=12),
+        )
+            .prop_map(|(tenant, emitter, ref_glyph_id, start, members)| {
+                members
+                    .iter()
+                    .map(|((payload, result), offset)| {
+                        ReceiptBuilder::from_payload(
+                            tenant.as_str(),
+                            ref_glyph_id,
+                            *result,
+                            emitter,
+                            payload,
+                        )
+                        .expect("payload encodes")
+                        .timestamp(start + offset)
+                    })
+                    .collect::<ReceiptBatch>()
+                    .seal(&MacKey::of(emitter.as_str()))
+                    .expect("generated receipts are valid")
+            })
+    }
+
+    fn anchor_payload(context: TargetContext) -> BoxedStrategy<JsonMap> {
+        match context {
+            TargetContext::TunnelBore => (
+                label(),
+                label(),
+                milli(0..=50_000_000),
+                milli(0..=200_000),
+                prop::sample::select(vec![
+                    "starting",
+                    "advancing",
+                    "paused",
+                    "completed",
+                    "fault",
+                ]),
+            )
+                .prop_map(|(segment_id, tbm_id, position_m, depth_m, status)| {
+                    object(json!({
+                        "segment_id": segment_id,
+                        "tbm_id": tbm_id,
+                        "position_m": position_m,
+                        "depth_m": depth_m,
+                        "status": status,
+                    }))
+                })
+                .boxed(),
+            TargetContext::OrbitalVerify => (
+                label(),
+                label(),
+                label(),
+                signed_milli(100_000),
+                signed_milli(60_000),
+                milli(0..=2_000_000),
+            )
+                .prop_map(|(orbit_slot, sat_id, link_id, drift_mm, snr_db, rtt_ms)| {
+                    object(json!({
+                        "orbit_slot": orbit_slot,
+                        "sat_id": sat_id,
+                        "link_id": link_id,
+                        "measurement": {
+                            "drift_mm": drift_mm,
+                            "snr_db": snr_db,
+                            "rtt_ms": rtt_ms,
+                        },
+                    }))
+                })
+                .boxed(),
+            TargetContext::ZkAnomalyShare => (label(), label(), hex32(), label())
+                .prop_map(
+                    |(anomaly_id, anomaly_class, public_inputs_hash, model_version_id)| {
+                        object(json!({
+                            "anomaly_id": anomaly_id,
+                            "anomaly_class": anomaly_class,
+                            "public_inputs_hash": public_inputs_hash,
+                            "model_version_id": model_version_id,
+                        }))
+                    },
+                )
+                .boxed(),
+            TargetContext::EntanglementPrediction => (
+                label(),
+                milli(0..=1000),
+                milli(0..=500_000),
+                label(),
+                hex32(),
+            )
+                .prop_map(
+                    |(scenario_id, bell_correlation, negation_ms, sim_backend, model_hash)| {
+                        object(json!({
+                            "scenario_id": scenario_id,
+                            "bell_correlation": bell_correlation,
+                            "negation_ms": negation_ms,
+                            "sim_backend": sim_backend,
+                            "model_hash": model_hash,
+                        }))
+                    },
+                )
+                .boxed(),
+        }
+    }
+
+    /// A quorum-signed anchor over a fresh receipt batch.
+    fn anchored_batch() -> impl Strategy<Value = (AnchorGlyph, Vec<ReceiptGlyph>)> {
+        let context_and_payload = prop::sample::select(TargetContext::ALL)
+            .prop_flat_map(|context| (Just(context), anchor_payload(context)));
+        (
+            receipt_batch(),
+            context_and_payload,
+            prop_oneof![
+                Just(ChainLink::Genesis),
+                glyph_id(GlyphKind::Anchor).prop_map(ChainLink::Anchor)
+            ],
+            prop::sample::select(&ANCHOR_EMITTERS[..]),
+            0i64..600,
+            prop::sample::subsequence(vec![Guardian::Nebula, Guardian::Mantis], 0..=2),
+        )
+            .prop_map(
+                |(receipts, (context, payload), previous, emitter, delay, extra)| {
+                    let last = receipts.iter().map(|r| r.timestamp).max().unwrap_or(0);
+                    let pending = AnchorBuilder::new(
+                        receipts[0].tenant_id.as_str(),
+                        context,
+                        previous,
+                        emitter,
+                    )
+                    .timestamp(last + delay)
+                    .receipts(&receipts)
+                    .payload(payload)
+                    .prepare()
+                    .expect("generated anchor is valid");
+                    let votes = WEIGHTED
+                        .iter()
+                        .chain(&extra)
+                        .map(|&g| {
+                            pending.vote(
+                                g,
+                                &guardian_key_id(g),
+                                Verdict::Approve,
+                                &MacKey::of(g.as_str()),
+                            )
+                        })
+                        .collect::<glyph_lib::Result<_>>()
+                        .expect("votes");
+                    let (anchor, _) = pending
+                        .finalize(policy(), &guardian_keyring(), votes)
+                        .expect("quorum is met");
+                    (anchor, receipts)
+                },
+            )
+    }
+
+    fn intent() -> impl Strategy<Value = IntentGlyph> {
+        let constraints = (
+            1u32..=10_000,
+            milli(707..=1000),
+            any::<bool>(),
+            proptest::option::of(milli(0..=5000)),
+        )
+            .prop_map(
+                |(
+                    max_latency_ms,
+                    min_entanglement_quality,
+                    require_zk_proof,
+                    max_drift_percent,
+                )| {
+                    IntentConstraints {
+                        max_latency_ms,
+                        min_entanglement_quality,
+                        require_zk_proof,
+                        max_drift_percent,
+                    }
+                },
+            );
+        (
+            (
+                tenant_id(),
+                prop::sample::select(IntentType::ALL),
+                prop::sample::select(RiskAppetite::ALL),
+                prop::sample::select(TargetContext::ALL),
+            ),
+            constraints,
+            timestamp(),
+            1i64..30 * 86_400,
+            prop::sample::select(Guardian::ALL),
+        )
+            .prop_map(
+                |((tenant, intent_type, risk, target), constraints, declared, ttl, guardian)| {
+                    IntentBuilder::new(
+                        tenant,
+                        intent_type,
+                        risk,
+                        target,
+                        constraints,
+                        declared + ttl,
+                    )
+                    .timestamp(declared)
+                    .build(guardian, &MacKey::of(guardian.as_str()))
+                    .expect("generated intent is valid")
+                },
+            )
+    }
+
+    fn status() -> impl Strategy<Value = DaemonStatusGlyph> {
+        let slo = (
+            milli(0..=600_000),
+            milli(0..=1000),
+            proptest::option::of(milli(0..=600_000)),
+            milli(0..=100_000),
+        )
+            .prop_map(
+                |(
+                    latency_p95_ms,
+                    entanglement_quality,
+                    zk_proof_time_ms,
+                    anomaly_rate_per_hour,
+                )| {
+                    SloCompliance {
+                        latency_p95_ms,
+                        entanglement_quality,
+                        zk_proof_time_ms,
+                        anomaly_rate_per_hour,
+                    }
+                },
+            );
+        (
+            (
+                prop::sample::select(Daemon::ALL),
+                prop::sample::select(Guardian::ALL),
+                prop::sample::select(DaemonHealth::ALL),
+                slo,
+            ),
+            (timestamp(), any::<u32>(), any::<u32>()),
+            proptest::option::of(proptest::option::of(glyph_id(GlyphKind::Anchor))),
+            proptest::option::of(1u8..=7),
+            proptest::option::of(any::<bool>()),
+        )
+            .prop_map(
+                |((daemon, guardian, health, slo), (at, uptime, emitted), last, phase, lie)| {
+                    let mut builder = DaemonStatusBuilder::new(daemon, guardian, health, slo)
+                        .timestamp(at)
+                        .uptime_seconds(u64::from(uptime))
+                        .glyphs_emitted(u64::from(emitted));
+                    if let Some(last) = last {
+                        builder = builder.last_anchor(last);
+                    }
+                    if let Some(phase) = phase {
+                        builder = builder.active_phase(phase);
+                    }
+                    if let Some(lie) = lie {
+                        builder = builder.self_diagnosed_lie(lie);
+                    }
+                    builder
+                        .build(&MacKey::of(daemon.as_str()))
+                        .expect("generated status is valid")
+                },
+            )
+    }
+
+    /// Where to flip one bit of a serialized glyph.
+    #[derive(Debug, Clone)]
+    struct BitFlip {
+        /// Pick among bytes outside long hex runs, which are almost all
+        /// signature bytes, so structure and short fields get hit too.
+        outside_signatures: bool,
+        byte: Index,
+        bit: u8,
+    }
+
+    fn bit_flip() -> impl Strategy<Value = BitFlip> {
+        (any::<bool>(), any::<Index>(), 0u8..8).prop_map(|(outside_signatures, byte, bit)| {
+            BitFlip {
+                outside_signatures,
+                byte,
+                bit,
+            }
+        })
+    }
+
+    impl BitFlip {
+        fn apply(&self, bytes: &[u8]) -> Vec<u8> {
+            let positions: Vec<usize> = if self.outside_signatures {
+                outside_long_hex(bytes)
+            } else {
+                (0..bytes.len()).collect()
+            };
+            let mut out = bytes.to_vec();
+            out[positions[self.byte.index(positions.len())]] ^= 1 << self.bit;
+            out
+        }
+    }
+
+    fn outside_long_hex(bytes: &[u8]) -> Vec<usize> {
+        const LONG: usize = 128;
+        let mut positions = Vec::new();
+        let mut run = 0..0;
+        for (i, b) in bytes.iter().enumerate() {
+            if matches!(b, b'0'..=b'9' | b'a'..=b'f') {
+                if run.is_empty() {
+                    run = i..i;
+                }
+                run.end = i + 1;
+                continue;
+            }
+            if run.len() < LONG {
+                positions.extend(run.clone());
+            }
+            run = i..i;
+            positions.push(i);
+        }
+        if run.len() < LONG {
+            positions.extend(run);
+        }
+        positions
+    }
+
+    // -----------------------------------------------------------------------
+    // Full verification, one function per glyph kind
+    // -----------------------------------------------------------------------
+
+    fn hash_matches<G: Glyph>(glyph: &G, claimed: &str) -> glyph_lib::Result<()> {
+        let expected = content_hash(glyph)?.to_hex().to_string();
+        if expected != claimed {
+            return Err(GlyphError::HashMismatch {
+                expected,
+                found: claimed.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    fn verify_receipt(receipt: &ReceiptGlyph, leaf_count: u64) -> glyph_lib::Result<()> {
+        verify_id(receipt)?;
+        hash_matches(receipt, &receipt.blake3_hash)?;
+        verify_receipt_emitter(receipt, &emitters())?;
+        let embedded = receipt
+            .merkle_proof
+            .as_ref()
+            .ok_or(GlyphError::InvalidMerkleProof {
+                reason: "receipt carries no inclusion proof",
+            })?;
+        let proof = MerkleProof::from_embedded(embedded, leaf_count)?;
+        if proof.compute_root(&receipt_leaf(receipt)?)? != hash_from_hex(&receipt.merkle_root)? {
+            return Err(GlyphError::InvalidMerkleProof {
+                reason: "proof does not lead to merkle_root",
+            });
+        }
+        check_glyph(receipt)?;
+        check_receipt(receipt)
+    }
+
+    fn verify_anchor(anchor: &AnchorGlyph) -> glyph_lib::Result<()> {
+        verify_id(anchor)?;
+        hash_matches(anchor, &anchor.blake3_hash)?;
+        check_glyph(anchor)?;
+        verify_quorum(anchor, policy(), &guardian_keyring()).map(|_| ())
+    }
+
+    fn verify_intent(intent: &IntentGlyph) -> glyph_lib::Result<()> {
+        verify_id(intent)?;
+        verify_intent_signature(intent, &MacKey::of(intent.authorized_by.as_str()))?;
+        check_glyph(intent)
+    }
+
+    fn verify_status(status: &DaemonStatusGlyph) -> glyph_lib::Result<()> {
+        verify_id(status)?;
+        hash_matches(status, &status.blake3_hash)?;
+        verify_status_signature(status, &MacKey::of(status.daemon_name.as_str()))?;
+        check_glyph(status)
+    }
+
+    /// Serializes, decodes and re-serializes `glyph`, checking nothing moves.
+    fn assert_round_trip<G: Glyph + PartialEq + Debug>(glyph: &G) -> Result<(), TestCaseError> {
+        let json = serde_json::to_string(glyph).unwrap();
+        let decoded: G = serde_json::from_str(&json).unwrap();
+        prop_assert_eq!(&decoded, glyph);
+        prop_assert_eq!(serde_json::to_string(&decoded).unwrap(), json);
+
+        let pretty: G =
+            serde_json::from_str(&serde_json::to_string_pretty(glyph).unwrap()).unwrap();
+        prop_assert_eq!(&pretty, glyph);
+
+        #[cfg(feature = "binary")]
+        {
+            use glyph_lib::encoding::binary::{content_hash_binary, from_binary, to_binary};
+            let bytes = to_binary(glyph).unwrap();
+            prop_assert_eq!(&from_binary::<G>(&bytes).unwrap(), glyph);
+            prop_assert_eq!(
+                content_hash_binary(G::KIND, &bytes).unwrap(),
+                content_hash(glyph).unwrap()
+            );
+        }
+        Ok(())
+    }
+
+    /// The content hash ignores formatting and key order, and canonical
+    /// bytes are a fixed point of decode-then-encode.
+    fn assert_hash_determinism<G: Glyph>(glyph: &G) -> Result<(), TestCaseError> {
+        let hash = content_hash(glyph).unwrap();
+        prop_assert_eq!(content_hash(glyph).unwrap(), hash);
+
+        let value = serde_json::to_value(glyph).unwrap();
+        let reordered: Value =
+            serde_json::from_str(&serde_json::to_string_pretty(&reversed(&value)).unwrap())
+                .unwrap();
+        prop_assert_eq!(content_hash_value(G::KIND, &reordered).unwrap(), hash);
+
+        let canonical = to_canonical_bytes(&value).unwrap();
+        let again: Value = serde_json::from_slice(&canonical).unwrap();
+        prop_assert_eq!(to_canonical_bytes(&again).unwrap(), canonical);
+        prop_assert_eq!(
+            to_canonical_string(&reordered).unwrap().into_bytes(),
+            to_canonical_bytes(&value).unwrap()
+        );
+        Ok(())
+    }
+
+    /// `value` with every object's members in reverse order.
+    fn reversed(value: &Value) -> Value {
+        match value {
+            Value::Object(map) => {
+                let mut out = serde_json::Map::new();
+                for (k, v) in map.iter().rev() {
+                    out.insert(k.clone(), reversed(v));
+                }
+                Value::Object(out)
+            }
+            Value::Array(items) => Value::Array(items.iter().map(reversed).collect()),
+            other => other.clone(),
+        }
+    }
+
+    /// Flips one bit of `glyph`'s JSON and requires decode or `verify` to fail.
+    fn assert_flip_rejected<G: Glyph>(
+        glyph: &G,
+        flip: &BitFlip,
+        verify: impl Fn(&G) -> glyph_lib::Result<()>,
+    ) -> Result<(), TestCaseError> {
+        let json = serde_json::to_vec(glyph).unwrap();
+        prop_assert!(verify(glyph).is_ok(), "unmutated glyph must verify");
+        let mutated = flip.apply(&json);
+        if let Ok(decoded) = serde_json::from_slice::<G>(&mutated) {
+            prop_assert!(
+                verify(&decoded).is_err(),
+                "bit flip was accepted: {}",
+                String::from_utf8_lossy(&mutated)
+            );
+        }
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // Properties
+    // -----------------------------------------------------------------------
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn receipts_round_trip_and_hash_deterministically(receipts in receipt_batch()) {
+            for receipt in &receipts {
+                assert_round_trip(receipt)?;
+                assert_hash_determinism(receipt)?;
+                let hash = content_hash(receipt).unwrap().to_hex();
+                prop_assert_eq!(hash.as_str(), receipt.blake3_hash.as_str());
+                prop_assert!(verify_receipt(receipt, receipts.len() as u64).is_ok());
+            }
+        }
+
+        #[test]
+        fn anchors_round_trip_and_hash_deterministically((anchor, _) in anchored_batch()) {
+            assert_round_trip(&anchor)?;
+            assert_hash_determinism(&anchor)?;
+            prop_assert!(verify_anchor(&anchor).is_ok());
+        }
+
+        #[test]
+        fn intents_round_trip_and_hash_deterministically(intent in intent()) {
+            assert_round_trip(&intent)?;
+            assert_hash_determinism(&intent)?;
+            prop_assert!(verify_intent(&intent).is_ok());
+        }
+
+        #[test]
+        fn statuses_round_trip_and_hash_deterministically(status in status()) {
+            assert_round_trip(&status)?;
+            assert_hash_determinism(&status)?;
+            prop_assert!(verify_status(&status).is_ok());
+        }
+
+        #[test]
+        fn anchor_root_covers_every_receipt((anchor, receipts) in anchored_batch()) {
+            let root = hash_from_hex(&anchor.merkle_root).unwrap();
+            prop_assert_eq!(MerkleTree::from_receipts(&receipts).unwrap().root(), root);
+            for receipt in &receipts {
+                let embedded = receipt.merkle_proof.as_ref().unwrap();
+                let proof = MerkleProof::from_embedded(embedded, receipts.len() as u64).unwrap();
+                prop_assert!(proof.verify_receipt(&root, receipt));
+            }
+        }
+
+        #[test]
+        fn any_bit_flip_of_a_signed_receipt_fails(
+            receipts in receipt_batch(),
+            pick in any::<Index>(),
+            flip in bit_flip(),
+        ) {
+            let leaf_count = receipts.len() as u64;
+            let receipt = pick.get(&receipts);
+            assert_flip_rejected(receipt, &flip, |r| verify_receipt(r, leaf_count))?;
+        }
+
+        #[test]
+        fn any_bit_flip_of_a_signed_anchor_fails(
+            (anchor, _) in anchored_batch(),
+            flip in bit_flip(),
+        ) {
+            assert_flip_rejected(&anchor, &flip, verify_anchor)?;
+        }
+
+        #[test]
+        fn any_bit_flip_of_a_signed_intent_fails(intent in intent(), flip in bit_flip()) {
+            assert_flip_rejected(&intent, &flip, verify_intent)?;
+        }
+
+        #[test]
+        fn any_bit_flip_of_a_signed_status_fails(status in status(), flip in bit_flip()) {
+            assert_flip_rejected(&status, &flip, verify_status)?;
+        }
+    }
+
+    fn leaves(count: usize, seed: u64) -> Vec<MerkleHash> {
+        (0..count)
+            .map(|i| leaf_hash(format!("leaf-{seed}-{i}").as_bytes()))
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn every_leaf_proof_verifies_and_survives_embedding(
+            count in 1usize..300,
+            seed in any::<u64>(),
+        ) {
+            let leaves = leaves(count, seed);
+            let tree = MerkleTree::from_leaf_hashes(leaves.clone());
+            let root = tree.root();
+            for (i, leaf) in leaves.iter().enumerate() {
+                let proof = tree.proof(i).unwrap();
+                prop_assert!(proof.verify(&root, leaf));
+                let embedded = proof.to_embedded();
+                prop_assert_eq!(
+                    &MerkleProof::from_embedded(&embedded, count as u64).unwrap(),
+                    &proof
+                );
+            }
+        }
+
+        #[test]
+        fn tampered_merkle_proofs_fail(
+            count in 2usize..300,
+            seed in any::<u64>(),
+            index in any::<Index>(),
+            other in any::<Index>(),
+            sibling in any::<Index>(),
+            bit in 0usize..256,
+        ) {
+            let leaves = leaves(count, seed);
+            let tree = MerkleTree::from_leaf_hashes(leaves.clone());
+            let root = tree.root();
+            let i = index.index(count);
+            let proof = tree.proof(i).unwrap();
+
+            let mut flipped = proof.clone();
+            let s = sibling.index(flipped.siblings.len());
+            flipped.siblings[s][bit / 8] ^= 1 << (bit % 8);
+            prop_assert!(!flipped.verify(&root, &leaves[i]));
+
+            let mut leaf = leaves[i];
+            leaf[bit / 8] ^= 1 << (bit % 8);
+            prop_assert!(!proof.verify(&root, &leaf));
+
+            let mut bad_root = root;
+            bad_root[bit / 8] ^= 1 << (bit % 8);
+            prop_assert!(!proof.verify(&bad_root, &leaves[i]));
+
+            let j = other.index(count);
+            if j != i {
+                prop_assert!(!proof.verify(&root, &leaves[j]));
+                let mut moved = proof.clone();
+                moved.leaf_index = j as u64;
+                prop_assert!(!moved.verify(&root, &leaves[i]));
+            }
+
+            let mut truncated = proof.clone();
+            truncated.siblings.pop();
+            prop_assert!(!truncated.verify(&root, &leaves[i]));
+            let mut extended = proof;
+            extended.siblings.push(root);
+            prop_assert!(!extended.verify(&root, &leaves[i]));
+        }
+
+        #[test]
+        fn embedded_proof_hex_is_strict(count in 2usize..64, index in any::<Index>()) {
+            let tree = MerkleTree::from_leaf_hashes(leaves(count, 0));
+            let mut embedded = tree.proof(index.index(count)).unwrap().to_embedded();
+            embedded.siblings[0] = embedded.siblings[0].to_uppercase();
+            prop_assume!(embedded.siblings[0].bytes().any(|b| b.is_ascii_uppercase()));
+            prop_assert!(MerkleProof::from_embedded(&embedded, count as u64).is_err());
+        }
+    }
+}
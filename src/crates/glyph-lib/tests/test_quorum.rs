@@ -0,0 +1,389 @@
+#[cfg(test)]
+mod test_quorum {
+    use glyph_lib::anchors::quorum::{
+        assemble_bundle, sign_vote, verify_quorum, GuardianRole, QuorumPolicy,
+    };
+    use glyph_lib::testing::{
+        guardian_key_id, guardian_keyring, read_repo_file, swarm_policy, MacKey, WEIGHTED,
+    };
+    use glyph_lib::{AnchorGlyph, GlyphError, Guardian, ReceiptResult, Verdict};
+    use serde_json::Value;
+
+    fn anchor() -> AnchorGlyph {
+        serde_json::from_str(&read_repo_file("glyphs/examples/anchor_glyph.example.json"))
+            .expect("anchor decode")
+    }
+
+    fn signed(votes: &[(Guardian, Verdict)]) -> AnchorGlyph {
+        signed_under(&swarm_policy(), votes)
+    }
+
+    fn signed_under(policy: &QuorumPolicy, votes: &[(Guardian, Verdict)]) -> AnchorGlyph {
+        sign_over(anchor(), policy, votes)
+    }
+
+    fn sign_over(
+        mut anchor: AnchorGlyph,
+        policy: &QuorumPolicy,
+        votes: &[(Guardian, Verdict)],
+    ) -> AnchorGlyph {
+        let signatures = votes
+            .iter()
+            .map(|&(g, v)| {
+                sign_vote(&anchor, g, &guardian_key_id(g), v, &MacKey::of(g.as_str())).unwrap()
+            })
+            .collect();
+        anchor.kyber_signature = assemble_bundle(policy, signatures);
+        anchor
+    }
+
+    fn all_approve() -> Vec<(Guardian, Verdict)> {
+        WEIGHTED.iter().map(|&g| (g, Verdict::Approve)).collect()
+    }
+
+    fn anomaly_anchor() -> AnchorGlyph {
+        let mut anchor = anchor();
+        anchor.receipts[0].result = ReceiptResult::Anomaly;
+        anchor
+    }
+
+    fn roles(weights: &[(Guardian, u32)]) -> std::collections::BTreeMap<Guardian, GuardianRole> {
+        weights
+            .iter()
+            .map(|&(g, weight)| {
+                (
+                    g,
+                    GuardianRole {
+                        weight,
+                        can_finalize: true,
+                        veto_power: false,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn swarm_roles_yaml_drives_the_policy() {
+        let policy = swarm_policy();
+        assert_eq!(policy.total_weight(), 55);
+        assert_eq!(policy.min_acceptance_score(), 0.93);
+        assert_eq!(policy.required_weight(), 52);
+        assert_eq!(
+            policy.role(Guardian::StarLord),
+            Some(&GuardianRole {
+                weight: 10,
+                can_finalize: true,
+                veto_power: false
+            })
+        );
+        assert!(policy.role(Guardian::Drax).unwrap().veto_power);
+        assert!(policy.drax_approval_on_anomaly());
+        assert!(policy.role(Guardian::Nebula).is_none());
+        assert!(QuorumPolicy::from_swarm_roles_yaml("roles: {}\nselection: {}\n").is_err());
+    }
+
+    #[test]
+    fn golden_example_bundle_is_assembled() {
+        let example = anchor();
+        assert_eq!(
+            assemble_bundle(&swarm_policy(), example.kyber_signature.signatures.clone()),
+            example.kyber_signature
+        );
+    }
+
+    #[test]
+    fn overflowing_weights_are_rejected() {
+        let hostile = roles(&[(Guardian::StarLord, u32::MAX), (Guardian::Gamora, 1)]);
+        assert!(matches!(
+            QuorumPolicy::new(hostile, 0.5),
+            Err(GlyphError::InvalidPolicy { .. })
+        ));
+        let yaml = format!(
+            "roles:\n  Star-Lord: {{ weight: {} }}\n  Gamora: {{ weight: 1 }}\nselection:\n  min_acceptance_score: 0.5\n",
+            u32::MAX
+        );
+        assert!(QuorumPolicy::from_swarm_roles_yaml(&yaml).is_err());
+    }
+
+    #[test]
+    fn anomaly_anchors_need_drax() {
+        let without_drax: Vec<_> = all_approve()
+            .into_iter()
+            .filter(|(g, _)| *g != Guardian::Drax)
+            .collect();
+        assert!(matches!(
+            verify_quorum(
+                &sign_over(anomaly_anchor(), &swarm_policy(), &without_drax),
+                &swarm_policy(),
+                &guardian_keyring()
+            ),
+            Err(GlyphError::QuorumMissingApproval {
+                guardian: Guardian::Drax
+            })
+        ));
+        let with_drax = sign_over(anomaly_anchor(), &swarm_policy(), &all_approve());
+        assert!(verify_quorum(&with_drax, &swarm_policy(), &guardian_keyring()).is_ok());
+
+        // Enough weight without Drax: only the flag and the anchor decide.
+        let policy = QuorumPolicy::new(roles(&[(Guardian::StarLord, 9), (Guardian::Drax, 1)]), 0.9)
+            .unwrap()
+            .with_drax_approval_on_anomaly(true);
+        let star_lord = [(Guardian::StarLord, Verdict::Approve)];
+        assert!(verify_quorum(
+            &signed_under(&policy, &star_lord),
+            &policy,
+            &guardian_keyring()
+        )
+        .is_ok());
+        assert!(matches!(
+            verify_quorum(
+                &sign_over(anomaly_anchor(), &policy, &star_lord),
+                &policy,
+                &guardian_keyring()
+            ),
+            Err(GlyphError::QuorumMissingApproval { .. })
+        ));
+        let lax = policy.clone().with_drax_approval_on_anomaly(false);
+        assert!(verify_quorum(
+            &sign_over(anomaly_anchor(), &lax, &star_lord),
+            &lax,
+            &guardian_keyring()
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn full_quorum_finalizes() {
+        let mut votes = all_approve();
+        votes.push((Guardian::Nebula, Verdict::Reject));
+        let anchor = signed(&votes);
+        assert_eq!(anchor.kyber_signature.quorum_threshold, 52);
+        assert_eq!(anchor.kyber_signature.quorum_observed, 55);
+
+        let outcome = verify_quorum(&anchor, &swarm_policy(), &guardian_keyring()).expect("quorum");
+        assert_eq!(outcome.approved_weight, 55);
+        assert_eq!(outcome.dissenters, vec![Guardian::Nebula]);
+        assert!(outcome.score >= 0.93);
+    }
+
+    #[test]
+    fn insufficient_weight_is_rejected() {
+        let votes: Vec<_> = all_approve()
+            .into_iter()
+            .filter(|(g, _)| *g != Guardian::Yondu)
+            .collect();
+        assert!(matches!(
+            verify_quorum(&signed(&votes), &swarm_policy(), &guardian_keyring()),
+            Err(GlyphError::QuorumNotMet { .. })
+        ));
+    }
+
+    #[test]
+    fn drax_veto_overrides_weight() {
+        let mut votes: Vec<_> = all_approve()
+            .into_iter()
+            .filter(|(g, _)| *g != Guardian::Drax)
+            .collect();
+        votes.push((Guardian::Drax, Verdict::Reject));
+        assert!(matches!(
+            verify_quorum(&signed(&votes), &swarm_policy(), &guardian_keyring()),
+            Err(GlyphError::QuorumVetoed {
+                guardian: Guardian::Drax
+            })
+        ));
+    }
+
+    #[test]
+    fn finalizer_is_required() {
+        let roles = [
+            (Guardian::StarLord, 1, true),
+            (Guardian::Rocket, 5, false),
+            (Guardian::Groot, 5, false),
+        ]
+        .into_iter()
+        .map(|(g, weight, can_finalize)| {
+            (
+                g,
+                GuardianRole {
+                    weight,
+                    can_finalize,
+                    veto_power: false,
+                },
+            )
+        })
+        .collect();
+        let policy = QuorumPolicy::new(roles, 0.9).unwrap();
+        assert_eq!(policy.required_weight(), 10);
+        let anchor = signed_under(
+            &policy,
+            &[
+                (Guardian::Rocket, Verdict::Approve),
+                (Guardian::Groot, Verdict::Approve),
+            ],
+        );
+        assert!(matches!(
+            verify_quorum(&anchor, &policy, &guardian_keyring()),
+            Err(GlyphError::QuorumNotFinalized)
+        ));
+    }
+
+    #[test]
+    fn tampered_bundles_fail() {
+        let anchor = signed(&all_approve());
+
+        let mut flipped = anchor.clone();
+        flipped.kyber_signature.signatures[4]
+            .metadata
+            .as_mut()
+            .unwrap()
+            .insert("verdict".into(), Value::from("reject"));
+        assert!(matches!(
+            verify_quorum(&flipped, &swarm_policy(), &guardian_keyring()),
+            Err(GlyphError::BadSignature { .. })
+        ));
+
+        let mut duplicated = anchor.clone();
+        let first = duplicated.kyber_signature.signatures[0].clone();
+        duplicated.kyber_signature.signatures.push(first);
+        assert!(verify_quorum(&duplicated, &swarm_policy(), &guardian_keyring()).is_err());
+
+        let mut rehashed = anchor.clone();
+        rehashed.merkle_root = rehashed.blake3_hash.clone();
+        assert!(verify_quorum(&rehashed, &swarm_policy(), &guardian_keyring()).is_err());
+
+        let mut impostor = anchor;
+        impostor.kyber_signature.signatures[0].public_key_id = guardian_key_id(Guardian::Gamora);
+        assert!(verify_quorum(&impostor, &swarm_policy(), &guardian_keyring()).is_err());
+    }
+
+    #[test]
+    fn unsigned_bundle_fields_fail() {
+        let anchor = signed(&all_approve());
+        let unsigned = |anchor: &AnchorGlyph| {
+            matches!(
+                verify_quorum(anchor, &swarm_policy(), &guardian_keyring()),
+                Err(GlyphError::BadSignature { .. })
+            )
+        };
+
+        let mut observed = anchor.clone();
+        observed.kyber_signature.quorum_observed += 1;
+        assert!(unsigned(&observed));
+
+        let mut threshold = anchor.clone();
+        threshold.kyber_signature.quorum_threshold -= 1;
+        assert!(unsigned(&threshold));
+
+        let mut policy = anchor.clone();
+        policy
+            .kyber_signature
+            .metadata
+            .as_mut()
+            .unwrap()
+            .insert("quorum_policy".into(), Value::from("majority"));
+        assert!(unsigned(&policy));
+
+        let mut role = anchor.clone();
+        role.kyber_signature.signatures[0]
+            .metadata
+            .as_mut()
+            .unwrap()
+            .insert("role".into(), Value::from("Gamora"));
+        assert!(unsigned(&role));
+
+        let mut note = anchor;
+        note.kyber_signature.signatures[0]
+            .metadata
+            .as_mut()
+            .unwrap()
+            .insert("note".into(), Value::from("unsigned"));
+        assert!(unsigned(&note));
+    }
+
+    #[test]
+    fn bundle_tampering_is_reported_before_the_quorum() {
+        let short: Vec<_> = all_approve()
+            .into_iter()
+            .filter(|(g, _)| *g != Guardian::Yondu)
+            .collect();
+        let mut inflated = signed(&short);
+        inflated.kyber_signature.quorum_observed = inflated.kyber_signature.quorum_threshold;
+        assert!(matches!(
+            verify_quorum(&inflated, &swarm_policy(), &guardian_keyring()),
+            Err(GlyphError::BadSignature { .. })
+        ));
+
+        let roles = [(Guardian::Rocket, 5), (Guardian::Groot, 5)]
+            .into_iter()
+            .map(|(g, weight)| {
+                (
+                    g,
+                    GuardianRole {
+                        weight,
+                        can_finalize: false,
+                        veto_power: false,
+                    },
+                )
+            })
+            .collect();
+        let policy = QuorumPolicy::new(roles, 0.9).unwrap();
+        let mut unfinalized = signed_under(
+            &policy,
+            &[
+                (Guardian::Rocket, Verdict::Approve),
+                (Guardian::Groot, Verdict::Approve),
+            ],
+        );
+        unfinalized.kyber_signature.quorum_threshold += 1;
+        assert!(matches!(
+            verify_quorum(&unfinalized, &policy, &guardian_keyring()),
+            Err(GlyphError::BadSignature { .. })
+        ));
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn dilithium5_votes_meet_the_quorum() {
+        use glyph_lib::pq::dilithium::DilithiumKeypair;
+        use glyph_lib::pq::envelope::{GlyphSigner, SignatureField, SignatureVerifier};
+        use std::collections::BTreeMap;
+
+        let keys: BTreeMap<Guardian, DilithiumKeypair> = WEIGHTED
+            .iter()
+            .map(|&g| (g, DilithiumKeypair::generate()))
+            .collect();
+        let keyring: BTreeMap<(Guardian, String), Box<dyn SignatureVerifier>> = keys
+            .iter()
+            .map(|(&g, k)| {
+                (
+                    (g, guardian_key_id(g)),
+                    Box::new(k.public_key().clone()) as Box<dyn SignatureVerifier>,
+                )
+            })
+            .collect();
+
+        let mut anchor = anchor();
+        let votes = keys
+            .iter()
+            .map(|(&g, k)| sign_vote(&anchor, g, &guardian_key_id(g), Verdict::Approve, k).unwrap())
+            .collect::<Vec<_>>();
+        for (vote, key) in votes.iter().zip(keys.values()) {
+            match SignatureField::parse(&vote.signature).unwrap() {
+                SignatureField::Envelope(env) => assert_eq!(env.key_id, key.key_id()),
+                SignatureField::Legacy(_) => panic!("votes are enveloped"),
+            }
+        }
+        anchor.kyber_signature = assemble_bundle(&swarm_policy(), votes);
+        let outcome = verify_quorum(&anchor, &swarm_policy(), &keyring).expect("quorum");
+        assert_eq!(outcome.approvers.len(), WEIGHTED.len());
+
+        let mut swapped = anchor.clone();
+        let gamora = keys[&Guardian::Gamora].sign(b"not the vote").to_hex();
+        swapped.kyber_signature.signatures[1].signature = gamora;
+        assert!(matches!(
+            verify_quorum(&swapped, &swarm_policy(), &keyring),
+            Err(GlyphError::BadSignature { .. })
+        ));
+    }
+}
@@ -0,0 +1,207 @@
+#[cfg(test)]
+mod test_receipt_payloads {
+    use glyph_lib::anchors::receipt_payload::{
+        allowed_results, check_receipt, AnomalyDetected, CompactionComplete, ReceiptPayload,
+    };
+    use glyph_lib::{AnomalySeverity, GlyphError, ReceiptGlyph, ReceiptResult, ReceiptType};
+    use serde_json::{json, Value};
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn examples_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../..")
+            .join("glyphs/examples")
+    }
+
+    fn load(name: &str) -> ReceiptGlyph {
+        let path = examples_dir().join(name);
+        let data = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()));
+        serde_json::from_str(&data).expect("receipt json")
+    }
+
+    fn compaction(input: u64, output: u64, reduction: f64) -> CompactionComplete {
+        CompactionComplete {
+            input_row_count: input,
+            output_row_count: output,
+            reduction_percent: Some(reduction),
+            rocksdb_live_bytes_before: None,
+            rocksdb_live_bytes_after: None,
+            pce_transitivity: Some(0.9731),
+            death_triggered: Some(false),
+        }
+    }
+
+    #[test]
+    fn examples_decode_into_their_variant() {
+        let jsonl = fs::read_to_string(examples_dir().join("receipt_glyph.example.jsonl")).unwrap();
+        let mut receipts: Vec<ReceiptGlyph> = jsonl
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        for name in [
+            "receipt_glyph.bore.example.json",
+            "receipt_glyph.orbital.example.json",
+            "receipt_glyph.zk_anomaly.example.json",
+            "receipt_glyph.entanglement.example.json",
+        ] {
+            receipts.push(load(name));
+        }
+        for receipt in &receipts {
+            let payload = ReceiptPayload::of(receipt)
+                .unwrap_or_else(|e| panic!("{}: {e}", receipt.receipt_type));
+            assert_eq!(payload.receipt_type(), receipt.receipt_type);
+            assert_eq!(payload.to_details().unwrap(), receipt.details);
+        }
+
+        match ReceiptPayload::of(&load("receipt_glyph.zk_anomaly.example.json")).unwrap() {
+            ReceiptPayload::ZkAnomalyProof(p) => {
+                assert_eq!(p.public_inputs.len(), 2);
+                assert_eq!(p.anomaly_hint.as_deref(), Some("timing_drift"));
+            }
+            other => panic!("expected a zk payload, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn semantic_violations_are_typed_errors() {
+        let invalid = |receipt: &ReceiptGlyph| {
+            matches!(
+                check_receipt(receipt),
+                Err(GlyphError::InvalidReceiptPayload { .. })
+            )
+        };
+
+        let mut ent = load("receipt_glyph.entanglement.example.json");
+        ent.details["correlation_score"] = json!(1.2);
+        assert!(invalid(&ent));
+        ent.details["correlation_score"] = json!(0.9);
+        ent.details["bell_state"] = json!([1.0, 0.0, 0.0, 1.0]);
+        assert!(invalid(&ent));
+        ent.details["bell_state"] = json!([1.0, 0.0, 0.0]);
+        assert!(invalid(&ent));
+
+        let mut zk = load("receipt_glyph.zk_anomaly.example.json");
+        zk.details.remove("zk_proof");
+        match check_receipt(&zk) {
+            Err(GlyphError::InvalidReceiptPayload {
+                receipt_type,
+                reason,
+            }) => {
+                assert_eq!(receipt_type, ReceiptType::ZkAnomalyProof);
+                assert!(reason.contains("zk_proof"), "{reason}");
+            }
+            other => panic!("expected InvalidReceiptPayload, got {other:?}"),
+        }
+        let mut zk = load("receipt_glyph.zk_anomaly.example.json");
+        zk.details["public_inputs"] = json!(["0x01"]);
+        assert!(invalid(&zk));
+
+        let mut orbital = load("receipt_glyph.orbital.example.json");
+        orbital.details["latency_ms"] = json!(-1.0);
+        assert!(invalid(&orbital));
+
+        let mut bore = load("receipt_glyph.bore.example.json");
+        bore.details
+            .insert("satellite_id".into(), json!("starlink-5508"));
+        assert!(invalid(&bore));
+
+        let halt = ReceiptPayload::AnomalyDetected(AnomalyDetected {
+            severity: AnomalySeverity::Warning,
+            drift_value: 0.4,
+            auto_halt_triggered: Some(true),
+        });
+        assert!(matches!(
+            halt.validate(ReceiptResult::Anomaly),
+            Err(GlyphError::InvalidReceiptPayload { .. })
+        ));
+
+        assert!(matches!(
+            ReceiptPayload::from_details(ReceiptType::SwarmVote, &bore.details),
+            Err(GlyphError::InvalidReceiptPayload { .. })
+        ));
+    }
+
+    #[test]
+    fn fraud_is_reserved_for_verification_receipts() {
+        for receipt_type in ReceiptType::ALL {
+            let fraud = allowed_results(*receipt_type).contains(&ReceiptResult::Fraud);
+            assert_eq!(
+                fraud,
+                matches!(
+                    receipt_type,
+                    ReceiptType::OrbitalTelemetry
+                        | ReceiptType::ZkAnomalyProof
+                        | ReceiptType::AnomalyDetected
+                ),
+                "{receipt_type}"
+            );
+        }
+
+        let mut bore = load("receipt_glyph.bore.example.json");
+        bore.result = ReceiptResult::Fraud;
+        assert!(matches!(
+            check_receipt(&bore),
+            Err(GlyphError::ResultNotAllowed {
+                receipt_type: ReceiptType::BoreProgress,
+                result: ReceiptResult::Fraud,
+            })
+        ));
+
+        let mut zk = load("receipt_glyph.zk_anomaly.example.json");
+        zk.result = ReceiptResult::Fraud;
+        check_receipt(&zk).unwrap();
+
+        let anomaly = ReceiptPayload::AnomalyDetected(AnomalyDetected {
+            severity: AnomalySeverity::Critical,
+            drift_value: 3.2,
+            auto_halt_triggered: Some(true),
+        });
+        anomaly.validate(ReceiptResult::Fraud).unwrap();
+        assert!(matches!(
+            anomaly.validate(ReceiptResult::Ok),
+            Err(GlyphError::ResultNotAllowed { .. })
+        ));
+    }
+
+    #[test]
+    fn compaction_counts_must_agree() {
+        let ok = ReceiptPayload::CompactionComplete(compaction(10_000, 7_512, 24.88));
+        ok.validate(ReceiptResult::Ok).unwrap();
+        #[cfg(feature = "schemas")]
+        {
+            let mut receipt = load("receipt_glyph.bore.example.json");
+            receipt.receipt_type = ReceiptType::CompactionComplete;
+            receipt.emitted_by = glyph_lib::Daemon::DraxMetrics;
+            receipt.details = ok.to_details().unwrap();
+            glyph_lib::schemas::validator::check_glyph(&receipt).unwrap();
+            receipt.details["output_row_count"] = json!(-1);
+            assert!(glyph_lib::schemas::validator::check_glyph(&receipt).is_err());
+        }
+
+        for bad in [compaction(10, 11, 0.0), compaction(10_000, 7_512, 30.0)] {
+            assert!(matches!(
+                ReceiptPayload::CompactionComplete(bad).validate(ReceiptResult::Ok),
+                Err(GlyphError::InvalidReceiptPayload { .. })
+            ));
+        }
+
+        // Live-byte reduction can exceed the row reduction.
+        let mut rocks = compaction(100, 100, 50.0);
+        rocks.rocksdb_live_bytes_before = Some(2_000);
+        rocks.rocksdb_live_bytes_after = Some(1_000);
+        ReceiptPayload::CompactionComplete(rocks)
+            .validate(ReceiptResult::Ok)
+            .unwrap();
+
+        // The weekly compaction script writes explicit nulls.
+        let mut details = ok.to_details().unwrap();
+        details.insert("rocksdb_live_bytes_before".into(), Value::Null);
+        details.insert("rocksdb_live_bytes_after".into(), Value::Null);
+        let decoded =
+            ReceiptPayload::from_details(ReceiptType::CompactionComplete, &details).unwrap();
+        assert_eq!(decoded, ok);
+    }
+}
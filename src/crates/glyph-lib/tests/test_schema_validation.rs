@@ -0,0 +1,163 @@
+#[cfg(test)]
+mod test_schema_validation {
+    use glyph_lib::schemas::validator::{
+        check_glyph, check_value, kind_from_schema_name, schema, validate,
+    };
+    use glyph_lib::{GlyphError, GlyphKind, ReceiptGlyph};
+    use serde_json::{json, Value};
+    use std::fs;
+    use std::io::Write;
+    use std::path::PathBuf;
+    use std::process::{Command, Stdio};
+
+    fn examples_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../..")
+            .join("glyphs")
+            .join("examples")
+    }
+
+    fn load_example(name: &str) -> Value {
+        let path = examples_dir().join(name);
+        let data = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()));
+        serde_json::from_str(&data)
+            .unwrap_or_else(|e| panic!("failed to parse {}: {e}", path.display()))
+    }
+
+    #[test]
+    fn embedded_schemas_match_the_repository() {
+        for (name, kind) in [
+            ("intent_glyph", GlyphKind::Intent),
+            ("receipt_glyph", GlyphKind::Receipt),
+            ("anchor_glyph", GlyphKind::Anchor),
+            ("daemon_status_glyph", GlyphKind::DaemonStatus),
+        ] {
+            assert_eq!(kind_from_schema_name(name), Some(kind));
+            let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("../../..")
+                .join("glyphs/schemas")
+                .join(format!("{name}.schema.json"));
+            let on_disk: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+            assert_eq!(schema(kind), &on_disk, "{name} embedded copy is stale");
+        }
+        assert_eq!(kind_from_schema_name("glyph"), None);
+    }
+
+    #[test]
+    fn examples_are_valid() {
+        for (file, kind) in [
+            ("intent_glyph.example.json", GlyphKind::Intent),
+            ("anchor_glyph.example.json", GlyphKind::Anchor),
+            ("daemon_status_glyph.example.json", GlyphKind::DaemonStatus),
+            ("receipt_glyph.bore.example.json", GlyphKind::Receipt),
+            ("receipt_glyph.orbital.example.json", GlyphKind::Receipt),
+            ("receipt_glyph.zk_anomaly.example.json", GlyphKind::Receipt),
+            (
+                "receipt_glyph.entanglement.example.json",
+                GlyphKind::Receipt,
+            ),
+        ] {
+            let violations = validate(kind, &load_example(file));
+            assert!(violations.is_empty(), "{file}: {violations:?}");
+        }
+
+        let receipt: ReceiptGlyph =
+            serde_json::from_value(load_example("receipt_glyph.bore.example.json")).unwrap();
+        check_glyph(&receipt).expect("typed receipt must validate");
+    }
+
+    #[test]
+    fn violations_carry_pointer_keyword_and_expected_value() {
+        let mut raw = load_example("receipt_glyph.entanglement.example.json");
+        raw["correlation_score"] = json!(0.5);
+        raw.as_object_mut().unwrap().remove("merkle_root");
+        raw["merkle_proof"] = json!({"leaf_index": -1, "siblings": ["zz"]});
+
+        let violations = validate(GlyphKind::Receipt, &raw);
+        let find = |path: &str, keyword: &str| {
+            violations
+                .iter()
+                .find(|v| v.instance_path == path && v.keyword == keyword)
+                .unwrap_or_else(|| panic!("no {keyword} at {path:?} in {violations:?}"))
+        };
+
+        let corr = find("/correlation_score", "minimum");
+        assert_eq!(corr.expected, json!(0.707));
+        assert_eq!(
+            corr.schema_path,
+            "/allOf/3/then/properties/correlation_score/minimum"
+        );
+        assert_eq!(find("", "required").expected, json!("merkle_root"));
+        assert_eq!(
+            find("/merkle_proof/leaf_index", "minimum").expected,
+            json!(0)
+        );
+        assert_eq!(
+            find("/merkle_proof/siblings/0", "pattern").expected,
+            json!("^[a-f0-9]{64}$")
+        );
+        assert_eq!(violations.len(), 4, "{violations:?}");
+
+        match check_value(GlyphKind::Receipt, &raw) {
+            Err(GlyphError::SchemaInvalid { kind, violations }) => {
+                assert_eq!(kind, GlyphKind::Receipt);
+                assert_eq!(violations.len(), 4);
+            }
+            other => panic!("expected SchemaInvalid, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn type_specific_fields_are_closed_per_receipt_type() {
+        let mut raw = load_example("receipt_glyph.bore.example.json");
+        raw["satellite_id"] = json!("starlink-5508");
+        let violations = validate(GlyphKind::Receipt, &raw);
+        assert_eq!(violations.len(), 1, "{violations:?}");
+        assert_eq!(violations[0].instance_path, "/satellite_id");
+        assert_eq!(violations[0].keyword, "unevaluatedProperties");
+
+        let mut status = load_example("daemon_status_glyph.example.json");
+        status["last_anchor_glyph_id"] = Value::Null;
+        assert!(validate(GlyphKind::DaemonStatus, &status).is_empty());
+        status["last_anchor_glyph_id"] = json!("anchor-xyz");
+        assert_eq!(
+            validate(GlyphKind::DaemonStatus, &status)[0].keyword,
+            "pattern"
+        );
+    }
+
+    fn run_cli(args: &[&str], stdin: &str) -> (i32, String) {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_glyph-lib"))
+            .args(args)
+            .stdin(Stdio::piped())
+            .stderr(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()
+            .expect("spawn glyph-lib");
+        // A CLI that rejects its arguments exits without reading stdin.
+        let _ = child.stdin.take().unwrap().write_all(stdin.as_bytes());
+        let out = child.wait_with_output().unwrap();
+        (
+            out.status.code().unwrap_or(-1),
+            String::from_utf8_lossy(&out.stderr).into_owned(),
+        )
+    }
+
+    #[test]
+    fn cli_validates_stdin_like_check_receipts_sh() {
+        let jsonl = fs::read_to_string(examples_dir().join("receipt_glyph.example.jsonl")).unwrap();
+        let (code, _) = run_cli(&["validate", "--type=receipt_glyph"], &jsonl);
+        assert_eq!(code, 0);
+
+        let mut bad = load_example("receipt_glyph.bore.example.json");
+        bad["receipt_type"] = json!("teleport");
+        let (code, stderr) = run_cli(&["validate", "--type=receipt_glyph"], &bad.to_string());
+        assert_eq!(code, 1);
+        assert!(stderr.contains("/receipt_type: enum"), "{stderr}");
+
+        assert_eq!(run_cli(&["validate", "--type=receipt_glyph"], "{").0, 2);
+        assert_eq!(run_cli(&["validate", "--type=nope"], "{}").0, 2);
+        assert_eq!(run_cli(&["validate"], "{}").0, 2);
+    }
+}
@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod test_verify_engine {
+    use glyph_lib::hashing::ids::GlyphId;
+    use glyph_lib::pq::envelope::{sign_receipt, GlyphSigner, KeyId, SignatureVerifier};
+    use glyph_lib::testing::{self, approved, guardian_keyring, swarm_policy, MacKey, WEIGHTED};
+    use glyph_lib::verify::engine::{BatchReport, Check, VerificationEngine};
+    use glyph_lib::{
+        AnchorGlyph, ChainLink, Daemon, GlyphError, GlyphKind, Guardian, ReceiptGlyph,
+    };
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    const TENANT_ID: &str = "xai-memphis-01";
+    const NOW: i64 = 1_764_382_460;
+
+    fn emitter() -> MacKey {
+        MacKey::of("rocket-engine-key-01")
+    }
+
+    fn emitters() -> BTreeMap<(Daemon, KeyId), Box<dyn SignatureVerifier + Send + Sync>> {
+        let key = emitter();
+        BTreeMap::from([(
+            (Daemon::RocketEngine, GlyphSigner::key_id(&key)),
+            Box::new(key) as Box<dyn SignatureVerifier + Send + Sync>,
+        )])
+    }
+
+    fn sealed(count: usize, offset: i64) -> Vec<ReceiptGlyph> {
+        let intent = GlyphId::derive(GlyphKind::Intent, b"verify-engine intent");
+        testing::sealed(TENANT_ID, intent, NOW + offset, count, &emitter())
+    }
+
+    fn anchored(receipts: &[ReceiptGlyph], voters: &[Guardian]) -> AnchorGlyph {
+        let anchor = testing::anchored(receipts, NOW + 100, ChainLink::Genesis);
+        if voters.is_empty() {
+            return anchor;
+        }
+        approved(anchor, voters)
+    }
+
+    fn verify(anchor: &AnchorGlyph, receipts: &[ReceiptGlyph]) -> BatchReport {
+        let (policy, guardians, emitters) = (swarm_policy(), guardian_keyring(), emitters());
+        VerificationEngine::new(&policy, &guardians, &emitters).verify_batch(anchor, receipts)
+    }
+
+    fn failed_checks(report: &BatchReport, receipt: &ReceiptGlyph) -> Vec<Check> {
+        let verdict = report
+            .receipts
+            .iter()
+            .find(|v| v.glyph_id == receipt.receipt_id)
+            .expect("verdict for every input receipt");
+        verdict.failures.iter().map(|f| f.check).collect()
+    }
+
+    #[test]
+    fn a_sealed_batch_verifies_in_any_order() {
+        let receipts = sealed(37, 0);
+        let anchor = anchored(&receipts, &WEIGHTED);
+
+        let mut shuffled = receipts.clone();
+        shuffled.reverse();
+        shuffled.swap(3, 20);
+        let report = verify(&anchor, &shuffled);
+        assert!(report.is_valid(), "{report:#?}");
+        assert!(report.missing.is_empty());
+        assert_eq!(report.quorum.as_ref().unwrap().approvers.len(), 7);
+        let order: Vec<_> = report.receipts.iter().map(|v| v.glyph_id).collect();
+        let expected: Vec<_> = shuffled.iter().map(|r| r.receipt_id).collect();
+        assert_eq!(order, expected);
+        assert!(report.receipts_per_second() > 0.0);
+    }
+
+    #[test]
+    fn each_failure_is_pinned_to_its_glyph_and_check() {
+        let mut receipts = sealed(8, 0);
+        let anchor = anchored(&receipts, &WEIGHTED);
+
+        // Edited payload: the ID, hash and signature no longer match.
+        receipts[1]
+            .details
+            .insert("meters_advanced".into(), json!(99.0));
+        // Re-signed by a key the keyring does not know.
+        sign_receipt(&mut receipts[2], &MacKey::of("rogue")).unwrap();
+        // Proof moved to another leaf.
+        receipts[3].merkle_proof = receipts[4].merkle_proof.clone();
+        // A validly sealed receipt from a different batch.
+        let stray = sealed(1, 500).remove(0);
+        receipts.push(stray.clone());
+
+        let report = verify(&anchor, &receipts);
+        assert!(!report.is_valid());
+        assert!(report.anchor.is_valid(), "{:#?}", report.anchor);
+        assert_eq!(report.rejected().count(), 4);
+
+        assert_eq!(
+            failed_checks(&report, &receipts[1])[..2],
+            [Check::Id, Check::Hash]
+        );
+        assert_eq!(failed_checks(&report, &receipts[2]), [Check::Signature]);
+        assert_eq!(failed_checks(&report, &receipts[3]), [Check::Merkle]);
+        assert_eq!(
+            failed_checks(&report, &stray),
+            [Check::Membership, Check::Merkle]
+        );
+        let stray_verdict = report.rejected().last().unwrap();
+        assert!(matches!(
+            stray_verdict.failures[0].error,
+            GlyphError::NotAnchored { receipt_id, .. } if receipt_id == stray.receipt_id
+        ));
+        for receipt in [&receipts[0], &receipts[5], &receipts[6], &receipts[7]] {
+            assert!(failed_checks(&report, receipt).is_empty());
+        }
+    }
+
+    #[test]
+    fn anchor_failures_and_gaps_are_reported() {
+        let receipts = sealed(5, 0);
+        let unsigned = anchored(&receipts, &[]);
+        let report = verify(&unsigned, &receipts);
+        assert!(report.anchor.failed(Check::Quorum));
+        assert!(report.quorum.is_none());
+        assert!(report.receipts.iter().all(|v| v.is_valid()));
+
+        let anchor = anchored(&receipts, &WEIGHTED);
+        let report = verify(&anchor, &receipts[1..]);
+        assert_eq!(report.missing, [receipts[0].receipt_id]);
+        assert!(report.anchor.is_valid());
+        assert!(!report.is_valid());
+
+        let doubled = [&receipts[..], &receipts[..1]].concat();
+        let report = verify(&anchor, &doubled);
+        assert!(report.receipts[5].failed(Check::Membership));
+        assert!(report.receipts[..5].iter().all(|v| v.is_valid()));
+
+        let mut tampered = anchor.clone();
+        tampered.tenant_id = "someone-else".into();
+        let report = verify(&tampered, &receipts);
+        assert!(report.anchor.failed(Check::Id));
+        assert!(report.anchor.failed(Check::Hash));
+        // Votes cover the claimed hash, so only the hash check catches this.
+        assert!(report.quorum.is_some());
+        assert!(report.receipts.iter().all(|v| v
+            .failures
+            .iter()
+            .map(|f| f.check)
+            .eq([Check::Membership])));
+    }
+}
@@ -0,0 +1,151 @@
+#[cfg(test)]
+mod test_zk_anomaly {
+    use glyph_lib::zk::groth16::{
+        check_zk_extension, commit, public_inputs_hash, random_blinding, DriftBoundProof,
+        DriftBoundProver, DriftBoundVerifier, DRIFT_BOUND_CIRCUIT_ID,
+    };
+    use glyph_lib::{GlyphError, ProofSystem, ReceiptGlyph};
+    use rand_chacha::rand_core::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+    use serde_json::{json, Value};
+    use std::fs;
+    use std::path::PathBuf;
+    use std::sync::OnceLock;
+
+    fn prover() -> &'static DriftBoundProver {
+        static PROVER: OnceLock<DriftBoundProver> = OnceLock::new();
+        PROVER.get_or_init(|| {
+            DriftBoundProver::setup(&mut ChaCha20Rng::seed_from_u64(7)).expect("setup")
+        })
+    }
+
+    fn zk_receipt() -> ReceiptGlyph {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../..")
+            .join("glyphs/examples/receipt_glyph.zk_anomaly.example.json");
+        let data = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()));
+        serde_json::from_str(&data).expect("zk receipt")
+    }
+
+    #[test]
+    fn proves_drift_below_bound_without_revealing_it() {
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        let verifier = prover().verifier();
+        let blinding = random_blinding(&mut rng);
+
+        let proof = prover().prove(0.42, 1.5, blinding, &mut rng).unwrap();
+        verifier.verify(&proof).expect("honest proof verifies");
+        assert_eq!(proof.public.max_drift_percent(), 1.5);
+        assert_eq!(proof.public.commitment, commit(0.42, &blinding).unwrap());
+        assert_ne!(
+            proof.public.commitment,
+            commit(0.42, &random_blinding(&mut rng)).unwrap(),
+            "commitment is blinded"
+        );
+
+        let mut looser = proof.clone();
+        looser.public.max_drift -= 1;
+        assert!(matches!(
+            verifier.verify(&looser),
+            Err(GlyphError::ZkProofRejected)
+        ));
+        let mut other_reading = proof.clone();
+        other_reading.public.commitment = commit(0.41, &blinding).unwrap();
+        assert!(verifier.verify(&other_reading).is_err());
+
+        let other_setup = DriftBoundProver::setup(&mut ChaCha20Rng::seed_from_u64(8)).unwrap();
+        assert!(other_setup.verifier().verify(&proof).is_err());
+    }
+
+    #[test]
+    fn refuses_drift_at_or_above_bound() {
+        let mut rng = ChaCha20Rng::seed_from_u64(2);
+        let blinding = random_blinding(&mut rng);
+        for drift in [1.5, 2.0] {
+            assert!(matches!(
+                prover().prove(drift, 1.5, blinding, &mut rng),
+                Err(GlyphError::DriftExceedsBound { .. })
+            ));
+        }
+        assert!(matches!(
+            prover().prove(-0.1, 1.5, blinding, &mut rng),
+            Err(GlyphError::InvalidZkProof { .. })
+        ));
+        assert!(prover().prove(1.4999, 1.5, blinding, &mut rng).is_ok());
+    }
+
+    #[test]
+    fn receipt_round_trip_matches_schema_shape() {
+        let mut rng = ChaCha20Rng::seed_from_u64(3);
+        let verifier = prover().verifier();
+        let proof = prover()
+            .prove(0.8, 2.0, random_blinding(&mut rng), &mut rng)
+            .unwrap();
+
+        let mut receipt = zk_receipt();
+        proof.write_details(&mut receipt.details);
+        let zk = &receipt.details["zk_proof"];
+        assert_eq!(zk["pi_a"].as_array().unwrap().len(), 2);
+        assert_eq!(zk["pi_b"][1].as_array().unwrap().len(), 2);
+        assert_eq!(
+            receipt.details["public_inputs"].as_array().unwrap().len(),
+            2
+        );
+        #[cfg(feature = "schemas")]
+        glyph_lib::schemas::validator::check_glyph(&receipt).expect("schema-valid receipt");
+
+        let public = verifier.verify_receipt(&receipt).unwrap();
+        assert_eq!(public, proof.public);
+        assert_eq!(DriftBoundProof::from_receipt(&receipt).unwrap(), proof);
+
+        let mut tampered = receipt.clone();
+        let pi_c = tampered.details["zk_proof"]["pi_c"].clone();
+        tampered.details["zk_proof"]["pi_a"] = pi_c;
+        assert!(verifier.verify_receipt(&tampered).is_err());
+
+        let mut garbled = receipt;
+        garbled.details["zk_proof"]["pi_b"][0][0] = json!("zz");
+        assert!(matches!(
+            verifier.verify_receipt(&garbled),
+            Err(GlyphError::InvalidZkProof { .. })
+        ));
+
+        // The shape-only example fixture is not a real proof.
+        assert!(verifier.verify_receipt(&zk_receipt()).is_err());
+    }
+
+    #[test]
+    fn anchor_extension_commits_to_public_inputs() {
+        let mut rng = ChaCha20Rng::seed_from_u64(4);
+        let proof = prover()
+            .prove(0.1, 0.5, random_blinding(&mut rng), &mut rng)
+            .unwrap();
+        let hex_inputs = proof.public.to_hex();
+        let mut concat = Vec::new();
+        for input in &hex_inputs {
+            assert_eq!(input.len(), 64);
+            concat.extend(hex::decode(input).unwrap());
+        }
+        let expected = blake3::hash(&concat).to_hex().to_string();
+        assert_eq!(public_inputs_hash(&hex_inputs).unwrap(), expected);
+
+        let ext = proof
+            .public
+            .zk_extension("receipt-1752145ceaa5397afd19880e8133947f");
+        assert_eq!(ext.proof_system, Some(ProofSystem::Groth16));
+        assert_eq!(ext.circuit_id.as_deref(), Some(DRIFT_BOUND_CIRCUIT_ID));
+        assert_eq!(ext.public_inputs_hash.as_deref(), Some(expected.as_str()));
+        check_zk_extension(&ext, &proof.public).unwrap();
+
+        let raw: Value = serde_json::to_value(&ext).unwrap();
+        assert_eq!(raw["proof_system"], json!("groth16"));
+
+        let mut other = proof.public;
+        other.max_drift += 1;
+        assert!(check_zk_extension(&ext, &other).is_err());
+
+        let restored = DriftBoundVerifier::from_bytes(&prover().verifier().to_bytes()).unwrap();
+        restored.verify(&proof).unwrap();
+    }
+}
@@ -0,0 +1,556 @@
+//! Three-way consistency audit of the ledger.
+//!
+//! The JSONL log, the SQLite hot path and the RocksDB cold archive must
+//! agree on every tenant's receipts and anchors. [`Auditor::run`] streams
+//! all three and compares, per tenant:
+//!
+//! - the receipt and anchor IDs in the log against those in either store:
+//!   a logged glyph neither store holds is [`Divergence::Missing`] from the
+//!   layer that should hold it, and a stored one the log never recorded is
+//!   [`Divergence::Extra`];
+//! - each stored receipt's `blake3_hash` and each stored anchor's
+//!   `merkle_root` against the log's;
+//! - every record against itself: IDs and hashes are re-derived, and each
+//!   anchor's tree is rebuilt and checked against its `merkle_root`.
+//!
+//! A receipt may sit in both stores, when a later window shares it or an
+//! archive run stopped before evicting; both copies are checked.
+
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::io::BufRead;
+
+use glyph_lib::anchors::merkle::{hash_from_hex, hash_to_hex, receipt_leaf, MerkleTree};
+use glyph_lib::hashing::ids::verify_id;
+use glyph_lib::{AnchorGlyph, Glyph, GlyphId, ReceiptGlyph};
+
+use crate::error::{LedgerError, Result};
+use crate::log::{LogGlyph, LogReader};
+use crate::rocksdb_store::{ArchivedAnchor, RocksDbStore};
+use crate::sqlite_store::{hash_matches, SqliteStore};
+
+/// One of the three ledger layers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Layer {
+    Log,
+    Sqlite,
+    RocksDb,
+}
+
+impl Layer {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Layer::Log => "log",
+            Layer::Sqlite => "sqlite",
+            Layer::RocksDb => "rocksdb",
+        }
+    }
+}
+
+impl fmt::Display for Layer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One disagreement between the layers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Divergence {
+    /// A logged receipt that `layer` should hold and does not.
+    Missing { layer: Layer, glyph_id: GlyphId },
+    /// A stored glyph the log never recorded, or a log line repeating an ID.
+    Extra { layer: Layer, glyph_id: GlyphId },
+    /// `layer`'s copy of a receipt has another `blake3_hash` than the log's.
+    HashMismatch {
+        layer: Layer,
+        glyph_id: GlyphId,
+        expected: String,
+        found: String,
+    },
+    /// `layer`'s copy of an anchor commits to another `merkle_root` than
+    /// the log's.
+    AnchorMismatch {
+        layer: Layer,
+        anchor_id: GlyphId,
+        expected: String,
+        found: String,
+    },
+    /// An anchor whose stored tree no longer has its `merkle_root`.
+    RootMismatch {
+        layer: Layer,
+        anchor_id: GlyphId,
+        expected: String,
+        found: String,
+    },
+    /// A record that no longer decodes or verifies on its own.
+    Tampered {
+        layer: Layer,
+        glyph_id: String,
+        reason: String,
+    },
+    /// A log line that is not a glyph; `offset` is its first byte.
+    Malformed {
+        line: u64,
+        offset: u64,
+        reason: String,
+    },
+}
+
+impl Divergence {
+    /// The glyph this divergence is about, if it names one.
+    pub fn glyph_id(&self) -> Option<GlyphId> {
+        match self {
+            Divergence::Missing { glyph_id, .. }
+            | Divergence::Extra { glyph_id, .. }
+            | Divergence::HashMismatch { glyph_id, .. } => Some(*glyph_id),
+            Divergence::AnchorMismatch { anchor_id, .. }
+            | Divergence::RootMismatch { anchor_id, .. } => Some(*anchor_id),
+            Divergence::Tampered { glyph_id, .. } => glyph_id.parse().ok(),
+            Divergence::Malformed { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Divergence::Missing { layer, glyph_id } => write!(f, "{glyph_id} missing from {layer}"),
+            Divergence::Extra { layer, glyph_id } => {
+                write!(f, "{glyph_id} in {layer} but not in the log")
+            }
+            Divergence::HashMismatch {
+                layer,
+                glyph_id,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{glyph_id} in {layer} has blake3_hash {found}, the log has {expected}"
+            ),
+            Divergence::AnchorMismatch {
+                layer,
+                anchor_id,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{anchor_id} in {layer} has merkle_root {found}, the log has {expected}"
+            ),
+            Divergence::RootMismatch {
+                layer,
+                anchor_id,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{anchor_id} in {layer} commits to {expected}, its tree gives {found}"
+            ),
+            Divergence::Tampered {
+                layer,
+                glyph_id,
+                reason,
+            } => write!(f, "{glyph_id} in {layer} does not verify: {reason}"),
+            Divergence::Malformed {
+                line,
+                offset,
+                reason,
+            } => write!(
+                f,
+                "log line {line} (byte {offset}) is not a glyph: {reason}"
+            ),
+        }
+    }
+}
+
+/// What the audit found for one tenant.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TenantAudit {
+    pub log_receipts: usize,
+    pub log_anchors: usize,
+    pub hot_receipts: usize,
+    pub cold_receipts: usize,
+    pub hot_anchors: usize,
+    pub cold_anchors: usize,
+    /// The newest anchor seen, hot before cold: the ledger state the
+    /// divergences cast doubt on.
+    pub latest_anchor: Option<GlyphId>,
+    pub divergences: Vec<Divergence>,
+}
+
+/// The outcome of one [`Auditor::run`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditReport {
+    pub tenants: BTreeMap<String, TenantAudit>,
+    /// Log lines that could not be read as a glyph, so belong to no
+    /// tenant.
+    pub malformed: Vec<Divergence>,
+}
+
+impl AuditReport {
+    /// True if all three layers agree.
+    pub fn is_consistent(&self) -> bool {
+        self.malformed.is_empty() && self.diverged().next().is_none()
+    }
+
+    /// Tenants with at least one divergence.
+    pub fn diverged(&self) -> impl Iterator<Item = (&str, &TenantAudit)> {
+        self.tenants
+            .iter()
+            .filter(|(_, t)| !t.divergences.is_empty())
+            .map(|(id, t)| (id.as_str(), t))
+    }
+}
+
+/// `glyph`, if its ID and `blake3_hash` are the ones its content derives.
+fn checked<G: Glyph>(glyph: G, blake3_hash: impl Fn(&G) -> &str) -> Result<G> {
+    verify_id(&glyph)?;
+    hash_matches(&glyph, blake3_hash(&glyph))?;
+    Ok(glyph)
+}
+
+fn tampered(layer: Layer, glyph_id: &str, err: LedgerError) -> Divergence {
+    Divergence::Tampered {
+        layer,
+        glyph_id: glyph_id.to_string(),
+        reason: err.to_string(),
+    }
+}
+
+/// Compares the log against both stores.
+pub struct Auditor<'a> {
+    sqlite: &'a SqliteStore,
+    rocks: &'a RocksDbStore,
+}
+
+impl<'a> Auditor<'a> {
+    pub fn new(sqlite: &'a SqliteStore, rocks: &'a RocksDbStore) -> Self {
+        Auditor { sqlite, rocks }
+    }
+
+    /// Streams `log` and both stores and reports every divergence.
+    ///
+    /// Only failures to read a layer at all are errors; anything the
+    /// layers hold is reported, not returned.
+    pub fn run(&self, log: impl BufRead) -> Result<AuditReport> {
+        let mut report = AuditReport::default();
+        let logged = read_log(log, &mut report)?;
+        let mut tenants: BTreeSet<String> = report.tenants.keys().cloned().collect();
+        tenants.extend(self.sqlite.tenants().map(str::to_string));
+        tenants.extend(self.rocks.tenants().map(str::to_string));
+        let empty = Logged::default();
+        for tenant_id in tenants {
+            let audit = report.tenants.entry(tenant_id.clone()).or_default();
+            self.tenant(&tenant_id, logged.get(&tenant_id).unwrap_or(&empty), audit)?;
+        }
+        Ok(report)
+    }
+
+    fn tenant(&self, tenant_id: &str, logged: &Logged, audit: &mut TenantAudit) -> Result<()> {
+        let mut hot = BTreeMap::new();
+        self.sqlite
+            .each_glyph::<ReceiptGlyph>(tenant_id, |id, receipt| {
+                audit.hot_receipts += 1;
+                match receipt.and_then(|r| checked(r, |r| &r.blake3_hash)) {
+                    Ok(r) => {
+                        hot.insert(r.receipt_id, r.blake3_hash);
+                    }
+                    Err(e) => audit.divergences.push(tampered(Layer::Sqlite, id, e)),
+                }
+                Ok(())
+            })?;
+        let mut hot_anchors = BTreeMap::new();
+        self.sqlite
+            .each_glyph::<AnchorGlyph>(tenant_id, |id, anchor| {
+                audit.hot_anchors += 1;
+                match anchor.and_then(|a| checked(a, |a| &a.blake3_hash)) {
+                    Ok(anchor) => {
+                        self.hot_window(&anchor, audit)?;
+                        audit.latest_anchor = Some(anchor.glyph_id);
+                        hot_anchors.insert(anchor.glyph_id, anchor.merkle_root);
+                    }
+                    Err(e) => audit.divergences.push(tampered(Layer::Sqlite, id, e)),
+                }
+                Ok(())
+            })?;
+
+        let mut cold = BTreeMap::new();
+        let mut cold_anchors = BTreeMap::new();
+        if self.rocks.tenants().any(|t| t == tenant_id) {
+            self.rocks.each_receipt(tenant_id, |id, receipt| {
+                audit.cold_receipts += 1;
+                match receipt.and_then(|r| checked(r, |r| &r.blake3_hash)) {
+                    Ok(r) => {
+                        // Point lookups go through the `glyphs` index, so
+                        // it must lead to this copy.
+                        let meta = self.rocks.meta(tenant_id, &r.receipt_id)?;
+                        if meta.is_none_or(|m| {
+                            m.blake3_hash != r.blake3_hash || m.timestamp != r.timestamp
+                        }) {
+                            audit.divergences.push(Divergence::Tampered {
+                                layer: Layer::RocksDb,
+                                glyph_id: id.to_string(),
+                                reason: "index entry missing or stale".into(),
+                            });
+                        }
+                        cold.insert(r.receipt_id, r.blake3_hash);
+                    }
+                    Err(e) => audit.divergences.push(tampered(Layer::RocksDb, id, e)),
+                }
+                Ok(())
+            })?;
+            self.rocks.each_anchor(tenant_id, |id, archived| {
+                audit.cold_anchors += 1;
+                let archived = archived.and_then(|a| {
+                    checked(a.anchor, |a| &a.blake3_hash).map(|anchor| ArchivedAnchor {
+                        anchor,
+                        leaves: a.leaves,
+                    })
+                });
+                match archived {
+                    Ok(archived) => {
+                        let anchor_id = archived.anchor.glyph_id;
+                        self.cold_window(&archived, audit)?;
+                        if hot_anchors.is_empty() {
+                            audit.latest_anchor = Some(anchor_id);
+                        }
+                        if !hot_anchors.contains_key(&anchor_id)
+                            && !self.sqlite.is_archived(&anchor_id)?
+                        {
+                            audit.divergences.push(Divergence::Extra {
+                                layer: Layer::RocksDb,
+                                glyph_id: anchor_id,
+                            });
+                        }
+                        cold_anchors.insert(anchor_id, archived.anchor.merkle_root);
+                    }
+                    Err(e) => audit.divergences.push(tampered(Layer::RocksDb, id, e)),
+                }
+                Ok(())
+            })?;
+        }
+
+        self.compare(
+            &logged.receipts,
+            &hot,
+            &cold,
+            |layer, glyph_id, expected, found| Divergence::HashMismatch {
+                layer,
+                glyph_id,
+                expected: expected.to_string(),
+                found: found.to_string(),
+            },
+            audit,
+        )?;
+        self.compare(
+            &logged.anchors,
+            &hot_anchors,
+            &cold_anchors,
+            |layer, anchor_id, expected, found| Divergence::AnchorMismatch {
+                layer,
+                anchor_id,
+                expected: expected.to_string(),
+                found: found.to_string(),
+            },
+            audit,
+        )
+    }
+
+    /// Holds the stores' `hot` and `cold` copies to the log's: every logged
+    /// ID must be stored with the logged value, and every stored ID logged.
+    fn compare(
+        &self,
+        logged: &BTreeMap<GlyphId, String>,
+        hot: &BTreeMap<GlyphId, String>,
+        cold: &BTreeMap<GlyphId, String>,
+        mismatch: impl Fn(Layer, GlyphId, &str, &str) -> Divergence,
+        audit: &mut TenantAudit,
+    ) -> Result<()> {
+        for (glyph_id, expected) in logged {
+            let copies = [
+                (Layer::Sqlite, hot.get(glyph_id)),
+                (Layer::RocksDb, cold.get(glyph_id)),
+            ];
+            if copies.iter().all(|(_, found)| found.is_none()) {
+                let layer = if self.sqlite.is_archived(glyph_id)? {
+                    Layer::RocksDb
+                } else {
+                    Layer::Sqlite
+                };
+                audit.divergences.push(Divergence::Missing {
+                    layer,
+                    glyph_id: *glyph_id,
+                });
+            }
+            for (layer, found) in copies {
+                if let Some(found) = found.filter(|found| *found != expected) {
+                    let divergence = mismatch(layer, *glyph_id, expected, found);
+                    audit.divergences.push(divergence);
+                }
+            }
+        }
+        for (layer, stored) in [(Layer::Sqlite, hot), (Layer::RocksDb, cold)] {
+            for glyph_id in stored.keys().filter(|id| !logged.contains_key(id)) {
+                let extra = Divergence::Extra {
+                    layer,
+                    glyph_id: *glyph_id,
+                };
+                // An archived anchor SQLite never held is flagged already.
+                if !audit.divergences.contains(&extra) {
+                    audit.divergences.push(extra);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Rebuilds a hot anchor's tree from the stored receipts.
+    fn hot_window(&self, anchor: &AnchorGlyph, audit: &mut TenantAudit) -> Result<()> {
+        let window = match self.sqlite.window(&anchor.glyph_id) {
+            Ok(Some(window)) => window,
+            Ok(None) => return Ok(()),
+            Err(LedgerError::MissingReceipt { receipt_id, .. }) => {
+                audit.divergences.push(Divergence::Missing {
+                    layer: Layer::Sqlite,
+                    glyph_id: receipt_id,
+                });
+                return Ok(());
+            }
+            Err(e) => {
+                audit
+                    .divergences
+                    .push(tampered(Layer::Sqlite, &anchor.glyph_id.to_string(), e));
+                return Ok(());
+            }
+        };
+        let found = hash_to_hex(&window.tree()?.root());
+        if found != anchor.merkle_root {
+            audit.divergences.push(Divergence::RootMismatch {
+                layer: Layer::Sqlite,
+                anchor_id: anchor.glyph_id,
+                expected: anchor.merkle_root.clone(),
+                found,
+            });
+        }
+        Ok(())
+    }
+
+    /// Rebuilds a cold anchor's tree from its archived leaves and checks
+    /// each leaf against the archived receipt it stands for.
+    fn cold_window(&self, archived: &ArchivedAnchor, audit: &mut TenantAudit) -> Result<()> {
+        let anchor = &archived.anchor;
+        let leaves = match archived
+            .leaves
+            .iter()
+            .map(|l| hash_from_hex(l))
+            .collect::<glyph_lib::Result<Vec<_>>>()
+        {
+            Ok(leaves) => leaves,
+            Err(e) => {
+                audit.divergences.push(tampered(
+                    Layer::RocksDb,
+                    &anchor.glyph_id.to_string(),
+                    e.into(),
+                ));
+                return Ok(());
+            }
+        };
+        let found = hash_to_hex(&MerkleTree::from_leaf_hashes(leaves).root());
+        if found != anchor.merkle_root {
+            audit.divergences.push(Divergence::RootMismatch {
+                layer: Layer::RocksDb,
+                anchor_id: anchor.glyph_id,
+                expected: anchor.merkle_root.clone(),
+                found,
+            });
+        }
+        for (i, r) in anchor.receipts.iter().enumerate() {
+            let Some(receipt) = self.rocks.receipt(&anchor.tenant_id, &r.receipt_id)? else {
+                audit.divergences.push(Divergence::Missing {
+                    layer: Layer::RocksDb,
+                    glyph_id: r.receipt_id,
+                });
+                continue;
+            };
+            if archived.leaves.get(i) != Some(&hash_to_hex(&receipt_leaf(&receipt)?)) {
+                audit.divergences.push(Divergence::Tampered {
+                    layer: Layer::RocksDb,
+                    glyph_id: anchor.glyph_id.to_string(),
+                    reason: format!("leaf {i} is not {}", r.receipt_id),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What the log holds for one tenant.
+#[derive(Debug, Default)]
+struct Logged {
+    /// `receipt_id → blake3_hash`.
+    receipts: BTreeMap<GlyphId, String>,
+    /// `anchor_id → merkle_root`.
+    anchors: BTreeMap<GlyphId, String>,
+}
+
+/// Reads the log per tenant, noting unreadable lines, receipts and
+/// anchors that do not verify and repeated IDs.
+///
+/// Intents are skipped: nothing in either store is checked against them.
+fn read_log(log: impl BufRead, report: &mut AuditReport) -> Result<BTreeMap<String, Logged>> {
+    let mut logged: BTreeMap<String, Logged> = BTreeMap::new();
+    let mut reader = LogReader::new(log);
+    while let Some(line) = reader.next_line()? {
+        // A line that no longer verifies still claims a hash or root the
+        // stores can be held to.
+        let (tenant_id, glyph_id, value, verified, is_anchor) = match line.glyph {
+            Ok(LogGlyph::Receipt(r)) => (
+                r.tenant_id.clone(),
+                r.receipt_id,
+                r.blake3_hash.clone(),
+                checked(r, |r| &r.blake3_hash).map(drop),
+                false,
+            ),
+            Ok(LogGlyph::Anchor(a)) => (
+                a.tenant_id.clone(),
+                a.glyph_id,
+                a.merkle_root.clone(),
+                checked(*a, |a| &a.blake3_hash).map(drop),
+                true,
+            ),
+            Ok(LogGlyph::Intent(_)) => continue,
+            Err(e) => {
+                report.malformed.push(Divergence::Malformed {
+                    line: line.line,
+                    offset: line.offset,
+                    reason: e.to_string(),
+                });
+                continue;
+            }
+        };
+        let audit = report.tenants.entry(tenant_id.clone()).or_default();
+        let logged = logged.entry(tenant_id).or_default();
+        let seen = if is_anchor {
+            audit.log_anchors += 1;
+            &mut logged.anchors
+        } else {
+            audit.log_receipts += 1;
+            &mut logged.receipts
+        };
+        if let Err(e) = verified {
+            audit
+                .divergences
+                .push(tampered(Layer::Log, &glyph_id.to_string(), e));
+        }
+        match seen.entry(glyph_id) {
+            Entry::Occupied(_) => audit.divergences.push(Divergence::Extra {
+                layer: Layer::Log,
+                glyph_id,
+            }),
+            Entry::Vacant(v) => {
+                v.insert(value);
+            }
+        }
+    }
+    Ok(logged)
+}
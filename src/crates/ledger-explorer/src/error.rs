@@ -0,0 +1,105 @@
+//! Error type shared by every ledger-explorer module.
+
+use glyph_lib::{GlyphError, GlyphId};
+use thiserror::Error;
+
+/// Everything that can stop a glyph from being stored or served.
+#[derive(Debug, Error)]
+pub enum LedgerError {
+    /// The glyph itself is malformed or does not verify.
+    #[error(transparent)]
+    Glyph(#[from] GlyphError),
+
+    /// The SQLite hot path failed, including an append-only trigger firing.
+    #[cfg(feature = "rusqlite")]
+    #[error("sqlite: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+
+    /// The RocksDB cold archive failed.
+    #[cfg(feature = "rocksdb")]
+    #[error("rocksdb: {0}")]
+    RocksDb(#[from] rocksdb::Error),
+
+    /// Reading the JSONL log failed.
+    #[error("io: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// A glyph with this ID is already in the ledger; appends never replace.
+    #[error("{glyph_id} is already in the ledger")]
+    Duplicate { glyph_id: GlyphId },
+
+    /// The tenant has no block in the ledger config, so it has no cap.
+    #[error("tenant {tenant_id:?} is not configured for this ledger")]
+    UnknownTenant { tenant_id: String },
+
+    /// Appending would take the tenant past its `max_size_bytes`.
+    #[error(
+        "tenant {tenant_id} is full: {used} + {adding} bytes exceeds its cap of {max_size_bytes}"
+    )]
+    TenantFull {
+        tenant_id: String,
+        used: u64,
+        adding: u64,
+        max_size_bytes: u64,
+    },
+
+    /// An anchor commits to a receipt the ledger has not stored.
+    #[error("anchor {anchor_id} references receipt {receipt_id}, which is not in the ledger")]
+    MissingReceipt {
+        anchor_id: GlyphId,
+        receipt_id: GlyphId,
+    },
+
+    /// An anchor's window holds a receipt of another tenant.
+    #[error("anchor {anchor_id} of {tenant_id} holds receipt {receipt_id} of {receipt_tenant}")]
+    TenantMismatch {
+        anchor_id: GlyphId,
+        tenant_id: String,
+        receipt_id: GlyphId,
+        receipt_tenant: String,
+    },
+
+    /// A stored row no longer decodes; the database was edited outside
+    /// the store.
+    #[error("corrupt ledger row for {glyph_id}: {reason}")]
+    Corrupt { glyph_id: String, reason: String },
+
+    /// The last audit found the ledger layers disagreeing for this tenant,
+    /// so no proof is served until an audit comes back clean.
+    #[error(
+        "proofs for tenant {tenant_id} are refused: the last audit found {divergences} divergences"
+    )]
+    ProofsRefused {
+        tenant_id: String,
+        divergences: usize,
+    },
+
+    /// No audit has found the tenant's layers agreeing since the ledger
+    /// was opened.
+    #[error("proofs for tenant {tenant_id} are refused until an audit clears it")]
+    NotAudited { tenant_id: String },
+
+    /// A replay checkpoint does not describe the log it is resumed on.
+    #[error("checkpoint at byte {offset} does not match the log: {reason}")]
+    CheckpointMismatch { offset: u64, reason: String },
+
+    /// A provenance hop whose evidence does not check out.
+    #[error("hop {from} -> {to} does not verify: {reason}")]
+    BadEvidence {
+        from: GlyphId,
+        to: GlyphId,
+        reason: String,
+    },
+
+    /// A ledger SQL statement outside the read-only dialect.
+    #[error("unsupported query: {reason}")]
+    BadQuery { reason: String },
+
+    /// A ledger SQL statement pinning a tenant other than the one running
+    /// it.
+    #[error("query run for tenant {tenant_id} pins tenant_id = {pinned:?}")]
+    WrongTenant { tenant_id: String, pinned: String },
+}
+
+/// Convenience alias used throughout ledger-explorer.
+pub type Result<T> = std::result::Result<T, LedgerError>;
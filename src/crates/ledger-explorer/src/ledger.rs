@@ -0,0 +1,192 @@
+//! Both stores behind the one path that serves proofs.
+//!
+//! "All three must agree": [`Ledger::audit`] runs the [`Auditor`], and
+//! every tenant it finds a divergence for gets an `anomaly_detected`
+//! receipt and stops being served proofs until an audit comes back clean.
+//! A log line that belongs to no tenant stops proofs for every tenant.
+//!
+//! A ledger fails closed: no tenant is served until an audit has found
+//! its layers agreeing.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::BufRead;
+
+use glyph_lib::anchors::receipt_payload::{AnomalyDetected, ReceiptPayload};
+use glyph_lib::builders::receipt::ReceiptBuilder;
+use glyph_lib::pq::envelope::GlyphSigner;
+use glyph_lib::{AnomalySeverity, Daemon, GlyphId, GlyphKind, ReceiptGlyph, ReceiptResult};
+
+use crate::audit::{AuditReport, Auditor, TenantAudit};
+use crate::error::{LedgerError, Result};
+use crate::rocksdb_store::RocksDbStore;
+use crate::sqlite_store::{AnchoredProof, SqliteStore};
+
+/// What [`Ledger::audit`] found and what it emitted about it.
+#[derive(Debug, Clone)]
+pub struct AuditOutcome {
+    pub report: AuditReport,
+    /// One `anomaly_detected` receipt per diverged tenant with an anchor
+    /// to reference, to be appended to the log like any other receipt.
+    pub anomalies: Vec<ReceiptGlyph>,
+    /// Diverged tenants with no anchor for an anomaly receipt to
+    /// reference; refused all the same.
+    pub unreported: Vec<String>,
+}
+
+/// The hot path and the cold archive, serving proofs while they agree.
+pub struct Ledger {
+    sqlite: SqliteStore,
+    rocks: RocksDbStore,
+    /// Tenants the last audit found consistent; no other tenant is served.
+    cleared: BTreeSet<String>,
+    /// Divergences the last audit found, per refused tenant.
+    refused: BTreeMap<String, usize>,
+    /// Log lines the last audit could not attribute to a tenant.
+    malformed: usize,
+}
+
+impl Ledger {
+    pub fn new(sqlite: SqliteStore, rocks: RocksDbStore) -> Self {
+        Ledger {
+            sqlite,
+            rocks,
+            cleared: BTreeSet::new(),
+            refused: BTreeMap::new(),
+            malformed: 0,
+        }
+    }
+
+    pub fn sqlite(&self) -> &SqliteStore {
+        &self.sqlite
+    }
+
+    pub fn sqlite_mut(&mut self) -> &mut SqliteStore {
+        &mut self.sqlite
+    }
+
+    pub fn rocks(&self) -> &RocksDbStore {
+        &self.rocks
+    }
+
+    /// Both stores at once, for moving windows from one to the other.
+    pub fn stores_mut(&mut self) -> (&mut SqliteStore, &RocksDbStore) {
+        (&mut self.sqlite, &self.rocks)
+    }
+
+    /// Errors with [`LedgerError::ProofsRefused`] if the last audit found
+    /// `tenant_id`'s layers disagreeing, and with [`LedgerError::NotAudited`]
+    /// if no audit has covered `tenant_id` yet.
+    pub fn check_serving(&self, tenant_id: &str) -> Result<()> {
+        let divergences = self.refused.get(tenant_id).copied().unwrap_or(0) + self.malformed;
+        if divergences > 0 {
+            return Err(LedgerError::ProofsRefused {
+                tenant_id: tenant_id.to_string(),
+                divergences,
+            });
+        }
+        if !self.cleared.contains(tenant_id) {
+            return Err(LedgerError::NotAudited {
+                tenant_id: tenant_id.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Inclusion proof of one of `tenant_id`'s receipts, from the hot path
+    /// if it is anchored there, else from the cold archive.
+    pub fn receipt_proof(
+        &self,
+        tenant_id: &str,
+        receipt_id: &GlyphId,
+    ) -> Result<Option<AnchoredProof>> {
+        self.check_serving(tenant_id)?;
+        let hot = match self.sqlite.receipt(receipt_id)? {
+            Some(receipt) if receipt.tenant_id == tenant_id => {
+                self.sqlite.receipt_proof(receipt_id)?
+            }
+            _ => None,
+        };
+        match hot {
+            Some(proof) => Ok(Some(proof)),
+            None => self.rocks.receipt_proof(tenant_id, receipt_id),
+        }
+    }
+
+    /// Audits both stores against `log`, refuses proofs for every tenant
+    /// that diverged and resumes them for every tenant that did not.
+    pub fn audit(
+        &mut self,
+        log: impl BufRead,
+        signer: &dyn GlyphSigner,
+        now: i64,
+    ) -> Result<AuditOutcome> {
+        let report = self.check(log)?;
+        let mut anomalies = Vec::new();
+        let mut unreported = Vec::new();
+        for (tenant_id, audit) in report.diverged() {
+            match anomaly(tenant_id, audit, signer, now)? {
+                Some(receipt) => anomalies.push(receipt),
+                None => unreported.push(tenant_id.to_string()),
+            }
+        }
+        Ok(AuditOutcome {
+            report,
+            anomalies,
+            unreported,
+        })
+    }
+
+    /// [`Ledger::audit`] without the anomaly receipts, for read-only
+    /// callers that have no key to sign them with.
+    pub fn check(&mut self, log: impl BufRead) -> Result<AuditReport> {
+        let report = Auditor::new(&self.sqlite, &self.rocks).run(log)?;
+        self.cleared = report
+            .tenants
+            .iter()
+            .filter(|(_, audit)| audit.divergences.is_empty())
+            .map(|(tenant_id, _)| tenant_id.clone())
+            .collect();
+        self.refused = report
+            .diverged()
+            .map(|(tenant_id, audit)| (tenant_id.to_string(), audit.divergences.len()))
+            .collect();
+        self.malformed = report.malformed.len();
+        Ok(report)
+    }
+}
+
+/// The `anomaly_detected` receipt for a diverged tenant: critical, with
+/// `drift_value` the number of divergences. `ref_glyph_id` is the first
+/// anchor named, else the tenant's newest anchor; `None` if the tenant has
+/// no anchor to reference.
+fn anomaly(
+    tenant_id: &str,
+    audit: &TenantAudit,
+    signer: &dyn GlyphSigner,
+    now: i64,
+) -> Result<Option<ReceiptGlyph>> {
+    let ref_glyph_id = audit
+        .divergences
+        .iter()
+        .filter_map(|d| d.glyph_id())
+        .find(|id| id.kind() == GlyphKind::Anchor)
+        .or(audit.latest_anchor);
+    let Some(ref_glyph_id) = ref_glyph_id else {
+        return Ok(None);
+    };
+    let payload = ReceiptPayload::AnomalyDetected(AnomalyDetected {
+        severity: AnomalySeverity::Critical,
+        drift_value: audit.divergences.len() as f64,
+        auto_halt_triggered: None,
+    });
+    let receipt = ReceiptBuilder::from_payload(
+        tenant_id,
+        ref_glyph_id,
+        ReceiptResult::Anomaly,
+        Daemon::LedgerExplorer,
+        &payload,
+    )?
+    .timestamp(now)
+    .build(signer)?;
+    Ok(Some(receipt))
+}
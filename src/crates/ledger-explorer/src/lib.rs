@@ -0,0 +1,42 @@
+//! ledger-explorer — Kraglin's append-only ledger.
+//!
+//! Owns the on-disk state of the swarm: the SQLite hot path, the RocksDB
+//! cold archive and the queries that serve Merkle proofs out of them.
+//! Every glyph rule (IDs, hashes, Merkle trees) comes from glyph-lib; this
+//! crate only stores and indexes what glyph-lib has already accepted.
+
+pub mod error;
+
+/// Reader for the JSONL log, `glyphs/receipts/receipts.jsonl`.
+pub mod log;
+
+/// SQLite hot path configured by `config/ledger.sqlite.toml`.
+#[cfg(feature = "rusqlite")]
+pub mod sqlite_store;
+
+/// RocksDB cold archive configured by `config/ledger.rocksdb.toml`; it is
+/// filled from the SQLite store, so it needs both backends.
+#[cfg(all(feature = "rusqlite", feature = "rocksdb"))]
+pub mod rocksdb_store;
+
+/// Three-way consistency audit of the log and both stores.
+#[cfg(all(feature = "rusqlite", feature = "rocksdb"))]
+pub mod audit;
+
+/// Proof serving over both stores, gated by the last audit.
+#[cfg(all(feature = "rusqlite", feature = "rocksdb"))]
+pub mod ledger;
+
+/// Provenance walks over both stores, with a Merkle proof per hop.
+#[cfg(all(feature = "rusqlite", feature = "rocksdb"))]
+pub mod query;
+
+/// Read-only SQL over both stores, parsed with `sqlparser`.
+#[cfg(all(feature = "rusqlite", feature = "rocksdb"))]
+pub mod sql;
+
+/// Rebuild of both stores from the log, resumable from a checkpoint.
+#[cfg(all(feature = "rusqlite", feature = "rocksdb"))]
+pub mod replay;
+
+pub use error::{LedgerError, Result};
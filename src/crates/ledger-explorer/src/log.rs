@@ -0,0 +1,143 @@
+//! Reader for `glyphs/receipts/receipts.jsonl`.
+//!
+//! The log holds one glyph per line in canonical JSON: receipts, and the
+//! intents and anchors they hang off. [`LogReader`] yields each non-blank
+//! line with its number and the offset of its first byte, so whoever
+//! rejects a line can say exactly where the log stops being usable, and
+//! keeps a running hash of everything read, so a later run can tell
+//! whether the log it resumes on still starts the same way.
+
+use std::io::{self, BufRead, Read};
+
+use glyph_lib::{AnchorGlyph, GlyphError, GlyphId, GlyphKind, IntentGlyph, ReceiptGlyph};
+use serde_json::Value;
+
+use crate::error::Result;
+
+/// A glyph the log may hold.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogGlyph {
+    Intent(IntentGlyph),
+    Receipt(ReceiptGlyph),
+    Anchor(Box<AnchorGlyph>),
+}
+
+impl LogGlyph {
+    /// Parses one log line; the kind comes from the `receipt_id` field or
+    /// the prefix of `glyph_id`.
+    pub fn from_json(line: &[u8]) -> std::result::Result<Self, GlyphError> {
+        let value: Value = serde_json::from_slice(line)?;
+        if value.get("receipt_id").is_some() {
+            return Ok(LogGlyph::Receipt(serde_json::from_value(value)?));
+        }
+        let glyph_id: GlyphId = value
+            .get("glyph_id")
+            .and_then(Value::as_str)
+            .ok_or(GlyphError::InvalidGlyphId {
+                value: String::new(),
+                reason: "line has neither receipt_id nor glyph_id",
+            })?
+            .parse()?;
+        match glyph_id.kind() {
+            GlyphKind::Intent => Ok(LogGlyph::Intent(serde_json::from_value(value)?)),
+            GlyphKind::Anchor => Ok(LogGlyph::Anchor(Box::new(serde_json::from_value(value)?))),
+            kind => Err(GlyphError::UnknownVariant {
+                kind: "log glyph",
+                value: kind.id_prefix().to_string(),
+            }),
+        }
+    }
+
+    pub fn id(&self) -> GlyphId {
+        match self {
+            LogGlyph::Intent(intent) => intent.glyph_id,
+            LogGlyph::Receipt(receipt) => receipt.receipt_id,
+            LogGlyph::Anchor(anchor) => anchor.glyph_id,
+        }
+    }
+
+    pub fn tenant_id(&self) -> &str {
+        match self {
+            LogGlyph::Intent(intent) => &intent.tenant_id,
+            LogGlyph::Receipt(receipt) => &receipt.tenant_id,
+            LogGlyph::Anchor(anchor) => &anchor.tenant_id,
+        }
+    }
+}
+
+/// One non-blank log line.
+#[derive(Debug)]
+pub struct LogLine {
+    /// 1-based line number.
+    pub line: u64,
+    /// Offset of the line's first byte.
+    pub offset: u64,
+    pub glyph: std::result::Result<LogGlyph, GlyphError>,
+}
+
+/// Streams a log line by line, hashing every byte it consumes.
+pub struct LogReader<R> {
+    log: R,
+    line: u64,
+    offset: u64,
+    prefix: blake3::Hasher,
+}
+
+impl<R: BufRead> LogReader<R> {
+    pub fn new(log: R) -> Self {
+        LogReader {
+            log,
+            line: 0,
+            offset: 0,
+            prefix: blake3::Hasher::new(),
+        }
+    }
+
+    /// Lines consumed so far, blank ones included.
+    pub fn line(&self) -> u64 {
+        self.line
+    }
+
+    /// Bytes consumed so far; the next line starts here.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// BLAKE3 of the bytes consumed so far.
+    pub fn prefix_hash(&self) -> String {
+        self.prefix.finalize().to_hex().to_string()
+    }
+
+    /// Consumes the next `bytes` bytes unparsed, counting them as `lines`
+    /// lines, and returns how many bytes there were.
+    pub fn skip(&mut self, lines: u64, bytes: u64) -> Result<u64> {
+        let skipped = io::copy(&mut (&mut self.log).take(bytes), &mut self.prefix)?;
+        self.line += lines;
+        self.offset += skipped;
+        Ok(skipped)
+    }
+
+    /// The next non-blank line, or `None` at the end of the log.
+    pub fn next_line(&mut self) -> Result<Option<LogLine>> {
+        loop {
+            let mut bytes = Vec::new();
+            let read = self.log.read_until(b'\n', &mut bytes)?;
+            if read == 0 {
+                return Ok(None);
+            }
+            self.prefix.update(&bytes);
+            self.line += 1;
+            let offset = self.offset;
+            self.offset += read as u64;
+            if bytes.iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+            let glyph = LogGlyph::from_json(&bytes);
+            return Ok(Some(LogLine {
+                line: self.line,
+                offset,
+                glyph,
+            }));
+        }
+    }
+}
@@ -0,0 +1,236 @@
+//! `ledger-explorer` command-line tool.
+//!
+//! ```text
+//! ledger-explorer replay --keys=<FILE> [--log=<FILE>] [--config=<DIR>]
+//!                        [--checkpoint=<FILE>] [--archive-before=<UNIX>]
+//! ledger-explorer sql --tenant=<ID> [--proofs] [--log=<FILE>] [--config=<DIR>]
+//!                     <STATEMENT>
+//! ```
+//!
+//! `replay` rebuilds the SQLite hot path and the RocksDB cold archive named
+//! in `ledger.sqlite.toml` and `ledger.rocksdb.toml` from the log (default
+//! `glyphs/receipts/receipts.jsonl`), verifying every glyph on the way.
+//! Guardians, tenants and the quorum policy come from the config directory
+//! (default `config`); public keys from the key file (`keys:` entries with
+//! `owner`, `key_id`, `algorithm`, hex `public_key` and a validity window).
+//! With `--checkpoint`, progress is recorded in FILE and a rerun resumes
+//! from it; with `--archive-before`, windows anchored before UNIX are moved
+//! to the cold archive as the replay goes.
+//!
+//! Exit status: 0 when the whole log replayed, 1 when a line does not
+//! verify (its line and byte offset are printed), 2 on usage, config, I/O
+//! or storage errors.
+//!
+//! `sql` audits both stores against the log first and answers only if the
+//! tenant's layers agree, printing any divergence found. It then runs one
+//! read-only `SELECT` for the tenant (see `ledger_explorer::sql`) and
+//! prints each row as a JSON object keyed by column, with its `glyph_id`
+//! and `layer`; with `--proofs`, receipt rows also carry their anchor,
+//! Merkle root and proof path. Exit status: 0 on success, 2 when the
+//! statement is rejected or the ledger cannot serve it.
+
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::Path;
+use std::process::ExitCode;
+
+use glyph_lib::anchors::merkle::hash_to_hex;
+use glyph_lib::config::ledger::{RocksDbLedgerConfig, SqliteLedgerConfig};
+use glyph_lib::pq::dilithium::DilithiumPublicKey;
+use glyph_lib::pq::registry::{KeyRegistry, RegisteredKey};
+use ledger_explorer::ledger::Ledger;
+use ledger_explorer::replay::Replayer;
+use ledger_explorer::rocksdb_store::RocksDbStore;
+use ledger_explorer::sql::SqlQuery;
+use ledger_explorer::sqlite_store::SqliteStore;
+
+const USAGE: &str = "usage: ledger-explorer replay --keys=<FILE> [--log=<FILE>] [--config=<DIR>]
+                              [--checkpoint=<FILE>] [--archive-before=<UNIX>]
+       ledger-explorer sql --tenant=<ID> [--proofs] [--log=<FILE>] [--config=<DIR>]
+                           <STATEMENT>";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(message) => {
+            eprintln!("ledger-explorer: {message}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<bool, String> {
+    let (command, rest) = args.split_first().ok_or(USAGE)?;
+    match command.as_str() {
+        "replay" => run_replay(rest),
+        "sql" => run_sql(rest),
+        _ => Err(format!("unknown command {command:?}\n{USAGE}")),
+    }
+}
+
+fn run_replay(rest: &[String]) -> Result<bool, String> {
+    let mut log = "glyphs/receipts/receipts.jsonl";
+    let mut config = "config";
+    let mut keys = None;
+    let mut checkpoint = None;
+    let mut archive_before = None;
+    for arg in rest {
+        let (name, value) = arg
+            .split_once('=')
+            .ok_or_else(|| format!("unexpected argument {arg:?}\n{USAGE}"))?;
+        match name {
+            "--log" => log = value,
+            "--config" => config = value,
+            "--keys" => keys = Some(value),
+            "--checkpoint" => checkpoint = Some(value),
+            "--archive-before" => {
+                archive_before = Some(
+                    value
+                        .parse::<i64>()
+                        .map_err(|e| format!("--archive-before={value}: {e}"))?,
+                )
+            }
+            _ => return Err(format!("unknown option {name:?}\n{USAGE}")),
+        }
+    }
+    let keys = keys.ok_or(USAGE)?;
+    let config = Path::new(config);
+
+    let registry = registry(config, Path::new(keys))?;
+    let mut ledger = open_ledger(config)?;
+
+    let file = File::open(log).map_err(|e| format!("reading {log}: {e}"))?;
+    let mut replayer = Replayer::new(&mut ledger, &registry);
+    if let Some(path) = checkpoint {
+        replayer = replayer.checkpoint(path);
+    }
+    if let Some(before) = archive_before {
+        replayer = replayer.archive_before(before);
+    }
+    let report = replayer
+        .run(BufReader::new(file))
+        .map_err(|e| format!("{log}: {e}"))?;
+
+    println!(
+        "{log}: replayed {} intents, {} receipts, {} anchors from byte {} to byte {}; archived {} windows",
+        report.intents,
+        report.receipts,
+        report.anchors,
+        report.resumed_from,
+        report.checkpoint.offset,
+        report.archived,
+    );
+    match &report.failure {
+        Some(failure) => {
+            eprintln!("{log}: {failure}");
+            Ok(false)
+        }
+        None => Ok(true),
+    }
+}
+
+fn run_sql(rest: &[String]) -> Result<bool, String> {
+    let mut log = "glyphs/receipts/receipts.jsonl";
+    let mut config = "config";
+    let mut tenant = None;
+    let mut proofs = false;
+    let mut statement = None;
+    for arg in rest {
+        match arg.split_once('=') {
+            Some(("--log", value)) => log = value,
+            Some(("--config", value)) => config = value,
+            Some(("--tenant", value)) => tenant = Some(value),
+            _ if arg == "--proofs" => proofs = true,
+            _ if arg.starts_with("--") => return Err(format!("unknown option {arg:?}\n{USAGE}")),
+            _ if statement.is_none() => statement = Some(arg.as_str()),
+            _ => return Err(format!("unexpected argument {arg:?}\n{USAGE}")),
+        }
+    }
+    let (tenant, statement) = tenant.zip(statement).ok_or(USAGE)?;
+
+    let mut query = SqlQuery::parse(tenant, statement).map_err(|e| e.to_string())?;
+    if proofs {
+        query = query.with_proofs();
+    }
+    let mut ledger = open_ledger(Path::new(config))?;
+    let file = File::open(log).map_err(|e| format!("reading {log}: {e}"))?;
+    let report = ledger
+        .check(BufReader::new(file))
+        .map_err(|e| format!("{log}: {e}"))?;
+    let diverged = report.tenants.get(tenant).map(|t| &t.divergences);
+    for divergence in report
+        .malformed
+        .iter()
+        .chain(diverged.into_iter().flatten())
+    {
+        eprintln!("{log}: {divergence}");
+    }
+    let result = query.run(&ledger).map_err(|e| e.to_string())?;
+    for row in &result.rows {
+        let mut out = serde_json::Map::new();
+        out.insert("glyph_id".into(), row.glyph_id.to_string().into());
+        out.insert("layer".into(), row.layer.as_str().into());
+        for (column, value) in result.columns.iter().zip(&row.values) {
+            out.insert(column.clone(), value.clone());
+        }
+        if let Some(proof) = &row.proof {
+            out.insert(
+                "proof".into(),
+                serde_json::json!({
+                    "anchor_id": proof.anchor_id.to_string(),
+                    "merkle_root": hash_to_hex(&proof.merkle_root),
+                    "leaf_count": proof.proof.leaf_count,
+                    "merkle_proof": proof.proof.to_embedded(),
+                }),
+            );
+        }
+        println!("{}", serde_json::Value::Object(out));
+    }
+    Ok(true)
+}
+
+/// Both stores as configured in `config`.
+fn open_ledger(config: &Path) -> Result<Ledger, String> {
+    let sqlite = SqliteLedgerConfig::from_toml(&read(&config.join("ledger.sqlite.toml"))?)
+        .map_err(|e| e.to_string())?;
+    let rocks = RocksDbLedgerConfig::from_toml(&read(&config.join("ledger.rocksdb.toml"))?)
+        .map_err(|e| e.to_string())?;
+    Ok(Ledger::new(
+        SqliteStore::open(&sqlite).map_err(|e| e.to_string())?,
+        RocksDbStore::open(&rocks).map_err(|e| e.to_string())?,
+    ))
+}
+
+/// Guardians, swarm roles and tenants from `config`, keys from `keys`.
+fn registry(config: &Path, keys: &Path) -> Result<KeyRegistry, String> {
+    let mut registry = KeyRegistry::from_yaml(
+        &read(&config.join("agents/guardians_org.yaml"))?,
+        &read(&config.join("agents/swarm_roles.yaml"))?,
+    )
+    .map_err(|e| e.to_string())?;
+    let tenants = config.join("tenants");
+    let mut files = fs::read_dir(&tenants)
+        .map_err(|e| format!("reading {}: {e}", tenants.display()))?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("reading {}: {e}", tenants.display()))?;
+    files.retain(|path| path.extension().is_some_and(|ext| ext == "yaml"));
+    files.sort();
+    for path in files {
+        registry
+            .add_tenant_yaml(&read(&path)?)
+            .map_err(|e| format!("{}: {e}", path.display()))?;
+    }
+    registry
+        .load_keys_yaml(&read(keys)?, &|_, bytes| {
+            Ok(Box::new(DilithiumPublicKey::from_bytes(bytes)?) as RegisteredKey)
+        })
+        .map_err(|e| format!("{}: {e}", keys.display()))?;
+    Ok(registry)
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("reading {}: {e}", path.display()))
+}
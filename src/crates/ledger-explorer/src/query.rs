@@ -0,0 +1,476 @@
+//! Provenance queries over both stores.
+//!
+//! Glyphs depend on each other through three edges:
+//!
+//! - [`Edge::Ref`]: a receipt's `ref_glyph_id` names the intent or anchor
+//!   it was emitted under;
+//! - [`Edge::Previous`]: an anchor's `previous_glyph_id` names the anchor
+//!   it extends;
+//! - [`Edge::Commits`]: an anchor lists a receipt in its Merkle tree.
+//!
+//! [`ProvenanceQuery::run`] walks them from any glyph, backward to what it
+//! depends on, forward to what depends on it, or both, breadth first. The
+//! walk never leaves the query's tenant and stops `max_depth` hops out.
+//! Hops are always stored dependent → dependency, whichever way they were
+//! walked, so "orbital feed → ZK anomaly proof → bore receipt →
+//! entanglement prediction → anchor" reads the same from either end.
+//!
+//! Every hop with a receipt on it carries that receipt's inclusion proof:
+//! a `Commits` hop under its own anchor, a `Ref` hop under the anchor the
+//! ledger serves the receipt's proof from. A `Previous` link is a field of
+//! the newer anchor, so its evidence is that anchor's `blake3_hash`.
+//! [`Provenance::verify`] re-checks all of it from the returned glyphs.
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use glyph_lib::anchors::merkle::{hash_from_hex, MerkleTree};
+use glyph_lib::hashing::ids::verify_id;
+use glyph_lib::{ChainLink, GlyphId, GlyphKind};
+
+use crate::audit::Layer;
+use crate::error::{LedgerError, Result};
+use crate::ledger::Ledger;
+use crate::log::LogGlyph;
+use crate::sqlite_store::{hash_matches, AnchoredProof};
+
+/// How far a query walks unless told otherwise.
+pub const DEFAULT_MAX_DEPTH: usize = 16;
+
+/// Which way a query walks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// To what the start glyph depends on.
+    Backward,
+    /// To what depends on the start glyph.
+    Forward,
+    Both,
+}
+
+/// How one glyph depends on another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Edge {
+    /// Receipt → the intent or anchor in its `ref_glyph_id`.
+    Ref,
+    /// Anchor → the anchor in its `previous_glyph_id`.
+    Previous,
+    /// Anchor → a receipt it commits to.
+    Commits,
+}
+
+impl Edge {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Edge::Ref => "ref_glyph_id",
+            Edge::Previous => "previous_glyph_id",
+            Edge::Commits => "receipts",
+        }
+    }
+}
+
+/// A glyph the walk reached.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node {
+    pub glyph: LogGlyph,
+    /// The store it was read from.
+    pub layer: Layer,
+    /// Hops from the start glyph.
+    pub depth: usize,
+}
+
+/// One dependency: `from` depends on `to`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hop {
+    pub from: GlyphId,
+    pub to: GlyphId,
+    pub edge: Edge,
+    /// Inclusion proof of the hop's receipt; `None` for `Previous` hops
+    /// and for receipts no anchor has committed to yet.
+    pub proof: Option<AnchoredProof>,
+}
+
+/// The part of the provenance graph a query reached.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Provenance {
+    pub start: GlyphId,
+    pub nodes: BTreeMap<GlyphId, Node>,
+    /// In the order the walk found them.
+    pub hops: Vec<Hop>,
+    /// Glyphs a reached glyph depends on that the tenant's ledger does not
+    /// hold.
+    pub missing: BTreeSet<GlyphId>,
+    /// True if `max_depth` cut off edges the walk would have followed.
+    pub truncated: bool,
+}
+
+impl Provenance {
+    /// Hops with a receipt on them but no inclusion proof.
+    pub fn unproven(&self) -> impl Iterator<Item = &Hop> {
+        self.hops
+            .iter()
+            .filter(|h| h.edge != Edge::Previous && h.proof.is_none())
+    }
+
+    /// Re-checks every node's ID and hash, that every hop's edge is in its
+    /// `from` glyph, and every proof against its receipt and, when the walk
+    /// reached it, its anchor's `merkle_root`.
+    pub fn verify(&self) -> Result<()> {
+        for node in self.nodes.values() {
+            match &node.glyph {
+                LogGlyph::Intent(intent) => verify_id(intent)?,
+                LogGlyph::Receipt(receipt) => {
+                    verify_id(receipt)?;
+                    hash_matches(receipt, &receipt.blake3_hash)?;
+                }
+                LogGlyph::Anchor(anchor) => {
+                    verify_id(&**anchor)?;
+                    hash_matches(&**anchor, &anchor.blake3_hash)?;
+                }
+            }
+        }
+        for hop in &self.hops {
+            let bad = |reason: &str| LedgerError::BadEvidence {
+                from: hop.from,
+                to: hop.to,
+                reason: reason.to_string(),
+            };
+            let from = self
+                .glyph(&hop.from)
+                .ok_or_else(|| bad("from is not a node"))?;
+            let to = self.glyph(&hop.to).ok_or_else(|| bad("to is not a node"))?;
+            if !edges_of(from).contains(&(hop.to, hop.edge)) {
+                return Err(bad(&format!("{} does not name it", hop.edge.as_str())));
+            }
+            let Some(proof) = &hop.proof else {
+                continue;
+            };
+            let receipt = match (hop.edge, from, to) {
+                (Edge::Ref, LogGlyph::Receipt(receipt), _)
+                | (Edge::Commits, _, LogGlyph::Receipt(receipt)) => receipt,
+                _ => return Err(bad("a proof on a hop without a receipt")),
+            };
+            if !proof.verify_receipt(receipt) {
+                return Err(bad("inclusion proof does not verify"));
+            }
+            if hop.edge == Edge::Commits && proof.anchor_id != hop.from {
+                return Err(bad("proof is under another anchor"));
+            }
+            if let Some(LogGlyph::Anchor(anchor)) = self.glyph(&proof.anchor_id) {
+                if hash_from_hex(&anchor.merkle_root)? != proof.merkle_root {
+                    return Err(bad("proof root is not the anchor's merkle_root"));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn glyph(&self, glyph_id: &GlyphId) -> Option<&LogGlyph> {
+        self.nodes.get(glyph_id).map(|n| &n.glyph)
+    }
+}
+
+/// What `glyph` depends on, by edge.
+fn edges_of(glyph: &LogGlyph) -> Vec<(GlyphId, Edge)> {
+    match glyph {
+        LogGlyph::Intent(_) => Vec::new(),
+        LogGlyph::Receipt(receipt) => vec![(receipt.ref_glyph_id, Edge::Ref)],
+        LogGlyph::Anchor(anchor) => {
+            let mut edges: Vec<_> = match anchor.previous_glyph_id {
+                ChainLink::Anchor(previous) => vec![(previous, Edge::Previous)],
+                ChainLink::Genesis => Vec::new(),
+            };
+            edges.extend(
+                anchor
+                    .receipts
+                    .iter()
+                    .map(|r| (r.receipt_id, Edge::Commits)),
+            );
+            edges
+        }
+    }
+}
+
+/// A provenance walk from one glyph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProvenanceQuery {
+    pub tenant_id: String,
+    pub start: GlyphId,
+    pub direction: Direction,
+    pub max_depth: usize,
+}
+
+impl ProvenanceQuery {
+    /// Everything `start` depends on, up to [`DEFAULT_MAX_DEPTH`] hops.
+    pub fn new(tenant_id: impl Into<String>, start: GlyphId) -> Self {
+        ProvenanceQuery {
+            tenant_id: tenant_id.into(),
+            start,
+            direction: Direction::Backward,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Walks `ledger`; `None` if `start` is not one of the tenant's glyphs.
+    ///
+    /// Refused like any proof while the last audit found the tenant's
+    /// layers disagreeing.
+    pub fn run(&self, ledger: &Ledger) -> Result<Option<Provenance>> {
+        ledger.check_serving(&self.tenant_id)?;
+        Walk {
+            query: self,
+            ledger,
+            cold: None,
+            trees: BTreeMap::new(),
+        }
+        .run()
+    }
+}
+
+/// Forward edges of the tenant's cold archive, which has no index on them.
+#[derive(Default)]
+struct ColdIndex {
+    referencing: BTreeMap<GlyphId, Vec<GlyphId>>,
+    successors: BTreeMap<GlyphId, Vec<GlyphId>>,
+    committing: BTreeMap<GlyphId, Vec<GlyphId>>,
+}
+
+struct Walk<'q> {
+    query: &'q ProvenanceQuery,
+    ledger: &'q Ledger,
+    cold: Option<ColdIndex>,
+    /// Trees of the anchors `Commits` hops were proven under.
+    trees: BTreeMap<GlyphId, MerkleTree>,
+}
+
+impl Walk<'_> {
+    fn run(mut self) -> Result<Option<Provenance>> {
+        let start = self.query.start;
+        let Some((glyph, layer)) = self.fetch(&start)? else {
+            return Ok(None);
+        };
+        let mut graph = Provenance {
+            start,
+            nodes: BTreeMap::from([(
+                start,
+                Node {
+                    glyph,
+                    layer,
+                    depth: 0,
+                },
+            )]),
+            hops: Vec::new(),
+            missing: BTreeSet::new(),
+            truncated: false,
+        };
+        let mut seen = BTreeSet::new();
+        let mut queue = VecDeque::from([(start, 0)]);
+        while let Some((glyph_id, depth)) = queue.pop_front() {
+            let mut edges = Vec::new();
+            if self.query.direction != Direction::Forward {
+                edges.extend(
+                    edges_of(&graph.nodes[&glyph_id].glyph)
+                        .into_iter()
+                        .map(|(to, edge)| (glyph_id, to, edge)),
+                );
+            }
+            if self.query.direction != Direction::Backward {
+                edges.extend(
+                    self.dependents(&graph.nodes[&glyph_id].glyph)?
+                        .into_iter()
+                        .map(|(from, edge)| (from, glyph_id, edge)),
+                );
+            }
+            for (from, to, edge) in edges {
+                if !seen.insert((from, to, edge)) {
+                    continue;
+                }
+                let next = if from == glyph_id { to } else { from };
+                if !graph.nodes.contains_key(&next) {
+                    if depth == self.query.max_depth {
+                        graph.truncated = true;
+                        seen.remove(&(from, to, edge));
+                        continue;
+                    }
+                    let Some((glyph, layer)) = self.fetch(&next)? else {
+                        graph.missing.insert(next);
+                        continue;
+                    };
+                    graph.nodes.insert(
+                        next,
+                        Node {
+                            glyph,
+                            layer,
+                            depth: depth + 1,
+                        },
+                    );
+                    queue.push_back((next, depth + 1));
+                }
+                let proof = self.proof(&graph, from, to, edge)?;
+                graph.hops.push(Hop {
+                    from,
+                    to,
+                    edge,
+                    proof,
+                });
+            }
+        }
+        Ok(Some(graph))
+    }
+
+    /// `glyph_id` if it is one of the tenant's glyphs, with its store.
+    fn fetch(&self, glyph_id: &GlyphId) -> Result<Option<(LogGlyph, Layer)>> {
+        let tenant_id = &self.query.tenant_id;
+        if let Some(body) = self.ledger.sqlite().glyph_json(glyph_id)? {
+            let glyph = LogGlyph::from_json(body.as_bytes()).map_err(|e| LedgerError::Corrupt {
+                glyph_id: glyph_id.to_string(),
+                reason: e.to_string(),
+            })?;
+            return Ok((glyph.tenant_id() == tenant_id).then_some((glyph, Layer::Sqlite)));
+        }
+        let rocks = self.ledger.rocks();
+        if !rocks.tenants().any(|t| t == tenant_id) {
+            return Ok(None);
+        }
+        let glyph = match glyph_id.kind() {
+            GlyphKind::Receipt => rocks.receipt(tenant_id, glyph_id)?.map(LogGlyph::Receipt),
+            GlyphKind::Anchor => rocks
+                .anchor(tenant_id, glyph_id)?
+                .map(|a| LogGlyph::Anchor(Box::new(a))),
+            _ => None,
+        };
+        Ok(glyph.map(|g| (g, Layer::RocksDb)))
+    }
+
+    /// The tenant's glyphs that depend on `glyph`, by edge.
+    fn dependents(&mut self, glyph: &LogGlyph) -> Result<Vec<(GlyphId, Edge)>> {
+        let (query, ledger) = (self.query, self.ledger);
+        let (tenant_id, glyph_id) = (&query.tenant_id, glyph.id());
+        let sqlite = ledger.sqlite();
+        let mut found = Vec::new();
+        let mut add = |ids: Vec<GlyphId>, edge| {
+            for id in ids {
+                if !found.contains(&(id, edge)) {
+                    found.push((id, edge));
+                }
+            }
+        };
+        let cold = self.cold_index()?;
+        let cold_ids =
+            |map: &BTreeMap<GlyphId, Vec<GlyphId>>| map.get(&glyph_id).cloned().unwrap_or_default();
+        match glyph {
+            LogGlyph::Intent(_) => {
+                add(sqlite.referencing(tenant_id, &glyph_id)?, Edge::Ref);
+                add(cold_ids(&cold.referencing), Edge::Ref);
+            }
+            LogGlyph::Anchor(_) => {
+                add(sqlite.referencing(tenant_id, &glyph_id)?, Edge::Ref);
+                add(cold_ids(&cold.referencing), Edge::Ref);
+                add(sqlite.successors(tenant_id, &glyph_id)?, Edge::Previous);
+                add(cold_ids(&cold.successors), Edge::Previous);
+            }
+            LogGlyph::Receipt(_) => {
+                add(sqlite.committing(tenant_id, &glyph_id)?, Edge::Commits);
+                add(cold_ids(&cold.committing), Edge::Commits);
+            }
+        }
+        Ok(found)
+    }
+
+    /// Scans the tenant's cold archive once, the first time a walk goes
+    /// forward.
+    fn cold_index(&mut self) -> Result<&ColdIndex> {
+        if self.cold.is_none() {
+            let mut index = ColdIndex::default();
+            let (rocks, tenant_id) = (self.ledger.rocks(), &self.query.tenant_id);
+            if rocks.tenants().any(|t| t == tenant_id) {
+                rocks.each_receipt(tenant_id, |_, receipt| {
+                    let receipt = receipt?;
+                    index
+                        .referencing
+                        .entry(receipt.ref_glyph_id)
+                        .or_default()
+                        .push(receipt.receipt_id);
+                    Ok(())
+                })?;
+                rocks.each_anchor(tenant_id, |_, archived| {
+                    let anchor = archived?.anchor;
+                    if let ChainLink::Anchor(previous) = anchor.previous_glyph_id {
+                        index
+                            .successors
+                            .entry(previous)
+                            .or_default()
+                            .push(anchor.glyph_id);
+                    }
+                    for r in &anchor.receipts {
+                        index
+                            .committing
+                            .entry(r.receipt_id)
+                            .or_default()
+                            .push(anchor.glyph_id);
+                    }
+                    Ok(())
+                })?;
+            }
+            self.cold = Some(index);
+        }
+        Ok(self.cold.get_or_insert_with(ColdIndex::default))
+    }
+
+    /// The inclusion proof a hop carries.
+    fn proof(
+        &mut self,
+        graph: &Provenance,
+        from: GlyphId,
+        to: GlyphId,
+        edge: Edge,
+    ) -> Result<Option<AnchoredProof>> {
+        match edge {
+            Edge::Previous => Ok(None),
+            Edge::Ref => self.ledger.receipt_proof(&self.query.tenant_id, &from),
+            Edge::Commits => {
+                let node = &graph.nodes[&from];
+                let LogGlyph::Anchor(anchor) = &node.glyph else {
+                    return Ok(None);
+                };
+                let Some(index) = anchor.receipts.iter().position(|r| r.receipt_id == to) else {
+                    return Ok(None);
+                };
+                if !self.trees.contains_key(&from) {
+                    let tree = match node.layer {
+                        Layer::RocksDb => self
+                            .ledger
+                            .rocks()
+                            .archived_anchor(&self.query.tenant_id, &from)?
+                            .map(|a| a.tree())
+                            .transpose()?,
+                        _ => self
+                            .ledger
+                            .sqlite()
+                            .window(&from)?
+                            .map(|w| w.tree())
+                            .transpose()?,
+                    };
+                    let Some(tree) = tree else {
+                        return Ok(None);
+                    };
+                    self.trees.insert(from, tree);
+                }
+                let tree = &self.trees[&from];
+                Ok(Some(AnchoredProof {
+                    anchor_id: from,
+                    merkle_root: tree.root(),
+                    proof: tree.proof(index)?,
+                }))
+            }
+        }
+    }
+}
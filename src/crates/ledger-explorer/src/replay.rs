@@ -0,0 +1,333 @@
+//! Rebuilding both stores from the log.
+//!
+//! The log is the canonical replay source: [`Replayer::run`] reads it from
+//! the top, or from a [`Checkpoint`], and appends every glyph to the hot
+//! path after checking it the way a fresh ledger would:
+//!
+//! - intents: ID and the `authorized_by` Guardian's signature;
+//! - receipts: ID, `blake3_hash`, the `emitted_by` signature and the typed
+//!   payload;
+//! - anchors: ID, `blake3_hash`, the Guardian quorum bundle, and the Merkle
+//!   root rebuilt from the receipts replayed before it.
+//!
+//! Signing keys are looked up in a [`KeyRegistry`], so a key is only good
+//! for the tenants it is allowed for and at the glyph's own timestamp. The
+//! timestamp of the latest anchor replayed before a glyph is a lower bound
+//! on when the log saw it, so that is the trusted time revocation is
+//! checked at.
+//!
+//! With an archive cutoff, every anchor is followed by moving the tenant's
+//! windows anchored before the cutoff into the cold archive, stamped with
+//! the cutoff itself: the same log and cutoff always rebuild the same
+//! stores.
+//!
+//! Replay stops at the first line that does not verify and reports the
+//! offset of its first byte; everything before that line is stored. The
+//! checkpoint is rewritten after every anchor and when the run stops, and
+//! is only trusted on a log whose first `offset` bytes still hash to its
+//! `prefix_hash`. Glyphs stored after the last checkpoint by an
+//! interrupted run are accepted again if the stored copy is identical.
+
+use std::ffi::OsString;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead};
+use std::path::{Path, PathBuf};
+
+use glyph_lib::anchors::receipt_payload::check_receipt;
+use glyph_lib::hashing::ids::verify_id;
+use glyph_lib::pq::registry::KeyRegistry;
+use glyph_lib::{GlyphError, GlyphId};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{LedgerError, Result};
+use crate::ledger::Ledger;
+use crate::log::{LogGlyph, LogReader};
+use crate::sqlite_store::{canonical_body, hash_matches};
+
+/// How far a replay got.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// Lines replayed, blank ones included.
+    pub line: u64,
+    /// Bytes replayed; the next line starts here.
+    pub offset: u64,
+    /// BLAKE3 of the log's first `offset` bytes.
+    pub prefix_hash: String,
+    /// Timestamp of the latest anchor replayed, if any.
+    #[serde(default)]
+    pub anchored_at: Option<i64>,
+}
+
+impl Checkpoint {
+    fn of<R: BufRead>(reader: &LogReader<R>, anchored_at: Option<i64>) -> Self {
+        Checkpoint {
+            line: reader.line(),
+            offset: reader.offset(),
+            prefix_hash: reader.prefix_hash(),
+            anchored_at,
+        }
+    }
+
+    /// The checkpoint at `path`, or `None` if there is none yet.
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        match fs::read(path) {
+            Ok(bytes) => Ok(Some(
+                serde_json::from_slice(&bytes).map_err(GlyphError::from)?,
+            )),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Writes the checkpoint next to `path` and renames it into place, so
+    /// a crash leaves either the old checkpoint or the new one.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut tmp = OsString::from(path.as_os_str());
+        tmp.push(".tmp");
+        let json = serde_json::to_vec(self).map_err(GlyphError::from)?;
+        fs::write(&tmp, json)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+/// The first log line that did not replay.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayFailure {
+    pub line: u64,
+    /// Offset of the line's first byte.
+    pub offset: u64,
+    /// The glyph the line holds, if it parsed.
+    pub glyph_id: Option<GlyphId>,
+    pub reason: String,
+}
+
+impl fmt::Display for ReplayFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "log line {} (byte {})", self.line, self.offset)?;
+        if let Some(glyph_id) = &self.glyph_id {
+            write!(f, ", {glyph_id},")?;
+        }
+        write!(f, " does not verify: {}", self.reason)
+    }
+}
+
+/// The outcome of one [`Replayer::run`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayReport {
+    /// Where the run started: the top of the log, or the checkpoint.
+    pub resumed_from: u64,
+    /// Where the run stopped; saved if the replay has a checkpoint file.
+    pub checkpoint: Checkpoint,
+    pub intents: usize,
+    pub receipts: usize,
+    pub anchors: usize,
+    /// Windows moved to the cold archive.
+    pub archived: usize,
+    pub failure: Option<ReplayFailure>,
+}
+
+impl ReplayReport {
+    /// True if the replay reached the end of the log.
+    pub fn is_complete(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+/// Replays a log into a [`Ledger`].
+pub struct Replayer<'a> {
+    ledger: &'a mut Ledger,
+    registry: &'a KeyRegistry,
+    archive_before: Option<i64>,
+    checkpoint: Option<PathBuf>,
+}
+
+impl<'a> Replayer<'a> {
+    /// A replay into `ledger`, which should be empty unless it is being
+    /// resumed from a checkpoint.
+    pub fn new(ledger: &'a mut Ledger, registry: &'a KeyRegistry) -> Self {
+        Replayer {
+            ledger,
+            registry,
+            archive_before: None,
+            checkpoint: None,
+        }
+    }
+
+    /// Archives windows anchored before `before` as the replay goes.
+    pub fn archive_before(mut self, before: i64) -> Self {
+        self.archive_before = Some(before);
+        self
+    }
+
+    /// Resumes from, and records progress in, the checkpoint at `path`.
+    pub fn checkpoint(mut self, path: impl Into<PathBuf>) -> Self {
+        self.checkpoint = Some(path.into());
+        self
+    }
+
+    /// Replays `log` until its end or its first line that does not verify.
+    ///
+    /// A line that does not verify is reported, not returned; errors are
+    /// a checkpoint that does not match `log` and failures of the log or
+    /// the stores themselves.
+    pub fn run(&mut self, log: impl BufRead) -> Result<ReplayReport> {
+        let mut reader = LogReader::new(log);
+        let resumed = match &self.checkpoint {
+            Some(path) => Checkpoint::load(path)?,
+            None => None,
+        };
+        if let Some(start) = &resumed {
+            let skipped = reader.skip(start.line, start.offset)?;
+            let mismatch = if skipped < start.offset {
+                Some(format!("the log is only {skipped} bytes long"))
+            } else if reader.prefix_hash() != start.prefix_hash {
+                Some("the log before it has changed".to_string())
+            } else {
+                None
+            };
+            if let Some(reason) = mismatch {
+                return Err(LedgerError::CheckpointMismatch {
+                    offset: start.offset,
+                    reason,
+                });
+            }
+        }
+
+        let mut report = ReplayReport {
+            resumed_from: reader.offset(),
+            checkpoint: Checkpoint::of(&reader, resumed.as_ref().and_then(|c| c.anchored_at)),
+            intents: 0,
+            receipts: 0,
+            anchors: 0,
+            archived: 0,
+            failure: None,
+        };
+        while let Some(line) = reader.next_line()? {
+            let (glyph_id, applied) = match line.glyph {
+                Ok(glyph) => (
+                    Some(glyph.id()),
+                    self.apply(&glyph, resumed.is_some(), &mut report)
+                        .map(|()| match &glyph {
+                            LogGlyph::Anchor(anchor) => Some(anchor.timestamp),
+                            _ => None,
+                        }),
+                ),
+                Err(e) => (None, Err(e.into())),
+            };
+            match applied {
+                Ok(anchored) => {
+                    let anchored_at = report.checkpoint.anchored_at.max(anchored);
+                    report.checkpoint = Checkpoint::of(&reader, anchored_at);
+                    if anchored.is_some() {
+                        self.save(&report.checkpoint)?;
+                    }
+                }
+                Err(e) => {
+                    self.save(&report.checkpoint)?;
+                    if is_storage_error(&e) {
+                        return Err(e);
+                    }
+                    report.failure = Some(ReplayFailure {
+                        line: line.line,
+                        offset: line.offset,
+                        glyph_id,
+                        reason: e.to_string(),
+                    });
+                    return Ok(report);
+                }
+            }
+        }
+        self.save(&report.checkpoint)?;
+        Ok(report)
+    }
+
+    fn save(&self, checkpoint: &Checkpoint) -> Result<()> {
+        match &self.checkpoint {
+            Some(path) => checkpoint.save(path),
+            None => Ok(()),
+        }
+    }
+
+    /// Verifies one glyph and appends it.
+    fn apply(&mut self, glyph: &LogGlyph, resuming: bool, report: &mut ReplayReport) -> Result<()> {
+        let seen_at = report.checkpoint.anchored_at.unwrap_or(i64::MIN);
+        match glyph {
+            LogGlyph::Intent(intent) => {
+                verify_id(intent)?;
+                self.registry.verify_intent(intent, seen_at)?;
+            }
+            LogGlyph::Receipt(receipt) => {
+                verify_id(receipt)?;
+                hash_matches(receipt, &receipt.blake3_hash)?;
+                self.registry.verify_receipt(receipt, seen_at)?;
+                check_receipt(receipt)?;
+            }
+            LogGlyph::Anchor(anchor) => {
+                verify_id(&**anchor)?;
+                hash_matches(&**anchor, &anchor.blake3_hash)?;
+                self.registry.verify_anchor(anchor, seen_at)?;
+            }
+        }
+        if !(resuming && self.already_stored(glyph)?) {
+            let sqlite = self.ledger.sqlite_mut();
+            match glyph {
+                LogGlyph::Intent(intent) => sqlite.append_intent(intent)?,
+                LogGlyph::Receipt(receipt) => sqlite.append_receipt(receipt)?,
+                LogGlyph::Anchor(anchor) => sqlite.append_anchor(anchor)?,
+            }
+        }
+        match glyph {
+            LogGlyph::Intent(_) => report.intents += 1,
+            LogGlyph::Receipt(_) => report.receipts += 1,
+            LogGlyph::Anchor(anchor) => {
+                report.anchors += 1;
+                if let Some(before) = self.archive_before {
+                    let (sqlite, rocks) = self.ledger.stores_mut();
+                    report.archived += rocks
+                        .archive_aged(sqlite, &anchor.tenant_id, before, before)?
+                        .len();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// True if an interrupted run already stored exactly this glyph, in
+    /// the hot path or, once archived, in the cold archive.
+    fn already_stored(&self, glyph: &LogGlyph) -> Result<bool> {
+        let glyph_id = glyph.id();
+        let sqlite = self.ledger.sqlite();
+        if let Some(body) = sqlite.glyph_json(&glyph_id)? {
+            let json = match glyph {
+                LogGlyph::Intent(intent) => canonical_body(intent)?,
+                LogGlyph::Receipt(receipt) => canonical_body(receipt)?,
+                LogGlyph::Anchor(anchor) => canonical_body(&**anchor)?,
+            };
+            return Ok(body == json);
+        }
+        let blake3_hash = match glyph {
+            LogGlyph::Intent(_) => return Ok(false),
+            LogGlyph::Receipt(receipt) => &receipt.blake3_hash,
+            LogGlyph::Anchor(anchor) => &anchor.blake3_hash,
+        };
+        Ok(sqlite.is_archived(&glyph_id)?
+            && self
+                .ledger
+                .rocks()
+                .meta(glyph.tenant_id(), &glyph_id)?
+                .is_some_and(|m| m.blake3_hash == *blake3_hash))
+    }
+}
+
+/// Errors that say nothing about the log line being replayed.
+fn is_storage_error(e: &LedgerError) -> bool {
+    matches!(
+        e,
+        LedgerError::Sqlite(_)
+            | LedgerError::RocksDb(_)
+            | LedgerError::Io(_)
+            | LedgerError::Corrupt { .. }
+    )
+}
@@ -0,0 +1,502 @@
+//! RocksDB cold archive of the ledger.
+//!
+//! # Key layout
+//!
+//! Every key starts with the tenant's `prefix` from
+//! `config/ledger.rocksdb.toml` (`xai-memphis-01:`), so each tenant is one
+//! contiguous range in every column family.
+//!
+//! - `glyphs`: `prefix || glyph_id` → [`GlyphMeta`], the point-lookup index.
+//! - `receipts`: `prefix || time || receipt_id` → the ReceiptGlyph.
+//! - `anchors`: `prefix || time || anchor_id` → [`ArchivedAnchor`], the
+//!   AnchorGlyph together with the leaf hashes of its tree.
+//!
+//! `time` is the glyph's timestamp as 8 big-endian bytes with the sign bit
+//! flipped, so byte order is time order and a range scan over one tenant
+//! walks its history oldest first.
+//!
+//! # Archiving
+//!
+//! The archive is filled one [`Window`] at a time from the SQLite hot path.
+//! [`RocksDbStore::archive_aged`] writes a window in one synced batch,
+//! serves a proof for every receipt in it from what was written, and only
+//! then evicts the window from SQLite; a crash in between leaves the
+//! window in both stores, never in neither.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use glyph_lib::anchors::merkle::{hash_from_hex, hash_to_hex, MerkleTree};
+use glyph_lib::config::ledger::RocksDbLedgerConfig;
+use glyph_lib::{AnchorGlyph, GlyphError, GlyphId, ReceiptGlyph};
+use rocksdb::statistics::Ticker;
+use rocksdb::{
+    BlockBasedOptions, ColumnFamily, ColumnFamilyDescriptor, DBCompressionType, Direction,
+    IteratorMode, Options, WriteBatch, WriteOptions, DB,
+};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{LedgerError, Result};
+use crate::sqlite_store::{AnchoredProof, SqliteStore, Window};
+
+/// Column families the archive opens, in `ledger.rocksdb.toml` order.
+pub const COLUMN_FAMILIES: [&str; 3] = ["glyphs", "receipts", "anchors"];
+
+/// `glyphs` value: where an archived glyph lives and which anchor proves it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GlyphMeta {
+    pub timestamp: i64,
+    pub blake3_hash: String,
+    /// The anchor whose window archived this glyph (itself for anchors).
+    pub anchor_id: GlyphId,
+    /// Leaf index under `anchor_id`; `None` for the anchor.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leaf_index: Option<u64>,
+}
+
+/// `anchors` value: enough to rebuild every proof without the receipts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchivedAnchor {
+    pub anchor: AnchorGlyph,
+    /// Hex leaf hashes of the anchor's tree, in leaf order.
+    pub leaves: Vec<String>,
+}
+
+impl ArchivedAnchor {
+    /// The anchor's tree, checked against its `merkle_root`.
+    pub fn tree(&self) -> Result<MerkleTree> {
+        let leaves = self
+            .leaves
+            .iter()
+            .map(|l| hash_from_hex(l))
+            .collect::<glyph_lib::Result<Vec<_>>>()?;
+        let tree = MerkleTree::from_leaf_hashes(leaves);
+        let root = hash_to_hex(&tree.root());
+        if root != self.anchor.merkle_root {
+            return Err(LedgerError::Corrupt {
+                glyph_id: self.anchor.glyph_id.to_string(),
+                reason: format!(
+                    "archived leaves give root {root}, anchor has {}",
+                    self.anchor.merkle_root
+                ),
+            });
+        }
+        Ok(tree)
+    }
+}
+
+fn time_bytes(timestamp: i64) -> [u8; 8] {
+    ((timestamp as u64) ^ (1 << 63)).to_be_bytes()
+}
+
+fn time_key(prefix: &[u8], timestamp: i64, id: &GlyphId) -> Vec<u8> {
+    let mut key = prefix.to_vec();
+    key.extend_from_slice(&time_bytes(timestamp));
+    key.extend_from_slice(id.to_string().as_bytes());
+    key
+}
+
+fn id_key(prefix: &[u8], id: &GlyphId) -> Vec<u8> {
+    let mut key = prefix.to_vec();
+    key.extend_from_slice(id.to_string().as_bytes());
+    key
+}
+
+fn compression(name: &str) -> Result<DBCompressionType> {
+    Ok(match name {
+        "none" => DBCompressionType::None,
+        "snappy" => DBCompressionType::Snappy,
+        "zlib" => DBCompressionType::Zlib,
+        "bzip2" => DBCompressionType::Bz2,
+        "lz4" => DBCompressionType::Lz4,
+        "lz4hc" => DBCompressionType::Lz4hc,
+        "zstd" => DBCompressionType::Zstd,
+        other => {
+            return Err(GlyphError::InvalidConfig {
+                file: "ledger.rocksdb.toml".into(),
+                reason: format!("unknown compression {other:?}"),
+            }
+            .into())
+        }
+    })
+}
+
+fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    Ok(serde_json::to_vec(value).map_err(GlyphError::from)?)
+}
+
+fn decode<T: DeserializeOwned>(key: &[u8], bytes: &[u8]) -> Result<T> {
+    serde_json::from_slice(bytes).map_err(|e| LedgerError::Corrupt {
+        glyph_id: String::from_utf8_lossy(key).into_owned(),
+        reason: e.to_string(),
+    })
+}
+
+/// The RocksDB cold archive.
+pub struct RocksDbStore {
+    db: DB,
+    opts: Options,
+    prefixes: BTreeMap<String, Vec<u8>>,
+}
+
+impl RocksDbStore {
+    /// Opens the archive at `config.path`.
+    pub fn open(config: &RocksDbLedgerConfig) -> Result<Self> {
+        Self::open_at(&config.path, config)
+    }
+
+    /// Opens the archive at `path` with `config`'s options and layout.
+    pub fn open_at(path: impl AsRef<Path>, config: &RocksDbLedgerConfig) -> Result<Self> {
+        let o = &config.options;
+        let mut opts = Options::default();
+        opts.create_if_missing(config.create_if_missing);
+        opts.create_missing_column_families(true);
+        if config.enable_statistics {
+            opts.enable_statistics();
+            opts.set_stats_dump_period_sec(config.stats_dump_period_sec as u32);
+        }
+        opts.set_max_open_files(o.max_open_files);
+        opts.set_write_buffer_size(o.write_buffer_size as usize);
+        opts.set_target_file_size_base(o.target_file_size_base);
+        opts.set_max_background_jobs(o.max_background_jobs);
+        opts.set_compression_type(compression(&o.compression)?);
+        opts.set_bottommost_compression_type(compression(&o.bottommost_compression)?);
+
+        let mut families = Vec::with_capacity(COLUMN_FAMILIES.len());
+        for name in COLUMN_FAMILIES {
+            let cf = config.column_families.get(name).ok_or_else(|| {
+                LedgerError::from(GlyphError::InvalidConfig {
+                    file: "ledger.rocksdb.toml".into(),
+                    reason: format!("missing [column_families.{name}]"),
+                })
+            })?;
+            let mut table = BlockBasedOptions::default();
+            table.set_bloom_filter(cf.bloom_filter_bits_per_key as f64, false);
+            table.set_block_size(cf.block_size as usize);
+            let mut cf_opts = opts.clone();
+            cf_opts.set_block_based_table_factory(&table);
+            families.push(ColumnFamilyDescriptor::new(name, cf_opts));
+        }
+        let db = DB::open_cf_descriptors(&opts, path, families)?;
+        let prefixes = config
+            .tenant
+            .iter()
+            .map(|(id, t)| (id.clone(), t.prefix.as_bytes().to_vec()))
+            .collect();
+        Ok(RocksDbStore { db, opts, prefixes })
+    }
+
+    fn cf(&self, name: &str) -> &ColumnFamily {
+        self.db
+            .cf_handle(name)
+            .expect("every COLUMN_FAMILIES entry is opened")
+    }
+
+    fn prefix(&self, tenant_id: &str) -> Result<&[u8]> {
+        self.prefixes
+            .get(tenant_id)
+            .map(Vec::as_slice)
+            .ok_or_else(|| LedgerError::UnknownTenant {
+                tenant_id: tenant_id.to_string(),
+            })
+    }
+
+    fn get<T: DeserializeOwned>(&self, cf: &str, key: &[u8]) -> Result<Option<T>> {
+        self.db
+            .get_cf(self.cf(cf), key)?
+            .map(|bytes| decode(key, &bytes))
+            .transpose()
+    }
+
+    /// Index entry of an archived glyph.
+    pub fn meta(&self, tenant_id: &str, glyph_id: &GlyphId) -> Result<Option<GlyphMeta>> {
+        self.get("glyphs", &id_key(self.prefix(tenant_id)?, glyph_id))
+    }
+
+    /// An archived ReceiptGlyph.
+    pub fn receipt(&self, tenant_id: &str, receipt_id: &GlyphId) -> Result<Option<ReceiptGlyph>> {
+        let Some(meta) = self.meta(tenant_id, receipt_id)? else {
+            return Ok(None);
+        };
+        let key = time_key(self.prefix(tenant_id)?, meta.timestamp, receipt_id);
+        self.get("receipts", &key)
+    }
+
+    /// An archived anchor with its leaves.
+    pub fn archived_anchor(
+        &self,
+        tenant_id: &str,
+        anchor_id: &GlyphId,
+    ) -> Result<Option<ArchivedAnchor>> {
+        let Some(meta) = self.meta(tenant_id, anchor_id)? else {
+            return Ok(None);
+        };
+        let key = time_key(self.prefix(tenant_id)?, meta.timestamp, anchor_id);
+        self.get("anchors", &key)
+    }
+
+    /// An archived AnchorGlyph.
+    pub fn anchor(&self, tenant_id: &str, anchor_id: &GlyphId) -> Result<Option<AnchorGlyph>> {
+        Ok(self
+            .archived_anchor(tenant_id, anchor_id)?
+            .map(|a| a.anchor))
+    }
+
+    /// Inclusion proof of an archived receipt, rebuilt from its anchor's
+    /// archived leaves.
+    pub fn receipt_proof(
+        &self,
+        tenant_id: &str,
+        receipt_id: &GlyphId,
+    ) -> Result<Option<AnchoredProof>> {
+        let Some(GlyphMeta {
+            anchor_id,
+            leaf_index: Some(leaf_index),
+            ..
+        }) = self.meta(tenant_id, receipt_id)?
+        else {
+            return Ok(None);
+        };
+        let archived = self
+            .archived_anchor(tenant_id, &anchor_id)?
+            .ok_or_else(|| LedgerError::Corrupt {
+                glyph_id: receipt_id.to_string(),
+                reason: format!("indexed under {anchor_id}, which is not archived"),
+            })?;
+        let tree = archived.tree()?;
+        Ok(Some(AnchoredProof {
+            anchor_id,
+            merkle_root: tree.root(),
+            proof: tree.proof(leaf_index as usize)?,
+        }))
+    }
+
+    /// WAL syncs since the archive was opened; zero unless
+    /// `enable_statistics` is set.
+    pub fn wal_syncs(&self) -> u64 {
+        self.opts.get_ticker_count(Ticker::WalFileSynced)
+    }
+
+    /// Tenants with a key prefix, in ID order.
+    pub fn tenants(&self) -> impl Iterator<Item = &str> {
+        self.prefixes.keys().map(String::as_str)
+    }
+
+    /// Visits the values of `tenant_id` in `cf` with
+    /// `from <= timestamp < to`, oldest first, each with the glyph ID from
+    /// its key. An error returned by `visit` ends the walk.
+    fn each<T: DeserializeOwned>(
+        &self,
+        cf: &str,
+        tenant_id: &str,
+        from: i64,
+        to: i64,
+        mut visit: impl FnMut(&str, Result<T>) -> Result<()>,
+    ) -> Result<()> {
+        let prefix = self.prefix(tenant_id)?;
+        let mut start = prefix.to_vec();
+        start.extend_from_slice(&time_bytes(from));
+        let end = time_bytes(to);
+        for item in self
+            .db
+            .iterator_cf(self.cf(cf), IteratorMode::From(&start, Direction::Forward))
+        {
+            let (key, value) = item?;
+            let Some(rest) = key.strip_prefix(prefix).filter(|k| k.len() >= 8) else {
+                break;
+            };
+            let (time, id) = rest.split_at(8);
+            if time >= &end[..] {
+                break;
+            }
+            visit(&String::from_utf8_lossy(id), decode(&key, &value))?;
+        }
+        Ok(())
+    }
+
+    fn scan<T: DeserializeOwned>(
+        &self,
+        cf: &str,
+        tenant_id: &str,
+        from: i64,
+        to: i64,
+    ) -> Result<Vec<T>> {
+        let mut out = Vec::new();
+        self.each(cf, tenant_id, from, to, |_, value| {
+            out.push(value?);
+            Ok(())
+        })?;
+        Ok(out)
+    }
+
+    /// Visits every archived receipt of `tenant_id`, oldest first; see
+    /// [`SqliteStore::each_glyph`].
+    pub fn each_receipt(
+        &self,
+        tenant_id: &str,
+        visit: impl FnMut(&str, Result<ReceiptGlyph>) -> Result<()>,
+    ) -> Result<()> {
+        self.each("receipts", tenant_id, i64::MIN, i64::MAX, visit)
+    }
+
+    /// Visits every archived anchor of `tenant_id`, oldest first.
+    pub fn each_anchor(
+        &self,
+        tenant_id: &str,
+        visit: impl FnMut(&str, Result<ArchivedAnchor>) -> Result<()>,
+    ) -> Result<()> {
+        self.each("anchors", tenant_id, i64::MIN, i64::MAX, visit)
+    }
+
+    /// Archived receipts of `tenant_id` with `from <= timestamp < to`,
+    /// oldest first.
+    pub fn receipts_between(
+        &self,
+        tenant_id: &str,
+        from: i64,
+        to: i64,
+    ) -> Result<Vec<ReceiptGlyph>> {
+        self.scan("receipts", tenant_id, from, to)
+    }
+
+    /// Archived anchors of `tenant_id` with `from <= timestamp < to`,
+    /// oldest first.
+    pub fn anchors_between(
+        &self,
+        tenant_id: &str,
+        from: i64,
+        to: i64,
+    ) -> Result<Vec<ArchivedAnchor>> {
+        self.scan("anchors", tenant_id, from, to)
+    }
+
+    /// Writes a window in one batch, synced to the WAL before returning.
+    ///
+    /// The window's tree must have the anchor's `merkle_root`. A receipt
+    /// already archived under an earlier anchor keeps that index entry.
+    pub fn put_window(&self, window: &Window) -> Result<()> {
+        let anchor = &window.anchor;
+        let prefix = self.prefix(&anchor.tenant_id)?;
+        if self.meta(&anchor.tenant_id, &anchor.glyph_id)?.is_some() {
+            return Err(LedgerError::Duplicate {
+                glyph_id: anchor.glyph_id,
+            });
+        }
+        let tree = window.tree()?;
+        let root = hash_to_hex(&tree.root());
+        if root != anchor.merkle_root {
+            return Err(GlyphError::HashMismatch {
+                expected: root,
+                found: anchor.merkle_root.clone(),
+            }
+            .into());
+        }
+
+        let mut batch = WriteBatch::default();
+        for (i, receipt) in window.receipts.iter().enumerate() {
+            if receipt.tenant_id != anchor.tenant_id {
+                return Err(LedgerError::TenantMismatch {
+                    anchor_id: anchor.glyph_id,
+                    tenant_id: anchor.tenant_id.clone(),
+                    receipt_id: receipt.receipt_id,
+                    receipt_tenant: receipt.tenant_id.clone(),
+                });
+            }
+            if self.meta(&anchor.tenant_id, &receipt.receipt_id)?.is_some() {
+                continue;
+            }
+            let meta = GlyphMeta {
+                timestamp: receipt.timestamp,
+                blake3_hash: receipt.blake3_hash.clone(),
+                anchor_id: anchor.glyph_id,
+                leaf_index: Some(i as u64),
+            };
+            batch.put_cf(
+                self.cf("glyphs"),
+                id_key(prefix, &receipt.receipt_id),
+                encode(&meta)?,
+            );
+            batch.put_cf(
+                self.cf("receipts"),
+                time_key(prefix, receipt.timestamp, &receipt.receipt_id),
+                encode(receipt)?,
+            );
+        }
+        let meta = GlyphMeta {
+            timestamp: anchor.timestamp,
+            blake3_hash: anchor.blake3_hash.clone(),
+            anchor_id: anchor.glyph_id,
+            leaf_index: None,
+        };
+        let archived = ArchivedAnchor {
+            anchor: anchor.clone(),
+            leaves: tree.leaves().iter().map(hash_to_hex).collect(),
+        };
+        batch.put_cf(
+            self.cf("glyphs"),
+            id_key(prefix, &anchor.glyph_id),
+            encode(&meta)?,
+        );
+        batch.put_cf(
+            self.cf("anchors"),
+            time_key(prefix, anchor.timestamp, &anchor.glyph_id),
+            encode(&archived)?,
+        );
+        let mut synced = WriteOptions::default();
+        synced.set_sync(true);
+        self.db.write_opt(batch, &synced)?;
+        Ok(())
+    }
+
+    /// Moves every window of `tenant_id` anchored before `before` out of
+    /// `sqlite` and returns the anchors moved, oldest first.
+    ///
+    /// Each window is evicted only after the archive serves a proof that
+    /// verifies for each of its receipts. A window found already archived
+    /// (an earlier run stopped before evicting) is re-checked, not rewritten.
+    pub fn archive_aged(
+        &self,
+        sqlite: &mut SqliteStore,
+        tenant_id: &str,
+        before: i64,
+        archived_at: i64,
+    ) -> Result<Vec<GlyphId>> {
+        let mut moved = Vec::new();
+        for anchor_id in sqlite.aged_windows(tenant_id, before)? {
+            let window = sqlite
+                .window(&anchor_id)?
+                .ok_or_else(|| LedgerError::Corrupt {
+                    glyph_id: anchor_id.to_string(),
+                    reason: "listed as aged but not stored".into(),
+                })?;
+            if self.meta(tenant_id, &anchor_id)?.is_none() {
+                self.put_window(&window)?;
+            } else {
+                // Written by a run that may have died before the sync.
+                self.db.flush_wal(true)?;
+            }
+            self.check_window(&window)?;
+            sqlite.evict_window(&anchor_id, archived_at)?;
+            moved.push(anchor_id);
+        }
+        Ok(moved)
+    }
+
+    /// Errors unless the archive proves every receipt of `window`.
+    fn check_window(&self, window: &Window) -> Result<()> {
+        let tenant_id = &window.anchor.tenant_id;
+        for receipt in &window.receipts {
+            let proof = self.receipt_proof(tenant_id, &receipt.receipt_id)?;
+            let stored = self.receipt(tenant_id, &receipt.receipt_id)?;
+            if !proof.is_some_and(|p| p.verify_receipt(receipt)) || stored.as_ref() != Some(receipt)
+            {
+                return Err(LedgerError::Corrupt {
+                    glyph_id: receipt.receipt_id.to_string(),
+                    reason: "archived copy does not prove the hot-path receipt".into(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
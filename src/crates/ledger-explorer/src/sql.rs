@@ -0,0 +1,707 @@
+//! Read-only SQL over both stores.
+//!
+//! Operators ask ad-hoc questions in a small `SELECT` dialect parsed by
+//! `sqlparser`:
+//!
+//! ```text
+//! SELECT receipt_id, correlation_score FROM receipts
+//! WHERE tenant_id = 'xai-memphis-01'
+//!   AND receipt_type = 'entanglement_prediction'
+//!   AND timestamp > 1764380000
+//! ORDER BY correlation_score DESC LIMIT 20
+//! ```
+//!
+//! `FROM` names `receipts` or `anchors`. A column is any field of the
+//! glyph's JSON, receipt details included, and `zk_proof.pi_a` reaches into
+//! objects; a field a glyph lacks is `NULL`. `WHERE` takes `AND`, `OR`,
+//! `NOT`, comparisons, `IN`, `BETWEEN` and `IS [NOT] NULL` against literals
+//! under SQL's three-valued logic. Any other statement or clause is
+//! [`LedgerError::BadQuery`].
+//!
+//! A query runs for one tenant, and its `WHERE` must pin that tenant with a
+//! `tenant_id = '…'` outside any `OR` or `NOT`; pinning another is
+//! [`LedgerError::WrongTenant`]. Only the tenant's rows are ever read.
+//!
+//! [`SqlQuery::plan`] turns the other top-level conjuncts the indexes
+//! answer into a [`Plan`]: a point read when the glyph ID is pinned, else
+//! a `timestamp` range, narrowed by `receipt_type` on the hot path. The
+//! whole `WHERE` is then checked on every row the plan reads.
+
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::fmt;
+
+use glyph_lib::{Glyph, GlyphError, GlyphId};
+use serde_json::Value;
+use sqlparser::ast::{
+    BinaryOperator, Expr, GroupByExpr, Query, Select, SelectItem, SetExpr, Statement, TableFactor,
+    UnaryOperator, Value as Literal,
+};
+use sqlparser::dialect::GenericDialect;
+use sqlparser::parser::Parser;
+
+use crate::audit::Layer;
+use crate::error::{LedgerError, Result};
+use crate::ledger::Ledger;
+use crate::sqlite_store::AnchoredProof;
+
+/// A table a query reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Table {
+    Receipts,
+    Anchors,
+}
+
+impl Table {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Table::Receipts => "receipts",
+            Table::Anchors => "anchors",
+        }
+    }
+
+    /// The column holding a row's glyph ID.
+    pub fn id_column(self) -> &'static str {
+        match self {
+            Table::Receipts => "receipt_id",
+            Table::Anchors => "glyph_id",
+        }
+    }
+}
+
+/// A path into a glyph's JSON.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Column(Vec<String>);
+
+impl Column {
+    /// The field this column names, unless it is nested.
+    fn field(&self) -> Option<&str> {
+        match self.0.as_slice() {
+            [field] => Some(field),
+            _ => None,
+        }
+    }
+
+    /// Its value in `glyph`; `Null` where the glyph lacks it.
+    fn get<'v>(&self, glyph: &'v Value) -> &'v Value {
+        self.0.iter().fold(glyph, |value, field| {
+            value.get(field).unwrap_or(&Value::Null)
+        })
+    }
+}
+
+impl fmt::Display for Column {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0.join("."))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+}
+
+impl Op {
+    /// The same comparison with its operands swapped.
+    fn flip(self) -> Self {
+        match self {
+            Op::Lt => Op::Gt,
+            Op::LtEq => Op::GtEq,
+            Op::Gt => Op::Lt,
+            Op::GtEq => Op::LtEq,
+            other => other,
+        }
+    }
+
+    fn holds(self, ordering: Ordering) -> bool {
+        match self {
+            Op::Eq => ordering.is_eq(),
+            Op::NotEq => ordering.is_ne(),
+            Op::Lt => ordering.is_lt(),
+            Op::LtEq => ordering.is_le(),
+            Op::Gt => ordering.is_gt(),
+            Op::GtEq => ordering.is_ge(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Predicate {
+    And(Box<Predicate>, Box<Predicate>),
+    Or(Box<Predicate>, Box<Predicate>),
+    Not(Box<Predicate>),
+    Compare(Column, Op, Value),
+    IsNull(Column),
+}
+
+impl Predicate {
+    /// Truth on `glyph`; `None` is SQL's unknown.
+    fn eval(&self, glyph: &Value) -> Option<bool> {
+        match self {
+            Predicate::And(a, b) => match (a.eval(glyph), b.eval(glyph)) {
+                (Some(false), _) | (_, Some(false)) => Some(false),
+                (Some(true), Some(true)) => Some(true),
+                _ => None,
+            },
+            Predicate::Or(a, b) => match (a.eval(glyph), b.eval(glyph)) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            },
+            Predicate::Not(p) => p.eval(glyph).map(|b| !b),
+            Predicate::Compare(column, op, value) => {
+                compare(column.get(glyph), value).map(|o| op.holds(o))
+            }
+            Predicate::IsNull(column) => Some(column.get(glyph).is_null()),
+        }
+    }
+
+    /// The operands of a top-level `AND` chain.
+    fn conjuncts(&self) -> Vec<&Predicate> {
+        match self {
+            Predicate::And(a, b) => {
+                let mut all = a.conjuncts();
+                all.extend(b.conjuncts());
+                all
+            }
+            other => vec![other],
+        }
+    }
+}
+
+/// Orders two JSON scalars of one type; `None` for `NULL`, mixed types,
+/// arrays and objects.
+fn compare(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => match (a.as_i64(), b.as_i64()) {
+            (Some(a), Some(b)) => Some(a.cmp(&b)),
+            _ => a.as_f64()?.partial_cmp(&b.as_f64()?),
+        },
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
+
+/// The order `ORDER BY` sorts in: `NULL`, booleans, numbers, strings,
+/// then arrays and objects.
+fn sort_order(a: &Value, b: &Value) -> Ordering {
+    fn rank(value: &Value) -> u8 {
+        match value {
+            Value::Null => 0,
+            Value::Bool(_) => 1,
+            Value::Number(_) => 2,
+            Value::String(_) => 3,
+            Value::Array(_) => 4,
+            Value::Object(_) => 5,
+        }
+    }
+    compare(a, b).unwrap_or_else(|| rank(a).cmp(&rank(b)))
+}
+
+/// A parsed statement, run for one tenant.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqlQuery {
+    pub tenant_id: String,
+    pub table: Table,
+    /// Result column names in `SELECT` order; `*` for the whole glyph.
+    pub columns: Vec<String>,
+    /// Whether receipt rows carry their inclusion proof.
+    pub proofs: bool,
+    /// `None` for `*`.
+    projection: Vec<Option<Column>>,
+    filter: Predicate,
+    /// Sort keys, each `true` for `DESC`.
+    order_by: Vec<(Column, bool)>,
+    limit: Option<usize>,
+}
+
+impl SqlQuery {
+    /// Parses `sql` and checks that it pins `tenant_id`.
+    pub fn parse(tenant_id: impl Into<String>, sql: &str) -> Result<Self> {
+        let tenant_id = tenant_id.into();
+        let statements =
+            Parser::parse_sql(&GenericDialect {}, sql).map_err(|e| bad(e.to_string()))?;
+        let [Statement::Query(query)] = statements.as_slice() else {
+            return Err(bad("expected one SELECT statement"));
+        };
+        let Query {
+            with: None,
+            body,
+            order_by,
+            limit,
+            limit_by,
+            offset: None,
+            fetch: None,
+            locks,
+            ..
+        } = &**query
+        else {
+            return Err(bad(SHAPE));
+        };
+        let SetExpr::Select(select) = &**body else {
+            return Err(bad(SHAPE));
+        };
+        if !limit_by.is_empty() || !locks.is_empty() || !is_plain(select) {
+            return Err(bad(SHAPE));
+        }
+
+        let table = table(select)?;
+        let mut columns = Vec::new();
+        let mut projection = Vec::new();
+        for item in &select.projection {
+            let (name, column) = match item {
+                SelectItem::Wildcard(_) => ("*".to_string(), None),
+                SelectItem::UnnamedExpr(expr) => {
+                    let column = column(expr)?;
+                    (column.to_string(), Some(column))
+                }
+                SelectItem::ExprWithAlias { expr, alias } => {
+                    (alias.value.clone(), Some(column(expr)?))
+                }
+                other => return Err(bad(format!("unsupported select item {other}"))),
+            };
+            columns.push(name);
+            projection.push(column);
+        }
+
+        let filter = predicate(
+            select
+                .selection
+                .as_ref()
+                .ok_or_else(|| bad("WHERE must pin tenant_id"))?,
+        )?;
+        let pins: Vec<&str> = filter
+            .conjuncts()
+            .into_iter()
+            .filter_map(|p| match p {
+                Predicate::Compare(column, Op::Eq, Value::String(pinned))
+                    if column.field() == Some("tenant_id") =>
+                {
+                    Some(pinned.as_str())
+                }
+                _ => None,
+            })
+            .collect();
+        if pins.is_empty() {
+            return Err(bad("WHERE must pin tenant_id outside any OR or NOT"));
+        }
+        if let Some(pinned) = pins.iter().find(|p| **p != tenant_id) {
+            return Err(LedgerError::WrongTenant {
+                tenant_id,
+                pinned: pinned.to_string(),
+            });
+        }
+
+        let order_by = order_by
+            .iter()
+            .map(|o| {
+                if o.nulls_first.is_some() {
+                    return Err(bad("NULLS FIRST and NULLS LAST are not supported"));
+                }
+                Ok((column(&o.expr)?, o.asc == Some(false)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let limit = limit
+            .as_ref()
+            .map(|expr| match literal(expr)? {
+                Value::Number(n) => n
+                    .as_u64()
+                    .map(|n| n as usize)
+                    .ok_or_else(|| bad("LIMIT takes a non-negative integer")),
+                _ => Err(bad("LIMIT takes a non-negative integer")),
+            })
+            .transpose()?;
+
+        Ok(SqlQuery {
+            tenant_id,
+            table,
+            columns,
+            proofs: false,
+            projection,
+            filter,
+            order_by,
+            limit,
+        })
+    }
+
+    /// Attaches each receipt row's inclusion proof.
+    pub fn with_proofs(mut self) -> Self {
+        self.proofs = true;
+        self
+    }
+
+    /// How [`SqlQuery::run`] reads the stores.
+    pub fn plan(&self) -> Plan {
+        let mut plan = Plan {
+            table: self.table,
+            tenant_id: self.tenant_id.clone(),
+            glyph_id: None,
+            receipt_type: None,
+            from: i64::MIN,
+            to: i64::MAX,
+        };
+        for conjunct in self.filter.conjuncts() {
+            let Predicate::Compare(column, op, value) = conjunct else {
+                continue;
+            };
+            match (column.field(), *op, value) {
+                (Some("timestamp"), op, Value::Number(n)) => {
+                    if let Some(t) = n.as_i64() {
+                        plan.bound(op, t);
+                    }
+                }
+                (Some("receipt_type"), Op::Eq, Value::String(receipt_type))
+                    if self.table == Table::Receipts =>
+                {
+                    plan.receipt_type = Some(receipt_type.clone());
+                }
+                (Some(field), Op::Eq, Value::String(id)) if field == self.table.id_column() => {
+                    plan.glyph_id = id.parse().ok();
+                }
+                _ => {}
+            }
+        }
+        plan
+    }
+
+    /// Runs the query over `ledger`.
+    ///
+    /// Refused like any proof while the last audit found the tenant's
+    /// layers disagreeing.
+    pub fn run(&self, ledger: &Ledger) -> Result<ResultSet> {
+        ledger.check_serving(&self.tenant_id)?;
+        let plan = self.plan();
+        let mut matched: Vec<_> = plan
+            .read(ledger)?
+            .into_iter()
+            .filter(|(_, glyph, _)| self.filter.eval(glyph) == Some(true))
+            .collect();
+        matched.sort_by(|(a_id, a, _), (b_id, b, _)| {
+            self.order_by
+                .iter()
+                .map(|(column, desc)| {
+                    let ordering = sort_order(column.get(a), column.get(b));
+                    if *desc {
+                        ordering.reverse()
+                    } else {
+                        ordering
+                    }
+                })
+                .chain([sort_order(&a["timestamp"], &b["timestamp"]), a_id.cmp(b_id)])
+                .find(|o| o.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+        matched.truncate(self.limit.unwrap_or(usize::MAX));
+
+        let rows = matched
+            .into_iter()
+            .map(|(glyph_id, glyph, layer)| {
+                let proof = match self.table {
+                    Table::Receipts if self.proofs => {
+                        ledger.receipt_proof(&self.tenant_id, &glyph_id)?
+                    }
+                    _ => None,
+                };
+                let values = self
+                    .projection
+                    .iter()
+                    .map(|column| match column {
+                        Some(column) => column.get(&glyph).clone(),
+                        None => glyph.clone(),
+                    })
+                    .collect();
+                Ok(Row {
+                    glyph_id,
+                    layer,
+                    values,
+                    proof,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(ResultSet {
+            columns: self.columns.clone(),
+            rows,
+            plan,
+        })
+    }
+}
+
+const SHAPE: &str = "only SELECT … FROM … WHERE … ORDER BY … LIMIT is supported";
+
+fn bad(reason: impl Into<String>) -> LedgerError {
+    LedgerError::BadQuery {
+        reason: reason.into(),
+    }
+}
+
+/// True if `select` has no clause beyond projection, `FROM` and `WHERE`.
+fn is_plain(select: &Select) -> bool {
+    select.distinct.is_none()
+        && select.top.is_none()
+        && select.into.is_none()
+        && select.lateral_views.is_empty()
+        && select.group_by == GroupByExpr::Expressions(Vec::new())
+        && select.cluster_by.is_empty()
+        && select.distribute_by.is_empty()
+        && select.sort_by.is_empty()
+        && select.having.is_none()
+        && select.named_window.is_empty()
+        && select.qualify.is_none()
+}
+
+fn table(select: &Select) -> Result<Table> {
+    let [from] = select.from.as_slice() else {
+        return Err(bad("expected FROM receipts or FROM anchors"));
+    };
+    match &from.relation {
+        TableFactor::Table {
+            name,
+            alias: None,
+            args: None,
+            ..
+        } if from.joins.is_empty() => match name.to_string().to_ascii_lowercase().as_str() {
+            "receipts" => Ok(Table::Receipts),
+            "anchors" => Ok(Table::Anchors),
+            other => Err(bad(format!(
+                "unknown table {other}; expected receipts or anchors"
+            ))),
+        },
+        _ => Err(bad("expected FROM receipts or FROM anchors")),
+    }
+}
+
+fn column(expr: &Expr) -> Result<Column> {
+    match expr {
+        Expr::Identifier(ident) => Ok(Column(vec![ident.value.clone()])),
+        Expr::CompoundIdentifier(idents) => {
+            Ok(Column(idents.iter().map(|i| i.value.clone()).collect()))
+        }
+        Expr::Nested(expr) => column(expr),
+        other => Err(bad(format!("expected a column, found {other}"))),
+    }
+}
+
+fn literal(expr: &Expr) -> Result<Value> {
+    match expr {
+        Expr::Value(Literal::SingleQuotedString(s)) => Ok(Value::String(s.clone())),
+        Expr::Value(Literal::Number(n, _)) => number(n),
+        Expr::Value(Literal::Boolean(b)) => Ok(Value::Bool(*b)),
+        Expr::Value(Literal::Null) => Ok(Value::Null),
+        Expr::UnaryOp {
+            op: UnaryOperator::Minus,
+            expr: inner,
+        } => match &**inner {
+            Expr::Value(Literal::Number(n, _)) => number(&format!("-{n}")),
+            _ => Err(bad(format!("expected a literal, found {expr}"))),
+        },
+        Expr::Nested(expr) => literal(expr),
+        other => Err(bad(format!("expected a literal, found {other}"))),
+    }
+}
+
+fn number(n: &str) -> Result<Value> {
+    if let Ok(n) = n.parse::<i64>() {
+        return Ok(Value::from(n));
+    }
+    n.parse::<f64>()
+        .ok()
+        .and_then(serde_json::Number::from_f64)
+        .map(Value::Number)
+        .ok_or_else(|| bad(format!("{n} is not a number")))
+}
+
+fn predicate(expr: &Expr) -> Result<Predicate> {
+    let negate = |p: Predicate, negated: bool| {
+        if negated {
+            Predicate::Not(Box::new(p))
+        } else {
+            p
+        }
+    };
+    Ok(match expr {
+        Expr::Nested(expr) => predicate(expr)?,
+        Expr::UnaryOp {
+            op: UnaryOperator::Not,
+            expr,
+        } => Predicate::Not(Box::new(predicate(expr)?)),
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => Predicate::And(Box::new(predicate(left)?), Box::new(predicate(right)?)),
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::Or,
+            right,
+        } => Predicate::Or(Box::new(predicate(left)?), Box::new(predicate(right)?)),
+        Expr::BinaryOp { left, op, right } => {
+            let op = match op {
+                BinaryOperator::Eq => Op::Eq,
+                BinaryOperator::NotEq => Op::NotEq,
+                BinaryOperator::Lt => Op::Lt,
+                BinaryOperator::LtEq => Op::LtEq,
+                BinaryOperator::Gt => Op::Gt,
+                BinaryOperator::GtEq => Op::GtEq,
+                other => return Err(bad(format!("unsupported operator {other}"))),
+            };
+            match (column(left), column(right)) {
+                (Ok(column), _) => Predicate::Compare(column, op, literal(right)?),
+                (_, Ok(column)) => Predicate::Compare(column, op.flip(), literal(left)?),
+                _ => return Err(bad(format!("{expr} compares no column"))),
+            }
+        }
+        Expr::InList {
+            expr,
+            list,
+            negated,
+        } => {
+            let column = column(expr)?;
+            let mut any = None;
+            for value in list {
+                let eq = Predicate::Compare(column.clone(), Op::Eq, literal(value)?);
+                any = Some(match any {
+                    Some(p) => Predicate::Or(Box::new(p), Box::new(eq)),
+                    None => eq,
+                });
+            }
+            negate(any.ok_or_else(|| bad("IN needs a value"))?, *negated)
+        }
+        Expr::Between {
+            expr,
+            negated,
+            low,
+            high,
+        } => {
+            let column = column(expr)?;
+            let between = Predicate::And(
+                Box::new(Predicate::Compare(column.clone(), Op::GtEq, literal(low)?)),
+                Box::new(Predicate::Compare(column, Op::LtEq, literal(high)?)),
+            );
+            negate(between, *negated)
+        }
+        Expr::IsNull(expr) => Predicate::IsNull(column(expr)?),
+        Expr::IsNotNull(expr) => Predicate::Not(Box::new(Predicate::IsNull(column(expr)?))),
+        other => return Err(bad(format!("unsupported condition {other}"))),
+    })
+}
+
+/// How a query reads the stores.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    pub table: Table,
+    pub tenant_id: String,
+    /// A pinned glyph ID: one point read per store instead of a range.
+    pub glyph_id: Option<GlyphId>,
+    /// A pinned `receipt_type`, answered by the hot path's index.
+    pub receipt_type: Option<String>,
+    /// Rows with `from <= timestamp < to`.
+    pub from: i64,
+    pub to: i64,
+}
+
+impl Plan {
+    fn bound(&mut self, op: Op, t: i64) {
+        let (from, to) = match op {
+            Op::Eq => (t, t.saturating_add(1)),
+            Op::Gt => (t.saturating_add(1), i64::MAX),
+            Op::GtEq => (t, i64::MAX),
+            Op::Lt => (i64::MIN, t),
+            Op::LtEq => (i64::MIN, t.saturating_add(1)),
+            Op::NotEq => return,
+        };
+        self.from = self.from.max(from);
+        self.to = self.to.min(to);
+    }
+
+    /// The tenant's glyphs in range as JSON, each with its store, hot path
+    /// first; a glyph in both stores is read once.
+    fn read(&self, ledger: &Ledger) -> Result<Vec<(GlyphId, Value, Layer)>> {
+        let (sqlite, rocks) = (ledger.sqlite(), ledger.rocks());
+        let tenant_id = self.tenant_id.as_str();
+        let cold = rocks.tenants().any(|t| t == tenant_id);
+        let mut rows = Vec::new();
+        match (self.table, &self.glyph_id) {
+            (Table::Receipts, Some(id)) => {
+                let hot = sqlite.receipt(id)?.filter(|r| r.tenant_id == tenant_id);
+                push(&mut rows, hot, Layer::Sqlite)?;
+                if cold {
+                    push(&mut rows, rocks.receipt(tenant_id, id)?, Layer::RocksDb)?;
+                }
+            }
+            (Table::Receipts, None) => {
+                let hot = sqlite.receipts_between(
+                    tenant_id,
+                    self.receipt_type.as_deref(),
+                    self.from,
+                    self.to,
+                )?;
+                push(&mut rows, hot, Layer::Sqlite)?;
+                if cold {
+                    let archived = rocks.receipts_between(tenant_id, self.from, self.to)?;
+                    push(&mut rows, archived, Layer::RocksDb)?;
+                }
+            }
+            (Table::Anchors, Some(id)) => {
+                let hot = sqlite.anchor(id)?.filter(|a| a.tenant_id == tenant_id);
+                push(&mut rows, hot, Layer::Sqlite)?;
+                if cold {
+                    push(&mut rows, rocks.anchor(tenant_id, id)?, Layer::RocksDb)?;
+                }
+            }
+            (Table::Anchors, None) => {
+                let hot = sqlite.anchors_between(tenant_id, self.from, self.to)?;
+                push(&mut rows, hot, Layer::Sqlite)?;
+                if cold {
+                    let archived = rocks.anchors_between(tenant_id, self.from, self.to)?;
+                    push(
+                        &mut rows,
+                        archived.into_iter().map(|a| a.anchor),
+                        Layer::RocksDb,
+                    )?;
+                }
+            }
+        }
+        let mut seen = BTreeSet::new();
+        rows.retain(|(glyph_id, _, _)| seen.insert(*glyph_id));
+        Ok(rows)
+    }
+}
+
+fn push<G: Glyph>(
+    rows: &mut Vec<(GlyphId, Value, Layer)>,
+    glyphs: impl IntoIterator<Item = G>,
+    layer: Layer,
+) -> Result<()> {
+    for glyph in glyphs {
+        let json = serde_json::to_value(&glyph).map_err(GlyphError::from)?;
+        rows.push((*glyph.id(), json, layer));
+    }
+    Ok(())
+}
+
+/// One result row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row {
+    pub glyph_id: GlyphId,
+    /// The store it was read from.
+    pub layer: Layer,
+    /// One per result column.
+    pub values: Vec<Value>,
+    /// The receipt's inclusion proof when asked for; `None` for anchors and
+    /// for receipts no anchor has committed to yet.
+    pub proof: Option<AnchoredProof>,
+}
+
+/// What a query returned, with the plan that read it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultSet {
+    pub columns: Vec<String>,
+    pub rows: Vec<Row>,
+    pub plan: Plan,
+}
@@ -0,0 +1,788 @@
+//! SQLite hot path of the ledger.
+//!
+//! # Layout
+//!
+//! - `glyphs` holds every stored glyph's canonical JSON
+//!   ([`canonical_body`]), one row per ID, in append order (`seq`).
+//! - `receipts` and `anchors` index the typed fields queries filter on:
+//!   `tenant_id`, `receipt_type`, `timestamp`, `ref_glyph_id`.
+//! - `anchor_receipts` lists each anchor's receipts by leaf index, and
+//!   `proof_nodes` keeps every node of the anchor's Merkle tree, so an
+//!   inclusion proof is a handful of primary-key reads.
+//!
+//! # Append-only
+//!
+//! Every table carries `BEFORE UPDATE` and `BEFORE DELETE` triggers that
+//! abort the statement, on any connection to the file and not just this
+//! type. A glyph ID can be appended once; a second append is
+//! [`LedgerError::Duplicate`].
+//!
+//! The one way out is eviction of an aged [`Window`]: its glyph IDs are
+//! first appended to `archived`, and the delete triggers let through only
+//! rows of archived glyphs. `archived` is append-only too, and its insert
+//! trigger admits only glyphs of a stored window whose anchor is no newer
+//! than `archived_at`, so unanchored glyphs and fresh windows cannot be
+//! deleted at all.
+//!
+//! SQLite cannot see the cold archive, so that the window is safe there is
+//! checked by the caller, not the database: [`crate::rocksdb_store`]
+//! evicts a window only after the cold archive serves a verified proof for
+//! every receipt in it. A writer that bypasses it can still archive and
+//! delete an aged window, and one that drops the triggers can do anything;
+//! they guard the ledger against mistakes, not against its own host.
+//!
+//! # Tenant caps
+//!
+//! A tenant's size is the total length in bytes of the glyph JSON it has
+//! stored.
+//! An append that would take it past `max_size_bytes` in
+//! `config/ledger.sqlite.toml` is rejected, and tenants without a
+//! `[tenant."<id>"]` block cannot append at all.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use glyph_lib::anchors::merkle::{
+    hash_from_hex, hash_to_hex, leaf_hash, MerkleHash, MerkleProof, MerkleTree,
+};
+use glyph_lib::config::ledger::SqliteLedgerConfig;
+use glyph_lib::hashing::canonical::{content_hash, to_canonical_string};
+use glyph_lib::hashing::ids::verify_id;
+use glyph_lib::{AnchorGlyph, Glyph, GlyphError, GlyphId, IntentGlyph, ReceiptGlyph};
+use rusqlite::{params, Connection, ErrorCode, OptionalExtension, Transaction};
+
+use crate::error::{LedgerError, Result};
+
+/// Tables covered by the append-only triggers, each with the column that
+/// names the glyph a row belongs to.
+pub const LEDGER_TABLES: [(&str, &str); 5] = [
+    ("glyphs", "glyph_id"),
+    ("receipts", "receipt_id"),
+    ("anchors", "glyph_id"),
+    ("anchor_receipts", "anchor_id"),
+    ("proof_nodes", "anchor_id"),
+];
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS glyphs (
+    seq          INTEGER PRIMARY KEY AUTOINCREMENT,
+    glyph_id     TEXT    NOT NULL UNIQUE,
+    kind         TEXT    NOT NULL,
+    tenant_id    TEXT    NOT NULL,
+    timestamp    INTEGER NOT NULL,
+    blake3_hash  TEXT    NOT NULL,
+    body         TEXT    NOT NULL
+);
+CREATE INDEX IF NOT EXISTS glyphs_tenant_time ON glyphs (tenant_id, timestamp);
+CREATE INDEX IF NOT EXISTS glyphs_kind_time ON glyphs (kind, timestamp);
+
+CREATE TABLE IF NOT EXISTS receipts (
+    receipt_id   TEXT    PRIMARY KEY REFERENCES glyphs (glyph_id),
+    tenant_id    TEXT    NOT NULL,
+    receipt_type TEXT    NOT NULL,
+    ref_glyph_id TEXT    NOT NULL,
+    result       TEXT    NOT NULL,
+    timestamp    INTEGER NOT NULL,
+    blake3_hash  TEXT    NOT NULL,
+    merkle_root  TEXT    NOT NULL,
+    emitted_by   TEXT    NOT NULL
+);
+CREATE INDEX IF NOT EXISTS receipts_tenant_type_time
+    ON receipts (tenant_id, receipt_type, timestamp);
+CREATE INDEX IF NOT EXISTS receipts_tenant_time ON receipts (tenant_id, timestamp);
+CREATE INDEX IF NOT EXISTS receipts_type_time ON receipts (receipt_type, timestamp);
+CREATE INDEX IF NOT EXISTS receipts_ref ON receipts (ref_glyph_id);
+
+CREATE TABLE IF NOT EXISTS anchors (
+    glyph_id          TEXT    PRIMARY KEY REFERENCES glyphs (glyph_id),
+    tenant_id         TEXT    NOT NULL,
+    context           TEXT    NOT NULL,
+    previous_glyph_id TEXT    NOT NULL,
+    merkle_root       TEXT    NOT NULL,
+    leaf_count        INTEGER NOT NULL,
+    timestamp         INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS anchors_tenant_time ON anchors (tenant_id, timestamp);
+CREATE INDEX IF NOT EXISTS anchors_previous ON anchors (previous_glyph_id);
+
+CREATE TABLE IF NOT EXISTS anchor_receipts (
+    anchor_id  TEXT    NOT NULL REFERENCES anchors (glyph_id),
+    leaf_index INTEGER NOT NULL,
+    receipt_id TEXT    NOT NULL REFERENCES receipts (receipt_id),
+    PRIMARY KEY (anchor_id, leaf_index)
+);
+CREATE INDEX IF NOT EXISTS anchor_receipts_receipt ON anchor_receipts (receipt_id);
+
+CREATE TABLE IF NOT EXISTS proof_nodes (
+    anchor_id TEXT    NOT NULL REFERENCES anchors (glyph_id),
+    level     INTEGER NOT NULL,
+    position  INTEGER NOT NULL,
+    hash      TEXT    NOT NULL,
+    PRIMARY KEY (anchor_id, level, position)
+);
+
+CREATE TABLE IF NOT EXISTS archived (
+    glyph_id    TEXT    PRIMARY KEY,
+    archived_at INTEGER NOT NULL
+);
+CREATE TRIGGER IF NOT EXISTS archived_windows_only BEFORE INSERT ON archived
+WHEN NOT EXISTS (
+    SELECT 1 FROM anchors a
+    WHERE a.timestamp <= NEW.archived_at
+      AND (a.glyph_id = NEW.glyph_id
+           OR EXISTS (SELECT 1 FROM anchor_receipts m
+                      WHERE m.anchor_id = a.glyph_id AND m.receipt_id = NEW.glyph_id))
+)
+BEGIN SELECT RAISE(ABORT, 'append-only ledger: only aged anchored windows can be archived'); END;
+CREATE TRIGGER IF NOT EXISTS archived_no_update BEFORE UPDATE ON archived
+BEGIN SELECT RAISE(ABORT, 'append-only ledger: update on archived rejected'); END;
+CREATE TRIGGER IF NOT EXISTS archived_no_delete BEFORE DELETE ON archived
+BEGIN SELECT RAISE(ABORT, 'append-only ledger: delete on archived rejected'); END;
+";
+
+fn append_only_triggers() -> String {
+    LEDGER_TABLES
+        .iter()
+        .map(|(table, owner)| {
+            format!(
+                "CREATE TRIGGER IF NOT EXISTS {table}_no_update BEFORE UPDATE ON {table}
+                 BEGIN SELECT RAISE(ABORT, 'append-only ledger: update on {table} rejected'); END;
+                 CREATE TRIGGER IF NOT EXISTS {table}_no_delete BEFORE DELETE ON {table}
+                 WHEN NOT EXISTS (SELECT 1 FROM archived WHERE glyph_id = OLD.{owner})
+                 BEGIN SELECT RAISE(ABORT, 'append-only ledger: delete on {table} rejected'); END;\n"
+            )
+        })
+        .collect()
+}
+
+/// A receipt's inclusion proof under the anchor that first committed to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnchoredProof {
+    pub anchor_id: GlyphId,
+    pub merkle_root: MerkleHash,
+    pub proof: MerkleProof,
+}
+
+impl AnchoredProof {
+    /// True if `receipt` is the leaf this proof was served for.
+    pub fn verify_receipt(&self, receipt: &ReceiptGlyph) -> bool {
+        self.proof.verify_receipt(&self.merkle_root, receipt)
+    }
+}
+
+/// An anchor with its receipts in leaf order: the unit that ages out of
+/// the hot path into the cold archive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Window {
+    pub anchor: AnchorGlyph,
+    pub receipts: Vec<ReceiptGlyph>,
+}
+
+impl Window {
+    /// The window's Merkle tree; its root is the anchor's `merkle_root`.
+    pub fn tree(&self) -> Result<MerkleTree> {
+        Ok(MerkleTree::from_receipts(&self.receipts)?)
+    }
+}
+
+/// The SQLite hot path.
+pub struct SqliteStore {
+    conn: Connection,
+    caps: BTreeMap<String, u64>,
+    usage: BTreeMap<String, u64>,
+    vacuum_pages: u32,
+}
+
+impl SqliteStore {
+    /// Opens (creating if needed) the database at `config.path`.
+    pub fn open(config: &SqliteLedgerConfig) -> Result<Self> {
+        Self::open_at(&config.path, config)
+    }
+
+    /// Opens the database at `path` with `config`'s pragmas and caps.
+    pub fn open_at(path: impl AsRef<Path>, config: &SqliteLedgerConfig) -> Result<Self> {
+        Self::init(Connection::open(path)?, config)
+    }
+
+    /// An in-memory ledger, for tests and dry runs.
+    pub fn open_in_memory(config: &SqliteLedgerConfig) -> Result<Self> {
+        Self::init(Connection::open_in_memory()?, config)
+    }
+
+    fn init(conn: Connection, config: &SqliteLedgerConfig) -> Result<Self> {
+        // auto_vacuum only takes effect before the first table is created.
+        conn.pragma_update(None, "auto_vacuum", &config.vacuum.auto_vacuum)?;
+        conn.pragma_update(None, "journal_mode", &config.journal_mode)?;
+        conn.pragma_update(None, "synchronous", &config.synchronous)?;
+        conn.pragma_update(None, "cache_size", config.cache_size)?;
+        conn.pragma_update(None, "temp_store", &config.temp_store)?;
+        conn.pragma_update(None, "foreign_keys", config.foreign_keys)?;
+        conn.busy_timeout(std::time::Duration::from_millis(config.busy_timeout_ms))?;
+        // One file serves every tenant, so checkpoint as often as the
+        // strictest tenant asks.
+        if let Some(pages) = config.tenant.values().map(|t| t.wal_autocheckpoint).min() {
+            conn.pragma_update(None, "wal_autocheckpoint", pages)?;
+        }
+        conn.execute_batch(SCHEMA)?;
+        conn.execute_batch(&append_only_triggers())?;
+
+        let usage = conn
+            .prepare(
+                "SELECT tenant_id, SUM(length(CAST(body AS BLOB))) FROM glyphs GROUP BY tenant_id",
+            )?
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as u64))
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+        let caps = config
+            .tenant
+            .iter()
+            .map(|(id, t)| (id.clone(), t.max_size_bytes))
+            .collect();
+        Ok(SqliteStore {
+            conn,
+            caps,
+            usage,
+            vacuum_pages: config.vacuum.incremental_vacuum_pages,
+        })
+    }
+
+    /// Read access for queries; writes through it still hit the triggers.
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Tenants with a `[tenant."<id>"]` block, in ID order.
+    pub fn tenants(&self) -> impl Iterator<Item = &str> {
+        self.caps.keys().map(String::as_str)
+    }
+
+    /// Bytes of glyph JSON `tenant_id` has stored.
+    pub fn tenant_usage(&self, tenant_id: &str) -> u64 {
+        self.usage.get(tenant_id).copied().unwrap_or(0)
+    }
+
+    /// Returns up to `[vacuum] incremental_vacuum_pages` free pages to the
+    /// file system.
+    pub fn incremental_vacuum(&self) -> Result<()> {
+        let pages = self.vacuum_pages;
+        self.conn
+            .execute_batch(&format!("PRAGMA incremental_vacuum({pages});"))?;
+        Ok(())
+    }
+
+    /// Appends an IntentGlyph.
+    pub fn append_intent(&mut self, intent: &IntentGlyph) -> Result<()> {
+        verify_id(intent)?;
+        let hash = content_hash(intent)?.to_hex().to_string();
+        let body = canonical_body(intent)?;
+        self.check_cap(&intent.tenant_id, body.len())?;
+        let tx = self.conn.transaction()?;
+        insert_glyph(&tx, intent, &intent.tenant_id, &hash, &body)?;
+        tx.commit()?;
+        self.record(&intent.tenant_id, body.len());
+        Ok(())
+    }
+
+    /// Appends a ReceiptGlyph after checking its ID and `blake3_hash`.
+    pub fn append_receipt(&mut self, receipt: &ReceiptGlyph) -> Result<()> {
+        verify_id(receipt)?;
+        hash_matches(receipt, &receipt.blake3_hash)?;
+        let body = canonical_body(receipt)?;
+        self.check_cap(&receipt.tenant_id, body.len())?;
+        let tx = self.conn.transaction()?;
+        insert_glyph(
+            &tx,
+            receipt,
+            &receipt.tenant_id,
+            &receipt.blake3_hash,
+            &body,
+        )?;
+        tx.execute(
+            "INSERT INTO receipts (receipt_id, tenant_id, receipt_type, ref_glyph_id, result,
+                                   timestamp, blake3_hash, merkle_root, emitted_by)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                receipt.receipt_id.to_string(),
+                receipt.tenant_id,
+                receipt.receipt_type.as_str(),
+                receipt.ref_glyph_id.to_string(),
+                receipt.result.as_str(),
+                receipt.timestamp,
+                receipt.blake3_hash,
+                receipt.merkle_root,
+                receipt.emitted_by.as_str(),
+            ],
+        )?;
+        tx.commit()?;
+        self.record(&receipt.tenant_id, body.len());
+        Ok(())
+    }
+
+    /// Appends an AnchorGlyph together with its Merkle tree.
+    ///
+    /// Every receipt the anchor lists must already be stored for the same
+    /// tenant, and the tree rebuilt from them must have the anchor's
+    /// `merkle_root`; an anchor that cannot serve proofs is never stored.
+    pub fn append_anchor(&mut self, anchor: &AnchorGlyph) -> Result<()> {
+        verify_id(anchor)?;
+        hash_matches(anchor, &anchor.blake3_hash)?;
+        let mut leaves = Vec::with_capacity(anchor.receipts.len());
+        for r in &anchor.receipts {
+            let hash: Option<String> = self
+                .conn
+                .query_row(
+                    "SELECT blake3_hash FROM receipts WHERE receipt_id = ?1 AND tenant_id = ?2",
+                    params![r.receipt_id.to_string(), anchor.tenant_id],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            let hash = hash.ok_or(LedgerError::MissingReceipt {
+                anchor_id: anchor.glyph_id,
+                receipt_id: r.receipt_id,
+            })?;
+            leaves.push(leaf_hash(&hash_from_hex(&hash)?));
+        }
+        let tree = MerkleTree::from_leaf_hashes(leaves);
+        let root = hash_to_hex(&tree.root());
+        if root != anchor.merkle_root {
+            return Err(GlyphError::HashMismatch {
+                expected: root,
+                found: anchor.merkle_root.clone(),
+            }
+            .into());
+        }
+
+        let body = canonical_body(anchor)?;
+        self.check_cap(&anchor.tenant_id, body.len())?;
+        let tx = self.conn.transaction()?;
+        let id = anchor.glyph_id.to_string();
+        insert_glyph(&tx, anchor, &anchor.tenant_id, &anchor.blake3_hash, &body)?;
+        tx.execute(
+            "INSERT INTO anchors (glyph_id, tenant_id, context, previous_glyph_id, merkle_root,
+                                  leaf_count, timestamp)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                id,
+                anchor.tenant_id,
+                anchor.context.as_str(),
+                anchor.previous_glyph_id.to_string(),
+                anchor.merkle_root,
+                tree.len() as i64,
+                anchor.timestamp,
+            ],
+        )?;
+        {
+            let mut member = tx.prepare(
+                "INSERT INTO anchor_receipts (anchor_id, leaf_index, receipt_id) VALUES (?1, ?2, ?3)",
+            )?;
+            for (i, r) in anchor.receipts.iter().enumerate() {
+                member.execute(params![id, i as i64, r.receipt_id.to_string()])?;
+            }
+            let mut node = tx.prepare(
+                "INSERT INTO proof_nodes (anchor_id, level, position, hash) VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for (level, hashes) in tree.levels().iter().enumerate() {
+                for (position, hash) in hashes.iter().enumerate() {
+                    node.execute(params![
+                        id,
+                        level as i64,
+                        position as i64,
+                        hash_to_hex(hash)
+                    ])?;
+                }
+            }
+        }
+        tx.commit()?;
+        self.record(&anchor.tenant_id, body.len());
+        Ok(())
+    }
+
+    /// The stored JSON of any glyph.
+    pub fn glyph_json(&self, glyph_id: &GlyphId) -> Result<Option<String>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT body FROM glyphs WHERE glyph_id = ?1",
+                [glyph_id.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    /// A stored ReceiptGlyph.
+    pub fn receipt(&self, receipt_id: &GlyphId) -> Result<Option<ReceiptGlyph>> {
+        self.decode(receipt_id)
+    }
+
+    /// A stored AnchorGlyph.
+    pub fn anchor(&self, glyph_id: &GlyphId) -> Result<Option<AnchorGlyph>> {
+        self.decode(glyph_id)
+    }
+
+    /// A stored IntentGlyph.
+    pub fn intent(&self, glyph_id: &GlyphId) -> Result<Option<IntentGlyph>> {
+        self.decode(glyph_id)
+    }
+
+    fn decode<G: Glyph>(&self, glyph_id: &GlyphId) -> Result<Option<G>> {
+        if glyph_id.kind() != G::KIND {
+            return Ok(None);
+        }
+        self.glyph_json(glyph_id)?
+            .map(|body| {
+                serde_json::from_str(&body).map_err(|e| LedgerError::Corrupt {
+                    glyph_id: glyph_id.to_string(),
+                    reason: e.to_string(),
+                })
+            })
+            .transpose()
+    }
+
+    /// Visits every stored `G` of `tenant_id` in append order.
+    ///
+    /// A row that no longer decodes reaches `visit` as
+    /// [`LedgerError::Corrupt`] with its `glyph_id` column; an error
+    /// returned by `visit` ends the walk.
+    pub fn each_glyph<G: Glyph>(
+        &self,
+        tenant_id: &str,
+        mut visit: impl FnMut(&str, Result<G>) -> Result<()>,
+    ) -> Result<()> {
+        let mut stmt = self.conn.prepare(
+            "SELECT glyph_id, body FROM glyphs WHERE tenant_id = ?1 AND kind = ?2 ORDER BY seq",
+        )?;
+        let mut rows = stmt.query(params![tenant_id, G::KIND.id_prefix()])?;
+        while let Some(row) = rows.next()? {
+            let glyph_id: String = row.get(0)?;
+            let body: String = row.get(1)?;
+            let glyph = serde_json::from_str(&body).map_err(|e| LedgerError::Corrupt {
+                glyph_id: glyph_id.clone(),
+                reason: e.to_string(),
+            });
+            visit(&glyph_id, glyph)?;
+        }
+        Ok(())
+    }
+
+    /// Inclusion proof of a receipt under the first anchor that lists it,
+    /// read from the stored tree nodes.
+    pub fn receipt_proof(&self, receipt_id: &GlyphId) -> Result<Option<AnchoredProof>> {
+        let found: Option<(String, i64, i64, String)> = self
+            .conn
+            .query_row(
+                "SELECT a.glyph_id, m.leaf_index, a.leaf_count, a.merkle_root
+                 FROM anchor_receipts m JOIN anchors a ON a.glyph_id = m.anchor_id
+                 JOIN glyphs g ON g.glyph_id = a.glyph_id
+                 WHERE m.receipt_id = ?1
+                 ORDER BY g.seq LIMIT 1",
+                [receipt_id.to_string()],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .optional()?;
+        let Some((anchor_id, leaf_index, leaf_count, root)) = found else {
+            return Ok(None);
+        };
+        let corrupt = |reason: String| LedgerError::Corrupt {
+            glyph_id: anchor_id.clone(),
+            reason,
+        };
+        let mut node = self.conn.prepare_cached(
+            "SELECT hash FROM proof_nodes WHERE anchor_id = ?1 AND level = ?2 AND position = ?3",
+        )?;
+        let mut siblings = Vec::new();
+        let (mut i, mut width, mut level) = (leaf_index as u64, leaf_count as u64, 0i64);
+        while width > 1 {
+            if i ^ 1 < width {
+                let hash: Option<String> = node
+                    .query_row(params![anchor_id, level, (i ^ 1) as i64], |row| row.get(0))
+                    .optional()?;
+                let hash =
+                    hash.ok_or_else(|| corrupt(format!("no proof node {level}/{}", i ^ 1)))?;
+                siblings.push(hash_from_hex(&hash)?);
+            }
+            i /= 2;
+            width = width.div_ceil(2);
+            level += 1;
+        }
+        Ok(Some(AnchoredProof {
+            anchor_id: anchor_id
+                .parse()
+                .map_err(|e: GlyphError| corrupt(e.to_string()))?,
+            merkle_root: hash_from_hex(&root)?,
+            proof: MerkleProof {
+                leaf_index: leaf_index as u64,
+                leaf_count: leaf_count as u64,
+                siblings,
+            },
+        }))
+    }
+
+    /// Anchors of `tenant_id` older than `before`, in append order.
+    pub fn aged_windows(&self, tenant_id: &str, before: i64) -> Result<Vec<GlyphId>> {
+        self.ids(
+            "SELECT a.glyph_id FROM anchors a JOIN glyphs g ON g.glyph_id = a.glyph_id
+             WHERE a.tenant_id = ?1 AND a.timestamp < ?2 ORDER BY g.seq",
+            params![tenant_id, before],
+        )
+    }
+
+    /// Receipts of `tenant_id` whose `ref_glyph_id` is `glyph_id`, in
+    /// append order.
+    pub fn referencing(&self, tenant_id: &str, glyph_id: &GlyphId) -> Result<Vec<GlyphId>> {
+        self.ids(
+            "SELECT r.receipt_id FROM receipts r JOIN glyphs g ON g.glyph_id = r.receipt_id
+             WHERE r.ref_glyph_id = ?1 AND r.tenant_id = ?2 ORDER BY g.seq",
+            params![glyph_id.to_string(), tenant_id],
+        )
+    }
+
+    /// Anchors of `tenant_id` whose `previous_glyph_id` is `anchor_id`, in
+    /// append order.
+    pub fn successors(&self, tenant_id: &str, anchor_id: &GlyphId) -> Result<Vec<GlyphId>> {
+        self.ids(
+            "SELECT a.glyph_id FROM anchors a JOIN glyphs g ON g.glyph_id = a.glyph_id
+             WHERE a.previous_glyph_id = ?1 AND a.tenant_id = ?2 ORDER BY g.seq",
+            params![anchor_id.to_string(), tenant_id],
+        )
+    }
+
+    /// Anchors of `tenant_id` that list `receipt_id`, in append order.
+    pub fn committing(&self, tenant_id: &str, receipt_id: &GlyphId) -> Result<Vec<GlyphId>> {
+        self.ids(
+            "SELECT a.glyph_id FROM anchor_receipts m JOIN anchors a ON a.glyph_id = m.anchor_id
+             JOIN glyphs g ON g.glyph_id = a.glyph_id
+             WHERE m.receipt_id = ?1 AND a.tenant_id = ?2 ORDER BY g.seq",
+            params![receipt_id.to_string(), tenant_id],
+        )
+    }
+
+    /// Receipts of `tenant_id` with `from <= timestamp < to`, only those of
+    /// `receipt_type` if given, oldest first.
+    pub fn receipts_between(
+        &self,
+        tenant_id: &str,
+        receipt_type: Option<&str>,
+        from: i64,
+        to: i64,
+    ) -> Result<Vec<ReceiptGlyph>> {
+        match receipt_type {
+            Some(receipt_type) => self.bodies(
+                "SELECT g.glyph_id, g.body FROM receipts r JOIN glyphs g ON g.glyph_id = r.receipt_id
+                 WHERE r.tenant_id = ?1 AND r.receipt_type = ?2
+                   AND r.timestamp >= ?3 AND r.timestamp < ?4
+                 ORDER BY r.timestamp, g.seq",
+                params![tenant_id, receipt_type, from, to],
+            ),
+            None => self.bodies(
+                "SELECT g.glyph_id, g.body FROM receipts r JOIN glyphs g ON g.glyph_id = r.receipt_id
+                 WHERE r.tenant_id = ?1 AND r.timestamp >= ?2 AND r.timestamp < ?3
+                 ORDER BY r.timestamp, g.seq",
+                params![tenant_id, from, to],
+            ),
+        }
+    }
+
+    /// Anchors of `tenant_id` with `from <= timestamp < to`, oldest first.
+    pub fn anchors_between(&self, tenant_id: &str, from: i64, to: i64) -> Result<Vec<AnchorGlyph>> {
+        self.bodies(
+            "SELECT g.glyph_id, g.body FROM anchors a JOIN glyphs g ON g.glyph_id = a.glyph_id
+             WHERE a.tenant_id = ?1 AND a.timestamp >= ?2 AND a.timestamp < ?3
+             ORDER BY a.timestamp, g.seq",
+            params![tenant_id, from, to],
+        )
+    }
+
+    fn bodies<G: Glyph>(&self, sql: &str, params: impl rusqlite::Params) -> Result<Vec<G>> {
+        let rows = self
+            .conn
+            .prepare_cached(sql)?
+            .query_map(params, |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        rows.into_iter()
+            .map(|(glyph_id, body)| {
+                serde_json::from_str(&body).map_err(|e| LedgerError::Corrupt {
+                    glyph_id,
+                    reason: e.to_string(),
+                })
+            })
+            .collect()
+    }
+
+    fn ids(&self, sql: &str, params: impl rusqlite::Params) -> Result<Vec<GlyphId>> {
+        let ids = self
+            .conn
+            .prepare_cached(sql)?
+            .query_map(params, |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        ids.iter()
+            .map(|id| {
+                id.parse().map_err(|e: GlyphError| LedgerError::Corrupt {
+                    glyph_id: id.clone(),
+                    reason: e.to_string(),
+                })
+            })
+            .collect()
+    }
+
+    /// A stored anchor with its receipts.
+    pub fn window(&self, anchor_id: &GlyphId) -> Result<Option<Window>> {
+        let Some(anchor) = self.anchor(anchor_id)? else {
+            return Ok(None);
+        };
+        let receipts = anchor
+            .receipts
+            .iter()
+            .map(|r| {
+                self.receipt(&r.receipt_id)?
+                    .ok_or_else(|| LedgerError::MissingReceipt {
+                        anchor_id: *anchor_id,
+                        receipt_id: r.receipt_id,
+                    })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Some(Window { anchor, receipts }))
+    }
+
+    /// Removes a window from the hot path.
+    ///
+    /// Receipts that another stored anchor still lists stay. Only call this
+    /// once the window is safely archived elsewhere; the rows are gone for
+    /// good and `archived` keeps their IDs as the record of why.
+    pub fn evict_window(&mut self, anchor_id: &GlyphId, archived_at: i64) -> Result<()> {
+        let Some(anchor) = self.anchor(anchor_id)? else {
+            return Ok(());
+        };
+        let id = anchor_id.to_string();
+        let tx = self.conn.transaction()?;
+        let mut evicted = vec![id.clone()];
+        {
+            let mut shared = tx.prepare(
+                "SELECT 1 FROM anchor_receipts WHERE receipt_id = ?1 AND anchor_id != ?2",
+            )?;
+            for r in &anchor.receipts {
+                let receipt_id = r.receipt_id.to_string();
+                if !shared.exists(params![receipt_id, id])? && !evicted.contains(&receipt_id) {
+                    evicted.push(receipt_id);
+                }
+            }
+            let mut archive =
+                tx.prepare("INSERT INTO archived (glyph_id, archived_at) VALUES (?1, ?2)")?;
+            for glyph_id in &evicted {
+                archive.execute(params![glyph_id, archived_at])?;
+            }
+        }
+        let mut freed = 0u64;
+        for glyph_id in &evicted {
+            freed += tx.query_row(
+                "SELECT length(CAST(body AS BLOB)) FROM glyphs WHERE glyph_id = ?1",
+                [glyph_id],
+                |row| row.get::<_, i64>(0),
+            )? as u64;
+        }
+        tx.execute("DELETE FROM proof_nodes WHERE anchor_id = ?1", [&id])?;
+        tx.execute("DELETE FROM anchor_receipts WHERE anchor_id = ?1", [&id])?;
+        tx.execute("DELETE FROM anchors WHERE glyph_id = ?1", [&id])?;
+        for glyph_id in &evicted {
+            tx.execute("DELETE FROM receipts WHERE receipt_id = ?1", [glyph_id])?;
+            tx.execute("DELETE FROM glyphs WHERE glyph_id = ?1", [glyph_id])?;
+        }
+        tx.commit()?;
+        if let Some(used) = self.usage.get_mut(&anchor.tenant_id) {
+            *used = used.saturating_sub(freed);
+        }
+        Ok(())
+    }
+
+    /// True if `glyph_id` was evicted from the hot path.
+    pub fn is_archived(&self, glyph_id: &GlyphId) -> Result<bool> {
+        Ok(self
+            .conn
+            .prepare_cached("SELECT 1 FROM archived WHERE glyph_id = ?1")?
+            .exists([glyph_id.to_string()])?)
+    }
+
+    /// Errors unless `tenant_id` may store `adding` more bytes.
+    fn check_cap(&self, tenant_id: &str, adding: usize) -> Result<()> {
+        let max_size_bytes =
+            *self
+                .caps
+                .get(tenant_id)
+                .ok_or_else(|| LedgerError::UnknownTenant {
+                    tenant_id: tenant_id.to_string(),
+                })?;
+        let used = self.tenant_usage(tenant_id);
+        if used + adding as u64 > max_size_bytes {
+            return Err(LedgerError::TenantFull {
+                tenant_id: tenant_id.to_string(),
+                used,
+                adding: adding as u64,
+                max_size_bytes,
+            });
+        }
+        Ok(())
+    }
+
+    fn record(&mut self, tenant_id: &str, adding: usize) {
+        *self.usage.entry(tenant_id.to_string()).or_default() += adding as u64;
+    }
+}
+
+/// The JSON a glyph is stored as: its canonical form, nothing excluded.
+pub fn canonical_body<G: Glyph>(glyph: &G) -> Result<String> {
+    let value = serde_json::to_value(glyph).map_err(GlyphError::from)?;
+    Ok(to_canonical_string(&value)?)
+}
+
+fn insert_glyph<G: Glyph>(
+    tx: &Transaction<'_>,
+    glyph: &G,
+    tenant_id: &str,
+    blake3_hash: &str,
+    body: &str,
+) -> Result<()> {
+    // An evicted ID lives on in the cold archive; it cannot come back.
+    if tx
+        .prepare_cached("SELECT 1 FROM archived WHERE glyph_id = ?1")?
+        .exists([glyph.id().to_string()])?
+    {
+        return Err(LedgerError::Duplicate {
+            glyph_id: *glyph.id(),
+        });
+    }
+    let inserted = tx.execute(
+        "INSERT INTO glyphs (glyph_id, kind, tenant_id, timestamp, blake3_hash, body)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            glyph.id().to_string(),
+            G::KIND.id_prefix(),
+            tenant_id,
+            glyph.timestamp(),
+            blake3_hash,
+            body,
+        ],
+    );
+    match inserted {
+        Err(rusqlite::Error::SqliteFailure(e, _)) if e.code == ErrorCode::ConstraintViolation => {
+            Err(LedgerError::Duplicate {
+                glyph_id: *glyph.id(),
+            })
+        }
+        other => other.map(|_| ()).map_err(Into::into),
+    }
+}
+
+pub(crate) fn hash_matches<G: Glyph>(glyph: &G, claimed: &str) -> Result<()> {
+    let expected = content_hash(glyph)?.to_hex().to_string();
+    if expected == claimed {
+        Ok(())
+    } else {
+        Err(GlyphError::HashMismatch {
+            expected,
+            found: claimed.to_string(),
+        }
+        .into())
+    }
+}
@@ -0,0 +1,52 @@
+//! Fixtures shared by the ledger-explorer integration tests.
+
+// Each test binary compiles this module and uses only part of it.
+#![allow(dead_code)]
+
+use glyph_lib::config::ledger::{RocksDbLedgerConfig, SqliteLedgerConfig};
+use glyph_lib::hashing::ids::GlyphId;
+use glyph_lib::testing::{anchored, read_repo_file, sealed, MacKey};
+use glyph_lib::{ChainLink, GlyphKind};
+use ledger_explorer::rocksdb_store::RocksDbStore;
+use ledger_explorer::sqlite_store::{SqliteStore, Window};
+use tempfile::TempDir;
+
+pub const XAI: &str = "xai-memphis-01";
+pub const SPACEX: &str = "spacex-orbit-01";
+pub const NOW: i64 = 1_764_382_460;
+
+pub fn config_text(file: &str) -> String {
+    read_repo_file(format!("config/{file}"))
+}
+
+pub fn rocksdb_config() -> RocksDbLedgerConfig {
+    RocksDbLedgerConfig::from_toml(&config_text("ledger.rocksdb.toml")).unwrap()
+}
+
+/// An empty in-memory hot path.
+pub fn sqlite() -> SqliteStore {
+    let config = SqliteLedgerConfig::from_toml(&config_text("ledger.sqlite.toml")).unwrap();
+    SqliteStore::open_in_memory(&config).unwrap()
+}
+
+/// An empty archive in a directory removed when the guard drops; keep the
+/// guard alive as long as the store.
+pub fn archive() -> (RocksDbStore, TempDir) {
+    let dir = TempDir::new().unwrap();
+    let store = RocksDbStore::open_at(dir.path(), &rocksdb_config()).unwrap();
+    (store, dir)
+}
+
+/// A window of `count` receipts whose anchor is stamped at `at`.
+pub fn window(tenant_id: &str, at: i64, count: usize, previous: ChainLink) -> Window {
+    let intent = GlyphId::derive(GlyphKind::Intent, tenant_id.as_bytes());
+    let receipts = sealed(
+        tenant_id,
+        intent,
+        at - 60,
+        count,
+        &MacKey::of("rocket-engine"),
+    );
+    let anchor = anchored(&receipts, at, previous);
+    Window { anchor, receipts }
+}
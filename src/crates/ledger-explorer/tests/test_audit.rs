@@ -0,0 +1,261 @@
+mod common;
+
+#[cfg(test)]
+mod test_audit {
+    use crate::common::{self, window, NOW, SPACEX, XAI};
+    use glyph_lib::anchors::receipt_payload::ReceiptPayload;
+    use glyph_lib::testing::MacKey;
+    use glyph_lib::{AnchorGlyph, AnomalySeverity, ChainLink, ReceiptGlyph, ReceiptResult};
+    use ledger_explorer::audit::{Divergence, Layer};
+    use ledger_explorer::ledger::Ledger;
+    use ledger_explorer::LedgerError;
+    use serde::Serialize;
+    use tempfile::TempDir;
+
+    /// A ledger holding an archived and a hot window per tenant, with the
+    /// receipts and anchors the log recorded, oldest window first.
+    fn ledger() -> (Ledger, Vec<ReceiptGlyph>, Vec<AnchorGlyph>, TempDir) {
+        let mut sqlite = common::sqlite();
+        let (rocks, dir) = common::archive();
+        let (mut log, mut anchors) = (Vec::new(), Vec::new());
+        for tenant_id in [XAI, SPACEX] {
+            for at in [NOW - 1_000, NOW] {
+                let w = window(tenant_id, at, 4, ChainLink::Genesis);
+                for receipt in &w.receipts {
+                    sqlite.append_receipt(receipt).unwrap();
+                }
+                sqlite.append_anchor(&w.anchor).unwrap();
+                log.extend(w.receipts);
+                anchors.push(w.anchor);
+            }
+            rocks
+                .archive_aged(&mut sqlite, tenant_id, NOW - 500, NOW)
+                .unwrap();
+        }
+        (Ledger::new(sqlite, rocks), log, anchors, dir)
+    }
+
+    fn jsonl<G: Serialize>(glyphs: &[G]) -> String {
+        glyphs
+            .iter()
+            .map(|g| serde_json::to_string(g).unwrap() + "\n")
+            .collect()
+    }
+
+    #[test]
+    fn agreeing_layers_serve_proofs() {
+        let (mut ledger, log, anchors, _dir) = ledger();
+        let text = jsonl(&log) + &jsonl(&anchors);
+        let outcome = ledger
+            .audit(text.as_bytes(), &MacKey::of("ledger-explorer"), NOW)
+            .unwrap();
+        assert!(outcome.report.is_consistent(), "{:?}", outcome.report);
+        assert!(outcome.anomalies.is_empty());
+        assert!(outcome.unreported.is_empty());
+        let xai = &outcome.report.tenants[XAI];
+        assert_eq!(
+            (xai.log_receipts, xai.hot_receipts, xai.cold_receipts),
+            (8, 4, 4)
+        );
+        assert_eq!(
+            (xai.log_anchors, xai.hot_anchors, xai.cold_anchors),
+            (2, 1, 1)
+        );
+
+        for receipt in &log {
+            let proof = ledger
+                .receipt_proof(&receipt.tenant_id, &receipt.receipt_id)
+                .unwrap()
+                .unwrap();
+            assert!(proof.verify_receipt(receipt));
+        }
+        // A receipt is only proven for its own tenant.
+        assert!(ledger
+            .receipt_proof(SPACEX, &log[0].receipt_id)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn divergence_refuses_proofs_until_clean() {
+        let (mut ledger, log, anchors, _dir) = ledger();
+        let unstored = window(XAI, NOW + 10, 1, ChainLink::Genesis).receipts;
+        let mut edited = serde_json::to_value(&log[5]).unwrap();
+        edited["cutter_head_rpm"] = 7.into();
+        let text = format!(
+            "{}{}\n{}{}",
+            jsonl(&log[1..5]),
+            edited,
+            jsonl(&[&log[6..], &unstored[..]].concat()),
+            jsonl(&anchors),
+        );
+
+        let outcome = ledger
+            .audit(text.as_bytes(), &MacKey::of("ledger-explorer"), NOW)
+            .unwrap();
+        let report = &outcome.report;
+        assert!(!report.is_consistent());
+        assert!(report.malformed.is_empty());
+        assert_eq!(report.tenants[SPACEX].divergences, []);
+        let divergences = &report.tenants[XAI].divergences;
+        assert_eq!(divergences.len(), 3, "{divergences:?}");
+        assert!(divergences.contains(&Divergence::Extra {
+            layer: Layer::RocksDb,
+            glyph_id: log[0].receipt_id,
+        }));
+        assert!(divergences.contains(&Divergence::Missing {
+            layer: Layer::Sqlite,
+            glyph_id: unstored[0].receipt_id,
+        }));
+        assert!(divergences.iter().any(|d| matches!(
+            d,
+            Divergence::Tampered { layer: Layer::Log, glyph_id, .. }
+                if *glyph_id == log[5].receipt_id.to_string()
+        )));
+
+        assert_eq!(outcome.anomalies.len(), 1);
+        let anomaly = &outcome.anomalies[0];
+        assert_eq!(anomaly.tenant_id, XAI);
+        assert_eq!(anomaly.result, ReceiptResult::Anomaly);
+        // No divergence names an anchor, so the newest one is referenced.
+        assert_eq!(anomaly.ref_glyph_id, anchors[1].glyph_id);
+        match ReceiptPayload::from_details(anomaly.receipt_type, &anomaly.details).unwrap() {
+            ReceiptPayload::AnomalyDetected(a) => {
+                assert_eq!(a.severity, AnomalySeverity::Critical);
+                assert_eq!(a.drift_value, 3.0);
+            }
+            other => panic!("{other:?}"),
+        }
+
+        assert!(matches!(
+            ledger.receipt_proof(XAI, &log[1].receipt_id),
+            Err(LedgerError::ProofsRefused { divergences: 3, .. })
+        ));
+        assert!(ledger
+            .receipt_proof(SPACEX, &log[8].receipt_id)
+            .unwrap()
+            .is_some());
+
+        let text = jsonl(&log) + &jsonl(&anchors);
+        let outcome = ledger
+            .audit(text.as_bytes(), &MacKey::of("ledger-explorer"), NOW)
+            .unwrap();
+        assert!(outcome.report.is_consistent());
+        assert!(ledger
+            .receipt_proof(XAI, &log[1].receipt_id)
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn tampered_stores_and_unreadable_logs_are_reported() {
+        let (mut ledger, log, anchors, _dir) = ledger();
+        let conn = ledger.sqlite().connection();
+        conn.execute_batch("DROP TRIGGER glyphs_no_update").unwrap();
+        conn.execute(
+            "UPDATE glyphs SET body = replace(body, '\"cutter_head_rpm\":6', '\"cutter_head_rpm\":9')
+             WHERE glyph_id = ?1",
+            [log[4].receipt_id.to_string()],
+        )
+        .unwrap();
+
+        let clean = jsonl(&log) + &jsonl(&anchors);
+        let text = format!("{clean}#!/bin/bash\n");
+        let outcome = ledger
+            .audit(text.as_bytes(), &MacKey::of("ledger-explorer"), NOW)
+            .unwrap();
+        assert!(matches!(
+            outcome.report.malformed[..],
+            [Divergence::Malformed { line, offset, .. }]
+                if line == (log.len() + anchors.len()) as u64 + 1
+                    && offset == clean.len() as u64
+        ));
+        let divergences = &outcome.report.tenants[XAI].divergences;
+        assert!(divergences.iter().any(|d| matches!(
+            d,
+            Divergence::Tampered { layer: Layer::Sqlite, glyph_id, .. }
+                if *glyph_id == log[4].receipt_id.to_string()
+        )));
+        assert!(divergences.contains(&Divergence::Missing {
+            layer: Layer::Sqlite,
+            glyph_id: log[4].receipt_id,
+        }));
+        // Unattributable lines stop every tenant.
+        assert!(matches!(
+            ledger.receipt_proof(SPACEX, &log[8].receipt_id),
+            Err(LedgerError::ProofsRefused { .. })
+        ));
+    }
+
+    #[test]
+    fn nothing_is_served_before_a_clean_audit() {
+        let (mut ledger, log, anchors, _dir) = ledger();
+        assert!(matches!(
+            ledger.receipt_proof(XAI, &log[1].receipt_id),
+            Err(LedgerError::NotAudited { tenant_id }) if tenant_id == XAI
+        ));
+
+        // A log without its anchors does not clear anyone.
+        let outcome = ledger
+            .audit(jsonl(&log).as_bytes(), &MacKey::of("ledger-explorer"), NOW)
+            .unwrap();
+        assert_eq!(outcome.anomalies.len(), 2);
+        assert!(matches!(
+            ledger.receipt_proof(SPACEX, &log[8].receipt_id),
+            Err(LedgerError::ProofsRefused { divergences: 2, .. })
+        ));
+
+        let text = jsonl(&log) + &jsonl(&anchors[2..]);
+        ledger
+            .audit(text.as_bytes(), &MacKey::of("ledger-explorer"), NOW)
+            .unwrap();
+        assert!(ledger.receipt_proof(XAI, &log[1].receipt_id).is_err());
+        assert!(ledger
+            .receipt_proof(SPACEX, &log[8].receipt_id)
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn anchors_are_held_to_the_log() {
+        let (mut ledger, log, anchors, _dir) = ledger();
+        let unstored = window(XAI, NOW + 10, 1, ChainLink::Genesis).anchor;
+        let mut edited = serde_json::to_value(&anchors[0]).unwrap();
+        edited["merkle_root"] = "00".repeat(32).into();
+        let text = format!(
+            "{}{}\n{}",
+            jsonl(&log),
+            edited,
+            jsonl(&[&anchors[2..], &[unstored.clone()][..]].concat()),
+        );
+
+        let outcome = ledger
+            .audit(text.as_bytes(), &MacKey::of("ledger-explorer"), NOW)
+            .unwrap();
+        let report = &outcome.report;
+        assert_eq!(report.tenants[SPACEX].divergences, []);
+        let divergences = &report.tenants[XAI].divergences;
+        assert_eq!(divergences.len(), 4, "{divergences:?}");
+        assert!(divergences.iter().any(|d| matches!(
+            d,
+            Divergence::Tampered { layer: Layer::Log, glyph_id, .. }
+                if *glyph_id == anchors[0].glyph_id.to_string()
+        )));
+        assert!(divergences.contains(&Divergence::AnchorMismatch {
+            layer: Layer::RocksDb,
+            anchor_id: anchors[0].glyph_id,
+            expected: "00".repeat(32),
+            found: anchors[0].merkle_root.clone(),
+        }));
+        assert!(divergences.contains(&Divergence::Extra {
+            layer: Layer::Sqlite,
+            glyph_id: anchors[1].glyph_id,
+        }));
+        assert!(divergences.contains(&Divergence::Missing {
+            layer: Layer::Sqlite,
+            glyph_id: unstored.glyph_id,
+        }));
+        assert_eq!(outcome.anomalies[0].ref_glyph_id, anchors[0].glyph_id);
+        assert!(ledger.receipt_proof(XAI, &log[1].receipt_id).is_err());
+    }
+}
@@ -0,0 +1,341 @@
+mod common;
+
+#[cfg(test)]
+mod test_ledger_sql {
+    use crate::common::{self, NOW, SPACEX, XAI};
+    use glyph_lib::builders::anchor::AnchorBuilder;
+    use glyph_lib::builders::intent::IntentBuilder;
+    use glyph_lib::builders::receipt::{ReceiptBatch, ReceiptBuilder};
+    use glyph_lib::hashing::ids::GlyphId;
+    use glyph_lib::testing::MacKey;
+    use glyph_lib::{
+        AnchorGlyph, ChainLink, Daemon, Guardian, IntentConstraints, IntentType, ReceiptGlyph,
+        ReceiptResult, ReceiptType, RiskAppetite, TargetContext,
+    };
+    use ledger_explorer::audit::Layer;
+    use ledger_explorer::ledger::Ledger;
+    use ledger_explorer::sql::{SqlQuery, Table};
+    use ledger_explorer::LedgerError;
+    use serde_json::{json, Value};
+    use tempfile::TempDir;
+
+    fn prediction(tenant_id: &str, ref_glyph_id: GlyphId, at: i64, score: f64) -> ReceiptBuilder {
+        ReceiptBuilder::new(
+            tenant_id,
+            ReceiptType::EntanglementPrediction,
+            ref_glyph_id,
+            ReceiptResult::Ok,
+            Daemon::DigitalTwinGroot,
+        )
+        .timestamp(at)
+        .detail("correlation_score", score)
+        .detail("predicted_negation_ms", 1.9)
+    }
+
+    /// Predictions scored `scores` and one bore receipt, sealed as a batch.
+    fn window(
+        tenant_id: &str,
+        ref_glyph_id: GlyphId,
+        at: i64,
+        scores: &[f64],
+    ) -> Vec<ReceiptGlyph> {
+        let mut batch: ReceiptBatch = scores
+            .iter()
+            .enumerate()
+            .map(|(i, s)| prediction(tenant_id, ref_glyph_id, at - 60 + i as i64, *s))
+            .collect();
+        batch.push(
+            ReceiptBuilder::new(
+                tenant_id,
+                ReceiptType::BoreProgress,
+                ref_glyph_id,
+                ReceiptResult::Ok,
+                Daemon::RocketEngine,
+            )
+            .timestamp(at - 30)
+            .detail("segment_id", "memphis-segment-001")
+            .detail("meters_advanced", 1.5)
+            .detail("cutter_head_rpm", 6),
+        );
+        batch.seal(&MacKey::of("digital-twin-groot")).unwrap()
+    }
+
+    fn anchor(receipts: &[ReceiptGlyph], at: i64, previous: ChainLink) -> AnchorGlyph {
+        AnchorBuilder::new(
+            &receipts[0].tenant_id,
+            TargetContext::EntanglementPrediction,
+            previous,
+            Daemon::LedgerExplorer,
+        )
+        .timestamp(at)
+        .receipts(receipts)
+        .prepare()
+        .unwrap()
+        .anchor()
+        .clone()
+    }
+
+    struct History {
+        first: Vec<ReceiptGlyph>,
+        first_anchor: AnchorGlyph,
+        second: Vec<ReceiptGlyph>,
+        second_anchor: AnchorGlyph,
+        pending: ReceiptGlyph,
+    }
+
+    /// An xai window scored 0.72/0.82/0.91, archived; a second scored
+    /// 0.74/0.95/0.71, hot; an unanchored 0.99; and a spacex window.
+    fn ledger() -> (Ledger, History, TempDir) {
+        let mut sqlite = common::sqlite();
+        let (rocks, dir) = common::archive();
+
+        let intent = IntentBuilder::new(
+            XAI,
+            IntentType::InitiateBore,
+            RiskAppetite::Conservative,
+            TargetContext::TunnelBore,
+            IntentConstraints {
+                max_latency_ms: 800,
+                min_entanglement_quality: 0.8,
+                require_zk_proof: false,
+                max_drift_percent: None,
+            },
+            NOW + 86_400,
+        )
+        .timestamp(NOW - 2_000)
+        .build(Guardian::Gamora, &MacKey::of("Gamora"))
+        .unwrap();
+        let first = window(XAI, intent.glyph_id, NOW - 1_000, &[0.72, 0.82, 0.91]);
+        let first_anchor = anchor(&first, NOW - 1_000, ChainLink::Genesis);
+        let second = window(XAI, intent.glyph_id, NOW, &[0.74, 0.95, 0.71]);
+        let second_anchor = anchor(&second, NOW, ChainLink::Anchor(first_anchor.glyph_id));
+        let pending = prediction(XAI, intent.glyph_id, NOW + 10, 0.99)
+            .build(&MacKey::of("digital-twin-groot"))
+            .unwrap();
+        let spacex = window(SPACEX, intent.glyph_id, NOW, &[0.97]);
+
+        sqlite.append_intent(&intent).unwrap();
+        for (receipts, anchor) in [(&first, &first_anchor), (&second, &second_anchor)] {
+            for receipt in receipts {
+                sqlite.append_receipt(receipt).unwrap();
+            }
+            sqlite.append_anchor(anchor).unwrap();
+        }
+        for receipt in spacex.iter().chain([&pending]) {
+            sqlite.append_receipt(receipt).unwrap();
+        }
+        rocks
+            .archive_aged(&mut sqlite, XAI, NOW - 500, NOW)
+            .unwrap();
+        // Nothing is served until the stores are audited against the log
+        // they were filled from.
+        let mut log = serde_json::to_string(&intent).unwrap() + "\n";
+        for receipt in first.iter().chain(&second).chain(&spacex).chain([&pending]) {
+            log += &(serde_json::to_string(receipt).unwrap() + "\n");
+        }
+        for anchor in [&first_anchor, &second_anchor] {
+            log += &(serde_json::to_string(anchor).unwrap() + "\n");
+        }
+        let mut ledger = Ledger::new(sqlite, rocks);
+        assert!(ledger.check(log.as_bytes()).unwrap().is_consistent());
+        let history = History {
+            first,
+            first_anchor,
+            second,
+            second_anchor,
+            pending,
+        };
+        (ledger, history, dir)
+    }
+
+    fn receipt<'h>(h: &'h History, receipt_id: &GlyphId) -> &'h ReceiptGlyph {
+        h.first
+            .iter()
+            .chain(&h.second)
+            .chain([&h.pending])
+            .find(|r| r.receipt_id == *receipt_id)
+            .unwrap()
+    }
+
+    #[test]
+    fn predictions_come_back_from_both_stores_with_proofs() {
+        let (ledger, h, _dir) = ledger();
+        let query = SqlQuery::parse(
+            XAI,
+            &format!(
+                "SELECT receipt_id, correlation_score AS score FROM receipts
+                 WHERE tenant_id = '{XAI}' AND receipt_type = 'entanglement_prediction'
+                   AND timestamp >= {} AND correlation_score > 0.75
+                 ORDER BY correlation_score DESC",
+                NOW - 2_000
+            ),
+        )
+        .unwrap()
+        .with_proofs();
+
+        let plan = query.plan();
+        assert_eq!(plan.table, Table::Receipts);
+        assert_eq!(
+            plan.receipt_type.as_deref(),
+            Some("entanglement_prediction")
+        );
+        assert_eq!((plan.from, plan.to), (NOW - 2_000, i64::MAX));
+        assert_eq!(plan.glyph_id, None);
+
+        let result = query.run(&ledger).unwrap();
+        assert_eq!(result.columns, ["receipt_id", "score"]);
+        let scores: Vec<&Value> = result.rows.iter().map(|r| &r.values[1]).collect();
+        assert_eq!(
+            scores,
+            [&json!(0.99), &json!(0.95), &json!(0.91), &json!(0.82)]
+        );
+        let layers: Vec<Layer> = result.rows.iter().map(|r| r.layer).collect();
+        assert_eq!(
+            layers,
+            [Layer::Sqlite, Layer::Sqlite, Layer::RocksDb, Layer::RocksDb]
+        );
+
+        assert!(result.rows[0].proof.is_none());
+        for row in &result.rows[1..] {
+            assert_eq!(row.values[0], json!(row.glyph_id.to_string()));
+            let proof = row.proof.as_ref().unwrap();
+            assert!(proof.verify_receipt(receipt(&h, &row.glyph_id)));
+        }
+        assert_eq!(
+            result.rows[1].proof.as_ref().unwrap().anchor_id,
+            h.second_anchor.glyph_id
+        );
+        assert_eq!(
+            result.rows[2].proof.as_ref().unwrap().anchor_id,
+            h.first_anchor.glyph_id
+        );
+
+        // Proofs are only fetched when asked for.
+        let plain = SqlQuery::parse(
+            XAI,
+            &format!("SELECT * FROM receipts WHERE tenant_id = '{XAI}' LIMIT 3"),
+        )
+        .unwrap()
+        .run(&ledger)
+        .unwrap();
+        assert_eq!(plain.rows.len(), 3);
+        assert!(plain.rows.iter().all(|r| r.proof.is_none()));
+        assert_eq!(plain.rows[0].glyph_id, h.first[0].receipt_id);
+        assert_eq!(plain.rows[0].values[0]["tenant_id"], json!(XAI));
+    }
+
+    #[test]
+    fn point_reads_ranges_and_anchors() {
+        let (ledger, h, _dir) = ledger();
+        let id = h.first[1].receipt_id;
+        let point = SqlQuery::parse(
+            XAI,
+            &format!(
+                "SELECT timestamp FROM receipts WHERE receipt_id = '{id}' AND tenant_id = '{XAI}'"
+            ),
+        )
+        .unwrap();
+        assert_eq!(point.plan().glyph_id, Some(id));
+        let result = point.run(&ledger).unwrap();
+        assert_eq!(result.rows.len(), 1);
+        assert_eq!(result.rows[0].values, [json!(h.first[1].timestamp)]);
+
+        let between = SqlQuery::parse(
+            XAI,
+            &format!(
+                "SELECT receipt_id FROM receipts WHERE tenant_id = '{XAI}'
+                 AND timestamp BETWEEN {} AND {} AND receipt_type IN ('bore_progress')",
+                NOW - 1_100,
+                NOW - 900
+            ),
+        )
+        .unwrap();
+        let result = between.run(&ledger).unwrap();
+        assert_eq!(result.rows.len(), 1);
+        assert_eq!(result.rows[0].glyph_id, h.first[3].receipt_id);
+
+        // Bore receipts have no correlation_score: unknown, so never matched
+        // by a comparison, only by IS NULL.
+        let nulls = SqlQuery::parse(
+            XAI,
+            &format!(
+                "SELECT receipt_id FROM receipts WHERE tenant_id = '{XAI}'
+                 AND (correlation_score IS NULL OR NOT correlation_score < 2)"
+            ),
+        )
+        .unwrap()
+        .run(&ledger)
+        .unwrap();
+        assert_eq!(nulls.rows.len(), 2);
+
+        let anchors = SqlQuery::parse(
+            XAI,
+            &format!("SELECT glyph_id, merkle_root FROM anchors WHERE tenant_id = '{XAI}'"),
+        )
+        .unwrap()
+        .with_proofs()
+        .run(&ledger)
+        .unwrap();
+        let ids: Vec<GlyphId> = anchors.rows.iter().map(|r| r.glyph_id).collect();
+        assert_eq!(ids, [h.first_anchor.glyph_id, h.second_anchor.glyph_id]);
+        assert_eq!(anchors.rows[0].layer, Layer::RocksDb);
+        assert_eq!(
+            anchors.rows[1].values[1],
+            json!(h.second_anchor.merkle_root)
+        );
+        assert!(anchors.rows.iter().all(|r| r.proof.is_none()));
+    }
+
+    #[test]
+    fn statements_are_read_only_and_tenant_checked() {
+        let (mut ledger, _, _dir) = ledger();
+        for sql in [
+            format!("DELETE FROM receipts WHERE tenant_id = '{XAI}'"),
+            format!("UPDATE receipts SET result = 'ok' WHERE tenant_id = '{XAI}'"),
+            "SELECT 1; SELECT 2".to_string(),
+            format!("SELECT count(*) FROM receipts WHERE tenant_id = '{XAI}'"),
+            format!("SELECT receipt_type FROM receipts WHERE tenant_id = '{XAI}' GROUP BY receipt_type"),
+            format!("SELECT * FROM receipts r JOIN anchors a ON r.ref_glyph_id = a.glyph_id WHERE tenant_id = '{XAI}'"),
+            format!("SELECT * FROM intents WHERE tenant_id = '{XAI}'"),
+            format!("SELECT * FROM receipts WHERE tenant_id = '{XAI}' AND timestamp > other"),
+            "SELECT * FROM receipts".to_string(),
+            format!("SELECT * FROM receipts WHERE tenant_id = '{XAI}' OR timestamp > 0"),
+            format!("SELECT * FROM receipts WHERE NOT tenant_id != '{XAI}'"),
+        ] {
+            assert!(
+                matches!(SqlQuery::parse(XAI, &sql), Err(LedgerError::BadQuery { .. })),
+                "{sql}"
+            );
+        }
+
+        let spacex = format!("SELECT * FROM receipts WHERE tenant_id = '{SPACEX}'");
+        assert!(matches!(
+            SqlQuery::parse(XAI, &spacex),
+            Err(LedgerError::WrongTenant { .. })
+        ));
+        let result = SqlQuery::parse(SPACEX, &spacex)
+            .unwrap()
+            .run(&ledger)
+            .unwrap();
+        assert_eq!(result.rows.len(), 2);
+        assert!(result
+            .rows
+            .iter()
+            .all(|r| r.values[0]["tenant_id"] == json!(SPACEX)));
+
+        // An audit that finds the layers disagreeing stops queries too,
+        // even for a tenant with no anchor to raise an anomaly against.
+        let outcome = ledger
+            .audit(&b"not a glyph\n"[..], &MacKey::of("kraglin"), NOW)
+            .unwrap();
+        assert_eq!(outcome.unreported, [SPACEX]);
+        let query = SqlQuery::parse(
+            XAI,
+            &format!("SELECT * FROM receipts WHERE tenant_id = '{XAI}'"),
+        );
+        assert!(matches!(
+            query.unwrap().run(&ledger),
+            Err(LedgerError::ProofsRefused { .. })
+        ));
+    }
+}
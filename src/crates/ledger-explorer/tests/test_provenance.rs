@@ -0,0 +1,240 @@
+mod common;
+
+#[cfg(test)]
+mod test_provenance {
+    use crate::common::{self, NOW, SPACEX, XAI};
+    use glyph_lib::builders::intent::IntentBuilder;
+    use glyph_lib::hashing::ids::GlyphId;
+    use glyph_lib::testing::{anchored, sealed, MacKey};
+    use glyph_lib::{
+        AnchorGlyph, ChainLink, Guardian, IntentConstraints, IntentGlyph, IntentType, ReceiptGlyph,
+        RiskAppetite, TargetContext,
+    };
+    use ledger_explorer::audit::Layer;
+    use ledger_explorer::ledger::Ledger;
+    use ledger_explorer::log::LogGlyph;
+    use ledger_explorer::query::{Direction, Edge, ProvenanceQuery};
+    use ledger_explorer::LedgerError;
+    use std::collections::BTreeSet;
+    use tempfile::TempDir;
+
+    fn receipts(tenant_id: &str, ref_glyph_id: GlyphId, at: i64) -> Vec<ReceiptGlyph> {
+        sealed(
+            tenant_id,
+            ref_glyph_id,
+            at - 60,
+            3,
+            &MacKey::of("rocket-engine"),
+        )
+    }
+
+    /// The glyphs of [`ledger`], by role.
+    struct History {
+        intent: IntentGlyph,
+        first: Vec<ReceiptGlyph>,
+        first_anchor: AnchorGlyph,
+        second: Vec<ReceiptGlyph>,
+        second_anchor: AnchorGlyph,
+        spacex: Vec<ReceiptGlyph>,
+    }
+
+    /// An xai intent; a first window emitted under it, archived; a second
+    /// window emitted under the first anchor and chained to it, hot; and
+    /// unanchored spacex receipts that also name the first anchor.
+    fn ledger() -> (Ledger, History, TempDir) {
+        let mut sqlite = common::sqlite();
+        let (rocks, dir) = common::archive();
+
+        let intent = IntentBuilder::new(
+            XAI,
+            IntentType::InitiateBore,
+            RiskAppetite::Conservative,
+            TargetContext::TunnelBore,
+            IntentConstraints {
+                max_latency_ms: 800,
+                min_entanglement_quality: 0.8,
+                require_zk_proof: false,
+                max_drift_percent: None,
+            },
+            NOW + 86_400,
+        )
+        .timestamp(NOW - 2_000)
+        .build(Guardian::Gamora, &MacKey::of("Gamora"))
+        .unwrap();
+        let first = receipts(XAI, intent.glyph_id, NOW - 1_000);
+        let first_anchor = anchored(&first, NOW - 1_000, ChainLink::Genesis);
+        let second = receipts(XAI, first_anchor.glyph_id, NOW);
+        let second_anchor = anchored(&second, NOW, ChainLink::Anchor(first_anchor.glyph_id));
+        let spacex = receipts(SPACEX, first_anchor.glyph_id, NOW);
+
+        sqlite.append_intent(&intent).unwrap();
+        for (receipts, anchor) in [(&first, &first_anchor), (&second, &second_anchor)] {
+            for receipt in receipts {
+                sqlite.append_receipt(receipt).unwrap();
+            }
+            sqlite.append_anchor(anchor).unwrap();
+        }
+        for receipt in &spacex {
+            sqlite.append_receipt(receipt).unwrap();
+        }
+        rocks
+            .archive_aged(&mut sqlite, XAI, NOW - 500, NOW)
+            .unwrap();
+        // Nothing is served until the stores are audited against the log
+        // they were filled from.
+        let mut log = serde_json::to_string(&intent).unwrap() + "\n";
+        for receipt in first.iter().chain(&second).chain(&spacex) {
+            log += &(serde_json::to_string(receipt).unwrap() + "\n");
+        }
+        for anchor in [&first_anchor, &second_anchor] {
+            log += &(serde_json::to_string(anchor).unwrap() + "\n");
+        }
+        let mut ledger = Ledger::new(sqlite, rocks);
+        assert!(ledger.check(log.as_bytes()).unwrap().is_consistent());
+        let history = History {
+            intent,
+            first,
+            first_anchor,
+            second,
+            second_anchor,
+            spacex,
+        };
+        (ledger, history, dir)
+    }
+
+    fn ids(receipts: &[ReceiptGlyph]) -> Vec<GlyphId> {
+        receipts.iter().map(|r| r.receipt_id).collect()
+    }
+
+    #[test]
+    fn an_anchor_resolves_to_everything_it_depends_on() {
+        let (ledger, h, _dir) = ledger();
+        let graph = ProvenanceQuery::new(XAI, h.second_anchor.glyph_id)
+            .run(&ledger)
+            .unwrap()
+            .unwrap();
+
+        let mut expected: BTreeSet<GlyphId> = [
+            h.intent.glyph_id,
+            h.first_anchor.glyph_id,
+            h.second_anchor.glyph_id,
+        ]
+        .into();
+        expected.extend(ids(&h.first));
+        expected.extend(ids(&h.second));
+        assert_eq!(
+            graph.nodes.keys().copied().collect::<BTreeSet<_>>(),
+            expected
+        );
+        assert_eq!(graph.nodes[&h.first_anchor.glyph_id].layer, Layer::RocksDb);
+        assert_eq!(graph.nodes[&h.second_anchor.glyph_id].layer, Layer::Sqlite);
+        assert_eq!(graph.nodes[&h.intent.glyph_id].depth, 3);
+        assert!(graph.missing.is_empty() && !graph.truncated);
+
+        assert_eq!(graph.hops.len(), 13);
+        assert!(graph.hops.iter().any(|hop| hop.edge == Edge::Previous
+            && hop.from == h.second_anchor.glyph_id
+            && hop.to == h.first_anchor.glyph_id));
+        assert_eq!(graph.unproven().count(), 0);
+        for hop in graph.hops.iter().filter(|hop| hop.edge == Edge::Commits) {
+            let receipt = h
+                .first
+                .iter()
+                .chain(&h.second)
+                .find(|r| r.receipt_id == hop.to)
+                .unwrap();
+            let proof = hop.proof.as_ref().unwrap();
+            assert_eq!(proof.anchor_id, hop.from);
+            assert!(proof.verify_receipt(receipt));
+        }
+        graph.verify().unwrap();
+
+        let near = ProvenanceQuery::new(XAI, h.second_anchor.glyph_id)
+            .max_depth(1)
+            .run(&ledger)
+            .unwrap()
+            .unwrap();
+        assert_eq!(near.nodes.len(), 5);
+        assert!(near.truncated);
+        near.verify().unwrap();
+    }
+
+    #[test]
+    fn forward_walks_stay_inside_the_tenant() {
+        let (ledger, h, _dir) = ledger();
+        let graph = ProvenanceQuery::new(XAI, h.intent.glyph_id)
+            .direction(Direction::Forward)
+            .run(&ledger)
+            .unwrap()
+            .unwrap();
+        assert_eq!(graph.nodes.len(), 9);
+        assert_eq!(graph.nodes[&h.second_anchor.glyph_id].depth, 3);
+        for receipt in &h.spacex {
+            assert!(!graph.nodes.contains_key(&receipt.receipt_id));
+        }
+        graph.verify().unwrap();
+
+        // Both ways from the middle reaches the same glyphs.
+        let both = ProvenanceQuery::new(XAI, h.first[0].receipt_id)
+            .direction(Direction::Both)
+            .run(&ledger)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            both.nodes.keys().collect::<BTreeSet<_>>(),
+            graph.nodes.keys().collect::<BTreeSet<_>>()
+        );
+
+        // Another tenant sees neither the glyph nor anything behind it.
+        assert!(ProvenanceQuery::new(SPACEX, h.first_anchor.glyph_id)
+            .run(&ledger)
+            .unwrap()
+            .is_none());
+        let spacex = ProvenanceQuery::new(SPACEX, h.spacex[0].receipt_id)
+            .run(&ledger)
+            .unwrap()
+            .unwrap();
+        assert_eq!(spacex.nodes.len(), 1);
+        assert_eq!(spacex.missing, BTreeSet::from([h.first_anchor.glyph_id]));
+    }
+
+    #[test]
+    fn evidence_is_checked_and_refused_ledgers_serve_none() {
+        let (mut ledger, h, _dir) = ledger();
+        let graph = ProvenanceQuery::new(XAI, h.second_anchor.glyph_id)
+            .run(&ledger)
+            .unwrap()
+            .unwrap();
+
+        let mut swapped = graph.clone();
+        let commits: Vec<usize> = (0..swapped.hops.len())
+            .filter(|&i| swapped.hops[i].edge == Edge::Commits)
+            .collect();
+        let first_proof = swapped.hops[commits[0]].proof.clone();
+        swapped.hops[commits[0]].proof = swapped.hops[commits[1]].proof.clone();
+        swapped.hops[commits[1]].proof = first_proof;
+        assert!(matches!(
+            swapped.verify(),
+            Err(LedgerError::BadEvidence { .. })
+        ));
+
+        let mut edited = graph.clone();
+        if let Some(LogGlyph::Receipt(receipt)) = edited
+            .nodes
+            .get_mut(&h.second[1].receipt_id)
+            .map(|n| &mut n.glyph)
+        {
+            receipt.timestamp += 1;
+        }
+        assert!(edited.verify().is_err());
+
+        // An audit that finds the layers disagreeing stops provenance too.
+        ledger
+            .audit(&b"not a glyph\n"[..], &MacKey::of("kraglin"), NOW)
+            .unwrap();
+        assert!(matches!(
+            ProvenanceQuery::new(XAI, h.second_anchor.glyph_id).run(&ledger),
+            Err(LedgerError::ProofsRefused { .. })
+        ));
+    }
+}
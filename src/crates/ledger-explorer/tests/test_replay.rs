@@ -0,0 +1,293 @@
+mod common;
+
+#[cfg(test)]
+mod test_replay {
+    use crate::common::{self, NOW, XAI};
+    use glyph_lib::builders::intent::IntentBuilder;
+    use glyph_lib::pq::registry::{KeyRegistry, KeyWindow, Signer};
+    use glyph_lib::testing::{
+        anchored, approved, guardian_key_id, read_repo_file, sealed, MacKey, WEIGHTED,
+    };
+    use glyph_lib::{
+        AnchorGlyph, ChainLink, Daemon, Guardian, IntentConstraints, IntentGlyph, IntentType,
+        ReceiptGlyph, RiskAppetite, TargetContext,
+    };
+    use ledger_explorer::ledger::Ledger;
+    use ledger_explorer::log::LogGlyph;
+    use ledger_explorer::replay::{Checkpoint, ReplayReport, Replayer};
+    use ledger_explorer::LedgerError;
+    use serde::Serialize;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use tempfile::TempDir;
+
+    const T0: i64 = NOW - 86_400;
+
+    fn registry() -> KeyRegistry {
+        let mut registry = KeyRegistry::from_yaml(
+            &read_repo_file("config/agents/guardians_org.yaml"),
+            &read_repo_file("config/agents/swarm_roles.yaml"),
+        )
+        .unwrap();
+        registry
+            .add_tenant_yaml(&read_repo_file(
+                "src/crates/ledger-explorer/tests/fixtures/tenant_xai_memphis_01.yaml",
+            ))
+            .unwrap();
+        for guardian in Guardian::ALL {
+            registry
+                .register(
+                    Signer::Guardian(*guardian),
+                    guardian_key_id(*guardian),
+                    KeyWindow::from(T0),
+                    Box::new(MacKey::of(guardian.as_str())),
+                )
+                .unwrap();
+        }
+        registry
+            .register(
+                Signer::Daemon(Daemon::RocketEngine),
+                "rocket-engine-key-01",
+                KeyWindow::from(T0),
+                Box::new(MacKey::of("rocket-engine-key-01")),
+            )
+            .unwrap();
+        registry
+    }
+
+    fn empty_ledger() -> (Ledger, TempDir) {
+        let (rocks, dir) = common::archive();
+        (Ledger::new(common::sqlite(), rocks), dir)
+    }
+
+    /// A checkpoint file in a directory removed when the guard drops.
+    fn checkpoint_path() -> (PathBuf, TempDir) {
+        let dir = TempDir::new().unwrap();
+        (dir.path().join("replay.checkpoint"), dir)
+    }
+
+    fn intent(tenant_id: &str) -> IntentGlyph {
+        IntentBuilder::new(
+            tenant_id,
+            IntentType::InitiateBore,
+            RiskAppetite::Conservative,
+            TargetContext::TunnelBore,
+            IntentConstraints {
+                max_latency_ms: 800,
+                min_entanglement_quality: 0.8,
+                require_zk_proof: false,
+                max_drift_percent: None,
+            },
+            NOW + 86_400,
+        )
+        .timestamp(T0 + 60)
+        .build(Guardian::Gamora, &MacKey::of(Guardian::Gamora.as_str()))
+        .unwrap()
+    }
+
+    fn receipts(intent: &IntentGlyph, at: i64, signer: &MacKey) -> Vec<ReceiptGlyph> {
+        sealed(&intent.tenant_id, intent.glyph_id, at - 60, 3, signer)
+    }
+
+    fn anchor(receipts: &[ReceiptGlyph], at: i64, previous: ChainLink) -> AnchorGlyph {
+        approved(anchored(receipts, at, previous), &WEIGHTED)
+    }
+
+    fn line(glyph: &impl Serialize) -> String {
+        serde_json::to_string(glyph).unwrap() + "\n"
+    }
+
+    /// An intent, then two windows of receipts each followed by its
+    /// anchor, at NOW - 1000 and NOW.
+    fn log() -> Vec<String> {
+        let key = MacKey::of("rocket-engine-key-01");
+        let intent = intent(XAI);
+        let mut lines = vec![line(&intent)];
+        let mut previous = ChainLink::Genesis;
+        for at in [NOW - 1_000, NOW] {
+            let receipts = receipts(&intent, at, &key);
+            lines.extend(receipts.iter().map(line));
+            let anchor = anchor(&receipts, at, previous);
+            previous = ChainLink::Anchor(anchor.glyph_id);
+            lines.push(line(&anchor));
+        }
+        lines
+    }
+
+    fn glyph_rows(ledger: &Ledger) -> Vec<(String, String)> {
+        let conn = ledger.sqlite().connection();
+        let mut stmt = conn
+            .prepare("SELECT glyph_id, body FROM glyphs ORDER BY seq")
+            .unwrap();
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap();
+        rows.map(Result::unwrap).collect()
+    }
+
+    fn replay(ledger: &mut Ledger, text: &str, checkpoint: &Path) -> ReplayReport {
+        let registry = registry();
+        Replayer::new(ledger, &registry)
+            .checkpoint(checkpoint)
+            .run(text.as_bytes())
+            .unwrap()
+    }
+
+    #[test]
+    fn replay_rebuilds_both_stores_deterministically() {
+        let text = log().concat();
+        let registry = registry();
+        let mut rebuilt = Vec::new();
+        for _ in 0..2 {
+            let (mut ledger, _dir) = empty_ledger();
+            let report = Replayer::new(&mut ledger, &registry)
+                .archive_before(NOW - 500)
+                .run(text.as_bytes())
+                .unwrap();
+            assert!(report.is_complete(), "{:?}", report.failure);
+            assert_eq!((report.intents, report.receipts, report.anchors), (1, 6, 2));
+            assert_eq!(report.archived, 1);
+            assert_eq!(report.checkpoint.offset, text.len() as u64);
+
+            let audit = ledger.audit(text.as_bytes(), &MacKey::of("kraglin"), NOW);
+            let report = audit.unwrap().report;
+            assert!(report.is_consistent(), "{report:?}");
+            let xai = &report.tenants[XAI];
+            assert_eq!(
+                (xai.log_receipts, xai.hot_receipts, xai.cold_receipts),
+                (6, 3, 3)
+            );
+            for receipt in text
+                .lines()
+                .filter_map(|l| match LogGlyph::from_json(l.as_bytes()) {
+                    Ok(LogGlyph::Receipt(receipt)) => Some(receipt),
+                    _ => None,
+                })
+            {
+                let proof = ledger
+                    .receipt_proof(&receipt.tenant_id, &receipt.receipt_id)
+                    .unwrap()
+                    .unwrap();
+                assert!(proof.verify_receipt(&receipt));
+            }
+            let cold = ledger
+                .rocks()
+                .anchors_between(XAI, i64::MIN, i64::MAX)
+                .unwrap();
+            rebuilt.push((glyph_rows(&ledger), cold));
+        }
+        assert_eq!(rebuilt[0], rebuilt[1]);
+    }
+
+    #[test]
+    fn the_first_unverifiable_line_stops_the_replay() {
+        let mut lines = log();
+        let good = lines[2].clone();
+        // Same receipt content, signed by a key rocket-engine never had.
+        let forged = receipts(&intent(XAI), NOW - 1_000, &MacKey::of("impostor"))[1].clone();
+        lines[2] = line(&forged);
+        let offset: usize = lines[..2].iter().map(String::len).sum();
+        let (checkpoint, _scratch) = checkpoint_path();
+
+        let (mut ledger, _dir) = empty_ledger();
+        let report = replay(&mut ledger, &lines.concat(), &checkpoint);
+        let failure = report.failure.as_ref().unwrap();
+        assert_eq!((failure.line, failure.offset), (3, offset as u64));
+        assert_eq!(failure.glyph_id, Some(forged.receipt_id));
+        assert_eq!(report.receipts, 1);
+        assert_eq!(report.checkpoint.offset, offset as u64);
+        assert_eq!(
+            Checkpoint::load(&checkpoint).unwrap().as_ref(),
+            Some(&report.checkpoint)
+        );
+
+        // Unparseable lines stop it too.
+        let (mut broken, _broken_dir) = empty_ledger();
+        let text = format!("{}#!/bin/bash\n{}", lines[0], lines[1]);
+        let report = Replayer::new(&mut broken, &registry())
+            .run(text.as_bytes())
+            .unwrap();
+        let failure = report.failure.unwrap();
+        assert_eq!(
+            (failure.line, failure.offset, failure.glyph_id),
+            (2, lines[0].len() as u64, None)
+        );
+
+        // With the line repaired the replay resumes where it stopped.
+        lines[2] = good;
+        let text = lines.concat();
+        let report = replay(&mut ledger, &text, &checkpoint);
+        assert!(report.is_complete(), "{:?}", report.failure);
+        assert_eq!(report.resumed_from, offset as u64);
+        assert_eq!((report.intents, report.receipts, report.anchors), (0, 5, 2));
+        let audit = ledger.audit(text.as_bytes(), &MacKey::of("kraglin"), NOW);
+        assert!(audit.unwrap().report.is_consistent());
+    }
+
+    #[test]
+    fn a_revoked_key_cannot_backdate_past_a_later_anchor() {
+        let mut registry = registry();
+        registry
+            .register(
+                Signer::Daemon(Daemon::RocketEngine),
+                "rocket-engine-key-02",
+                KeyWindow::from(T0).revoked_at(NOW - 500),
+                Box::new(MacKey::of("rocket-engine-key-02")),
+            )
+            .unwrap();
+        let revoked = MacKey::of("rocket-engine-key-02");
+        let intent = intent(XAI);
+        let before = receipts(&intent, NOW - 1_000, &revoked);
+        // Timestamped inside the key's window, but logged after an anchor
+        // from after the revocation.
+        let backdated = receipts(&intent, NOW - 900, &revoked);
+        let mut lines = vec![line(&intent)];
+        lines.extend(before.iter().map(line));
+        lines.push(line(&anchor(&before, NOW - 400, ChainLink::Genesis)));
+        lines.extend(backdated.iter().map(line));
+
+        let (mut ledger, _dir) = empty_ledger();
+        let report = Replayer::new(&mut ledger, &registry)
+            .run(lines.concat().as_bytes())
+            .unwrap();
+        assert_eq!((report.receipts, report.anchors), (3, 1));
+        assert_eq!(report.checkpoint.anchored_at, Some(NOW - 400));
+        let failure = report.failure.unwrap();
+        assert_eq!(failure.glyph_id, Some(backdated[0].receipt_id));
+        assert!(failure.reason.contains("not active"), "{}", failure.reason);
+    }
+
+    #[test]
+    fn an_interrupted_replay_resumes_from_its_checkpoint() {
+        let lines = log();
+        let (checkpoint, _scratch) = checkpoint_path();
+        let (mut ledger, _dir) = empty_ledger();
+
+        // Up to the first anchor, then two more receipts whose checkpoint
+        // was lost in the crash.
+        let report = replay(&mut ledger, &lines[..5].concat(), &checkpoint);
+        assert!(report.is_complete());
+        let saved = fs::read(&checkpoint).unwrap();
+        replay(&mut ledger, &lines[..7].concat(), &checkpoint);
+        fs::write(&checkpoint, saved).unwrap();
+
+        let text = lines.concat();
+        let report = replay(&mut ledger, &text, &checkpoint);
+        assert!(report.is_complete(), "{:?}", report.failure);
+        assert_eq!(report.resumed_from, lines[..5].concat().len() as u64);
+        let audit = ledger.audit(text.as_bytes(), &MacKey::of("kraglin"), NOW);
+        assert!(audit.unwrap().report.is_consistent());
+
+        // The checkpoint only resumes the log it was taken on.
+        let mut changed = lines.clone();
+        changed.swap(0, 7);
+        for text in [changed.concat(), lines[..3].concat()] {
+            assert!(matches!(
+                Replayer::new(&mut ledger, &registry())
+                    .checkpoint(&checkpoint)
+                    .run(text.as_bytes()),
+                Err(LedgerError::CheckpointMismatch { .. })
+            ));
+        }
+    }
+}
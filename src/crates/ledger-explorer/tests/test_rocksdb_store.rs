@@ -0,0 +1,195 @@
+mod common;
+
+#[cfg(test)]
+mod test_rocksdb_store {
+    use crate::common::{self, rocksdb_config, window, NOW, SPACEX, XAI};
+    use glyph_lib::hashing::ids::GlyphId;
+    use glyph_lib::testing::anchored;
+    use glyph_lib::{AnchorGlyph, ChainLink, GlyphError};
+    use ledger_explorer::rocksdb_store::RocksDbStore;
+    use ledger_explorer::sqlite_store::{SqliteStore, Window};
+    use ledger_explorer::LedgerError;
+    use tempfile::TempDir;
+
+    const DAY: i64 = 86_400;
+
+    fn stores() -> (SqliteStore, RocksDbStore, TempDir) {
+        let (rocks, dir) = common::archive();
+        (common::sqlite(), rocks, dir)
+    }
+
+    /// Three daily windows for `tenant_id`, stored in the hot path.
+    fn history(sqlite: &mut SqliteStore, tenant_id: &str) -> Vec<Window> {
+        let mut previous = ChainLink::Genesis;
+        let mut windows = Vec::new();
+        for day in [-3, -2, -1] {
+            let w = window(tenant_id, NOW + day * DAY, 5, previous);
+            for receipt in &w.receipts {
+                sqlite.append_receipt(receipt).unwrap();
+            }
+            sqlite.append_anchor(&w.anchor).unwrap();
+            previous = ChainLink::Anchor(w.anchor.glyph_id);
+            windows.push(w);
+        }
+        windows
+    }
+
+    fn ids(anchors: &[AnchorGlyph]) -> Vec<GlyphId> {
+        anchors.iter().map(|a| a.glyph_id).collect()
+    }
+
+    #[test]
+    fn aged_windows_move_with_their_proofs() {
+        let (mut sqlite, rocks, _dir) = stores();
+        let xai = history(&mut sqlite, XAI);
+        let spacex = history(&mut sqlite, SPACEX);
+
+        let moved = rocks
+            .archive_aged(&mut sqlite, XAI, NOW - DAY - 1, NOW)
+            .unwrap();
+        assert_eq!(moved, [xai[0].anchor.glyph_id, xai[1].anchor.glyph_id]);
+        for w in &xai[..2] {
+            assert!(sqlite.anchor(&w.anchor.glyph_id).unwrap().is_none());
+            assert_eq!(
+                rocks.anchor(XAI, &w.anchor.glyph_id).unwrap().as_ref(),
+                Some(&w.anchor)
+            );
+            for receipt in &w.receipts {
+                assert!(sqlite.receipt(&receipt.receipt_id).unwrap().is_none());
+                assert_eq!(
+                    rocks.receipt(XAI, &receipt.receipt_id).unwrap().as_ref(),
+                    Some(receipt)
+                );
+                let proof = rocks
+                    .receipt_proof(XAI, &receipt.receipt_id)
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(proof.anchor_id, w.anchor.glyph_id);
+                assert!(proof.verify_receipt(receipt));
+            }
+        }
+        assert!(sqlite.anchor(&xai[2].anchor.glyph_id).unwrap().is_some());
+        assert!(rocks
+            .anchor(XAI, &xai[2].anchor.glyph_id)
+            .unwrap()
+            .is_none());
+        assert!(sqlite.anchor(&spacex[0].anchor.glyph_id).unwrap().is_some());
+
+        // Tenants are isolated: another tenant's prefix finds nothing.
+        let id = &xai[0].receipts[0].receipt_id;
+        assert!(rocks.receipt(SPACEX, id).unwrap().is_none());
+        assert!(matches!(
+            rocks.receipt("acme-logistics-01", id),
+            Err(LedgerError::UnknownTenant { .. })
+        ));
+
+        assert!(rocks
+            .archive_aged(&mut sqlite, XAI, NOW - DAY - 1, NOW)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn range_scans_are_ordered_by_tenant_and_time() {
+        let (mut sqlite, rocks, _dir) = stores();
+        let xai = history(&mut sqlite, XAI);
+        history(&mut sqlite, SPACEX);
+        rocks.archive_aged(&mut sqlite, SPACEX, NOW, NOW).unwrap();
+        rocks.archive_aged(&mut sqlite, XAI, NOW, NOW).unwrap();
+
+        let anchors: Vec<_> = rocks
+            .anchors_between(XAI, i64::MIN, i64::MAX)
+            .unwrap()
+            .into_iter()
+            .map(|a| a.anchor)
+            .collect();
+        let expected: Vec<_> = xai.iter().map(|w| w.anchor.clone()).collect();
+        assert_eq!(ids(&anchors), ids(&expected));
+
+        let day2 = rocks
+            .receipts_between(XAI, NOW - 2 * DAY - 60, NOW - DAY - 60)
+            .unwrap();
+        assert_eq!(day2, xai[1].receipts);
+        let all = rocks.receipts_between(SPACEX, i64::MIN, i64::MAX).unwrap();
+        assert_eq!(all.len(), 15);
+        assert!(all.iter().all(|r| r.tenant_id == SPACEX));
+        assert!(all.windows(2).all(|p| p[0].timestamp <= p[1].timestamp));
+
+        let early = window(XAI, -DAY, 2, ChainLink::Genesis);
+        rocks.put_window(&early).unwrap();
+        let first = rocks.anchors_between(XAI, i64::MIN, 0).unwrap();
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].anchor, early.anchor);
+    }
+
+    #[test]
+    fn an_interrupted_move_resumes() {
+        let (mut sqlite, rocks, _dir) = stores();
+        let xai = history(&mut sqlite, XAI);
+
+        // A previous run wrote the window but stopped before evicting it.
+        rocks.put_window(&xai[0]).unwrap();
+        assert!(matches!(
+            rocks.put_window(&xai[0]),
+            Err(LedgerError::Duplicate { glyph_id }) if glyph_id == xai[0].anchor.glyph_id
+        ));
+        assert!(sqlite.anchor(&xai[0].anchor.glyph_id).unwrap().is_some());
+
+        let moved = rocks
+            .archive_aged(&mut sqlite, XAI, NOW - 2 * DAY, NOW)
+            .unwrap();
+        assert_eq!(moved, [xai[0].anchor.glyph_id]);
+        assert!(sqlite.is_archived(&xai[0].anchor.glyph_id).unwrap());
+    }
+
+    #[test]
+    fn windows_must_match_their_anchor() {
+        let (_, rocks, dir) = stores();
+        let mut w = window(XAI, NOW, 4, ChainLink::Genesis);
+        w.receipts.swap(0, 1);
+        assert!(matches!(
+            rocks.put_window(&w),
+            Err(LedgerError::Glyph(GlyphError::HashMismatch { .. }))
+        ));
+        w.receipts.swap(0, 1);
+        w.receipts.pop();
+        assert!(rocks.put_window(&w).is_err());
+        assert!(rocks.anchor(XAI, &w.anchor.glyph_id).unwrap().is_none());
+
+        let mut other = window(SPACEX, NOW, 2, ChainLink::Genesis);
+        other.receipts[1] = w.receipts[1].clone();
+        other.anchor = anchored(&other.receipts, NOW, ChainLink::Genesis);
+        assert!(matches!(
+            rocks.put_window(&other),
+            Err(LedgerError::TenantMismatch { tenant_id, receipt_tenant, .. })
+                if tenant_id == SPACEX && receipt_tenant == XAI
+        ));
+
+        let mut config = rocksdb_config();
+        config.options.compression = "brotli".into();
+        assert!(matches!(
+            RocksDbStore::open_at(dir.path().join("brotli"), &config),
+            Err(LedgerError::Glyph(GlyphError::InvalidConfig { .. }))
+        ));
+    }
+
+    #[test]
+    fn windows_are_synced_before_eviction() {
+        let (mut sqlite, rocks, _dir) = stores();
+        let xai = history(&mut sqlite, XAI);
+
+        let before = rocks.wal_syncs();
+        rocks.put_window(&xai[0]).unwrap();
+        assert!(
+            rocks.wal_syncs() > before,
+            "put_window left the WAL unsynced"
+        );
+
+        // The resumed window was written by an earlier run; it is synced
+        // again before it leaves SQLite, as is every window written now.
+        let before = rocks.wal_syncs();
+        let moved = rocks.archive_aged(&mut sqlite, XAI, NOW, NOW).unwrap();
+        assert_eq!(moved.len(), 3);
+        assert!(rocks.wal_syncs() >= before + moved.len() as u64);
+    }
+}
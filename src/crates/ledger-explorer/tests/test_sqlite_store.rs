@@ -0,0 +1,333 @@
+#[cfg(test)]
+mod test_sqlite_store {
+    use glyph_lib::anchors::merkle::{hash_to_hex, MerkleTree};
+    use glyph_lib::builders::anchor::AnchorBuilder;
+    use glyph_lib::builders::receipt::ReceiptBuilder;
+    use glyph_lib::config::ledger::SqliteLedgerConfig;
+    use glyph_lib::hashing::ids::GlyphId;
+    use glyph_lib::pq::dilithium::DilithiumKeypair;
+    use glyph_lib::pq::envelope::{
+        receipt_signing_message, GlyphSigner, SignatureField, SignatureVerifier,
+    };
+    use glyph_lib::testing::{self, read_repo_file, MacKey};
+    use glyph_lib::ReceiptType;
+    use glyph_lib::{
+        AnchorGlyph, ChainLink, Daemon, GlyphError, GlyphKind, ReceiptGlyph, ReceiptResult,
+        TargetContext,
+    };
+    use ledger_explorer::sqlite_store::{canonical_body, SqliteStore, LEDGER_TABLES};
+    use ledger_explorer::LedgerError;
+    use std::fs;
+
+    const TENANT_ID: &str = "xai-memphis-01";
+    const NOW: i64 = 1_764_382_460;
+
+    fn config() -> SqliteLedgerConfig {
+        SqliteLedgerConfig::from_toml(&read_repo_file("config/ledger.sqlite.toml")).unwrap()
+    }
+
+    fn sealed(tenant_id: &str, count: usize) -> Vec<ReceiptGlyph> {
+        let intent = GlyphId::derive(GlyphKind::Intent, b"sqlite-store intent");
+        testing::sealed(tenant_id, intent, NOW, count, &MacKey::of("rocket-engine"))
+    }
+
+    fn anchored(receipts: &[ReceiptGlyph]) -> AnchorGlyph {
+        testing::anchored(receipts, NOW + 100, ChainLink::Genesis)
+    }
+
+    fn stored(store: &mut SqliteStore, receipts: &[ReceiptGlyph], anchor: &AnchorGlyph) {
+        for receipt in receipts {
+            store.append_receipt(receipt).unwrap();
+        }
+        store.append_anchor(anchor).unwrap();
+    }
+
+    #[test]
+    fn every_stored_receipt_has_a_proof() {
+        let mut store = SqliteStore::open_in_memory(&config()).unwrap();
+        let receipts = sealed(TENANT_ID, 11);
+        let anchor = anchored(&receipts);
+        stored(&mut store, &receipts, &anchor);
+
+        let tree = MerkleTree::from_receipts(&receipts).unwrap();
+        for (i, receipt) in receipts.iter().enumerate() {
+            assert_eq!(
+                store.receipt(&receipt.receipt_id).unwrap().as_ref(),
+                Some(receipt)
+            );
+            let served = store.receipt_proof(&receipt.receipt_id).unwrap().unwrap();
+            assert_eq!(served.anchor_id, anchor.glyph_id);
+            assert_eq!(served.proof, tree.proof(i).unwrap());
+            assert!(served.verify_receipt(receipt));
+        }
+        assert_eq!(
+            store.anchor(&anchor.glyph_id).unwrap(),
+            Some(anchor.clone())
+        );
+        assert_eq!(store.receipt(&anchor.glyph_id).unwrap(), None);
+        let unknown = GlyphId::derive(GlyphKind::Receipt, b"never stored");
+        assert_eq!(store.receipt_proof(&unknown).unwrap(), None);
+
+        let used = store.tenant_usage(TENANT_ID);
+        let expected: usize = receipts
+            .iter()
+            .map(|r| canonical_body(r).unwrap().len())
+            .sum::<usize>()
+            + canonical_body(&anchor).unwrap().len();
+        assert_eq!(used, expected as u64);
+        let body = store.glyph_json(&anchor.glyph_id).unwrap().unwrap();
+        assert_eq!(body, canonical_body(&anchor).unwrap());
+
+        let plan: String = store
+            .connection()
+            .query_row(
+                "EXPLAIN QUERY PLAN SELECT receipt_id FROM receipts
+                 WHERE tenant_id = ?1 AND receipt_type = ?2 AND timestamp > ?3",
+                [TENANT_ID, "bore_progress", "0"],
+                |row| row.get(3),
+            )
+            .unwrap();
+        assert!(plan.contains("receipts_tenant_type_time"), "{plan}");
+    }
+
+    #[test]
+    fn dilithium5_receipts_verify_after_the_round_trip() {
+        let rocket = DilithiumKeypair::generate();
+        let intent = GlyphId::derive(GlyphKind::Intent, b"sqlite-store intent");
+        let receipts = testing::sealed(TENANT_ID, intent, NOW, 4, &rocket);
+        let mut store = SqliteStore::open_in_memory(&config()).unwrap();
+        stored(&mut store, &receipts, &anchored(&receipts));
+
+        for receipt in &receipts {
+            let read = store.receipt(&receipt.receipt_id).unwrap().unwrap();
+            let envelope = SignatureField::parse(&read.kyber_signature)
+                .unwrap()
+                .into_envelope()
+                .unwrap();
+            assert_eq!(envelope.key_id, GlyphSigner::key_id(&rocket));
+            let message = receipt_signing_message(&read).unwrap();
+            rocket.public_key().verify(&message, &envelope).unwrap();
+            assert!(DilithiumKeypair::generate()
+                .public_key()
+                .verify(&message, &envelope)
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn the_database_rejects_updates_and_deletes() {
+        let mut store = SqliteStore::open_in_memory(&config()).unwrap();
+        let receipts = sealed(TENANT_ID, 3);
+        stored(&mut store, &receipts, &anchored(&receipts));
+
+        for (table, _) in LEDGER_TABLES {
+            for sql in [
+                format!("DELETE FROM {table}"),
+                format!("UPDATE {table} SET rowid = rowid"),
+            ] {
+                let err = store.connection().execute(&sql, []).unwrap_err();
+                assert!(
+                    err.to_string().contains("append-only ledger"),
+                    "{sql}: {err}"
+                );
+            }
+        }
+        assert_eq!(
+            store
+                .connection()
+                .query_row("SELECT COUNT(*) FROM glyphs", [], |r| r.get::<_, i64>(0))
+                .unwrap(),
+            4
+        );
+
+        assert!(matches!(
+            store.append_receipt(&receipts[0]),
+            Err(LedgerError::Duplicate { glyph_id }) if glyph_id == receipts[0].receipt_id
+        ));
+        let mut tampered = sealed(TENANT_ID, 4).pop().unwrap();
+        tampered.result = ReceiptResult::Anomaly;
+        assert!(matches!(
+            store.append_receipt(&tampered),
+            Err(LedgerError::Glyph(GlyphError::GlyphIdMismatch { .. }))
+        ));
+        let mut rehashed = sealed(TENANT_ID, 4).pop().unwrap();
+        rehashed.blake3_hash = receipts[0].blake3_hash.clone();
+        assert!(matches!(
+            store.append_receipt(&rehashed),
+            Err(LedgerError::Glyph(GlyphError::HashMismatch { .. }))
+        ));
+    }
+
+    #[test]
+    fn only_archived_windows_can_be_evicted() {
+        let mut store = SqliteStore::open_in_memory(&config()).unwrap();
+        let receipts = sealed(TENANT_ID, 6);
+        let old = anchored(&receipts[..4]);
+        let later = AnchorBuilder::new(
+            TENANT_ID,
+            TargetContext::TunnelBore,
+            ChainLink::Anchor(old.glyph_id),
+            Daemon::LedgerExplorer,
+        )
+        .timestamp(NOW + 150)
+        .receipts(&receipts[3..])
+        .prepare()
+        .unwrap()
+        .anchor()
+        .clone();
+        stored(&mut store, &receipts[..4], &old);
+        stored(&mut store, &receipts[4..], &later);
+        let before = store.tenant_usage(TENANT_ID);
+
+        assert_eq!(
+            store.aged_windows(TENANT_ID, NOW + 101).unwrap(),
+            [old.glyph_id]
+        );
+        let window = store.window(&old.glyph_id).unwrap().unwrap();
+        assert_eq!(window.receipts, receipts[..4]);
+        assert_eq!(hash_to_hex(&window.tree().unwrap().root()), old.merkle_root);
+
+        store.evict_window(&old.glyph_id, NOW + 1_000).unwrap();
+        assert!(store.anchor(&old.glyph_id).unwrap().is_none());
+        assert!(store.is_archived(&old.glyph_id).unwrap());
+        for receipt in &receipts[..3] {
+            assert!(store.receipt(&receipt.receipt_id).unwrap().is_none());
+            assert!(store.is_archived(&receipt.receipt_id).unwrap());
+        }
+        // Still listed by the later anchor, so it stays hot.
+        assert!(store.receipt(&receipts[3].receipt_id).unwrap().is_some());
+        assert!(!store.is_archived(&receipts[3].receipt_id).unwrap());
+        let proof = store
+            .receipt_proof(&receipts[3].receipt_id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(proof.anchor_id, later.glyph_id);
+        assert!(store.tenant_usage(TENANT_ID) < before);
+
+        assert!(matches!(
+            store.append_receipt(&receipts[0]),
+            Err(LedgerError::Duplicate { .. })
+        ));
+        // Archiving by hand: the later window is too fresh, and the
+        // receipts below are in no window at all.
+        let loose = sealed(TENANT_ID, 8).pop().unwrap();
+        store.append_receipt(&loose).unwrap();
+        for (glyph_id, archived_at) in [
+            (later.glyph_id, NOW + 149),
+            (receipts[4].receipt_id, NOW + 149),
+            (loose.receipt_id, NOW + 1_000),
+        ] {
+            let err = store
+                .connection()
+                .execute(
+                    "INSERT INTO archived (glyph_id, archived_at) VALUES (?1, ?2)",
+                    rusqlite::params![glyph_id.to_string(), archived_at],
+                )
+                .unwrap_err();
+            assert!(err.to_string().contains("append-only ledger"), "{err}");
+        }
+        for sql in [
+            "DELETE FROM archived",
+            "UPDATE archived SET archived_at = 0",
+            "DELETE FROM receipts",
+        ] {
+            let err = store.connection().execute(sql, []).unwrap_err();
+            assert!(
+                err.to_string().contains("append-only ledger"),
+                "{sql}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn anchors_need_their_receipts() {
+        let mut store = SqliteStore::open_in_memory(&config()).unwrap();
+        let receipts = sealed(TENANT_ID, 5);
+        for receipt in &receipts[..4] {
+            store.append_receipt(receipt).unwrap();
+        }
+        let anchor = anchored(&receipts);
+        assert!(matches!(
+            store.append_anchor(&anchor),
+            Err(LedgerError::MissingReceipt { receipt_id, .. }) if receipt_id == receipts[4].receipt_id
+        ));
+
+        let foreign = sealed("spacex-orbit-01", 5);
+        store.append_receipt(&foreign[4]).unwrap();
+        assert!(matches!(
+            store.append_anchor(&anchored(&[receipts[0].clone(), foreign[4].clone()])),
+            Err(LedgerError::MissingReceipt { receipt_id, .. }) if receipt_id == foreign[4].receipt_id
+        ));
+
+        store.append_receipt(&receipts[4]).unwrap();
+        store.append_anchor(&anchor).unwrap();
+        assert!(store
+            .receipt_proof(&foreign[4].receipt_id)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn tenant_caps_survive_reopening() {
+        let path = std::env::temp_dir().join(format!("ledger-{}.db", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut config = config();
+        let receipts = sealed(TENANT_ID, 3);
+        let one = canonical_body(&receipts[0]).unwrap().len() as u64;
+        config.tenant.get_mut(TENANT_ID).unwrap().max_size_bytes = one * 2 + one / 2;
+
+        {
+            let mut store = SqliteStore::open_at(&path, &config).unwrap();
+            store.append_receipt(&receipts[0]).unwrap();
+        }
+        let mut store = SqliteStore::open_at(&path, &config).unwrap();
+        assert_eq!(store.tenant_usage(TENANT_ID), one);
+        store.append_receipt(&receipts[1]).unwrap();
+        assert!(matches!(
+            store.append_receipt(&receipts[2]),
+            Err(LedgerError::TenantFull { ref tenant_id, .. }) if tenant_id == TENANT_ID
+        ));
+        assert!(store.receipt(&receipts[2].receipt_id).unwrap().is_none());
+        store
+            .append_receipt(&sealed("spacex-orbit-01", 1)[0])
+            .unwrap();
+        assert!(matches!(
+            store.append_receipt(&sealed("acme-logistics-01", 1)[0]),
+            Err(LedgerError::UnknownTenant { .. })
+        ));
+        store.incremental_vacuum().unwrap();
+        drop(store);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn usage_counts_bytes_not_characters() {
+        let path = std::env::temp_dir().join(format!("ledger-utf8-{}.db", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let receipt = ReceiptBuilder::new(
+            TENANT_ID,
+            ReceiptType::BoreProgress,
+            GlyphId::derive(GlyphKind::Intent, b"sqlite-store intent"),
+            ReceiptResult::Ok,
+            Daemon::RocketEngine,
+        )
+        .timestamp(NOW)
+        .detail("segment_id", "memphis-ségment-001 ✓")
+        .detail("meters_advanced", 0.5)
+        .detail("cutter_head_rpm", 6)
+        .build(&MacKey::of("rocket-engine"))
+        .unwrap();
+        let bytes = canonical_body(&receipt).unwrap().len() as u64;
+        assert!(bytes > canonical_body(&receipt).unwrap().chars().count() as u64);
+
+        {
+            let mut store = SqliteStore::open_at(&path, &config()).unwrap();
+            store.append_receipt(&receipt).unwrap();
+            assert_eq!(store.tenant_usage(TENANT_ID), bytes);
+        }
+        let store = SqliteStore::open_at(&path, &config()).unwrap();
+        assert_eq!(store.tenant_usage(TENANT_ID), bytes);
+        drop(store);
+        let _ = fs::remove_file(&path);
+    }
+}